@@ -0,0 +1,111 @@
+//! Generates the build hash used to version asset urls, see `src/asset_version.rs`, and the
+//! website themes from their base16 schemes, see `src/theme.rs`
+
+#[path = "build/scheme.rs"]
+mod scheme;
+
+use std::{
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+};
+
+use scheme::parse_scheme;
+
+/// The inputs of the website, the hash changes whenever any of them does
+const HASHED_PATHS: &[&str] = &["src", "articles", "assets", "Cargo.toml", "Cargo.lock"];
+
+/// Hash a file or all files within a directory, in a stable order
+fn hash_path(path: &Path, hasher: &mut DefaultHasher) {
+    if path.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        entries.sort();
+        for entry in entries {
+            hash_path(&entry, hasher);
+        }
+    } else if let Ok(contents) = fs::read(path) {
+        path.hash(hasher);
+        contents.hash(hasher);
+    }
+}
+
+/// The directory holding the base16 schemes, one `<name>.yaml` file per theme
+const THEMES_PATH: &str = "assets/themes";
+
+/// The palette keys of a base16 scheme, in order
+const BASE16_KEYS: [&str; 16] = [
+    "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+    "base09", "base0A", "base0B", "base0C", "base0D", "base0E", "base0F",
+];
+
+/// The syntax themes of schemes without a `syntax-theme`, for dark and light variants
+const DEFAULT_SYNTAX_THEMES: (&str, &str) = ("base16-ocean.dark", "base16-ocean.light");
+
+/// Generate a `Theme` expression for every base16 scheme in [`THEMES_PATH`], written to
+/// `<out_dir>/themes/<name>.rs` to be included by `src/theme.rs`
+fn generate_themes(out_dir: &Path) {
+    let themes_dir = out_dir.join("themes");
+    fs::create_dir_all(&themes_dir).unwrap();
+
+    for entry in fs::read_dir(THEMES_PATH).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "yaml") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        let scheme = parse_scheme(&source);
+        let field = |key: &str| {
+            *scheme
+                .get(key)
+                .unwrap_or_else(|| panic!("{} is missing `{key}`", path.display()))
+        };
+
+        let dark = match field("variant") {
+            "dark" => true,
+            "light" => false,
+            variant => panic!("{} has an unknown variant `{variant}`", path.display()),
+        };
+        let colors: String = BASE16_KEYS
+            .iter()
+            .map(|key| {
+                let color = field(key);
+                assert!(
+                    color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()),
+                    "{} has an invalid color `{key}: {color}`",
+                    path.display(),
+                );
+                format!("    {key}: \"#{}\",\n", color.to_ascii_lowercase())
+            })
+            .collect();
+
+        // The website specific keys are optional, so plain base16 schemes can be dropped in
+        let syntax_theme = scheme.get("syntax-theme").copied().unwrap_or(if dark {
+            DEFAULT_SYNTAX_THEMES.0
+        } else {
+            DEFAULT_SYNTAX_THEMES.1
+        });
+        let theme = format!(
+            "Theme {{\n    name: {:?},\n    author: {:?},\n    link: {:?},\n    dark: {dark},\n{colors}    syntax_theme: {syntax_theme:?},\n}}\n",
+            field("scheme"),
+            field("author"),
+            scheme.get("link"),
+        );
+        let name = path.file_stem().unwrap();
+        fs::write(themes_dir.join(name).with_extension("rs"), theme).unwrap();
+    }
+}
+
+fn main() {
+    let mut hasher = DefaultHasher::new();
+    for path in HASHED_PATHS {
+        println!("cargo::rerun-if-changed={path}");
+        hash_path(Path::new(path), &mut hasher);
+    }
+
+    println!("cargo::rustc-env=BUILD_HASH={:016x}", hasher.finish());
+
+    generate_themes(Path::new(&env::var("OUT_DIR").unwrap()));
+}
@@ -0,0 +1,80 @@
+//! Parsing base16 schemes, shared by the build script and its tests
+
+use std::collections::HashMap;
+
+/// Parse the flat `key: "value"` mappings of a base16 scheme, skipping comments and blank lines.
+///
+/// Base16 schemes never nest, so this avoids pulling in a full yaml parser.
+pub fn parse_scheme(source: &str) -> HashMap<&str, &str> {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), parse_value(value)))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// Parse a plain or quoted scalar, dropping a trailing `# ...` comment
+fn parse_value(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(quoted) = value.strip_prefix(quote) {
+            return quoted
+                .split_once(quote)
+                .map_or(quoted, |(quoted, _)| quoted);
+        }
+    }
+
+    // Comments have to be separated by whitespace, e.g. urls may contain a `#`
+    let end = value
+        .char_indices()
+        .find(|&(i, c)| c == '#' && value[..i].ends_with(char::is_whitespace))
+        .map_or(value.len(), |(i, _)| i);
+    value[..end].trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scheme with comments, quotes and blank lines
+    const SCHEME: &str = r#"
+# A comment
+scheme: "Ocean"
+
+base00: 2b303b # background
+link: https://example.com/#ocean
+"#;
+
+    #[test]
+    fn parse_value_unquotes_scalars() {
+        assert_eq!(parse_value(r#" "Base16 Ocean" "#), "Base16 Ocean");
+        assert_eq!(parse_value(" 'dark' "), "dark");
+        assert_eq!(parse_value(" c0c5ce "), "c0c5ce");
+    }
+
+    #[test]
+    fn parse_value_drops_comments() {
+        assert_eq!(parse_value(r#" "2b303b" # background"#), "2b303b");
+        assert_eq!(parse_value(" 2b303b # background"), "2b303b");
+        assert_eq!(parse_value(r#" "a # b""#), "a # b");
+    }
+
+    #[test]
+    fn parse_value_keeps_fragments() {
+        assert_eq!(
+            parse_value(" https://example.com/#scheme"),
+            "https://example.com/#scheme"
+        );
+    }
+
+    #[test]
+    fn parse_scheme_skips_comments() {
+        let scheme = parse_scheme(SCHEME);
+        assert_eq!(scheme.get("scheme"), Some(&"Ocean"));
+        assert_eq!(scheme.get("base00"), Some(&"2b303b"));
+        assert_eq!(scheme.get("link"), Some(&"https://example.com/#ocean"));
+        assert_eq!(scheme.len(), 3);
+    }
+}
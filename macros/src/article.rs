@@ -0,0 +1,255 @@
+//! Loading articles from markdown, see [`article!`](crate::article!)
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro2::TokenStream as TokenStream2;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use quote::quote;
+use syn::LitStr;
+
+/// The fence language embedding an interactive example by its id
+const EXAMPLE_LANG: &str = "example";
+
+/// A block of the parsed article, mirroring `Block` of the website
+enum Block {
+    Section { title: String, content: Vec<Block> },
+    Paragraph(Vec<Inline>),
+    Note(Vec<Block>),
+    Code { lang: String, code: String },
+    Example(String),
+    Figure { src: String, caption: String },
+    Math(String),
+}
+
+/// Inline content of the parsed article, mirroring `Inline` of the website
+enum Inline {
+    Text(String),
+    Code(String),
+    Link { text: String, href: String },
+    Math(String),
+}
+
+/// A top level item, before the content is grouped into sections by the headings
+enum Item {
+    Heading(HeadingLevel, String),
+    Block(Block),
+}
+
+/// Read the markdown file at the given path, relative to the crate root, and return the
+/// expression of the `Article` written in it
+pub fn article(path: &LitStr) -> syn::Result<TokenStream2> {
+    let file = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join(path.value());
+    let source = fs::read_to_string(&file)
+        .map_err(|error| syn::Error::new(path.span(), format!("{}: {error}", file.display())))?;
+    let (title, content) = parse_article(&source)
+        .map_err(|error| syn::Error::new(path.span(), format!("{}: {error}", path.value())))?;
+
+    let file = file.to_string_lossy();
+    let content = content.iter().map(Block::tokens);
+    Ok(quote! {
+        {
+            // Rebuild whenever the markdown changes
+            const _: &str = include_str!(#file);
+            crate::projects::article::Article {
+                title: #title,
+                content: &[#(#content),*],
+            }
+        }
+    })
+}
+
+/// Parse the title and content of an article. The title is the single `#` heading, every `##`
+/// heading starts a section holding the content up to the next one.
+fn parse_article(source: &str) -> Result<(String, Vec<Block>), String> {
+    let mut events = Parser::new_ext(source, Options::ENABLE_MATH);
+    let mut title = None;
+    let mut content = Vec::new();
+    let mut section: Option<(String, Vec<Block>)> = None;
+    for item in parse_items(&mut events, None)? {
+        match item {
+            Item::Heading(HeadingLevel::H1, text) if title.is_none() => title = Some(text),
+            Item::Heading(HeadingLevel::H1, text) => {
+                return Err(format!("found a second title `{text}`"));
+            }
+            Item::Heading(HeadingLevel::H2, text) => {
+                content.extend(
+                    section
+                        .replace((text, Vec::new()))
+                        .map(|(title, content)| Block::Section { title, content }),
+                );
+            }
+            Item::Heading(level, text) => {
+                return Err(format!("unsupported {level} heading `{text}`"));
+            }
+            Item::Block(block) => match &mut section {
+                Some((_, section_content)) => section_content.push(block),
+                None => content.push(block),
+            },
+        }
+    }
+    content.extend(section.map(|(title, content)| Block::Section { title, content }));
+
+    Ok((title.ok_or("missing the `#` title")?, content))
+}
+
+/// Parse blocks and headings up to the given end tag or the end of the input
+fn parse_items(events: &mut Parser, end: Option<TagEnd>) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    while let Some(event) = events.next() {
+        let block = match event {
+            Event::End(tag) if Some(tag) == end => return Ok(items),
+            Event::Start(Tag::Heading { level, .. }) => {
+                let text = parse_text(events, TagEnd::Heading(level))?;
+                items.push(Item::Heading(level, text));
+                continue;
+            }
+            Event::Start(Tag::Paragraph) => parse_paragraph(events)?,
+            Event::Start(Tag::BlockQuote(kind)) => {
+                let content = parse_items(events, Some(TagEnd::BlockQuote(kind)))?
+                    .into_iter()
+                    .map(|item| match item {
+                        Item::Block(block) => Ok(block),
+                        Item::Heading(_, text) => Err(format!("heading `{text}` inside a note")),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Block::Note(content)
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_owned(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let code = parse_text(events, TagEnd::CodeBlock)?;
+                if lang == EXAMPLE_LANG {
+                    Block::Example(code.trim().to_owned())
+                } else {
+                    Block::Code {
+                        lang,
+                        code: code.trim_end().to_owned(),
+                    }
+                }
+            }
+            event => return Err(format!("unsupported markdown {event:?}")),
+        };
+        items.push(Item::Block(block));
+    }
+
+    match end {
+        Some(end) => Err(format!("missing the end of {end:?}")),
+        None => Ok(items),
+    }
+}
+
+/// Parse a paragraph. A paragraph holding only an image becomes a figure captioned with its
+/// description, one holding only `$$` math becomes a displayed formula.
+fn parse_paragraph(events: &mut Parser) -> Result<Block, String> {
+    let mut inlines = Vec::new();
+    let mut standalone = None;
+    let push_text = |inlines: &mut Vec<Inline>, text: &str| match inlines.last_mut() {
+        Some(Inline::Text(previous)) => previous.push_str(text),
+        _ => inlines.push(Inline::Text(text.to_owned())),
+    };
+    loop {
+        match events.next().ok_or("missing the end of a paragraph")? {
+            Event::End(TagEnd::Paragraph) => break,
+            Event::Text(text) => push_text(&mut inlines, &text),
+            Event::SoftBreak | Event::HardBreak => push_text(&mut inlines, " "),
+            Event::Code(code) => inlines.push(Inline::Code(code.into_string())),
+            Event::InlineMath(tex) => inlines.push(Inline::Math(tex.into_string())),
+            Event::Start(Tag::Link { dest_url, .. }) => inlines.push(Inline::Link {
+                text: parse_text(events, TagEnd::Link)?,
+                href: dest_url.into_string(),
+            }),
+            Event::DisplayMath(tex) if standalone.is_none() => {
+                standalone = Some(Block::Math(tex.trim().to_owned()));
+            }
+            Event::Start(Tag::Image { dest_url, .. }) if standalone.is_none() => {
+                standalone = Some(Block::Figure {
+                    src: dest_url.into_string(),
+                    caption: parse_text(events, TagEnd::Image)?,
+                });
+            }
+            event => return Err(format!("unsupported markdown {event:?} in a paragraph")),
+        }
+    }
+
+    match standalone {
+        Some(block) if inlines.iter().all(Inline::is_blank) => Ok(block),
+        Some(_) => Err("images and `$$` math have to be paragraphs of their own".to_owned()),
+        None => Ok(Block::Paragraph(inlines)),
+    }
+}
+
+/// Parse plain text up to the given end tag, e.g. of a heading or a code block
+fn parse_text(events: &mut Parser, end: TagEnd) -> Result<String, String> {
+    let mut text = String::new();
+    loop {
+        match events
+            .next()
+            .ok_or_else(|| format!("missing the end of {end:?}"))?
+        {
+            Event::End(tag) if tag == end => return Ok(text),
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            event => return Err(format!("unsupported markdown {event:?} in plain text")),
+        }
+    }
+}
+
+impl Block {
+    /// Returns the expression creating this block
+    fn tokens(&self) -> TokenStream2 {
+        match self {
+            Block::Section { title, content } => {
+                let content = content.iter().map(Block::tokens);
+                quote! {
+                    crate::projects::article::Block::Section {
+                        title: #title,
+                        content: &[#(#content),*],
+                    }
+                }
+            }
+            Block::Paragraph(inlines) => {
+                let inlines = inlines.iter().map(Inline::tokens);
+                quote!(crate::projects::article::Block::Paragraph(&[#(#inlines),*]))
+            }
+            Block::Note(content) => {
+                let content = content.iter().map(Block::tokens);
+                quote!(crate::projects::article::Block::Note(&[#(#content),*]))
+            }
+            Block::Code { lang, code } => quote! {
+                crate::projects::article::Block::Code { lang: #lang, code: #code }
+            },
+            Block::Example(id) => quote! {
+                crate::projects::article::Block::Example(crate::projects::ExampleId::new(#id))
+            },
+            Block::Figure { src, caption } => quote! {
+                crate::projects::article::Block::Figure { src: #src, caption: #caption }
+            },
+            Block::Math(tex) => quote!(crate::projects::article::Block::Math(#tex)),
+        }
+    }
+}
+
+impl Inline {
+    /// Whether this is only whitespace, e.g. around a standalone image
+    fn is_blank(&self) -> bool {
+        matches!(self, Inline::Text(text) if text.trim().is_empty())
+    }
+
+    /// Returns the expression creating this inline content
+    fn tokens(&self) -> TokenStream2 {
+        match self {
+            Inline::Text(text) => quote!(crate::projects::article::Inline::Text(#text)),
+            Inline::Code(code) => quote!(crate::projects::article::Inline::Code(#code)),
+            Inline::Link { text, href } => quote! {
+                crate::projects::article::Inline::Link { text: #text, href: #href }
+            },
+            Inline::Math(tex) => quote!(crate::projects::article::Inline::Math(#tex)),
+        }
+    }
+}
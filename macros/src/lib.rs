@@ -0,0 +1,551 @@
+//! Procedural macros of the website
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, Fields, Ident, LitStr, Type, Visibility, parse_macro_input,
+};
+
+mod article;
+
+/// Load an article from a markdown file, given relative to the crate root.
+///
+/// This expands to a constant `Article` expression. The single `#` heading is the title, every
+/// `##` heading starts a section. Paragraphs may contain inline code, links and inline `$` math,
+/// paragraphs holding only an image or `$$` math become figures and displayed formulas, and block
+/// quotes become notes. Fenced code is highlighted by its language, except for the `example`
+/// language which embeds the interactive example with the given id. Any other markdown is
+/// rejected.
+///
+/// # Example
+/// ```ignore
+/// pub const ARTICLE: Article = article!("articles/boids.md");
+/// ```
+#[proc_macro]
+pub fn article(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    article::article(&path)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive the settings of an interactive example from a struct with named fields.
+///
+/// This generates
+/// - a [`Default`] implementation using the given default values,
+/// - a key enum with one variant per field,
+/// - a handles struct holding one `UseStateHandle` per field, which can read the current value,
+///   reset everything to the defaults, randomize everything inside the ranges of the controls,
+///   create a `SettingsTransfer` and return the `SettingDescriptor` of a field by its key, to be
+///   listed in a `SettingsDescriptor`,
+/// - a hook creating the handles, initialized with the `shared_settings` of the current page if
+///   there are any, or else with the `stored_settings` from the last visit. Changed settings are
+///   remembered under the given storage key until they are reset to the defaults, shared settings
+///   only once the user changes them.
+///
+/// The struct names the generated items and the storage key with `#[settings(...)]`. The struct
+/// still derives `Debug`, `Clone`, `PartialEq` and the `serde` traits itself.
+///
+/// Each field declares its key, label, default value and control with `#[setting(...)]`. Sliders
+/// can be declared using `min`, `max` and an optional `step`, every other control is given with
+/// `control = <expr>`. An optional `description` explains the setting in a tooltip next to its
+/// label. An optional `active` function receives the current settings and decides whether the
+/// control is enabled. An optional `low_power` value replaces the default while low power mode is
+/// enabled, it also takes precedence over the stored value then and is not remembered. The options
+/// of a field may be split across several `#[setting(...)]` attributes.
+///
+/// # Example
+/// ```ignore
+/// /// The settings
+/// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ExampleSettings)]
+/// #[settings(
+///     storage_key = "test",
+///     hook = use_test_settings,
+///     handles = TestSettingsHandles,
+///     key = TestSetting,
+/// )]
+/// pub struct TestSettings {
+///     /// Some weight
+///     #[setting(key = Weight, label = "Weight", min = 0.0, max = 1.0, step = 0.1, default = 0.5)]
+///     pub weight: f32,
+///     /// Whether to animate
+///     #[setting(key = Animate, label = "Animate", control = CheckboxControl, default = true)]
+///     pub animate: bool,
+///     /// Some angle
+///     #[setting(key = Angle, label = "Angle", min = 0.0, max = 360.0, default = 0.0)]
+///     #[setting(description = "The direction in degrees, clockwise from the top")]
+///     #[setting(active = |settings| !settings.animate, low_power = 90.0)]
+///     pub angle: f32,
+/// }
+/// ```
+#[proc_macro_derive(ExampleSettings, attributes(settings, setting))]
+pub fn derive_example_settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    example_settings(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The options given to the whole struct with `#[settings(...)]`
+struct SettingsOptions {
+    /// The key the changed settings are remembered under
+    storage_key: LitStr,
+    /// The name of the hook creating the handles
+    hook: Ident,
+    /// The name of the handles struct
+    handles: Ident,
+    /// The name of the key enum
+    key: Ident,
+}
+
+impl SettingsOptions {
+    /// Parse the options from the attributes of the struct
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let (mut storage_key, mut hook, mut handles, mut key) = (None, None, None, None);
+        for attr in settings_attributes(&input.attrs, "settings") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("storage_key") {
+                    storage_key = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+                let name = if meta.path.is_ident("hook") {
+                    &mut hook
+                } else if meta.path.is_ident("handles") {
+                    &mut handles
+                } else if meta.path.is_ident("key") {
+                    &mut key
+                } else {
+                    return Err(meta.error("unknown settings option"));
+                };
+                *name = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+        }
+
+        let missing = |option: &str| {
+            syn::Error::new_spanned(
+                &input.ident,
+                format!("missing the `{option}` option in `#[settings(...)]`"),
+            )
+        };
+        Ok(Self {
+            storage_key: storage_key.ok_or_else(|| missing("storage_key"))?,
+            hook: hook.ok_or_else(|| missing("hook"))?,
+            handles: handles.ok_or_else(|| missing("handles"))?,
+            key: key.ok_or_else(|| missing("key"))?,
+        })
+    }
+}
+
+/// A field of the settings with the options given with `#[setting(...)]`
+struct Setting {
+    /// The documentation of the field
+    docs: Vec<Attribute>,
+    /// The name of the field
+    field: Ident,
+    /// The type of the field
+    ty: Type,
+    /// The variant of the key enum
+    key: Ident,
+    /// The label shown next to the control
+    label: Expr,
+    /// The default value
+    default: Expr,
+    /// The expression creating the control of the setting
+    control: TokenStream2,
+    /// Explains the setting in a tooltip
+    description: Option<Expr>,
+    /// Whether the control is enabled for the current settings
+    active: Option<Expr>,
+    /// The value replacing the default in low power mode
+    low_power: Option<Expr>,
+}
+
+impl Setting {
+    /// Parse the options of the given field
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let ident = field
+            .ident
+            .clone()
+            .ok_or_else(|| syn::Error::new_spanned(field, "settings need named fields"))?;
+
+        let mut key = None;
+        let (mut label, mut default, mut control) = (None, None, None);
+        let (mut min, mut max, mut step) = (None, None, None);
+        let (mut description, mut active, mut low_power) = (None, None, None);
+        for attr in settings_attributes(&field.attrs, "setting") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("key") {
+                    key = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+                let option = [
+                    ("label", &mut label),
+                    ("default", &mut default),
+                    ("control", &mut control),
+                    ("min", &mut min),
+                    ("max", &mut max),
+                    ("step", &mut step),
+                    ("description", &mut description),
+                    ("active", &mut active),
+                    ("low_power", &mut low_power),
+                ]
+                .into_iter()
+                .find_map(|(name, option)| meta.path.is_ident(name).then_some(option))
+                .ok_or_else(|| meta.error("unknown setting option"))?;
+                *option = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+        }
+
+        let missing = |option: &str| {
+            syn::Error::new_spanned(
+                &ident,
+                format!("missing the `{option}` option in `#[setting(...)]`"),
+            )
+        };
+        // Sliders are declared with their range, every other control is given as is
+        let control = match (control, min, max, step) {
+            (Some(control), None, None, None) => quote!(#control),
+            (None, Some(min), Some(max), step) => {
+                let step = step.map_or_else(
+                    || quote!(crate::projects::interactive::SliderValue::ONE),
+                    |step| quote!(#step),
+                );
+                quote! {
+                    crate::projects::interactive::SliderControl {
+                        min: #min,
+                        max: #max,
+                        step: #step,
+                    }
+                }
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    "settings need either `min` and `max` with an optional `step` or a `control`",
+                ));
+            }
+        };
+        Ok(Self {
+            docs: settings_attributes(&field.attrs, "doc").cloned().collect(),
+            key: key.ok_or_else(|| missing("key"))?,
+            label: label.ok_or_else(|| missing("label"))?,
+            default: default.ok_or_else(|| missing("default"))?,
+            field: ident,
+            ty: field.ty.clone(),
+            control,
+            description,
+            active,
+            low_power,
+        })
+    }
+}
+
+/// Returns the attributes with the given name
+fn settings_attributes<'a>(
+    attrs: &'a [Attribute],
+    name: &'a str,
+) -> impl Iterator<Item = &'a Attribute> {
+    attrs.iter().filter(move |attr| attr.path().is_ident(name))
+}
+
+/// Generate everything derived for the given settings struct
+fn example_settings(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let SettingsOptions {
+        storage_key,
+        hook,
+        handles,
+        key,
+    } = SettingsOptions::parse(input)?;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "settings need to be a struct",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(input, "settings need named fields"));
+    };
+    let settings = fields
+        .named
+        .iter()
+        .map(Setting::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let visibility: &Visibility = &input.vis;
+    let field: Vec<_> = settings.iter().map(|setting| &setting.field).collect();
+    let ty: Vec<_> = settings.iter().map(|setting| &setting.ty).collect();
+    let docs: Vec<_> = settings.iter().map(|setting| &setting.docs).collect();
+    let variant: Vec<_> = settings.iter().map(|setting| &setting.key).collect();
+    let label: Vec<_> = settings.iter().map(|setting| &setting.label).collect();
+    let default: Vec<_> = settings.iter().map(|setting| &setting.default).collect();
+    let control: Vec<_> = settings.iter().map(|setting| &setting.control).collect();
+    let description = settings.iter().map(|setting| match &setting.description {
+        Some(description) => quote!(Some(#description)),
+        None => quote!(None),
+    });
+    let active = settings.iter().map(|setting| match &setting.active {
+        Some(active) => quote!(#active),
+        None => quote!(|_| true),
+    });
+    let low_power = settings.iter().map(|setting| {
+        let field = &setting.field;
+        match &setting.low_power {
+            Some(low_power) => quote!(#low_power),
+            None => quote!(default.#field),
+        }
+    });
+    let low_power_field = settings.iter().map(|setting| {
+        let field = &setting.field;
+        match &setting.low_power {
+            Some(_) => quote!(from.#field.clone()),
+            None => quote!(self.#field),
+        }
+    });
+
+    let key_doc = format!("Identifies a field of [`{name}`]");
+    let handles_doc = format!("State handles for each field of [`{name}`]");
+    let hook_doc = format!("Create the state handles for [`{name}`]");
+
+    Ok(quote! {
+        impl Default for #name {
+            fn default() -> Self {
+                Self {
+                    #(
+                        #field: #default
+                    ),*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #name {
+            /// Returns the default settings for low power mode
+            #[allow(unused_variables)]
+            pub fn low_power() -> Self {
+                let default = Self::default();
+                Self {
+                    #(
+                        #field: #low_power
+                    ),*
+                }
+            }
+
+            /// Returns these settings with the fields replaced in low power mode taken from the
+            /// given settings
+            #[allow(unused_variables)]
+            pub fn with_low_power_fields(self, from: &Self) -> Self {
+                Self {
+                    #(
+                        #field: #low_power_field
+                    ),*
+                }
+            }
+        }
+
+        #[doc = #key_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #visibility enum #key {
+            #(
+                #(#docs)*
+                #variant
+            ),*
+        }
+
+        #[doc = #handles_doc]
+        #[derive(Debug, Clone, PartialEq)]
+        #visibility struct #handles {
+            #(
+                #(#docs)*
+                pub #field: ::yew::UseStateHandle<#ty>,
+            )*
+            /// The defaults the handles were created with
+            defaults: #name,
+            /// Applies dropped settings to the handles, created once by the hook
+            apply_transfer: ::yew::Callback<String, Result<(), String>>,
+            /// Resets the handles, created by the hook whenever the defaults change
+            on_reset: ::yew::Callback<()>,
+            /// Randomizes the handles, created once by the hook
+            on_randomize: ::yew::Callback<()>,
+        }
+
+        #[allow(dead_code)]
+        impl #handles {
+            /// Returns the current settings
+            pub fn value(&self) -> #name {
+                #name {
+                    #(
+                        #field: (*self.#field).clone()
+                    ),*
+                }
+            }
+
+            /// Set all handles to the given settings
+            pub fn set(&self, value: #name) {
+                #(
+                    self.#field.set(value.#field);
+                )*
+            }
+
+            /// Reset all handles to their default values
+            pub fn reset(&self) {
+                self.set(self.defaults.clone());
+            }
+
+            /// Returns a callback resetting all handles to their default values
+            pub fn reset_callback(&self) -> ::yew::Callback<()> {
+                self.on_reset.clone()
+            }
+
+            /// Set all handles to random values inside the ranges of their controls
+            pub fn randomize(&self) {
+                self.on_randomize.emit(());
+            }
+
+            /// Returns a callback setting all handles to random values
+            pub fn randomize_callback(&self) -> ::yew::Callback<()> {
+                self.on_randomize.clone()
+            }
+
+            /// Returns a transfer of the current settings, applying dropped settings to the handles
+            pub fn transfer(&self) -> crate::projects::interactive::SettingsTransfer {
+                crate::projects::interactive::SettingsTransfer::new(
+                    &self.value(),
+                    self.apply_transfer.clone(),
+                )
+            }
+
+            /// Returns the descriptor of the given setting, shown by every version of the example
+            pub const fn descriptor<V>(
+                key: #key,
+            ) -> crate::projects::interactive::SettingDescriptor<Self, V> {
+                match key {
+                    #(
+                        #key::#variant => crate::projects::interactive::SettingDescriptor {
+                            label: #label,
+                            description: #description,
+                            visible: |_| true,
+                            active: #active,
+                            control: |handles, active| {
+                                crate::projects::interactive::SettingControl::<#ty>::html(
+                                    &#control,
+                                    handles.#field.clone(),
+                                    active,
+                                )
+                            },
+                        }
+                    ),*
+                }
+            }
+        }
+
+        impl crate::projects::interactive::SettingsHandles for #handles {
+            type Settings = #name;
+
+            fn value(&self) -> #name {
+                self.value()
+            }
+        }
+
+        #[doc = #hook_doc]
+        #[::yew::hook]
+        #visibility fn #hook() -> #handles {
+            let low_power = ::yew::use_context::<crate::power::LowPowerContext>()
+                .is_some_and(|low_power| low_power.enabled());
+            let defaults = if low_power {
+                #name::low_power()
+            } else {
+                #name::default()
+            };
+            let location = ::yew_router::hooks::use_location();
+            let shared = ::yew::use_memo((), |_| {
+                crate::projects::interactive::shared_settings::<#name>(location.as_ref())
+            });
+            let initial = ::yew::use_memo((), |_| {
+                (*shared)
+                    .clone()
+                    .or_else(|| {
+                        crate::projects::interactive::stored_settings::<#name>(#storage_key)
+                            .map(|stored| stored.with_low_power_fields(&defaults))
+                    })
+                    .unwrap_or_else(|| defaults.clone())
+            });
+            // Shared settings are only remembered once the user changed them
+            let unchanged_shared = ::yew::use_mut_ref(|| shared.is_some());
+            #(
+                let #field = ::yew::use_state(|| initial.#field.clone());
+            )*
+            // Setting a state works through any copy of its handle, so this is only created once
+            let apply_transfer = ::yew::use_memo((), |_| {
+                #(
+                    let #field = #field.clone();
+                )*
+                crate::projects::interactive::SettingsTransfer::apply_callback(
+                    move |value: #name| {
+                        #(
+                            #field.set(value.#field);
+                        )*
+                    },
+                )
+            });
+            let on_reset = ::yew::use_memo(defaults.clone(), |defaults| {
+                #(
+                    let #field = #field.clone();
+                )*
+                let defaults = defaults.clone();
+                ::yew::Callback::from(move |_| {
+                    #(
+                        #field.set(defaults.#field.clone());
+                    )*
+                })
+            });
+            let on_randomize = ::yew::use_memo((), |_| {
+                #(
+                    let #field = #field.clone();
+                )*
+                ::yew::Callback::from(move |_| {
+                    let mut rng =
+                        crate::webgl::SeededRng::new(crate::webgl::SeededRng::random_seed());
+                    #(
+                        #field.set(
+                            crate::projects::interactive::SettingControl::<#ty>::random_value(
+                                &#control,
+                                &mut rng,
+                            ),
+                        );
+                    )*
+                })
+            });
+            let handles = #handles {
+                #(
+                    #field,
+                )*
+                defaults,
+                apply_transfer: (*apply_transfer).clone(),
+                on_reset: (*on_reset).clone(),
+                on_randomize: (*on_randomize).clone(),
+            };
+            ::yew::use_effect_with(handles.value(), move |value: &#name| {
+                if *unchanged_shared.borrow() && Some(value) == shared.as_ref().as_ref() {
+                    return;
+                }
+                *unchanged_shared.borrow_mut() = false;
+
+                // The low power values are not remembered, the stored ones are kept instead
+                let value = if low_power {
+                    value.clone().with_low_power_fields(
+                        &crate::projects::interactive::stored_settings::<#name>(#storage_key)
+                            .unwrap_or_default(),
+                    )
+                } else {
+                    value.clone()
+                };
+                crate::projects::interactive::store_settings(#storage_key, &value, &#name::default())
+            });
+            handles
+        }
+    })
+}
@@ -5,15 +5,16 @@ use stylist::yew::use_style;
 use yew::prelude::*;
 
 use crate::{
+    asset_version::BUILD_HASH,
     navigation::Section,
-    theme::{ThemeColor, ThemeKind, use_theme},
+    projects::CodeExample,
+    theme::{SyntaxThemeSelector, ThemeColor, ThemeKind},
 };
 
 const WEBSITE_SOURCE_LINK: &str = "https://github.com/HannesFeil/HannesFeil.github.io";
 
 #[function_component(AboutPage)]
 pub fn about_page() -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             h1, h2, h3 {
@@ -36,7 +37,7 @@ pub fn about_page() -> Html {
                 text-align: center;
             }
         "#,
-        link_fg = theme.base0C,
+        link_fg = ThemeColor::Base0C.var(),
     );
     let author_sections = Author::iter().map(|a| {
         html! {
@@ -71,6 +72,25 @@ pub fn about_page() -> Html {
             <Section title="The Authors">
                 {for author_sections}
             </Section>
+            <Section title="Code Highlighting">
+                <p>
+                    {"
+                        Code is highlighted using the syntax theme matching the website theme,
+                        unless you prefer another one:
+                    "}
+                </p>
+                <Suspense fallback={"Loading syntax themes..."}>
+                    <SyntaxThemeSelector/>
+                </Suspense>
+                <CodeExample lang="Rust">
+                    {indoc::indoc! {r#"
+                        /// Returns the greeting shown to visitors
+                        pub fn greeting(name: &str) -> String {
+                            format!("Hello {name}!")
+                        }
+                    "#}}
+                </CodeExample>
+            </Section>
             <div class="centered-p">
                 <Section title="The Links">
                     <p>
@@ -86,6 +106,10 @@ pub fn about_page() -> Html {
                     <p>
                         <a href="https://pcaro.es/hermit/">{"Hermit"}</a>
                     </p>
+                    <h3>{"Build"}</h3>
+                    <p>
+                        <code>{BUILD_HASH}</code>
+                    </p>
                 </Section>
             </div>
         </div>
@@ -101,7 +125,6 @@ struct ImageSplitProperties {
 
 #[function_component(ImageSplitDiv)]
 fn image_split_div(props: &ImageSplitProperties) -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             display: flex;
@@ -121,7 +144,7 @@ fn image_split_div(props: &ImageSplitProperties) -> Html {
                 margin: 0px 100px;
             }
         "#,
-        image_p_bg = theme.base00,
+        image_p_bg = ThemeColor::Base00.var(),
     );
     html! {
         <div class={style}>
@@ -151,7 +174,6 @@ struct AuthorBadgeProperties {
 /// The author [`Author::badge()`] element
 #[function_component(AuthorBadge)]
 fn author_badge(AuthorBadgeProperties { name, color }: &AuthorBadgeProperties) -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             color: ${fg};
@@ -159,8 +181,8 @@ fn author_badge(AuthorBadgeProperties { name, color }: &AuthorBadgeProperties) -
             padding: 5px;
             border-radius: 5px;
         "#,
-        fg = theme.base00,
-        bg = theme[*color],
+        fg = ThemeColor::Base00.var(),
+        bg = color.var(),
     );
     html! {
         <span class={style}>{ name }</span>
@@ -168,7 +190,8 @@ fn author_badge(AuthorBadgeProperties { name, color }: &AuthorBadgeProperties) -
 }
 
 /// Authors of this website
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumIter)]
+#[strum(serialize_all = "kebab-case")]
 pub enum Author {
     Ciklon,
     DawnFirefly,
@@ -183,6 +206,7 @@ impl Author {
         }
     }
 
+    /// Returns the link to the authors profile
     pub const fn profile_link(self) -> &'static str {
         match self {
             Author::DawnFirefly => "https://github.com/Dawn-Firefly",
@@ -0,0 +1,164 @@
+//! Dismissible announcements, registered through a static manifest
+
+use std::rc::Rc;
+
+use gloo::net::http::Request;
+use gloo_storage::Storage;
+use stylist::yew::use_style;
+use web_sys::js_sys::Date;
+use yew::{platform::spawn_local, prelude::*};
+
+use crate::{asset_version::versioned, theme::ThemeColor};
+
+/// The path of the manifest listing all announcements
+pub const ANNOUNCEMENTS_MANIFEST_PATH: &str = "/assets/announcements.json";
+
+/// The local storage key prefix marking an announcement as dismissed, followed by its id
+const DISMISSED_STORAGE_KEY_PREFIX: &str = "DismissedAnnouncement.";
+
+/// A notice shown beneath the navigation bar until dismissed or expired
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Announcement {
+    /// A unique id, dismissals are remembered by it
+    pub id: String,
+    /// The message
+    pub message: String,
+    /// An optional link shown after the message
+    #[serde(default)]
+    pub link: Option<String>,
+    /// The last day the announcement is shown on, formatted as `YYYY-MM-DD`
+    pub expires: String,
+}
+
+impl Announcement {
+    /// Returns whether the expiry date has passed
+    pub fn expired(&self) -> bool {
+        // ISO dates compare correctly as strings
+        let today: String = Date::new_0().to_iso_string().into();
+        self.expires.as_str() < &today[..10]
+    }
+
+    /// Returns whether the user dismissed this announcement before
+    pub fn dismissed(&self) -> bool {
+        gloo_storage::LocalStorage::get(self.storage_key()).unwrap_or(false)
+    }
+
+    /// Remember that the user dismissed this announcement
+    pub fn dismiss(&self) {
+        gloo_storage::LocalStorage::set(self.storage_key(), true).unwrap();
+    }
+
+    /// Returns the local storage key of the dismissal
+    fn storage_key(&self) -> String {
+        format!("{DISMISSED_STORAGE_KEY_PREFIX}{}", self.id)
+    }
+}
+
+/// Fetch the announcements manifest
+async fn fetch_announcements() -> Result<Vec<Announcement>, gloo::net::Error> {
+    Request::get(&versioned(ANNOUNCEMENTS_MANIFEST_PATH))
+        .send()
+        .await?
+        .json()
+        .await
+}
+
+/// A hook fetching the announcements which are neither expired nor dismissed.
+///
+/// Returns an empty list until the manifest is loaded, or if it could not be loaded.
+#[hook]
+pub fn use_announcements() -> Rc<[Announcement]> {
+    let announcements = use_state(|| Rc::<[Announcement]>::from([]));
+
+    use_effect_with((), {
+        let announcements = announcements.setter();
+        move |_| {
+            spawn_local(async move {
+                match fetch_announcements().await {
+                    Ok(loaded) => announcements.set(
+                        loaded
+                            .into_iter()
+                            .filter(|announcement| {
+                                !announcement.expired() && !announcement.dismissed()
+                            })
+                            .collect(),
+                    ),
+                    Err(error) => log::warn!("Failed to load announcements: {error}"),
+                }
+            });
+        }
+    });
+
+    (*announcements).clone()
+}
+
+/// Shows all current announcements, each with a button dismissing it for good
+#[function_component(AnnouncementBanner)]
+pub fn announcement_banner() -> Html {
+    let style = use_style!(
+        r#"
+            display: flex;
+            align-items: center;
+            column-gap: 10px;
+            padding: 5px 20px;
+            color: ${fg};
+            background-color: ${bg};
+
+            p {
+                flex-grow: 1;
+                margin: 0px;
+            }
+
+            a {
+                color: ${fg};
+            }
+
+            button {
+                font-size: 20px;
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+        "#,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0C.var(),
+    );
+    let announcements = use_announcements();
+    // Dismissed during this visit, earlier dismissals are filtered when loading
+    let dismissed = use_state(Vec::<String>::new);
+
+    let banners = announcements
+        .iter()
+        .filter(|announcement| !dismissed.contains(&announcement.id))
+        .map(|announcement| {
+            let dismiss = Callback::from({
+                let announcement = announcement.clone();
+                let dismissed = dismissed.clone();
+                move |_| {
+                    announcement.dismiss();
+                    let mut ids = (*dismissed).clone();
+                    ids.push(announcement.id.clone());
+                    dismissed.set(ids);
+                }
+            });
+            html! {
+                <div class={style.clone()} role="status">
+                    <p>
+                        {&announcement.message}
+                        if let Some(link) = &announcement.link {
+                            {" "}
+                            <a href={link.clone()}>{"Read more"}</a>
+                        }
+                    </p>
+                    <button title="Dismiss" onclick={dismiss}>
+                        <i class="iconoir-xmark"/>
+                    </button>
+                </div>
+            }
+        });
+
+    html! {
+        {for banners}
+    }
+}
@@ -0,0 +1,14 @@
+//! Versioned asset urls, so browsers fetch fresh assets after each deployment.
+//!
+//! Without a version, a cached `worker.js` or manifest from a previous deployment may be used
+//! together with the new wasm module.
+
+/// A hash identifying the current build, generated by the build script from the website sources
+/// and assets
+pub const BUILD_HASH: &str = env!("BUILD_HASH");
+
+/// Returns the given asset url with the [`BUILD_HASH`] appended as a query parameter
+pub fn versioned(url: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}v={BUILD_HASH}")
+}
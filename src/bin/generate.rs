@@ -0,0 +1,50 @@
+//! Static file generator module
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use website::feeds::write_feeds;
+
+/// The page serving the website under a strict style policy, see [`write_strict_policy_page`]
+const STRICT_POLICY_PAGE: &str = "csp-check.html";
+
+/// The entry point for the static file generator
+///
+/// Writes all generated files into the directory given as the first argument
+pub fn main() -> std::io::Result<()> {
+    let directory: PathBuf = std::env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("dist"));
+
+    write_feeds(&directory)?;
+    write_strict_policy_page(&directory)
+}
+
+/// Write a copy of the built `index.html` served under a Content-Security-Policy without
+/// `'unsafe-inline'` styles, passing a nonce for this build to the website.
+///
+/// Opening `/csp-check.html` renders the home page under that policy, which stays in place while
+/// navigating, so every page can be checked for styles the policy blocks.
+fn write_strict_policy_page(directory: &Path) -> io::Result<()> {
+    use std::hash::{BuildHasher, RandomState};
+
+    let index = fs::read_to_string(directory.join("index.html"))?;
+    let nonce = format!("{:016x}", RandomState::new().hash_one(STRICT_POLICY_PAGE));
+    let head = format!(
+        r#"<head>
+  <meta http-equiv="Content-Security-Policy" content="style-src 'self' 'nonce-{nonce}' https://cdn.jsdelivr.net">
+  <meta property="csp-nonce" nonce="{nonce}">
+  <script>history.replaceState(null, "", "/");</script>"#
+    );
+    let Some(page) = index
+        .contains("<head>")
+        .then(|| index.replacen("<head>", &head, 1))
+    else {
+        return Err(io::Error::other("index.html has no head element"));
+    };
+
+    fs::write(directory.join(STRICT_POLICY_PAGE), page)
+}
@@ -0,0 +1,117 @@
+//! Blog posts about the website and its projects
+
+use stylist::yew::use_style;
+use yew::prelude::*;
+
+use crate::{
+    about::Author,
+    navigation::Route,
+    projects::{
+        ChangeDate,
+        article::{Article, ArticleView, article},
+    },
+    theme::ThemeColor,
+};
+
+/// An enum of all blog posts, identified by their slug
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, strum::EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Post {
+    /// Introduces the changes page and feed
+    FollowingChanges,
+    /// Boids writing their state to multiple render targets
+    BoidsRenderTargets,
+}
+
+/// Blog post metadata and content
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostMeta {
+    /// When the post was published
+    pub date: ChangeDate,
+    /// The authors
+    pub authors: &'static [Author],
+    /// Short tags categorizing the post, like the tags of projects
+    pub tags: &'static [&'static str],
+    /// A single sentence summary, listed on the changes page and in the changes feed
+    pub summary: &'static str,
+    /// The content, including the title
+    pub article: Article,
+}
+
+impl Post {
+    /// Returns the posts metadata
+    pub const fn meta(self) -> &'static PostMeta {
+        match self {
+            Post::FollowingChanges => &FOLLOWING_CHANGES,
+            Post::BoidsRenderTargets => &BOIDS_RENDER_TARGETS,
+        }
+    }
+
+    /// Returns the title of the post
+    pub const fn title(self) -> &'static str {
+        self.meta().article.title
+    }
+
+    /// Returns the route that leads to the post page
+    pub fn route(self) -> Route {
+        Route::Post { post: self }
+    }
+}
+
+const FOLLOWING_CHANGES: PostMeta = PostMeta {
+    date: ChangeDate {
+        year: 2026,
+        month: 10,
+        day: 16,
+    },
+    authors: &[Author::Ciklon],
+    tags: &["website"],
+    summary: "The changes to all projects can now be followed on a single page and in a feed",
+    article: article!("articles/blog/following-changes.md"),
+};
+
+const BOIDS_RENDER_TARGETS: PostMeta = PostMeta {
+    date: ChangeDate {
+        year: 2026,
+        month: 10,
+        day: 16,
+    },
+    authors: &[Author::Ciklon],
+    tags: &["webgl", "compute-shader", "simulation"],
+    summary: "The boids simulation writes positions and velocities to separate textures",
+    article: article!("articles/blog/boids-render-targets.md"),
+};
+
+/// Properties for the [`PostPage`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct PostPageProperties {
+    /// The shown post
+    pub post: Post,
+}
+
+/// The page of a single blog post
+#[function_component(PostPage)]
+pub fn post_page(PostPageProperties { post }: &PostPageProperties) -> Html {
+    let style = use_style!(
+        r#"
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            gap: 10px;
+            color: ${fg};
+        "#,
+        fg = ThemeColor::Base04.var(),
+    );
+
+    let meta = post.meta();
+    let byline = html! {
+        <p class={style}>
+            <span>{meta.date.to_string()}</span>
+            {for meta.authors.iter().map(|author| author.badge())}
+        </p>
+    };
+
+    html! {
+        <ArticleView article={meta.article} examples={Callback::from(|_| Html::default())} {byline}/>
+    }
+}
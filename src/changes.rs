@@ -0,0 +1,155 @@
+//! A page listing the changes to all projects
+
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    feeds::{CHANGES_FEED_PATH, Change, changes},
+    intl::{format_date, format_month, format_relative_date},
+    navigation::{Route, Section},
+    theme::ThemeColor,
+};
+
+#[function_component(ChangesPage)]
+pub fn changes_page() -> Html {
+    let style = use_style!(
+        r#"
+            margin: 0px 15%;
+
+            h1 {
+                text-align: center;
+            }
+
+            a {
+                color: ${link_fg};
+            }
+
+            ul {
+                list-style-type: none;
+                padding: 0px;
+            }
+
+            li {
+                margin: 10px 0px;
+            }
+
+            .date {
+                color: ${date_fg};
+                margin-right: 10px;
+            }
+
+            .tags {
+                display: flex;
+                flex-wrap: wrap;
+                align-items: center;
+                gap: 5px;
+            }
+
+            .tags button {
+                padding: 2px 10px;
+                border: 1px solid ${tag_bg};
+                border-radius: 10px;
+                color: ${fg};
+                background-color: transparent;
+                cursor: pointer;
+            }
+
+            .tags button.selected {
+                color: ${tag_fg};
+                background-color: ${tag_bg};
+            }
+        "#,
+        fg = ThemeColor::Base06.var(),
+        link_fg = ThemeColor::Base0C.var(),
+        date_fg = ThemeColor::Base04.var(),
+        tag_fg = ThemeColor::Base00.var(),
+        tag_bg = ThemeColor::Base0D.var(),
+    );
+
+    let changes = use_memo((), |_| changes());
+    let selected_tags = use_state(Vec::<&'static str>::new);
+
+    let mut tags: Vec<_> = changes
+        .iter()
+        .flat_map(|change| change.tags.iter().copied())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    let tag_buttons = tags.into_iter().map(|tag| {
+        let selected = selected_tags.contains(&tag);
+        let toggle = Callback::from({
+            let selected_tags = selected_tags.clone();
+            move |_| {
+                let mut tags = (*selected_tags).clone();
+                if selected {
+                    tags.retain(|selected| *selected != tag);
+                } else {
+                    tags.push(tag);
+                }
+                selected_tags.set(tags);
+            }
+        });
+        html! {
+            <button class={classes!(selected.then_some("selected"))} onclick={toggle}>
+                {tag}
+            </button>
+        }
+    });
+
+    // Changes matching any of the selected tags, all if none are selected
+    let filtered: Vec<&Change> = changes
+        .iter()
+        .filter(|change| {
+            selected_tags.is_empty() || change.tags.iter().any(|tag| selected_tags.contains(tag))
+        })
+        .collect();
+    let months = filtered
+        .chunk_by(|a, b| (a.date.year, a.date.month) == (b.date.year, b.date.month))
+        .map(|month| {
+            let date = month[0].date;
+            let entries = month.iter().map(|change| {
+                html! {
+                    <li>
+                        <span class="date" title={format_relative_date(change.date)}>
+                            {format_date(change.date)}
+                        </span>
+                        <Link<Route> to={change.source.route()}>
+                            {change.source.title()}
+                        </Link<Route>>
+                        {": "}{change.summary}
+                    </li>
+                }
+            });
+            html! {
+                <>
+                    <h2>{format_month(date)}</h2>
+                    <ul>
+                        {for entries}
+                    </ul>
+                </>
+            }
+        });
+
+    html! {
+        <div class={style}>
+            <h1>{"Changes"}</h1>
+            <Section title="Changes" hide_title=true>
+                <div class="tags">
+                    <span>{"Filter by tag:"}</span>
+                    {for tag_buttons}
+                </div>
+                if filtered.is_empty() {
+                    <p>{"No changes match the selected tags."}</p>
+                }
+                {for months}
+                <p>
+                    <a href={format!("/{CHANGES_FEED_PATH}")}>
+                        <i class="iconoir-rss-feed"/>
+                        {" Subscribe to the changes feed"}
+                    </a>
+                </p>
+            </Section>
+        </div>
+    }
+}
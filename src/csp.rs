@@ -0,0 +1,128 @@
+//! Support for running under a strict Content-Security-Policy
+//!
+//! Stylist mounts all styles as inline `<style>` elements, which a policy without
+//! `'unsafe-inline'` only allows if they carry the policy's nonce. Host pages pass their nonce
+//! through a `<meta property="csp-nonce" nonce="...">` element in the head, the
+//! [`App`](crate::App) then provides a style manager mounting every style with it.
+//!
+//! Such a policy also blocks inline `style` attributes, so components have to use classes created
+//! through the provided manager, e.g. with `use_style!` or [`use_style_manager`], instead.
+//!
+//! `tests/csp.rs` mounts the [`App`](crate::App) under such a policy and fails on any violation.
+
+use std::cell::OnceCell;
+
+use gloo::utils::document;
+use stylist::{Style, StyleSource, manager::StyleManager};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{HtmlElement, MutationObserver, MutationObserverInit, js_sys::Array};
+use yew::{AttrValue, hook, use_context, use_memo};
+
+/// The selector of the meta element holding the nonce
+const NONCE_META_SELECTOR: &str = r#"meta[property="csp-nonce"]"#;
+
+thread_local! {
+    /// The nonce passed by the host page, looked up once
+    static HOST_NONCE: OnceCell<Option<AttrValue>> = const { OnceCell::new() };
+}
+
+/// Returns the style nonce passed by the host page, if any
+pub fn host_nonce() -> Option<AttrValue> {
+    HOST_NONCE.with(|nonce| {
+        nonce
+            .get_or_init(|| {
+                let meta: HtmlElement = document()
+                    .query_selector(NONCE_META_SELECTOR)
+                    .ok()??
+                    .dyn_into()
+                    .ok()?;
+
+                // Browsers hide the nonce attribute, only the property keeps its value
+                let nonce = meta.nonce();
+                let nonce = if nonce.is_empty() {
+                    meta.get_attribute("content")?
+                } else {
+                    nonce
+                };
+
+                (!nonce.is_empty()).then(|| nonce.into())
+            })
+            .clone()
+    })
+}
+
+/// Create a style manager putting the given nonce on every `<style>` element it mounts.
+///
+/// Stylist offers no way to configure the elements it creates, so they are mounted into a detached
+/// container first, where no policy applies yet. An observer of that container puts the nonce on
+/// each of them and moves them into the head, before the page is painted again.
+pub fn nonce_style_manager(nonce: AttrValue) -> StyleManager {
+    let document = document();
+    let staging = document.create_element("div").unwrap();
+    let head = document.head().unwrap();
+
+    let mount = Closure::<dyn Fn(Array)>::new({
+        let staging = staging.clone();
+        move |_| {
+            while let Some(style) = staging.first_element_child() {
+                style.unchecked_ref::<HtmlElement>().set_nonce(&nonce);
+                head.append_child(&style).unwrap();
+            }
+        }
+    });
+    let observer = MutationObserver::new(mount.as_ref().unchecked_ref()).unwrap();
+    let options = MutationObserverInit::new();
+    options.set_child_list(true);
+    observer.observe_with_options(&staging, &options).unwrap();
+    // The observer stays in use for the lifetime of the page
+    mount.forget();
+
+    StyleManager::builder()
+        .container(staging.into())
+        .build()
+        .expect("Failed to create the style manager")
+}
+
+/// A hook creating the style manager for the whole website, applying the host page's nonce.
+///
+/// Debug builds also log every violation of the policy, so pages can be checked under the strict
+/// policy of `/csp-check.html`, written by the static file generator. Debug builds only run it with
+/// `WEBSITE_GENERATE=1` set, see `Trunk.toml`.
+#[hook]
+pub fn use_host_style_manager() -> StyleManager {
+    let manager = use_memo((), |_| {
+        #[cfg(debug_assertions)]
+        gloo::events::EventListener::new(&document(), "securitypolicyviolation", |event| {
+            if let Some(event) = event.dyn_ref::<web_sys::SecurityPolicyViolationEvent>() {
+                log::error!(
+                    "Blocked by the Content-Security-Policy ({}): {}",
+                    event.violated_directive(),
+                    event.sample(),
+                );
+            }
+        })
+        .forget();
+
+        host_nonce().map(nonce_style_manager).unwrap_or_default()
+    });
+
+    (*manager).clone()
+}
+
+/// A hook returning the style manager provided to the website.
+///
+/// Styles which can not be created with `use_style!`, e.g. inside loops, have to be created with
+/// it through [`manager_class`], since `css!` alone mounts them without the nonce.
+#[hook]
+pub fn use_style_manager() -> StyleManager {
+    use_context::<StyleManager>().unwrap_or_default()
+}
+
+/// Mount the given style with the given manager, returning its class name
+pub fn manager_class(manager: &StyleManager, css: StyleSource) -> AttrValue {
+    Style::new_with_manager(css, manager.clone())
+        .expect("Failed to mount the style")
+        .get_class_name()
+        .to_owned()
+        .into()
+}
@@ -0,0 +1,514 @@
+//! Development helpers, only active in debug builds
+
+#[cfg(debug_assertions)]
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+};
+
+#[cfg(debug_assertions)]
+use gloo::{
+    timers::callback::Interval,
+    utils::{document, document_element, window},
+};
+#[cfg(debug_assertions)]
+use gloo_storage::Storage;
+#[cfg(debug_assertions)]
+use stylist::{css, yew::use_style};
+#[cfg(debug_assertions)]
+use yew::prelude::*;
+#[cfg(debug_assertions)]
+use yew_router::Routable;
+
+#[cfg(debug_assertions)]
+use crate::{
+    csp::{manager_class, use_style_manager},
+    intl::{format_decimal, format_number},
+    navigation::use_navigation_context,
+    projects::interactive::export,
+    theme::ThemeColor,
+    webgl::GpuTimer,
+};
+
+/// A component wrapped for render tracking in debug builds, see [`Instrumented`]
+#[cfg(debug_assertions)]
+pub type Tracked<C> = Instrumented<C>;
+
+/// A component wrapped for render tracking in debug builds (a no-op in release builds)
+#[cfg(not(debug_assertions))]
+pub type Tracked<C> = C;
+
+/// The maximum number of render events kept in the [`RenderLog`]
+#[cfg(debug_assertions)]
+const MAX_RENDER_EVENTS: usize = 30;
+
+/// A trait for properties which can report which fields changed between renders
+#[cfg(debug_assertions)]
+pub trait PropDiff {
+    /// Returns the names of all fields which differ from `old`
+    fn changed_props(&self, old: &Self) -> Vec<&'static str>;
+}
+
+/// Implement [`PropDiff`] by comparing the given fields of two property values
+///
+/// # Example
+/// ```ignore
+/// fn changed_props(&self, old: &Self) -> Vec<&'static str> {
+///     changed_props!(self, old, [renderer, render_input])
+/// }
+/// ```
+#[macro_export]
+macro_rules! changed_props {
+    ($new:expr, $old:expr, [$($field:ident),* $(,)?]) => {{
+        let mut changed = Vec::new();
+        $(
+            if $new.$field != $old.$field {
+                changed.push(stringify!($field));
+            }
+        )*
+        changed
+    }};
+}
+
+/// A single re-render of an [`Instrumented`] component
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, PartialEq)]
+struct RenderEvent {
+    /// The (shortened) component type name
+    component: String,
+    /// The changed properties, `None` for the first render
+    changed: Option<Vec<&'static str>>,
+}
+
+/// The global log of render events, notifying the [`DevOverlay`] on change
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct RenderLog {
+    /// The most recent events, newest last
+    events: VecDeque<RenderEvent>,
+    /// Callbacks notified for each new event
+    listeners: Vec<Callback<()>>,
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// The unique render log used throughout this website
+    static RENDER_LOG: RefCell<RenderLog> = RefCell::default();
+}
+
+/// Push an event to the [`RenderLog`] and notify listeners
+#[cfg(debug_assertions)]
+fn log_render(event: RenderEvent) {
+    match &event.changed {
+        None => log::debug!("{} rendered for the first time", event.component),
+        Some(changed) if changed.is_empty() => {
+            log::debug!("{} re-rendered without prop changes", event.component)
+        }
+        Some(changed) => log::debug!(
+            "{} re-rendered, changed props: {}",
+            event.component,
+            changed.join(", ")
+        ),
+    }
+
+    let listeners = RENDER_LOG.with_borrow_mut(|log| {
+        log.events.push_back(event);
+        if log.events.len() > MAX_RENDER_EVENTS {
+            log.events.pop_front();
+        }
+        log.listeners.clone()
+    });
+
+    for listener in listeners {
+        listener.emit(());
+    }
+}
+
+/// Shorten a full type name by stripping module paths, e.g. `a::B<c::D>` to `B<D>`
+#[cfg(debug_assertions)]
+fn short_type_name<T: ?Sized>() -> String {
+    let name = std::any::type_name::<T>();
+    let mut result = String::with_capacity(name.len());
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            result.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            result.push(c);
+        }
+    }
+    result.push_str(segment.rsplit("::").next().unwrap_or_default());
+    result
+}
+
+/// A higher order component logging each render of the inner component with its changed props
+#[cfg(debug_assertions)]
+#[function_component(Instrumented)]
+pub fn instrumented<C>(props: &C::Properties) -> Html
+where
+    C: BaseComponent,
+    C::Properties: PropDiff + Clone,
+{
+    let previous = use_mut_ref(|| None::<C::Properties>);
+
+    let changed = previous
+        .borrow()
+        .as_ref()
+        .map(|old| props.changed_props(old));
+    *previous.borrow_mut() = Some(props.clone());
+    log_render(RenderEvent {
+        component: short_type_name::<C>(),
+        changed,
+    });
+
+    html! {
+        <C ..props.clone()/>
+    }
+}
+
+/// An overlay listing the most recent renders of [`Instrumented`] components
+#[cfg(debug_assertions)]
+#[function_component(DevOverlay)]
+pub fn dev_overlay() -> Html {
+    let style = use_style!(
+        r#"
+            position: fixed;
+            bottom: 10px;
+            right: 10px;
+            z-index: 2;
+            max-width: 500px;
+            max-height: 300px;
+            overflow-y: auto;
+            padding: 5px 10px;
+            background-color: ${bg};
+            color: ${fg};
+            font-size: 12px;
+            opacity: 0.9;
+
+            button {
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                float: right;
+            }
+
+            ul {
+                list-style-type: none;
+                padding: 0px;
+                margin: 0px;
+            }
+
+            .changed {
+                color: ${changed_fg};
+            }
+        "#,
+        bg = ThemeColor::Base00.var(),
+        fg = ThemeColor::Base05.var(),
+        changed_fg = ThemeColor::Base08.var(),
+    );
+    let expanded = use_state(|| false);
+    let events = use_state(Vec::<RenderEvent>::new);
+
+    use_effect_with((), {
+        let events = events.setter();
+        move |_| {
+            let listener = Callback::from(move |_| {
+                events.set(RENDER_LOG.with_borrow(|log| log.events.iter().cloned().collect()))
+            });
+            RENDER_LOG.with_borrow_mut(|log| log.listeners.push(listener.clone()));
+
+            move || RENDER_LOG.with_borrow_mut(|log| log.listeners.retain(|l| *l != listener))
+        }
+    });
+
+    let toggle = Callback::from({
+        let expanded = expanded.clone();
+        move |_| expanded.set(!*expanded)
+    });
+    let entries = events.iter().rev().map(|event| {
+        let description = match &event.changed {
+            None => html! { {"mounted"} },
+            Some(changed) if changed.is_empty() => html! { {"no prop changes"} },
+            Some(changed) => html! { <span class="changed">{changed.join(", ")}</span> },
+        };
+        html! {
+            <li>{&event.component}{": "}{description}</li>
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <button onclick={toggle}>
+                if *expanded {
+                    <i class="iconoir-nav-arrow-down"/>
+                } else {
+                    <i class="iconoir-nav-arrow-up"/>
+                }
+            </button>
+            {format!("Renders ({})", format_number(events.len() as f64))}
+            if *expanded {
+                <ul>
+                    {for entries}
+                </ul>
+            }
+        </div>
+    }
+}
+
+/// The local storage key of the [`ReadingLog`]
+#[cfg(debug_assertions)]
+const READING_LOG_STORAGE_KEY: &str = "DevReadingLog";
+
+/// The interval in which the section being read is sampled, in seconds
+#[cfg(debug_assertions)]
+const READING_SAMPLE_SECONDS: u32 = 1;
+
+/// Seconds spent on each section (by id), per page path
+#[cfg(debug_assertions)]
+type ReadingLog = BTreeMap<String, BTreeMap<String, u32>>;
+
+/// Returns the id of the innermost section crossing the middle of the screen
+#[cfg(debug_assertions)]
+fn section_being_read(sections: &[(AttrValue, usize)]) -> Option<AttrValue> {
+    let middle = window().inner_height().unwrap().as_f64().unwrap() / 2.0;
+    sections
+        .iter()
+        .filter(|(id, _)| {
+            document().get_element_by_id(id).is_some_and(|element| {
+                let rect = element.get_bounding_client_rect();
+                rect.top() <= middle && rect.bottom() >= middle
+            })
+        })
+        .max_by_key(|(_, level)| *level)
+        .map(|(id, _)| id.clone())
+}
+
+/// Properties for the [`ReadingHeatStrip`] component
+#[cfg(debug_assertions)]
+#[derive(Debug, PartialEq, Properties)]
+pub struct ReadingHeatStripProperties {
+    /// The left offset of the strip, valid css
+    pub left: AttrValue,
+    /// The top offset of the strip, valid css
+    pub top: AttrValue,
+}
+
+/// A strip next to the sidebar visualizing how long each section of the current page was read.
+///
+/// Every second the innermost section in the middle of the screen is recorded into a
+/// [`ReadingLog`] kept in local storage, which helps with restructuring long pages. The log can be
+/// exported as json or cleared.
+#[cfg(debug_assertions)]
+#[function_component(ReadingHeatStrip)]
+pub fn reading_heat_strip(
+    ReadingHeatStripProperties { left, top }: &ReadingHeatStripProperties,
+) -> Html {
+    let navigation = use_navigation_context();
+    let log = use_state(|| {
+        gloo_storage::LocalStorage::get::<ReadingLog>(READING_LOG_STORAGE_KEY).unwrap_or_default()
+    });
+    let path = navigation.route().to_path();
+    let sections: Vec<(AttrValue, usize)> = navigation
+        .sections()
+        .map(|section| (section.id().clone(), section.level()))
+        .collect();
+
+    use_effect_with((path.clone(), sections.clone()), {
+        let log = log.setter();
+        move |(path, sections)| {
+            let path = path.clone();
+            let sections = sections.clone();
+            let interval = Interval::new(READING_SAMPLE_SECONDS * 1000, move || {
+                if document().hidden() {
+                    return;
+                }
+                let Some(section) = section_being_read(&sections) else {
+                    return;
+                };
+                let mut updated =
+                    gloo_storage::LocalStorage::get::<ReadingLog>(READING_LOG_STORAGE_KEY)
+                        .unwrap_or_default();
+                *updated
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(section.to_string())
+                    .or_default() += READING_SAMPLE_SECONDS;
+                gloo_storage::LocalStorage::set(READING_LOG_STORAGE_KEY, &updated).unwrap();
+                log.set(updated);
+            });
+
+            move || drop(interval)
+        }
+    });
+
+    let export = Callback::from({
+        let log = log.clone();
+        move |_| {
+            if let Err(error) = export::export_json(&*log, "reading-log.json") {
+                log::error!("Failed to export reading log: {error:?}");
+            }
+        }
+    });
+    let clear = Callback::from({
+        let log = log.clone();
+        move |_| {
+            gloo_storage::LocalStorage::delete(READING_LOG_STORAGE_KEY);
+            log.set(ReadingLog::default());
+        }
+    });
+
+    let style_manager = use_style_manager();
+    let style = use_style!(
+        r#"
+            position: fixed;
+            z-index: 1;
+            top: ${top};
+            left: ${left};
+            bottom: 0px;
+            width: 24px;
+            display: flex;
+            flex-direction: column;
+            background-color: ${bg};
+            transition: left 0.5s;
+
+            .strip {
+                position: relative;
+                flex-grow: 1;
+            }
+
+            .segment {
+                position: absolute;
+                width: 100%;
+                background-color: ${heat};
+            }
+
+            button {
+                padding: 2px 0px;
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+        "#,
+        top = top,
+        left = left,
+        bg = ThemeColor::Base00.var(),
+        fg = ThemeColor::Base05.var(),
+        heat = ThemeColor::Base08.var(),
+    );
+
+    // Map each read section onto the strip according to its position on the page
+    let page_times = log.get(&path);
+    let max_time = page_times
+        .and_then(|times| times.values().max().copied())
+        .unwrap_or_default()
+        .max(1);
+    let page_height = f64::from(document_element().scroll_height().max(1));
+    let scroll_y = window().scroll_y().unwrap_or_default();
+    let mut read_sections: Vec<_> = sections
+        .iter()
+        .filter_map(|(id, level)| {
+            let time = *page_times?.get(id.as_str())?;
+            let rect = document().get_element_by_id(id)?.get_bounding_client_rect();
+            Some((id, *level, time, rect.top() + scroll_y, rect.height()))
+        })
+        .collect();
+    // Draw inner sections on top of their parents
+    read_sections.sort_by_key(|(_, level, ..)| *level);
+    let segments = read_sections.into_iter().map(|(id, _, time, top, height)| {
+        html! {
+            <div
+                class={classes!("segment", manager_class(&style_manager, css!(
+                    "top: ${top}%; height: ${height}%; opacity: ${opacity};",
+                    top = top / page_height * 100.0,
+                    height = height / page_height * 100.0,
+                    opacity = f64::from(time) / f64::from(max_time),
+                )))}
+                title={format!("{id}: {} s", format_number(f64::from(time)))}
+            />
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <div class="strip">
+                {for segments}
+            </div>
+            <button title="Export reading log" onclick={export}>
+                <i class="iconoir-download"/>
+            </button>
+            <button title="Clear reading log" onclick={clear}>
+                <i class="iconoir-trash"/>
+            </button>
+        </div>
+    }
+}
+
+/// The interval in which the [`GpuTimingOverlay`] is refreshed, in milliseconds
+#[cfg(debug_assertions)]
+const GPU_TIMING_REFRESH_INTERVAL: u32 = 500;
+
+/// Properties for the [`GpuTimingOverlay`] component
+#[cfg(debug_assertions)]
+#[derive(Debug, PartialEq, Properties)]
+pub struct GpuTimingOverlayProperties {
+    /// The timer of the measured renderer
+    pub timer: GpuTimer,
+}
+
+/// An overlay listing the gpu time of each render pass, enabling the given timer
+#[cfg(debug_assertions)]
+#[function_component(GpuTimingOverlay)]
+pub fn gpu_timing_overlay(
+    GpuTimingOverlayProperties { timer }: &GpuTimingOverlayProperties,
+) -> Html {
+    let style = use_style!(
+        r#"
+            position: absolute;
+            bottom: 10px;
+            left: 10px;
+            padding: 2px 10px;
+            color: ${fg};
+            background-color: ${bg};
+            font-family: monospace;
+            font-size: 12px;
+            opacity: 0.8;
+            pointer-events: none;
+        "#,
+        fg = ThemeColor::Base05.var(),
+        bg = ThemeColor::Base00.var(),
+    );
+    let timings = use_state(Vec::new);
+    let supported = use_state(|| None);
+
+    use_effect_with(timer.clone(), {
+        let timings = timings.setter();
+        let supported = supported.setter();
+        move |timer| {
+            timer.enable();
+            let timer = timer.clone();
+            let interval = Interval::new(GPU_TIMING_REFRESH_INTERVAL, move || {
+                timings.set(timer.timings());
+                supported.set(timer.supported());
+            });
+
+            move || drop(interval)
+        }
+    });
+
+    let content = match *supported {
+        Some(false) => html! { {"GPU timing unsupported"} },
+        _ => timings
+            .iter()
+            .map(|(pass, milliseconds)| format!("{pass}: {} ms", format_decimal(*milliseconds, 2)))
+            .collect::<Vec<_>>()
+            .join(" · ")
+            .into(),
+    };
+
+    html! {
+        <div class={style}>{content}</div>
+    }
+}
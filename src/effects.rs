@@ -0,0 +1,198 @@
+//! Small celebratory effects
+
+use std::{cell::RefCell, f64::consts::PI, rc::Rc};
+
+use gloo::utils::{document, window};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, js_sys::Math};
+use yew::{Callback, hook};
+
+use crate::{
+    motion::{prefers_reduced_motion, use_pause},
+    theme::use_theme,
+};
+
+/// The amount of confetti per burst
+const PARTICLE_COUNT: usize = 80;
+
+/// How long a burst lasts, in milliseconds
+const BURST_DURATION: f64 = 1800.0;
+
+/// The downwards acceleration of the confetti, in pixels per square millisecond
+const GRAVITY: f64 = 0.0012;
+
+/// A single piece of confetti
+struct Particle {
+    /// The position in pixels
+    position: (f64, f64),
+    /// The velocity in pixels per millisecond
+    velocity: (f64, f64),
+    /// The rotation in radians
+    rotation: f64,
+    /// The rotation speed in radians per millisecond
+    spin: f64,
+    /// The width and height in pixels
+    size: (f64, f64),
+    /// The css color
+    color: String,
+}
+
+impl Particle {
+    /// Create a particle at the given position flying upwards in a random direction
+    fn random(position: (f64, f64), color: String) -> Self {
+        let angle = -PI / 2.0 + (Math::random() - 0.5) * PI * 0.8;
+        let speed = 0.4 + Math::random() * 0.6;
+        let size = 6.0 + Math::random() * 6.0;
+        Self {
+            position,
+            velocity: (angle.cos() * speed, angle.sin() * speed),
+            rotation: Math::random() * PI,
+            spin: (Math::random() - 0.5) * 0.02,
+            size: (size, size * 0.6),
+            color,
+        }
+    }
+
+    /// Advance the particle by the given amount of milliseconds
+    fn update(&mut self, delta_time: f64) {
+        self.velocity.1 += GRAVITY * delta_time;
+        self.position.0 += self.velocity.0 * delta_time;
+        self.position.1 += self.velocity.1 * delta_time;
+        self.rotation += self.spin * delta_time;
+    }
+
+    /// Draw the particle onto the given context
+    fn draw(&self, context: &CanvasRenderingContext2d) {
+        context.save();
+        let _ = context.translate(self.position.0, self.position.1);
+        let _ = context.rotate(self.rotation);
+        context.set_fill_style_str(&self.color);
+        context.fill_rect(
+            -self.size.0 / 2.0,
+            -self.size.1 / 2.0,
+            self.size.0,
+            self.size.1,
+        );
+        context.restore();
+    }
+}
+
+/// Burst confetti in the given colors from a position in the viewport.
+///
+/// The confetti is drawn on a temporary canvas covering the window, which removes itself once
+/// the burst faded out.
+pub fn celebrate(origin: (f64, f64), colors: &[&str]) {
+    if colors.is_empty() {
+        return;
+    }
+
+    let Some(canvas) = document()
+        .create_element("canvas")
+        .ok()
+        .and_then(|canvas| canvas.dyn_into::<HtmlCanvasElement>().ok())
+    else {
+        return;
+    };
+    let Some(context) = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok())
+    else {
+        return;
+    };
+
+    let width = window().inner_width().unwrap().as_f64().unwrap();
+    let height = window().inner_height().unwrap().as_f64().unwrap();
+    canvas.set_width(width as u32);
+    canvas.set_height(height as u32);
+    // Set through the style object, so it works under a strict Content-Security-Policy
+    let style = canvas.style();
+    for (property, value) in [
+        ("position", "fixed"),
+        ("top", "0px"),
+        ("left", "0px"),
+        ("width", "100%"),
+        ("height", "100%"),
+        ("pointer-events", "none"),
+        ("z-index", "1000"),
+    ] {
+        let _ = style.set_property(property, value);
+    }
+    if document().body().unwrap().append_child(&canvas).is_err() {
+        return;
+    }
+
+    let mut particles: Vec<_> = (0..PARTICLE_COUNT)
+        .map(|i| Particle::random(origin, colors[i % colors.len()].to_owned()))
+        .collect();
+
+    type SelfOwnedSharedFunction<T> = Rc<RefCell<Option<Closure<dyn FnMut(T)>>>>;
+    let cb: SelfOwnedSharedFunction<f64> = Rc::new(RefCell::new(None));
+
+    *cb.borrow_mut() = Some(Closure::wrap(Box::new({
+        let cb = cb.clone();
+        let mut start_time = None;
+        let mut last_time = 0.0;
+        move |time: f64| {
+            let start_time = *start_time.get_or_insert(time);
+            let elapsed = time - start_time;
+            // Avoid huge jumps if the tab was in the background
+            let delta_time = (time - last_time).min(50.0);
+            last_time = time;
+
+            if elapsed >= BURST_DURATION {
+                canvas.remove();
+                *cb.borrow_mut() = None;
+                return;
+            }
+
+            context.clear_rect(0.0, 0.0, width, height);
+            context.set_global_alpha(1.0 - elapsed / BURST_DURATION);
+            for particle in &mut particles {
+                if elapsed > 0.0 {
+                    particle.update(delta_time);
+                }
+                particle.draw(&context);
+            }
+
+            request_frame(cb.borrow().as_ref().unwrap());
+        }
+    }) as Box<dyn FnMut(f64)>));
+
+    request_frame(cb.borrow().as_ref().unwrap());
+}
+
+/// Request the given function to be called on the next animation frame
+fn request_frame(function: &Closure<dyn FnMut(f64)>) {
+    window()
+        .request_animation_frame(function.as_ref().unchecked_ref())
+        .unwrap();
+}
+
+/// A hook returning a callback which bursts confetti in the theme colors from a viewport position.
+///
+/// Does nothing while animations are paused or the user prefers reduced motion.
+#[hook]
+pub fn use_celebrate() -> Callback<(f64, f64)> {
+    let theme = use_theme();
+    let paused = use_pause().paused();
+
+    Callback::from(move |origin| {
+        if paused || prefers_reduced_motion() {
+            return;
+        }
+        celebrate(
+            origin,
+            &[
+                theme.base08,
+                theme.base09,
+                theme.base0A,
+                theme.base0B,
+                theme.base0C,
+                theme.base0D,
+                theme.base0E,
+            ],
+        );
+    })
+}
@@ -0,0 +1,328 @@
+//! Static feeds generated from the project metadata
+
+use std::{cmp::Reverse, fs, io, path::Path};
+
+use convert_case::{Case, Casing};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use yew_router::Routable;
+
+use crate::{
+    about::Author,
+    blog::Post,
+    navigation::Route,
+    projects::{ChangeDate, Project},
+};
+
+/// The public url this website is hosted at
+pub const WEBSITE_URL: &str = "https://hannesfeil.github.io";
+
+/// The path of the site-wide changes feed relative to the website root
+pub const CHANGES_FEED_PATH: &str = "feeds/changes.atom";
+
+/// A single contribution of an author as listed in the feeds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Contribution {
+    /// The project title
+    pub title: &'static str,
+    /// The project description
+    pub description: &'static str,
+    /// The absolute link to the project page
+    pub link: String,
+    /// The absolute link to the project preview image
+    pub image: String,
+    /// The project tags
+    pub tags: &'static [&'static str],
+    /// The names of all authors of the project
+    pub authors: Vec<&'static str>,
+    /// When the project last changed, taken from its changelog
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<ChangeDate>,
+}
+
+impl Contribution {
+    /// Collect the contribution data of the given project
+    fn new(project: Project) -> Self {
+        let meta = project.meta();
+        Self {
+            title: meta.title,
+            description: meta.description.trim(),
+            link: absolute_link(&project.route()),
+            image: format!("{WEBSITE_URL}/{}", project.preview_image_path()),
+            tags: meta.tags,
+            authors: meta.authors.iter().map(|author| author.name()).collect(),
+            date: meta.changelog.iter().map(|entry| entry.date).max(),
+        }
+    }
+}
+
+/// The JSON résumé of an author
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Resume {
+    /// The author name
+    pub name: &'static str,
+    /// The link to the authors profile
+    pub profile: &'static str,
+    /// The link to the RSS feed of the author
+    pub feed: String,
+    /// All contributions of the author
+    pub contributions: Vec<Contribution>,
+}
+
+impl Resume {
+    /// Collect the résumé of the given author
+    pub fn new(author: Author) -> Self {
+        Self {
+            name: author.name(),
+            profile: author.profile_link(),
+            feed: format!("{WEBSITE_URL}/{}", feed_path(author, "xml")),
+            contributions: Project::iter()
+                .filter(|project| project.meta().authors.contains(&author))
+                .map(Contribution::new)
+                .collect(),
+        }
+    }
+
+    /// Render this résumé as an RSS 2.0 feed
+    pub fn to_rss(&self) -> String {
+        let items: String = self
+            .contributions
+            .iter()
+            .map(|contribution| {
+                let categories: String = contribution
+                    .tags
+                    .iter()
+                    .map(|tag| format!("<category>{}</category>", escape_xml(tag)))
+                    .collect();
+                let pub_date = contribution
+                    .date
+                    .map(|date| format!("<pubDate>{}</pubDate>", date.to_rfc822()))
+                    .unwrap_or_default();
+                format!(
+                    "<item><title>{}</title><link>{}</link><guid>{}</guid>{pub_date}<description>{}</description>{categories}</item>",
+                    escape_xml(contribution.title),
+                    escape_xml(&contribution.link),
+                    escape_xml(&contribution.link),
+                    escape_xml(contribution.description),
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0"><channel><title>{}</title><link>{}</link><description>{}</description>{items}</channel></rss>"#,
+            escape_xml(&format!("Cute Codlings - {}", self.name)),
+            escape_xml(WEBSITE_URL),
+            escape_xml(&format!("Contributions by {}", self.name)),
+        )
+    }
+}
+
+/// What a [`Change`] belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSource {
+    /// An entry of a projects changelog
+    Project(Project),
+    /// A blog post
+    Post(Post),
+}
+
+impl ChangeSource {
+    /// Returns the title of the project or post
+    pub fn title(self) -> &'static str {
+        match self {
+            ChangeSource::Project(project) => project.meta().title,
+            ChangeSource::Post(post) => post.title(),
+        }
+    }
+
+    /// Returns the route to the project or post
+    pub fn route(self) -> Route {
+        match self {
+            ChangeSource::Project(project) => project.route(),
+            ChangeSource::Post(post) => post.route(),
+        }
+    }
+
+    /// Returns the authors of the project or post
+    pub fn authors(self) -> &'static [Author] {
+        match self {
+            ChangeSource::Project(project) => project.meta().authors,
+            ChangeSource::Post(post) => post.meta().authors,
+        }
+    }
+}
+
+/// A single entry of the site-wide changes, see [`changes`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// When the change was published
+    pub date: ChangeDate,
+    /// The changed project or the published post
+    pub source: ChangeSource,
+    /// A short summary of the change
+    pub summary: &'static str,
+    /// The tags of the changed project or the post
+    pub tags: &'static [&'static str],
+}
+
+impl Change {
+    /// Returns the absolute link to the changed project or the post
+    pub fn link(&self) -> String {
+        absolute_link(&self.source.route())
+    }
+
+    /// Returns a unique and stable id of the change, as a tag URI
+    fn id(&self) -> String {
+        format!(
+            "tag:{},{}:{}:{}",
+            WEBSITE_URL.trim_start_matches("https://"),
+            self.date,
+            self.source.route().to_path(),
+            self.summary.to_case(Case::Kebab),
+        )
+    }
+}
+
+/// Collect the changelog entries of all projects and all blog posts, newest first.
+///
+/// Both the changes page and the changes feed are built from this list.
+pub fn changes() -> Vec<Change> {
+    let projects = Project::sorted().into_iter().flat_map(|project| {
+        let meta = project.meta();
+        meta.changelog.iter().map(move |entry| Change {
+            date: entry.date,
+            source: ChangeSource::Project(project),
+            summary: entry.summary,
+            tags: meta.tags,
+        })
+    });
+    let posts = Post::iter().map(|post| {
+        let meta = post.meta();
+        Change {
+            date: meta.date,
+            source: ChangeSource::Post(post),
+            summary: meta.summary,
+            tags: meta.tags,
+        }
+    });
+
+    let mut changes: Vec<_> = projects.chain(posts).collect();
+    // The sort is stable, so changes of the same day keep the project order
+    changes.sort_by_key(|change| Reverse(change.date));
+    changes
+}
+
+/// Render the given changes as an Atom feed
+pub fn changes_to_atom(changes: &[Change]) -> String {
+    let timestamp = |date: ChangeDate| format!("{date}T00:00:00Z");
+
+    let entries: String = changes
+        .iter()
+        .map(|change| {
+            let categories: String = change
+                .tags
+                .iter()
+                .map(|tag| format!(r#"<category term="{}"/>"#, escape_xml(tag)))
+                .collect();
+            let authors: String = change
+                .source
+                .authors()
+                .iter()
+                .map(|author| {
+                    format!(
+                        "<author><name>{}</name><uri>{}</uri></author>",
+                        escape_xml(author.name()),
+                        escape_xml(author.profile_link()),
+                    )
+                })
+                .collect();
+            let title = match change.source {
+                ChangeSource::Project(project) => {
+                    format!("{}: {}", project.meta().title, change.summary)
+                }
+                ChangeSource::Post(post) => post.title().to_owned(),
+            };
+            format!(
+                r#"<entry><title>{}</title><id>{}</id><link href="{}"/><updated>{}</updated>{authors}<summary>{}</summary>{categories}</entry>"#,
+                escape_xml(&title),
+                escape_xml(&change.id()),
+                escape_xml(&change.link()),
+                timestamp(change.date),
+                escape_xml(change.summary),
+            )
+        })
+        .collect();
+    let page = format!("{WEBSITE_URL}{}", Route::Changes.to_path());
+    let updated = changes
+        .iter()
+        .map(|change| change.date)
+        .max()
+        .map_or_else(|| "1970-01-01T00:00:00Z".to_owned(), timestamp);
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title><subtitle>{}</subtitle><id>{}</id><link href="{}"/><link rel="self" href="{}"/><updated>{updated}</updated><author><name>{}</name></author>{entries}</feed>"#,
+        escape_xml("Cute Codlings - Changes"),
+        escape_xml("Changes to all projects and new blog posts"),
+        escape_xml(&page),
+        escape_xml(&page),
+        escape_xml(&format!("{WEBSITE_URL}/{CHANGES_FEED_PATH}")),
+        escape_xml("Cute Codlings"),
+    )
+}
+
+/// Returns the path of an authors feed with the given extension relative to the website root
+pub fn feed_path(author: Author, extension: &str) -> String {
+    format!("feeds/{author}.{extension}")
+}
+
+/// Write the RSS feed and JSON résumé of every author and the changes feed into the given
+/// directory
+pub fn write_feeds(directory: &Path) -> io::Result<()> {
+    fs::create_dir_all(directory.join("feeds"))?;
+    fs::write(
+        directory.join(CHANGES_FEED_PATH),
+        changes_to_atom(&changes()),
+    )?;
+
+    for author in Author::iter() {
+        let resume = Resume::new(author);
+        fs::write(directory.join(feed_path(author, "xml")), resume.to_rss())?;
+        fs::write(
+            directory.join(feed_path(author, "json")),
+            serde_json::to_string_pretty(&resume).map_err(io::Error::other)?,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns the absolute link to the given route
+fn absolute_link(route: &Route) -> String {
+    format!("{WEBSITE_URL}{}", route.to_path())
+}
+
+/// Escape the xml special characters in the given text
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_xml_escapes_special_characters() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn escape_xml_escapes_ampersands_once() {
+        assert_eq!(escape_xml("&lt;"), "&amp;lt;");
+        assert_eq!(escape_xml("plain text"), "plain text");
+    }
+}
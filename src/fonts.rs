@@ -0,0 +1,150 @@
+//! Loading the website font through the FontFace api
+
+use std::cell::Cell;
+
+use gloo::utils::{document, window};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    FontFace, FontFaceDescriptors,
+    js_sys::{Array, Promise},
+};
+use yew::{
+    Children, ContextProvider, Html, Properties, function_component, hook, html,
+    platform::spawn_local, use_context, use_effect_with, use_state,
+};
+
+use crate::asset_version::versioned;
+
+/// The family name of the website font
+pub const FONT_FAMILY: &str = "hermit";
+
+/// The fonts used if the website font can not be loaded
+pub const FALLBACK_FONT_STACK: &str = "ui-monospace, SFMono-Regular, Menlo, Consolas, monospace";
+
+/// How long to wait for the website font before falling back, in milliseconds
+const FONT_LOAD_TIMEOUT: i32 = 3000;
+
+/// The faces of the website font (url, style, weight)
+const FONT_FACES: [(&str, &str, &str); 4] = [
+    ("/assets/fonts/hermit-regular.otf", "normal", "normal"),
+    ("/assets/fonts/hermit-regularitalic.otf", "italic", "normal"),
+    ("/assets/fonts/hermit-bold.otf", "normal", "bold"),
+    ("/assets/fonts/hermit-bolditalic.otf", "italic", "bold"),
+];
+
+/// The loading state of the website font
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FontState {
+    /// Still loading
+    #[default]
+    Loading,
+    /// Loaded successfully
+    Loaded,
+    /// Failed to load in time, the fallback fonts are used
+    Failed,
+}
+
+impl FontState {
+    /// Returns whether loading finished one way or another, so text can be measured reliably
+    pub fn ready(self) -> bool {
+        self != FontState::Loading
+    }
+
+    /// Returns the css font family list to use
+    pub fn font_family(self) -> String {
+        match self {
+            FontState::Failed => FALLBACK_FONT_STACK.to_owned(),
+            FontState::Loading | FontState::Loaded => {
+                format!("{FONT_FAMILY}, {FALLBACK_FONT_STACK}")
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// The font state, accessible outside of components
+    static FONT_STATE: Cell<FontState> = Cell::default();
+}
+
+/// Returns whether the website font finished loading.
+///
+/// Unlike [`use_fonts`] this can be used outside of components, e.g. by canvas renderers which
+/// should wait for it before measuring text.
+pub fn fonts_ready() -> bool {
+    FONT_STATE.get().ready()
+}
+
+/// Returns the css font family list matching the current [`FontState`]
+pub fn font_family() -> String {
+    FONT_STATE.get().font_family()
+}
+
+/// Load all faces of the website font, failing if any of them takes longer than the timeout
+async fn load_fonts() -> Result<(), JsValue> {
+    let loads = Array::new();
+    for (url, style, weight) in FONT_FACES {
+        let descriptors = FontFaceDescriptors::new();
+        descriptors.set_style(style);
+        descriptors.set_weight(weight);
+        let face = FontFace::new_with_str_and_descriptors(
+            FONT_FAMILY,
+            &format!("url({})", versioned(url)),
+            &descriptors,
+        )?;
+        document().fonts().add(&face)?;
+        loads.push(&face.load()?.into());
+    }
+
+    let timeout = Promise::new(&mut |_, reject| {
+        let _ = window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&reject, FONT_LOAD_TIMEOUT);
+    });
+    JsFuture::from(Promise::race(&Array::of2(&Promise::all(&loads), &timeout))).await?;
+
+    Ok(())
+}
+
+/// Properties for the [`FontProvider`]
+#[derive(Debug, PartialEq, Properties)]
+pub(crate) struct FontProviderProps {
+    pub children: Children,
+}
+
+/// A context provider for the [`FontState`], loading the website font when mounted
+#[function_component(FontProvider)]
+pub(crate) fn font_provider(props: &FontProviderProps) -> Html {
+    let state = use_state(|| FONT_STATE.get());
+
+    use_effect_with((), {
+        let state = state.setter();
+        move |_| {
+            spawn_local(async move {
+                let loaded = match load_fonts().await {
+                    Ok(()) => FontState::Loaded,
+                    Err(error) => {
+                        log::warn!("Failed to load the website font, falling back: {error:?}");
+                        FontState::Failed
+                    }
+                };
+                FONT_STATE.set(loaded);
+                state.set(loaded);
+            });
+        }
+    });
+
+    html! {
+        <ContextProvider<FontState> context={*state}>
+            {props.children.clone()}
+        </ContextProvider<FontState>>
+    }
+}
+
+/// A convenient hook for accessing the current [`FontState`].
+///
+/// # Panics
+/// If the [`FontState`] has not been provided.
+#[hook]
+pub fn use_fonts() -> FontState {
+    use_context::<FontState>().unwrap()
+}
@@ -0,0 +1,97 @@
+//! Locale aware formatting of numbers and dates, using the browser `Intl` api
+
+use wasm_bindgen::JsValue;
+use web_sys::js_sys::{Array, Date, Function, Intl, Object, Reflect};
+
+use crate::projects::ChangeDate;
+
+/// The amount of milliseconds in a day
+const MILLISECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Create an `Intl` options object from key value pairs
+fn options(entries: &[(&str, &str)]) -> Object {
+    let options = Object::new();
+    for (key, value) in entries {
+        let _ = Reflect::set(&options, &JsValue::from_str(key), &JsValue::from_str(value));
+    }
+    options
+}
+
+/// Call a bound `format` function of an `Intl` formatter
+fn call_format(format: Function, value: &JsValue) -> Option<String> {
+    format
+        .call1(&JsValue::UNDEFINED, value)
+        .ok()
+        .and_then(|formatted| formatted.as_string())
+}
+
+/// Returns the local midnight of the given date
+fn to_js_date(date: ChangeDate) -> Date {
+    Date::new_with_year_month_day(
+        u32::from(date.year),
+        i32::from(date.month) - 1,
+        i32::from(date.day),
+    )
+}
+
+/// Format a number for the users locale, e.g. `12,345.6` in english
+pub fn format_number(value: f64) -> String {
+    let format = Intl::NumberFormat::new(&Array::new(), &Object::new()).format();
+    call_format(format, &JsValue::from_f64(value)).unwrap_or_else(|| value.to_string())
+}
+
+/// Format a number with the given amount of fraction digits for the users locale, e.g. `1,234.50`
+/// with two digits in english
+pub fn format_decimal(value: f64, fraction_digits: u8) -> String {
+    let options = Object::new();
+    for key in ["minimumFractionDigits", "maximumFractionDigits"] {
+        let _ = Reflect::set(
+            &options,
+            &JsValue::from_str(key),
+            &JsValue::from(fraction_digits),
+        );
+    }
+    let format = Intl::NumberFormat::new(&Array::new(), &options).format();
+    call_format(format, &JsValue::from_f64(value))
+        .unwrap_or_else(|| format!("{value:.*}", usize::from(fraction_digits)))
+}
+
+/// Format a date for the users locale, e.g. `Oct 16, 2026` in english
+pub fn format_date(date: ChangeDate) -> String {
+    let format =
+        Intl::DateTimeFormat::new(&Array::new(), &options(&[("dateStyle", "medium")])).format();
+    call_format(format, &to_js_date(date)).unwrap_or_else(|| date.to_string())
+}
+
+/// Format the month and year of a date for the users locale, e.g. `October 2026` in english
+pub fn format_month(date: ChangeDate) -> String {
+    let format = Intl::DateTimeFormat::new(
+        &Array::new(),
+        &options(&[("month", "long"), ("year", "numeric")]),
+    )
+    .format();
+    call_format(format, &to_js_date(date))
+        .unwrap_or_else(|| format!("{} {}", date.month_name(), date.year))
+}
+
+/// Format a date relative to today for the users locale, e.g. `3 weeks ago` in english
+pub fn format_relative_date(date: ChangeDate) -> String {
+    let now = Date::new_0();
+    let today = Date::new_with_year_month_day(
+        now.get_full_year(),
+        now.get_month() as i32,
+        now.get_date() as i32,
+    );
+    // Rounded, since days differ in length when the daylight saving time changes
+    let days = ((to_js_date(date).get_time() - today.get_time()) / MILLISECONDS_PER_DAY).round();
+    let (value, unit) = match days.abs() {
+        ..7.0 => (days, "day"),
+        ..30.0 => ((days / 7.0).round(), "week"),
+        ..365.0 => ((days / 30.0).round(), "month"),
+        _ => ((days / 365.0).round(), "year"),
+    };
+
+    Intl::RelativeTimeFormat::new(&Array::new(), &options(&[("numeric", "auto")]))
+        .format(value, unit)
+        .into()
+}
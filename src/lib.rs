@@ -1,12 +1,21 @@
 #![warn(rustdoc::broken_intra_doc_links)]
 
-use projects::ProjectPreview;
+use fonts::{FontProvider, use_fonts};
+use gloo_storage::Storage;
+use motion::{ParallaxScope, PauseProvider, use_parallax};
+use power::LowPowerProvider;
+use projects::{
+    PROJECT_LAYOUT_STORAGE_KEY, ProjectCard, ProjectLayout, ProjectPreview,
+    external::{ExternalProjectPreview, use_external_projects},
+    interactive::{Selection, SelectionOption},
+};
 use strum::IntoEnumIterator as _;
 use stylist::{
     css,
-    yew::{Global, use_style},
+    yew::{Global, ManagerProvider, use_style},
 };
-use theme::{ThemeProvider, use_theme};
+use theme::{ThemeColor, ThemeProperties, ThemeProvider, use_theme};
+use toast::ToastProvider;
 use yew::prelude::*;
 use yew_agent::oneshot::OneshotProvider;
 
@@ -17,45 +26,81 @@ use crate::{
 };
 
 pub mod about;
+pub mod announcements;
+pub mod asset_version;
+pub mod blog;
+pub mod changes;
+pub mod csp;
+pub mod dev;
+pub mod effects;
+pub mod feeds;
+pub mod fonts;
+pub mod intl;
+pub mod motion;
 pub mod navigation;
+pub mod power;
 pub mod projects;
 pub mod theme;
+pub mod toast;
 pub mod webgl;
 
 #[function_component(App)]
 pub fn app() -> Html {
+    // Styles are mounted by the children, so they all have to use the manager applying the nonce
+    let style_manager = csp::use_host_style_manager();
+
+    #[cfg(debug_assertions)]
+    let dev_overlay = html! { <dev::DevOverlay/> };
+    #[cfg(not(debug_assertions))]
+    let dev_overlay = Html::default();
+
     html! {
-        <OneshotProvider<LoadSyntaxTheme> path="/worker.js">
+        <ManagerProvider manager={style_manager}>
+        <OneshotProvider<LoadSyntaxTheme> path={asset_version::versioned("/worker.js")}>
             <ThemeProvider>
-                <GlobalStyle/>
-                <PageSwitch/>
+                <ToastProvider>
+                    <LowPowerProvider>
+                        <PauseProvider>
+                            <FontProvider>
+                                <GlobalStyle/>
+                                <PageSwitch/>
+                                {dev_overlay}
+                            </FontProvider>
+                        </PauseProvider>
+                    </LowPowerProvider>
+                </ToastProvider>
             </ThemeProvider>
         </OneshotProvider<LoadSyntaxTheme>>
+        </ManagerProvider>
     }
 }
 
 #[function_component(GlobalStyle)]
 fn global_style() -> Html {
-    let theme = use_theme();
+    let fonts = use_fonts();
     html! {
-        <Global css={
-            css!(r#"
-                    body {
-                        background: ${bg};
-                        color: ${fg};
-                        margin: 0px;
-                        font-family: "hermit";
-                    }
-
-                    :where(h1) {
-                      margin-block: 0.67em;
-                      font-size: 2em;
-                    }
-                "#,
-                bg = theme.base01,
-                fg = theme.base06,
-            )
-        }/>
+        <>
+            <ThemeProperties/>
+            <Global css={
+                css!(r#"
+                        body {
+                            background: ${bg};
+                            color: ${fg};
+                            margin: 0px;
+                            font-family: ${font};
+                        }
+
+                        :where(h1) {
+                          margin-block: 0.67em;
+                          font-size: 2em;
+                        }
+                    "#,
+                    bg = ThemeColor::Base01.var(),
+                    fg = ThemeColor::Base06.var(),
+                    font = fonts.font_family(),
+                )
+            }/>
+        </>
     }
 }
 
@@ -67,6 +112,10 @@ fn home_page() -> Html {
                 text-align: center;
             }
 
+            .hero {
+                transition: transform 0.3s ease-out;
+            }
+
             p {
                 text-align: center;
                 margin: 2px 15%;
@@ -81,23 +130,89 @@ fn home_page() -> Html {
                 margin: 20px 0;
             }
 
+            ul.grid {
+                display: grid;
+                grid-template-columns: repeat(auto-fill, minmax(300px, 1fr));
+                gap: 20px;
+                margin: 0px 10%;
+            }
+
+            ul.grid li {
+                margin: 0px;
+            }
+
+            .layout {
+                display: flex;
+                justify-content: end;
+                align-items: center;
+                column-gap: 10px;
+                margin: 0px 10%;
+            }
+
             section {
                 margin-bottom: 50px;
             }
         "#
     );
-    let projects = Project::iter().map(|project| html! { <li><ProjectPreview {project}/></li> });
+    let hero_heading = use_node_ref();
+    let hero_text = use_node_ref();
+    use_parallax(hero_heading.clone(), 12.0, ParallaxScope::Window);
+    use_parallax(hero_text.clone(), 5.0, ParallaxScope::Window);
+
+    let layout = use_state(|| {
+        gloo_storage::LocalStorage::get(PROJECT_LAYOUT_STORAGE_KEY).unwrap_or_default()
+    });
+    use_effect_with(*layout, |layout| {
+        gloo_storage::LocalStorage::set(PROJECT_LAYOUT_STORAGE_KEY, layout).unwrap()
+    });
+    let grid = *layout == ProjectLayout::Grid;
+
+    let external_projects = use_external_projects();
+    let (featured, projects): (Vec<_>, Vec<_>) = Project::sorted()
+        .into_iter()
+        .partition(|project| project.meta().featured);
+    let has_featured = !featured.is_empty();
+    let featured = featured.into_iter().map(|project| {
+        html! { <li><ProjectPreview {project} large=true/></li> }
+    });
+    let projects = projects.into_iter().map(|project| {
+        if grid {
+            html! { <li><ProjectCard {project}/></li> }
+        } else {
+            html! { <li><ProjectPreview {project}/></li> }
+        }
+    });
+    let external_projects = external_projects.iter().map(|project| {
+        html! { <li><ExternalProjectPreview project={project.clone()} {grid}/></li> }
+    });
     html! {
         <div class={style}>
             <Section title="Welcome" hide_title=true>
-                <h1>{"Cute Codlings"}</h1>
-                <p>{"So you found your way to our little website?"}</p>
-                <p>{"Lucky you :)"}</p>
-                <p>{"Feel free to wander around and enjoy our little codlings." }</p>
+                <h1 class="hero" ref={hero_heading}>{"Cute Codlings"}</h1>
+                <div class="hero" ref={hero_text}>
+                    <p>{"So you found your way to our little website?"}</p>
+                    <p>{"Lucky you :)"}</p>
+                    <p>{"Feel free to wander around and enjoy our little codlings." }</p>
+                </div>
             </Section>
+            if has_featured {
+                <Section title="Featured">
+                    <ul>
+                        {for featured}
+                    </ul>
+                </Section>
+            }
             <Section title="Projects">
-                <ul>
+                <div class="layout">
+                    <label>{"Layout"}</label>
+                    <Selection<ProjectLayout>
+                        value={layout}
+                        options={ProjectLayout::iter().map(SelectionOption::new).collect::<Box<[_]>>()}
+                    />
+                </div>
+                <ul class={classes!(grid.then_some("grid"))}>
                     {for projects}
+                    {for external_projects}
                 </ul>
             </Section>
         </div>
@@ -125,7 +240,7 @@ fn not_fount_page() -> Html {
         <div class={style}>
             <h1>{"Page Not Found"}</h1>
             <div>
-                <img src="assets/images/cod_question_512.png"/>
+                <img src={asset_version::versioned("assets/images/cod_question_512.png")}/>
             </div>
         </div>
     }
@@ -135,6 +250,7 @@ fn not_fount_page() -> Html {
 #[function_component(TestPage)]
 fn test_page() -> Html {
     let theme = use_theme();
+    let style_manager = csp::use_style_manager();
     let colors: Html = [
         &theme.base00,
         &theme.base01,
@@ -157,7 +273,9 @@ fn test_page() -> Html {
     .enumerate()
     .map(|(i, color)| {
         html! {
-            <p style={format!("color:{color};")}>{format!("⏹ Base 0{i:X?}")}</p>
+            <p class={csp::manager_class(&style_manager, css!("color: ${color};", color = color))}>
+                {format!("⏹ Base 0{i:X?}")}
+            </p>
         }
     })
     .collect();
@@ -166,6 +284,9 @@ fn test_page() -> Html {
             <Section title="Colors">
                 {colors}
             </Section>
+            <Section title="Theme Comparison">
+                <ThemeComparisonTest/>
+            </Section>
             <Section title="Syntax Themes">
                 <Suspense fallback="Loading syntax themes">
                     <SyntaxThemesTest/>
@@ -175,6 +296,147 @@ fn test_page() -> Html {
     }
 }
 
+/// Renders the same page fragments under every theme side by side, for reviewing palette changes
+#[cfg(debug_assertions)]
+#[function_component(ThemeComparisonTest)]
+fn test_theme_comparison() -> Html {
+    use projects::interactive::Checkbox;
+    use theme::{ThemeKind, ThemeScope};
+
+    let style = use_style!(
+        r#"
+            .fragments {
+                display: flex;
+                column-gap: 20px;
+                margin-bottom: 10px;
+            }
+
+            .fragments label {
+                display: flex;
+                align-items: center;
+                column-gap: 5px;
+            }
+
+            .themes {
+                display: grid;
+                grid-template-columns: repeat(auto-fit, minmax(400px, 1fr));
+                gap: 10px;
+            }
+        "#
+    );
+    let preview_card = use_state(|| true);
+    let code_example = use_state(|| true);
+    let settings_grid = use_state(|| true);
+
+    let themes = ThemeKind::iter().map(|kind| {
+        html! {
+            <ThemeScope key={kind.to_string()} {kind}>
+                <ThemeSample
+                    preview_card={*preview_card}
+                    code_example={*code_example}
+                    settings_grid={*settings_grid}
+                />
+            </ThemeScope>
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <div class="fragments">
+                <label><Checkbox value={preview_card.clone()}/>{"Preview card"}</label>
+                <label><Checkbox value={code_example.clone()}/>{"Code example"}</label>
+                <label><Checkbox value={settings_grid.clone()}/>{"Settings grid"}</label>
+            </div>
+            <div class="themes">
+                {for themes}
+            </div>
+        </div>
+    }
+}
+
+/// Properties for the [`ThemeSample`] component
+#[cfg(debug_assertions)]
+#[derive(Debug, PartialEq, Properties)]
+struct ThemeSampleProperties {
+    /// Whether to show a project preview card
+    preview_card: bool,
+    /// Whether to show a code example
+    code_example: bool,
+    /// Whether to show a settings grid
+    settings_grid: bool,
+}
+
+/// A representative page fragment in the current theme, on the page background
+#[cfg(debug_assertions)]
+#[function_component(ThemeSample)]
+fn theme_sample(props: &ThemeSampleProperties) -> Html {
+    use projects::interactive::{Checkbox, ColorPicker, Slider};
+
+    let theme = use_theme();
+    let style = use_style!(
+        r#"
+            display: flex;
+            flex-direction: column;
+            row-gap: 10px;
+            padding: 10px;
+            color: ${fg};
+            background-color: ${bg};
+
+            a {
+                color: ${link_fg};
+            }
+
+            .settings {
+                display: grid;
+                grid-template-columns: max-content auto max-content auto;
+                column-gap: 20px;
+                background-color: ${settings_bg};
+                padding: 10px 20px;
+                align-items: center;
+            }
+
+            .settings * {
+                font-size: 13px;
+            }
+        "#,
+        fg = ThemeColor::Base06.var(),
+        bg = ThemeColor::Base01.var(),
+        link_fg = ThemeColor::Base0C.var(),
+        settings_bg = ThemeColor::Base00.var(),
+    );
+    let slider = use_state(|| 0.5);
+    let checkbox = use_state(|| true);
+    let color = use_state(|| color::AlphaColor::from_rgb8(0xff, 0xff, 0xff));
+
+    html! {
+        <div class={style}>
+            <h3>{theme.kind().to_string()}</h3>
+            if props.preview_card {
+                <ProjectCard project={Project::sorted()[0]}/>
+            }
+            if props.code_example {
+                <CodeExample lang="Rust">
+                    {indoc::indoc! {r#"
+                        pub fn main() {
+                            println!("Hello World");
+                        }
+                    "#}}
+                </CodeExample>
+            }
+            if props.settings_grid {
+                <div class="settings">
+                    <p>{"Slider"}</p>
+                    <Slider<f32> min={0.0} max={1.0} step={0.1} value={slider}/>
+                    <p>{"Checkbox"}</p>
+                    <Checkbox value={checkbox}/>
+                    <p>{"Color"}</p>
+                    <ColorPicker value={color}/>
+                </div>
+            }
+        </div>
+    }
+}
+
 #[function_component(SyntaxThemesTest)]
 fn test_syntax_themes() -> HtmlResult {
     let highlight_set = use_highlight_set()?;
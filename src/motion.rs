@@ -0,0 +1,219 @@
+//! Site-wide pausing of all animations and motion effects
+
+use gloo::{events::EventListener, utils::window};
+use gloo_storage::Storage;
+use stylist::yew::use_style;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, MouseEvent};
+use yew::{
+    Callback, Children, ContextProvider, Html, NodeRef, Properties, UseStateHandle,
+    function_component, hook, html, use_context, use_effect_with, use_state,
+};
+
+use crate::{theme::ThemeColor, webgl::CanvasScheduler};
+
+const PAUSE_STORAGE_KEY: &str = "PauseAnimations";
+
+/// A context used to relay whether animations are paused through the website
+#[derive(Debug, Clone, PartialEq)]
+pub struct PauseContext {
+    /// Whether all animations are paused
+    inner: UseStateHandle<bool>,
+}
+
+impl PauseContext {
+    /// Pause or resume all animations, persisting the choice
+    pub fn set(&self, paused: bool) {
+        gloo_storage::LocalStorage::set(PAUSE_STORAGE_KEY, paused).unwrap();
+        self.inner.set(paused)
+    }
+
+    /// Returns whether all animations are paused
+    pub fn paused(&self) -> bool {
+        *self.inner
+    }
+}
+
+/// Properties for the [`PauseProvider`]
+#[derive(Debug, PartialEq, Properties)]
+pub(crate) struct PauseProviderProps {
+    pub children: Children,
+}
+
+/// A context provider for the [`PauseContext`].
+///
+/// Also provides the surrounding [`CanvasScheduler`] with all render loops paused accordingly.
+#[function_component(PauseProvider)]
+pub(crate) fn pause_provider(props: &PauseProviderProps) -> Html {
+    // Follow the reduced motion preference until the user chooses explicitly
+    let paused = use_state(|| {
+        gloo_storage::LocalStorage::get(PAUSE_STORAGE_KEY)
+            .unwrap_or_else(|_| prefers_reduced_motion())
+    });
+    let scheduler = use_context::<CanvasScheduler>().unwrap_or_default();
+
+    let scheduler = CanvasScheduler {
+        paused: *paused,
+        ..scheduler
+    };
+    let pause_ctx = PauseContext { inner: paused };
+
+    html! {
+        <ContextProvider<PauseContext> context={pause_ctx}>
+            <ContextProvider<CanvasScheduler> context={scheduler}>
+                {props.children.clone()}
+            </ContextProvider<CanvasScheduler>>
+        </ContextProvider<PauseContext>>
+    }
+}
+
+/// A convenient hook for accessing the current pause context.
+///
+/// # Panics
+/// If [`PauseContext`] has not been provided.
+#[hook]
+pub fn use_pause() -> PauseContext {
+    use_context::<PauseContext>().unwrap()
+}
+
+/// Returns whether the user asked the system to minimize motion
+pub(crate) fn prefers_reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// What the cursor position is measured against by [`use_parallax`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallaxScope {
+    /// Follow the cursor anywhere in the window, relative to the window center
+    Window,
+    /// Follow the cursor while hovering the element, relative to the element center
+    Element,
+}
+
+/// A hook shifting the given element with the cursor position, giving it a sense of depth.
+///
+/// The element moves by at most `depth` pixels in each direction, larger depths appear closer
+/// to the viewer. The transform is written to the element style directly, so moving the cursor
+/// does not re-render the component. Disabled while animations are paused or the user prefers
+/// reduced motion.
+#[hook]
+pub fn use_parallax(node: NodeRef, depth: f64, scope: ParallaxScope) {
+    let enabled = !use_pause().paused() && !prefers_reduced_motion();
+
+    use_effect_with(
+        (node, depth, scope, enabled),
+        |(node, depth, scope, enabled)| {
+            let element = node.cast::<HtmlElement>().filter(|_| *enabled);
+            let listeners = element.clone().map(|element| {
+                let depth = *depth;
+                let scope = *scope;
+                let target = match scope {
+                    ParallaxScope::Window => window().into(),
+                    ParallaxScope::Element => element.clone().into(),
+                };
+
+                let on_move = EventListener::new(&target, "mousemove", {
+                    let element = element.clone();
+                    move |event| {
+                        let event: &MouseEvent = event.unchecked_ref();
+                        let (x, y) = (f64::from(event.client_x()), f64::from(event.client_y()));
+                        let (center, half_size) = match scope {
+                            ParallaxScope::Window => {
+                                let width = window().inner_width().unwrap().as_f64().unwrap();
+                                let height = window().inner_height().unwrap().as_f64().unwrap();
+                                ((width / 2.0, height / 2.0), (width / 2.0, height / 2.0))
+                            }
+                            ParallaxScope::Element => {
+                                let rect = element.get_bounding_client_rect();
+                                (
+                                    (
+                                        rect.left() + rect.width() / 2.0,
+                                        rect.top() + rect.height() / 2.0,
+                                    ),
+                                    (rect.width() / 2.0, rect.height() / 2.0),
+                                )
+                            }
+                        };
+                        let offset = (
+                            ((x - center.0) / half_size.0.max(1.0)).clamp(-1.0, 1.0) * depth,
+                            ((y - center.1) / half_size.1.max(1.0)).clamp(-1.0, 1.0) * depth,
+                        );
+                        set_parallax_offset(&element, Some(offset));
+                    }
+                });
+                let on_leave = (scope == ParallaxScope::Element).then(|| {
+                    EventListener::new(&element.clone(), "mouseleave", move |_| {
+                        set_parallax_offset(&element, None)
+                    })
+                });
+
+                (on_move, on_leave)
+            });
+
+            move || {
+                drop(listeners);
+                if let Some(element) = element {
+                    set_parallax_offset(&element, None);
+                }
+            }
+        },
+    );
+}
+
+/// Apply a parallax offset in pixels to the element, or reset it
+fn set_parallax_offset(element: &HtmlElement, offset: Option<(f64, f64)>) {
+    let style = element.style();
+    match offset {
+        Some((x, y)) => {
+            let _ = style.set_property("transform", &format!("translate({x:.1}px, {y:.1}px)"));
+        }
+        None => {
+            let _ = style.remove_property("transform");
+        }
+    }
+}
+
+/// A navigation bar button pausing or resuming all animations
+#[function_component(PauseToggle)]
+pub fn pause_toggle() -> Html {
+    let pause = use_pause();
+    let style = use_style!(
+        r#"
+            height: 100%;
+            font-size: 30px;
+            padding: 0px 10px;
+            color: ${fg};
+            background-color: ${bg};
+            border: none;
+
+            :hover {
+                background-color: ${bg_hover};
+            }
+        "#,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
+    );
+    let toggle = Callback::from({
+        let pause = pause.clone();
+        move |_| pause.set(!pause.paused())
+    });
+    let title = if pause.paused() {
+        "Resume all animations"
+    } else {
+        "Pause all animations"
+    };
+    html! {
+        <button class={style} {title} onclick={toggle}>
+            if pause.paused() {
+                <i class="iconoir-play"/>
+            } else {
+                <i class="iconoir-pause"/>
+            }
+        </button>
+    }
+}
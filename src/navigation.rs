@@ -4,12 +4,22 @@ use std::{fmt::Display, rc::Rc};
 
 use convert_case::Casing;
 use strum::IntoEnumIterator as _;
-use stylist::{css, yew::use_style};
+use stylist::yew::use_style;
 use yew::prelude::*;
 use yew_router::{BrowserRouter, Routable, Switch, prelude::Link};
 
+use gloo::{timers::callback::Timeout, utils::document};
+
 use crate::{
-    HomePage, NotFoundPage, about::AboutPage, projects::Project, theme::ThemeSelector, use_theme,
+    HomePage, NotFoundPage,
+    about::AboutPage,
+    announcements::AnnouncementBanner,
+    blog::{Post, PostPage},
+    changes::ChangesPage,
+    motion::PauseToggle,
+    power::LowPowerToggle,
+    projects::{EmbedPage, ExampleId, Project},
+    theme::{ThemeAccent, ThemeColor, ThemeSelector},
 };
 
 #[cfg(debug_assertions)]
@@ -17,6 +27,9 @@ use crate::TestPage;
 
 type Context = UseReducerHandle<NavigationContext>;
 
+/// The number of locations remembered by the navigation history
+const HISTORY_LENGTH: usize = 10;
+
 /// The available routes for the website
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Routable)]
 pub enum Route {
@@ -26,6 +39,15 @@ pub enum Route {
     Project { project: Project },
     #[at("/about")]
     About,
+    #[at("/changes")]
+    Changes,
+    #[at("/blog/:post")]
+    Post { post: Post },
+    #[at("/embed/:project/:example")]
+    Embed {
+        project: Project,
+        example: ExampleId,
+    },
     #[cfg(debug_assertions)]
     #[at("/test")]
     Test,
@@ -42,10 +64,13 @@ impl Display for Route {
             match self {
                 Route::Home => "Home",
                 Route::About => "About",
+                Route::Changes => "Changes",
                 #[cfg(debug_assertions)]
                 Route::Test => "Test",
                 Route::NotFound => "Not Found",
                 Route::Project { project } => project.meta().title,
+                Route::Post { post } => post.title(),
+                Route::Embed { project, .. } => project.meta().title,
             }
         )
     }
@@ -84,6 +109,32 @@ impl SectionData {
     pub fn id(&self) -> &AttrValue {
         &self.id
     }
+
+    /// Returns the section level
+    pub fn level(&self) -> usize {
+        self.level
+    }
+}
+
+/// A location visited during this session
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// The visited route
+    route: Route,
+    /// The visited section of the route, if any
+    section: Option<SectionData>,
+}
+
+impl HistoryEntry {
+    /// Returns the visited route
+    pub fn route(&self) -> Route {
+        self.route
+    }
+
+    /// Returns the visited section, if any
+    pub fn section(&self) -> Option<&SectionData> {
+        self.section.as_ref()
+    }
 }
 
 /// Actions for modifying the [`NavigationContext`]
@@ -97,6 +148,11 @@ pub enum NavigationContextAction {
     },
     /// Set the current [`Route`] for the context
     SetRoute(Route),
+    /// Record a visit of the current [`Route`] in the history
+    Visit {
+        /// The id of the visited section, if any
+        section_id: Option<AttrValue>,
+    },
 }
 
 /// The navigation context, keeping track of currently relevant [`Sections`](Section)
@@ -106,6 +162,25 @@ pub struct NavigationContext {
     route: Route,
     /// The flat [`Section`] tree (Optional parent id, data)
     sections: Vec<(Option<AttrValue>, SectionData)>,
+    /// The locations visited this session, most recent first
+    history: Vec<HistoryEntry>,
+}
+
+impl NavigationContext {
+    /// Returns the current [`Route`]
+    pub fn route(&self) -> Route {
+        self.route
+    }
+
+    /// Returns all sections registered for the current route
+    pub fn sections(&self) -> impl Iterator<Item = &SectionData> {
+        self.sections.iter().map(|(_, data)| data)
+    }
+
+    /// Returns the locations visited this session, most recent first
+    pub fn history(&self) -> &[HistoryEntry] {
+        &self.history
+    }
 }
 
 impl Reducible for NavigationContext {
@@ -127,6 +202,30 @@ impl Reducible for NavigationContext {
                     this.sections.clear();
                 }
             }
+            NavigationContextAction::Visit { section_id } => {
+                // Sections may not be registered yet when visiting a page, derive the title from
+                // the id in that case
+                let section = section_id.map(|id| {
+                    this.sections()
+                        .find(|data| *data.id() == id)
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            let title = id.as_str().to_case(convert_case::Case::Title).into();
+                            SectionData::new_with_id(title, id, 0)
+                        })
+                });
+                let entry = HistoryEntry {
+                    route: this.route,
+                    section,
+                };
+                this.history.retain(|visited| {
+                    visited.route != entry.route
+                        || visited.section().map(SectionData::id)
+                            != entry.section().map(SectionData::id)
+                });
+                this.history.insert(0, entry);
+                this.history.truncate(HISTORY_LENGTH);
+            }
         }
 
         self
@@ -163,21 +262,34 @@ fn switch(route: Route) -> Html {
     let content = match route {
         Route::Home => html! { <HomePage/> },
         Route::About => html! { <AboutPage/> },
+        Route::Changes => html! { <ChangesPage/> },
+        Route::Post { post } => html! { <PostPage {post}/> },
         Route::NotFound => html! { <NotFoundPage/> },
         #[cfg(debug_assertions)]
         Route::Test => html! { <TestPage/> },
         Route::Project { project } => project.html(),
+        // Embedded examples are shown without any navigation
+        Route::Embed { project, example } => {
+            return html! {
+                <ThemeAccent accents={project.meta().accents}>
+                    <EmbedPage {project} {example}/>
+                </ThemeAccent>
+            };
+        }
+    };
+    let accents = match route {
+        Route::Project { project } => project.meta().accents,
+        _ => &[],
     };
 
     html! {
-        <>
+        <ThemeAccent {accents}>
             <NavBar route={route} height={NAV_BAR_HEIGHT} sidebar_width={NAV_BAR_WIDTH}/>
-            <div class={css!("translate: 0px ${height};", height = NAV_BAR_HEIGHT)}>
-                <SwitchInner {route}>
-                    {content}
-                </SwitchInner>
-            </div>
-        </>
+            <SwitchInner {route} offset={NAV_BAR_HEIGHT}>
+                <AnnouncementBanner/>
+                {content}
+            </SwitchInner>
+        </ThemeAccent>
     }
 }
 
@@ -186,18 +298,55 @@ fn switch(route: Route) -> Html {
 struct SwitchInnerProperties {
     /// The current route
     route: Route,
+    /// The height of the navigation bar the page is moved below
+    offset: AttrValue,
     /// children
     children: Children,
 }
 
-/// A wrapper component to update the [`NavigationContext`] route
+/// A wrapper component to update the [`NavigationContext`] route, placing the page below the
+/// navigation bar
 #[function_component(SwitchInner)]
 fn switch_inner(props: &SwitchInnerProperties) -> Html {
+    let style = use_style!("translate: 0px ${offset};", offset = props.offset);
     let context = use_navigation_context();
     context.dispatch(NavigationContextAction::SetRoute(props.route));
 
+    let hash = yew_router::hooks::use_location()
+        .map(|location| location.hash().to_owned())
+        .unwrap_or_default();
+    use_effect_with((props.route, hash), move |(_, hash)| {
+        let section_id = hash
+            .strip_prefix('#')
+            .filter(|id| !id.is_empty())
+            .map(|id| AttrValue::from(id.to_owned()));
+        context.dispatch(NavigationContextAction::Visit { section_id });
+
+        #[cfg(debug_assertions)]
+        warn_unknown_section(hash);
+    });
+
     html! {
-        {props.children.clone()}
+        <div class={style}>
+            {props.children.clone()}
+        </div>
+    }
+}
+
+/// Warn if the url hash links to neither a section nor a section alias of the current page
+#[cfg(debug_assertions)]
+fn warn_unknown_section(hash: &str) {
+    let Some(id) = hash.strip_prefix('#').filter(|id| !id.is_empty()) else {
+        return;
+    };
+    let links_section = document()
+        .get_element_by_id(id)
+        .is_some_and(|element| element.matches("section, .section-alias").unwrap_or(false));
+    if !links_section {
+        log::warn!(
+            "The url links to the unknown section `{id}`, if a section was renamed consider adding \
+             its old id to `alias_ids`"
+        );
     }
 }
 
@@ -217,7 +366,6 @@ struct NavigationBarProperties {
 /// Additionally an expandable sidebar is also created for listing relevant sections.
 #[function_component(NavBar)]
 fn navigation_bar(props: &NavigationBarProperties) -> Html {
-    let theme = use_theme();
     let nav_bar_style = use_style!(
         r#"
             position: fixed;
@@ -257,10 +405,15 @@ fn navigation_bar(props: &NavigationBarProperties) -> Html {
             .sidebar-button:hover {
                 background-color: ${bg_hover};
             }
+
+            .controls {
+                margin: 0px 10px;
+                display: flex;
+            }
         "#,
-        fg = theme.base00,
-        bg = theme.base0D,
-        bg_hover = theme.base0C,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
         height = props.height,
     );
     let sidebar_visible = use_state(|| false);
@@ -280,7 +433,7 @@ fn navigation_bar(props: &NavigationBarProperties) -> Html {
     } else {
         &[]
     };
-    let buttons: Html = [Route::Home, Route::About]
+    let buttons: Html = [Route::Home, Route::Changes, Route::About]
         .into_iter()
         .chain(Project::iter().map(|project| project.route()))
         .chain(debug_pages.iter().copied())
@@ -312,7 +465,10 @@ fn navigation_bar(props: &NavigationBarProperties) -> Html {
                         {buttons}
                     </ul>
                 </nav>
-                <div class={css!("margin: 0px 10px;")}>
+                <div class="controls">
+                    <HistoryDropdown height={props.height.clone()}/>
+                    <LowPowerToggle/>
+                    <PauseToggle/>
                     <ThemeSelector/>
                 </div>
             </div>
@@ -326,6 +482,130 @@ fn navigation_bar(props: &NavigationBarProperties) -> Html {
     }
 }
 
+/// Properties for the [`HistoryDropdown`] component
+#[derive(Debug, PartialEq, Properties)]
+struct HistoryDropdownProperties {
+    /// The height of the navigation bar, the dropdown opens beneath it
+    height: AttrValue,
+}
+
+/// A navigation bar dropdown listing the recently visited routes and sections
+#[function_component(HistoryDropdown)]
+fn history_dropdown(props: &HistoryDropdownProperties) -> Html {
+    let style = use_style!(
+        r#"
+            position: relative;
+
+            .history-button {
+                height: 100%;
+                font-size: 30px;
+                padding: 0px 10px;
+                color: ${fg};
+                background-color: ${bg};
+                border: none;
+            }
+
+            .history-button:hover {
+                background-color: ${bg_hover};
+            }
+
+            .history-entries {
+                position: absolute;
+                top: ${height};
+                right: 0px;
+                min-width: 250px;
+                display: flex;
+                flex-direction: column;
+                background-color: ${list_bg};
+            }
+
+            .history-entries button, .history-entries span {
+                padding: 5px 10px;
+                text-align: left;
+                color: ${list_fg};
+                background-color: transparent;
+                border: none;
+            }
+
+            .history-entries button {
+                cursor: pointer;
+            }
+
+            .history-entries button:hover {
+                background-color: ${bg_hover};
+            }
+        "#,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
+        list_fg = ThemeColor::Base06.var(),
+        list_bg = ThemeColor::Base03.var(),
+        height = props.height,
+    );
+    let context = use_navigation_context();
+    let navigator = yew_router::hooks::use_navigator().unwrap();
+    let open = use_state(|| false);
+    let toggle = Callback::from({
+        let open = open.clone();
+        move |_| open.set(!*open)
+    });
+
+    // The most recent entry is the current location
+    let entries: Html = context
+        .history()
+        .iter()
+        .skip(1)
+        .map(|entry| {
+            let route = entry.route();
+            let section_id = entry.section().map(|section| section.id().clone());
+            let onclick = Callback::from({
+                let open = open.clone();
+                let navigator = navigator.clone();
+                let current_route = context.route();
+                move |_| {
+                    open.set(false);
+                    if route != current_route {
+                        navigator.push(&route);
+                    }
+                    if let Some(id) = section_id.clone() {
+                        // Wait for the route to be rendered before scrolling
+                        Timeout::new(0, move || {
+                            if let Some(section) = document().get_element_by_id(&id) {
+                                section.scroll_into_view();
+                            }
+                        })
+                        .forget();
+                    }
+                }
+            });
+            let text = match entry.section() {
+                Some(section) => format!("{route} › {}", section.title()),
+                None => route.to_string(),
+            };
+            html! {
+                <button {onclick}>{text}</button>
+            }
+        })
+        .collect();
+
+    html! {
+        <div class={style}>
+            <button class="history-button" title="Recently viewed" onclick={toggle}>
+                <i class="iconoir-clock-rotate-right"/>
+            </button>
+            if *open {
+                <div class="history-entries">
+                    if context.history().len() > 1 {
+                        {entries}
+                    } else {
+                        <span>{"Nothing else viewed yet"}</span>
+                    }
+                </div>
+            }
+        </div>
+    }
+}
+
 /// Properties for the [`NavigationButton`] component
 #[derive(Properties, PartialEq)]
 struct NavigationButtonProperties {
@@ -345,7 +625,6 @@ struct NavigationButtonProperties {
 #[function_component(NavigationButton)]
 fn navigation_button(props: &NavigationButtonProperties) -> Html {
     let route = props.route;
-    let theme = use_theme();
     let style = use_style!(
         r#"
             float: left;
@@ -369,13 +648,13 @@ fn navigation_button(props: &NavigationButtonProperties) -> Html {
             }
         "#,
         height = props.height,
-        fg = theme.base00,
+        fg = ThemeColor::Base00.var(),
         bg = if props.active {
-            &theme.base0B
+            ThemeColor::Base0B.var()
         } else {
-            &theme.base0D
+            ThemeColor::Base0D.var()
         },
-        bg_hover = theme.base0C,
+        bg_hover = ThemeColor::Base0C.var(),
     );
     html! {
         <li class={style}>
@@ -400,7 +679,6 @@ struct NavigationSidebarProperties {
 /// Creates an expandable sidebar for listing available sections
 #[function_component(NavigationSidebar)]
 fn navigation_sidebar(props: &NavigationSidebarProperties) -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             .sidebar {
@@ -424,7 +702,7 @@ fn navigation_sidebar(props: &NavigationSidebarProperties) -> Html {
             }
         "#,
         width = props.width,
-        bg = theme.base03,
+        bg = ThemeColor::Base03.var(),
         height = props.navigation_bar_height,
     );
     let classes = if props.visible {
@@ -432,12 +710,24 @@ fn navigation_sidebar(props: &NavigationSidebarProperties) -> Html {
     } else {
         classes!("sidebar", "sidebar-hidden")
     };
+
+    #[cfg(debug_assertions)]
+    let reading_heat_strip = html! {
+        <crate::dev::ReadingHeatStrip
+            left={if props.visible { props.width.clone() } else { AttrValue::from("0px") }}
+            top={props.navigation_bar_height.clone()}
+        />
+    };
+    #[cfg(not(debug_assertions))]
+    let reading_heat_strip = Html::default();
+
     html! {
         <div class={style}>
             <div class={classes}>
                 <h1>{props.route.to_string()}</h1>
                 <SectionLinks/>
             </div>
+            {reading_heat_strip}
         </div>
     }
 }
@@ -447,7 +737,6 @@ fn navigation_sidebar(props: &NavigationSidebarProperties) -> Html {
 #[function_component(SectionLinks)]
 fn section_links() -> Html {
     let context = use_navigation_context();
-    let theme = use_theme();
     let style = use_style!(
         r#"
             ol {
@@ -474,8 +763,8 @@ fn section_links() -> Html {
                 color: ${fg_hover};
             }
         "#,
-        fg = theme.base06,
-        fg_hover = theme.base07,
+        fg = ThemeColor::Base06.var(),
+        fg_hover = ThemeColor::Base07.var(),
     );
     let sections = context.sections.as_slice();
 
@@ -530,6 +819,9 @@ pub struct SectionProperties {
     pub hide_title: bool,
     /// The section title
     pub title: AttrValue,
+    /// Previous ids of this section, which keep old links working after renaming it
+    #[prop_or_default]
+    pub alias_ids: Vec<AttrValue>,
     /// The inner content
     pub children: Children,
 }
@@ -580,9 +872,14 @@ pub fn section(props: &SectionProperties) -> Html {
         4.. => html! { <h6>{props.title.clone()}</h6> },
     });
 
+    let aliases = props.alias_ids.iter().map(|alias| {
+        html! { <span id={alias.clone()} class="section-alias"/> }
+    });
+
     html! {
         <ContextProvider<SectionContext> context={new_context}>
             <section {id}>
+                {for aliases}
                 {title}
                 {props.children.clone()}
             </section>
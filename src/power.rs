@@ -0,0 +1,199 @@
+//! Site-wide low power mode
+
+use gloo::utils::window;
+use gloo_storage::Storage;
+use stylist::yew::use_style;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Function, Promise, Reflect};
+use yew::{
+    Callback, Children, ContextProvider, Html, Properties, UseStateHandle, classes,
+    function_component, hook, html, platform::spawn_local, use_context, use_effect_with, use_state,
+};
+
+use crate::{theme::ThemeColor, webgl::CanvasScheduler};
+
+const LOW_POWER_STORAGE_KEY: &str = "LowPower";
+
+/// The frame rate all canvases are capped at in low power mode
+const LOW_POWER_MAX_FPS: u32 = 30;
+
+/// The battery level below which low power mode is suggested
+const LOW_BATTERY_LEVEL: f64 = 0.2;
+
+/// A context used to relay the low power mode through the website
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowPowerContext {
+    /// Whether low power mode is enabled
+    inner: UseStateHandle<bool>,
+    /// Whether low power mode should be suggested to the user
+    suggested: UseStateHandle<bool>,
+}
+
+impl LowPowerContext {
+    /// Enable or disable low power mode, persisting the choice
+    pub fn set(&self, enabled: bool) {
+        gloo_storage::LocalStorage::set(LOW_POWER_STORAGE_KEY, enabled).unwrap();
+        self.suggested.set(false);
+        self.inner.set(enabled)
+    }
+
+    /// Returns whether low power mode is enabled
+    pub fn enabled(&self) -> bool {
+        *self.inner
+    }
+
+    /// Returns whether low power mode is suggested, because the device is mobile or low on battery
+    pub fn suggested(&self) -> bool {
+        *self.suggested
+    }
+
+    /// Returns the [`CanvasScheduler`] matching the current mode
+    pub fn scheduler(&self) -> CanvasScheduler {
+        CanvasScheduler {
+            max_fps: self.enabled().then_some(LOW_POWER_MAX_FPS),
+            low_power: self.enabled(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Properties for the [`LowPowerProvider`]
+#[derive(Debug, PartialEq, Properties)]
+pub(crate) struct LowPowerProviderProps {
+    pub children: Children,
+}
+
+/// A context provider for the [`LowPowerContext`] and the matching [`CanvasScheduler`]
+#[function_component(LowPowerProvider)]
+pub(crate) fn low_power_provider(props: &LowPowerProviderProps) -> Html {
+    let enabled =
+        use_state(|| gloo_storage::LocalStorage::get(LOW_POWER_STORAGE_KEY).unwrap_or_default());
+    let suggested = use_state(|| false);
+
+    use_effect_with((), {
+        let suggested = suggested.setter();
+        move |_| {
+            // Only suggest low power mode if the user never chose explicitly
+            if gloo_storage::LocalStorage::get::<bool>(LOW_POWER_STORAGE_KEY).is_err() {
+                spawn_local(async move {
+                    suggested.set(is_mobile() || is_low_on_battery().await);
+                });
+            }
+        }
+    });
+
+    let low_power_ctx = LowPowerContext {
+        inner: enabled,
+        suggested,
+    };
+    let scheduler = low_power_ctx.scheduler();
+
+    html! {
+        <ContextProvider<LowPowerContext> context={low_power_ctx}>
+            <ContextProvider<CanvasScheduler> context={scheduler}>
+                {props.children.clone()}
+            </ContextProvider<CanvasScheduler>>
+        </ContextProvider<LowPowerContext>>
+    }
+}
+
+/// A convenient hook for accessing the current low power context.
+///
+/// # Panics
+/// If [`LowPowerContext`] has not been provided.
+#[hook]
+pub fn use_low_power() -> LowPowerContext {
+    use_context::<LowPowerContext>().unwrap()
+}
+
+/// Returns whether the primary input is a touch screen, which indicates a mobile device
+pub(crate) fn is_mobile() -> bool {
+    window()
+        .match_media("(pointer: coarse)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// Query the battery status, returns `false` if the battery api is unavailable
+async fn is_low_on_battery() -> bool {
+    let navigator = window().navigator();
+    let Some(get_battery) = Reflect::get(&navigator, &JsValue::from_str("getBattery"))
+        .ok()
+        .and_then(|function| function.dyn_into::<Function>().ok())
+    else {
+        return false;
+    };
+    let Some(battery) = get_battery
+        .call0(&navigator)
+        .ok()
+        .and_then(|promise| promise.dyn_into::<Promise>().ok())
+    else {
+        return false;
+    };
+    let Ok(battery) = JsFuture::from(battery).await else {
+        return false;
+    };
+
+    let level = Reflect::get(&battery, &JsValue::from_str("level"))
+        .ok()
+        .and_then(|level| level.as_f64())
+        .unwrap_or(1.0);
+    let charging = Reflect::get(&battery, &JsValue::from_str("charging"))
+        .ok()
+        .and_then(|charging| charging.as_bool())
+        .unwrap_or(true);
+
+    level < LOW_BATTERY_LEVEL && !charging
+}
+
+/// A navigation bar button toggling low power mode
+#[function_component(LowPowerToggle)]
+pub fn low_power_toggle() -> Html {
+    let low_power = use_low_power();
+    let style = use_style!(
+        r#"
+            height: 100%;
+            font-size: 30px;
+            padding: 0px 10px;
+            color: ${fg};
+            background-color: ${bg};
+            border: none;
+
+            :hover {
+                background-color: ${bg_hover};
+            }
+
+            &.suggested {
+                color: ${suggested_fg};
+            }
+        "#,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
+        suggested_fg = ThemeColor::Base08.var(),
+    );
+    let toggle = Callback::from({
+        let low_power = low_power.clone();
+        move |_| low_power.set(!low_power.enabled())
+    });
+    let title = match (low_power.enabled(), low_power.suggested()) {
+        (true, _) => "Disable low power mode",
+        (false, true) => "Enable low power mode (recommended for this device)",
+        (false, false) => "Enable low power mode",
+    };
+    html! {
+        <button
+            class={classes!(style, low_power.suggested().then_some("suggested"))}
+            {title}
+            onclick={toggle}
+        >
+            if low_power.enabled() {
+                <i class="iconoir-battery-25"/>
+            } else {
+                <i class="iconoir-battery-full"/>
+            }
+        </button>
+    }
+}
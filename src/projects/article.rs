@@ -0,0 +1,271 @@
+//! A structured content model for project articles.
+//!
+//! Articles are defined as plain data and mapped onto the usual page components by
+//! [`ArticleView`], so other consumers can walk the same structure instead of the rendered html.
+//! They are usually written in markdown and loaded with [`article!`].
+
+use stylist::yew::use_style;
+use yew::prelude::*;
+use yew_router::{Routable, prelude::Link};
+
+use crate::{
+    navigation::{Route, Section},
+    projects::{CodeExample, ExampleId, Note, ProjectSite, math},
+    theme::ThemeColor,
+};
+
+pub use website_macros::article;
+
+/// A project article
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Article {
+    /// The title shown above the content
+    pub title: &'static str,
+    /// The top level content
+    pub content: &'static [Block],
+}
+
+impl Article {
+    /// Returns the text content of the whole article, with blocks separated by blank lines
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for block in self.content {
+            block.write_plain_text(&mut text);
+        }
+        text
+    }
+}
+
+/// A block of content within an [`Article`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Block {
+    /// A titled section, listed in the navigation sidebar
+    Section {
+        title: &'static str,
+        content: &'static [Block],
+    },
+    /// A paragraph of text
+    Paragraph(&'static [Inline]),
+    /// A highlighted note box
+    Note(&'static [Block]),
+    /// A syntax highlighted code listing
+    Code {
+        lang: &'static str,
+        code: &'static str,
+    },
+    /// An interactive example of the project, rendered by [`ArticleViewProperties::examples`]
+    Example(ExampleId),
+    /// An image with a caption
+    Figure {
+        src: &'static str,
+        caption: &'static str,
+    },
+    /// A displayed formula, written in TeX notation
+    Math(&'static str),
+}
+
+impl Block {
+    /// Append the text content of this block to the given string
+    fn write_plain_text(&self, text: &mut String) {
+        match self {
+            Block::Section { title, content } => {
+                push_paragraph(text, title);
+                for block in *content {
+                    block.write_plain_text(text);
+                }
+            }
+            Block::Paragraph(inlines) => {
+                let paragraph: String = inlines.iter().map(Inline::text).collect();
+                push_paragraph(text, &paragraph);
+            }
+            Block::Note(content) => {
+                for block in *content {
+                    block.write_plain_text(text);
+                }
+            }
+            Block::Code { code, .. } => push_paragraph(text, code),
+            Block::Example(_) => {}
+            Block::Figure { caption, .. } => push_paragraph(text, caption),
+            Block::Math(tex) => push_paragraph(text, tex),
+        }
+    }
+}
+
+/// Append a paragraph with collapsed whitespace to the given string
+fn push_paragraph(text: &mut String, paragraph: &str) {
+    if !text.is_empty() {
+        text.push_str("\n\n");
+    }
+    text.push_str(&paragraph.split_whitespace().collect::<Vec<_>>().join(" "));
+}
+
+/// Inline content of a [`Block::Paragraph`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Inline {
+    /// Plain text, whitespace is collapsed like in html
+    Text(&'static str),
+    /// Inline code
+    Code(&'static str),
+    /// A link, to another page of this website if the target starts with a `/`
+    Link {
+        text: &'static str,
+        href: &'static str,
+    },
+    /// A formula within the text, written in TeX notation
+    Math(&'static str),
+}
+
+impl Inline {
+    /// Returns the visible text
+    fn text(&self) -> &'static str {
+        match self {
+            Inline::Text(text)
+            | Inline::Code(text)
+            | Inline::Link { text, .. }
+            | Inline::Math(text) => text,
+        }
+    }
+
+    /// Render the inline content
+    fn render(&self) -> Html {
+        match *self {
+            Inline::Text(text) => html! { {text} },
+            Inline::Code(code) => html! { <code>{code}</code> },
+            Inline::Link { text, href } => {
+                // Pages of this website are navigated to without reloading, unlike other files
+                let route = href
+                    .starts_with('/')
+                    .then(|| Route::recognize(href))
+                    .flatten()
+                    .filter(|route| *route != Route::NotFound);
+                match route {
+                    Some(route) => html! { <Link<Route> to={route}>{text}</Link<Route>> },
+                    None => html! { <a {href}>{text}</a> },
+                }
+            }
+            Inline::Math(tex) => math::render(tex, false),
+        }
+    }
+}
+
+/// Properties for the [`ArticleView`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct ArticleViewProperties {
+    /// The rendered article
+    pub article: Article,
+    /// Renders the interactive examples of the article
+    pub examples: Callback<ExampleId, Html>,
+    /// Shown between the title and the content, e.g. the date and authors of a post
+    #[prop_or_default]
+    pub byline: Html,
+}
+
+/// Renders an [`Article`] as a project page
+#[function_component(ArticleView)]
+pub fn article_view(
+    ArticleViewProperties {
+        article,
+        examples,
+        byline,
+    }: &ArticleViewProperties,
+) -> Html {
+    html! {
+        <ProjectSite title={article.title}>
+            {byline.clone()}
+            {for article.content.iter().map(|block| render_block(block, examples))}
+        </ProjectSite>
+    }
+}
+
+/// Render a single block and its content
+fn render_block(block: &Block, examples: &Callback<ExampleId, Html>) -> Html {
+    let render_content = |content: &[Block]| -> Html {
+        content
+            .iter()
+            .map(|block| render_block(block, examples))
+            .collect()
+    };
+
+    match *block {
+        Block::Section { title, content } => html! {
+            <Section {title}>
+                {render_content(content)}
+            </Section>
+        },
+        Block::Paragraph(inlines) => html! {
+            <p>
+                {for inlines.iter().map(Inline::render)}
+            </p>
+        },
+        Block::Note(content) => html! {
+            <Note>
+                {render_content(content)}
+            </Note>
+        },
+        Block::Code { lang, code } => html! {
+            <CodeExample {lang}>
+                {code}
+            </CodeExample>
+        },
+        Block::Example(example) => examples.emit(example),
+        Block::Figure { src, caption } => html! { <Figure {src} {caption}/> },
+        Block::Math(tex) => html! { <MathBlock {tex}/> },
+    }
+}
+
+/// Properties for the [`Figure`] component
+#[derive(Debug, PartialEq, Properties)]
+struct FigureProperties {
+    src: &'static str,
+    caption: &'static str,
+}
+
+/// Displays an image with a caption beneath it
+#[function_component(Figure)]
+fn figure(FigureProperties { src, caption }: &FigureProperties) -> Html {
+    let style = use_style!(
+        r#"
+            margin: 10px 0px;
+            text-align: center;
+
+            img {
+                max-width: 100%;
+            }
+
+            figcaption {
+                color: ${caption_fg};
+                font-style: italic;
+            }
+        "#,
+        caption_fg = ThemeColor::Base04.var(),
+    );
+    html! {
+        <figure class={style}>
+            <img src={*src} alt={*caption}/>
+            <figcaption>{*caption}</figcaption>
+        </figure>
+    }
+}
+
+/// Properties for the [`MathBlock`] component
+#[derive(Debug, PartialEq, Properties)]
+struct MathBlockProperties {
+    tex: &'static str,
+}
+
+/// Displays a formula centered on its own line, scrolling horizontally if it is too wide
+#[function_component(MathBlock)]
+fn math_block(MathBlockProperties { tex }: &MathBlockProperties) -> Html {
+    let style = use_style!(
+        r#"
+            margin: 10px 0px;
+            overflow-x: auto;
+            font-size: 1.2em;
+        "#
+    );
+    html! {
+        <div class={style}>
+            {math::render(tex, true)}
+        </div>
+    }
+}
@@ -1,12 +1,19 @@
 precision mediump float;
  
+// The boid positions
 uniform sampler2D u_input_0;
+// The boid velocities
+uniform sampler2D u_input_1;
+// The separation falloff curve, sampled along the first row
+uniform sampler2D u_input_2;
 uniform vec2 u_dimensions;
 
 uniform vec2 u_space;
 uniform float u_cohesion;
 uniform float u_separation;
 uniform float u_alignment;
+// One of EDGE_AVOID, EDGE_WRAP or EDGE_BOUNCE
+uniform float u_edge_behavior;
 uniform float u_edge_avoidance;
 uniform float u_avoidance_radius;
 uniform float u_detection_radius;
@@ -14,12 +21,29 @@ uniform float u_min_velocity;
 uniform float u_max_velocity;
 uniform float u_max_acceleration;
 
+#define EDGE_AVOID 0.0
+#define EDGE_WRAP 1.0
+#define EDGE_BOUNCE 2.0
+
+// Returns the sample of the separation falloff curve with the given index
+float falloff_sample(float index) {
+   return texture2D(u_input_2, vec2((index + 0.5) / u_dimensions.x, 0.5 / u_dimensions.y)).r;
+}
+
+// Returns the separation falloff at the given relative distance, interpolating between the samples
+float falloff(float relative_distance) {
+   float position = clamp(relative_distance, 0.0, 1.0) * float(FALLOFF_SAMPLES - 1);
+   float lower = floor(position);
+   float upper = min(lower + 1.0, float(FALLOFF_SAMPLES - 1));
+   return mix(falloff_sample(lower), falloff_sample(upper), position - lower);
+}
+
 void main() {
-   vec4 data = texture2D(u_input_0, gl_FragCoord.xy / u_dimensions);
-   int max = 100; // TODO: make this a uniform
+   vec2 texcoord = gl_FragCoord.xy / u_dimensions;
+   vec2 position = texture2D(u_input_0, texcoord).xy;
+   vec2 velocity = texture2D(u_input_1, texcoord).xy;
    int myIndex = int(floor(gl_FragCoord.y) * u_dimensions.x
                   + floor(gl_FragCoord.x));
-   // float angle = atan(data.w, data.z);
 
 
    int num_friends = 0;
@@ -29,25 +53,25 @@ void main() {
    vec2 separation = vec2(0);
 
 
-   for (int i = 0; i < 10000; ++i) {
-      if (i < max) {
-         float fi = float(i);
-         float yIndex = floor(fi / u_dimensions.x) / u_dimensions.y + 0.5;
-         float xIndex = mod(fi, u_dimensions.x) / u_dimensions.x + 0.5;
-
-         vec4 other = texture2D(u_input_0, vec2(xIndex, yIndex));
-         if(other != data) {
-
-            float distance = length(other.xy - data.xy);
-            if(distance < u_detection_radius) {
-               num_friends += 1;
-               cohesion += other.xy;
-               //TODO: Zero case
-               alignment += normalize(other.wz);
-               if(distance < u_avoidance_radius) {
-                  num_avoid += 1;
-                  separation += data.xy - other.xy;
-               }
+   for (int i = 0; i < BOID_COUNT; ++i) {
+      float fi = float(i);
+      float yIndex = (floor(fi / u_dimensions.x) + 0.5) / u_dimensions.y;
+      float xIndex = (mod(fi, u_dimensions.x) + 0.5) / u_dimensions.x;
+
+      if(i != myIndex) {
+         vec2 other_position = texture2D(u_input_0, vec2(xIndex, yIndex)).xy;
+         vec2 other_velocity = texture2D(u_input_1, vec2(xIndex, yIndex)).xy;
+
+         float distance = length(other_position - position);
+         if(distance < u_detection_radius) {
+            num_friends += 1;
+            cohesion += other_position;
+            //TODO: Zero case
+            alignment += normalize(other_velocity);
+            // Boids at the same position have no direction to separate in
+            if(distance < u_avoidance_radius && distance > 0.0) {
+               num_avoid += 1;
+               separation += normalize(position - other_position) * falloff(distance / u_avoidance_radius);
             }
          }
       }
@@ -59,7 +83,7 @@ void main() {
 
    if(num_friends > 0) {
       cohesion = cohesion / float(num_friends);
-      cohesionVel = normalize(cohesion - data.xy) * u_cohesion;
+      cohesionVel = normalize(cohesion - position) * u_cohesion;
 
       alignment = alignment / float(num_friends);
       alignmentVel = normalize(alignment) * u_alignment;
@@ -69,21 +93,39 @@ void main() {
       }
    }
 
-   data.wz = (data.wz + alignmentVel + cohesionVel + separationVel);
+   vec2 acceleration = alignmentVel + cohesionVel + separationVel;
+
+   if(u_edge_behavior == EDGE_AVOID && length(position) > 0.95) {
+      acceleration += normalize(-position) * u_edge_avoidance;
+   }
 
-   if(length(data.xy) > 0.95) {
-      data.wz += normalize(-data.xy) * u_edge_avoidance;
+   if(length(acceleration) > u_max_acceleration) {
+      acceleration = normalize(acceleration) * u_max_acceleration;
    }
 
-   if(length(data.wz) > u_max_velocity) {
-      data.wz = normalize(data.wz) * u_max_velocity;
+   velocity += acceleration;
+
+   if(length(velocity) == 0.0){
+      velocity = vec2(0.0001);
    }
 
-   if(length(data.wz) == 0.0){
-      data.wz = vec2(0.0001);
+   velocity = normalize(velocity) * clamp(length(velocity), u_min_velocity, u_max_velocity);
+
+   position += velocity;
+
+   if(u_edge_behavior == EDGE_WRAP) {
+      position = mod(position + u_space, 2.0 * u_space) - u_space;
+   } else if(u_edge_behavior == EDGE_BOUNCE) {
+      if(abs(position.x) > u_space.x) {
+         position.x = sign(position.x) * u_space.x;
+         velocity.x = -velocity.x;
+      }
+      if(abs(position.y) > u_space.y) {
+         position.y = sign(position.y) * u_space.y;
+         velocity.y = -velocity.y;
+      }
    }
 
-   data.xy += data.wz;
-   
-   gl_FragColor = data;
+   position_output = vec4(position, 0.0, 1.0);
+   velocity_output = vec4(velocity, 0.0, 1.0);
 }
@@ -1,84 +1,222 @@
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
 mod render;
 
-use crate::projects::{
-    ProjectSite,
-    boids::render::{BoidsRenderInput, BoidsRenderer},
-    interactive::{InteractiveExample, Slider},
+use crate::{
+    about::Author,
+    dev::Tracked,
+    projects::{
+        ChangeDate, ChangelogEntry, ExampleId, Project, ProjectDescriptor, ProjectMeta,
+        article::{Article, ArticleView, article},
+        boids::render::{BoidsRenderer, EDGE_BEHAVIORS, EdgeBehavior},
+        interactive::{
+            AutoplayPolicy, Curve, CurveControl, ExampleSettings, Gradient, GradientControl,
+            GradientStop, InteractiveExample, Preset, RangeSliderControl, SettingsDescriptor,
+            SettingsPresets, ToggleGroupControl,
+        },
+    },
+    theme::{AccentOverride, ThemeColor},
+    webgl::use_renderer,
 };
 
-#[function_component(BoidsPage)]
-pub fn boids_page() -> Html {
-    let cohesion = use_state(|| 0.5);
-    let separation = use_state(|| 0.5);
-    let alignment = use_state(|| 0.5);
-    let edge_avoidance = use_state(|| 0.5);
-    let avoidance_radius = use_state(|| 0.1);
-    let detection_radius = use_state(|| 0.2);
-    let min_velocity = use_state(|| 0.005);
-    let max_velocity = use_state(|| 0.005);
-    let max_acceleration = use_state(|| 0.005);
+/// Input for the [`BoidsRenderer`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ExampleSettings)]
+#[settings(
+    storage_key = "boids",
+    hook = use_boids_settings,
+    handles = BoidsSettingsHandles,
+    key = BoidsSetting,
+)]
+pub struct BoidsRenderInput {
+    /// Weight for boids being attracted to the group center of mass
+    #[setting(key = Cohesion, label = "Cohesion", min = 0.0, max = 1.0, step = 0.1, default = 0.5)]
+    pub cohesion: f32,
+    /// Weight for boids being repelled by each other
+    #[setting(key = Separation, label = "Separation", min = 0.0, max = 1.0, step = 0.1, default = 0.5)]
+    pub separation: f32,
+    /// Strength of the separation depending on the distance relative to the avoidance radius
+    #[setting(key = SeparationFalloff, label = "Separation Falloff", control = CurveControl, default = Curve::new(vec![(0.0, 1.0), (1.0, 0.0)]))]
+    pub separation_falloff: Curve,
+    /// Weight for boids aligning to the same direction
+    #[setting(key = Alignment, label = "Alignment", min = 0.0, max = 1.0, step = 0.1, default = 0.5)]
+    pub alignment: f32,
+    /// What boids do when reaching the edges
+    #[setting(key = EdgeBehavior, label = "Edge Behavior", control = ToggleGroupControl { options: EDGE_BEHAVIORS }, default = EdgeBehavior::Avoid)]
+    pub edge_behavior: EdgeBehavior,
+    /// Weight for boids avoiding edges
+    #[setting(key = EdgeAvoidance, label = "Edge Avoidance", min = 0.0, max = 1.0, step = 0.1, default = 0.5)]
+    pub edge_avoidance: f32,
+    /// Radius for boids vision
+    #[setting(key = DetectionRadius, label = "Detection Radius", min = 0.0, max = 1.0, step = 0.1, default = 0.2)]
+    pub detection_radius: f32,
+    /// Radius for boids avoiding each other
+    #[setting(key = AvoidanceRadius, label = "Avoidance Radius", min = 0.0, max = 1.0, step = 0.1, default = 0.1)]
+    pub avoidance_radius: f32,
+    /// Minimum and maximum boid velocity
+    #[setting(
+        key = Velocity,
+        label = "Velocity",
+        control = RangeSliderControl { min: 0.0, max: 0.1, step: 0.005 },
+        default = (0.005, 0.005),
+    )]
+    pub velocity: (f32, f32),
+    /// Maximum boid acceleration
+    #[setting(key = MaximumAcceleration, label = "Maximum Acceleration", min = 0.0, max = 0.1, step = 0.005, default = 0.005)]
+    pub max_acceleration: f32,
+    /// Boid colors from the minimum to the maximum velocity
+    #[setting(key = SpeedColors, label = "Speed Colors", control = GradientControl)]
+    #[setting(default = Gradient::new(vec![
+        GradientStop::new(0.0, [153, 0, 255]),
+        GradientStop::new(1.0, [255, 64, 160]),
+    ]))]
+    pub speed_colors: Gradient,
+}
+
+/// The settings shown by the boids example, in the order of the fields
+const SETTINGS: SettingsDescriptor<BoidsSettingsHandles, ()> = {
+    use BoidsSetting::*;
+
+    SettingsDescriptor {
+        settings: &[
+            BoidsSettingsHandles::descriptor(Cohesion),
+            BoidsSettingsHandles::descriptor(Separation),
+            BoidsSettingsHandles::descriptor(SeparationFalloff),
+            BoidsSettingsHandles::descriptor(Alignment),
+            BoidsSettingsHandles::descriptor(EdgeBehavior),
+            BoidsSettingsHandles::descriptor(EdgeAvoidance),
+            BoidsSettingsHandles::descriptor(DetectionRadius),
+            BoidsSettingsHandles::descriptor(AvoidanceRadius),
+            BoidsSettingsHandles::descriptor(Velocity),
+            BoidsSettingsHandles::descriptor(MaximumAcceleration),
+            BoidsSettingsHandles::descriptor(SpeedColors),
+        ],
+        groups: &[],
+    }
+};
+
+/// The curated presets of the boids settings
+const PRESETS: &[Preset<BoidsRenderInput>] = &[
+    Preset {
+        name: "Schooling",
+        settings: || BoidsRenderInput {
+            cohesion: 0.8,
+            separation: 0.3,
+            alignment: 1.0,
+            detection_radius: 0.4,
+            velocity: (0.01, 0.02),
+            ..BoidsRenderInput::default()
+        },
+    },
+    Preset {
+        name: "Scatter",
+        settings: || BoidsRenderInput {
+            cohesion: 0.0,
+            separation: 1.0,
+            alignment: 0.1,
+            avoidance_radius: 0.3,
+            velocity: (0.02, 0.05),
+            max_acceleration: 0.02,
+            ..BoidsRenderInput::default()
+        },
+    },
+];
+
+/// The embeddable boids examples
+pub const EXAMPLES: &[ExampleId] = &[ExampleId::new("simulation")];
+
+/// The milliseconds simulated per step, so the boids move at the same speed on every display
+const BOIDS_TIMESTEP: u32 = 16;
+
+/// Returns the html of a single embedded example
+pub fn embed(example: ExampleId, show_settings: bool) -> Option<Html> {
+    (example == EXAMPLES[0]).then(|| html! { <BoidsExample embedded=true {show_settings}/> })
+}
+
+/// Properties for the [`BoidsExample`] component
+#[derive(Debug, PartialEq, Properties)]
+struct BoidsExampleProperties {
+    #[prop_or_default]
+    embedded: bool,
+    #[prop_or(true)]
+    show_settings: bool,
+}
 
-    let render_input = BoidsRenderInput {
-        cohesion: *cohesion,
-        separation: *separation,
-        alignment: *alignment,
-        edge_avoidance: *edge_avoidance,
-        avoidance_radius: *avoidance_radius,
-        detection_radius: *detection_radius,
-        min_velocity: *min_velocity,
-        max_velocity: *max_velocity,
-        max_acceleration: *max_acceleration,
-    };
+/// The boids simulation example with its settings
+#[function_component(BoidsExample)]
+fn boids_example(
+    BoidsExampleProperties {
+        embedded,
+        show_settings,
+    }: &BoidsExampleProperties,
+) -> Html {
+    let renderer = use_renderer(BoidsRenderer::default);
+    let settings = use_boids_settings();
+    let presets = use_memo((), |_| SettingsPresets::new(PRESETS));
 
-    let settings = vec![
-        (
-            "Cohesion".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={cohesion}/> },
-        ),
-        (
-            "Separation".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={separation}/> },
-        ),
-        (
-            "Alignment".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={alignment}/> },
-        ),
-        (
-            "Edge Avoidance".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={edge_avoidance}/> },
-        ),
-        (
-            "Detection Radius".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={detection_radius}/> },
-        ),
-        (
-            "Avoidance Radius".to_string(),
-            html! { <Slider<f32> min={0.0} max={1.0} step={0.1} value={avoidance_radius}/> },
-        ),
-        (
-            "Minimum Velocity".to_string(),
-            html! { <Slider<f32> min={0.0} max={0.1} step={0.005} value={min_velocity}/> },
-        ),
-        (
-            "Maximum Velocity".to_string(),
-            html! { <Slider<f32> min={0.0} max={0.1} step={0.005} value={max_velocity}/> },
-        ),
-        (
-            "Maximum Acceleration".to_string(),
-            html! { <Slider<f32> min={0.0} max={0.1} step={0.005} value={max_acceleration}/> },
-        ),
-    ];
+    html! {
+        <Tracked<InteractiveExample<BoidsRenderer>>
+            {renderer}
+            render_input={settings.value()}
+            initially_active=true
+            settings={SETTINGS.panel(&settings, ())}
+            title={Project::Boids.meta().title}
+            show_settings={*show_settings}
+            height={if *embedded { "100vh" } else { "500px" }}
+            example={EXAMPLES[0]}
+            embed_route={(!*embedded).then(|| Project::Boids.embed_route(EXAMPLES[0]))}
+            settings_transfer={settings.transfer()}
+            on_reset_settings={settings.reset_callback()}
+            on_randomize_settings={settings.randomize_callback()}
+            presets={(*presets).clone()}
+            autoplay={
+                if *embedded { AutoplayPolicy::Always } else { AutoplayPolicy::for_device() }
+            }
+            fixed_timestep={Some(BOIDS_TIMESTEP)}
+            frame_controls=true
+            resettable=true
+        />
+    }
+}
 
+/// The registration of this project, see [`Project::descriptor`]
+pub const DESCRIPTOR: ProjectDescriptor = ProjectDescriptor {
+    meta: ProjectMeta {
+        title: "Boids",
+        description: indoc::indoc! {"
+            This interactive tutorial guides you through implementing the Boids algorithm,
+            originally developed by Craig Reynolds in 1986, using a compute shader.
+        "},
+        authors: &[Author::DawnFirefly],
+        tags: &["webgl", "compute-shader", "simulation"],
+        order: 1,
+        featured: false,
+        // Purple, like the default speed colors of the boids
+        accents: &[AccentOverride {
+            color: ThemeColor::Base0D,
+            with: ThemeColor::Base0E,
+        }],
+        changelog: &[ChangelogEntry {
+            date: ChangeDate {
+                year: 2026,
+                month: 10,
+                day: 16,
+            },
+            summary: "Simulations can be recorded and played back as replays",
+        }],
+    },
+    page: || html! { <BoidsPage/> },
+    examples: EXAMPLES,
+    embed,
+};
+
+/// The boids article
+pub const ARTICLE: Article = article!("articles/boids.md");
+
+#[function_component(BoidsPage)]
+pub fn boids_page() -> Html {
+    let examples = Callback::from(|_| html! { <BoidsExample/> });
     html! {
-        <ProjectSite title="Boids">
-            <InteractiveExample<BoidsRenderer>
-                renderer={BoidsRenderer {}}
-                render_input={render_input.clone()}
-                initially_active=true
-                settings={settings.clone()}
-            />
-        </ProjectSite>
+        <ArticleView article={ARTICLE} {examples}/>
     }
 }
@@ -1,5 +1,9 @@
 precision mediump float;
 
+uniform sampler2D u_speed_colors;
+
+varying float v_speed;
+
 void main() {
-    gl_FragColor = vec4(0.6, 0.0, 1.0, 1.0);
+    gl_FragColor = vec4(texture2D(u_speed_colors, vec2(v_speed, 0.5)).rgb, 1.0);
 }
@@ -1,9 +1,18 @@
-use web_sys::js_sys::Math::random;
-use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext as GL};
+use std::fmt::Display;
 
+use web_sys::{WebGlProgram, WebGlRenderingContext as GL, WebGlTexture};
+
+use crate::projects::boids::BoidsRenderInput;
+use crate::projects::interactive::SelectionOption;
 use crate::uniform_set;
-use crate::webgl::{CanvasRenderer, RenderData, Uniform, create_program};
-use crate::webgl::{ComputeProgram, compile_shader};
+use crate::webgl::{
+    AttributeType, CanvasRenderer, Geometry, RenderData, TextureUniform, TextureUnits, Uniform,
+    VertexLayout, WebglError, create_program, create_ramp_texture, instancing_supported,
+    update_ramp_texture,
+};
+use crate::webgl::{
+    ComputeProgram, GpuTimer, SeededRng, compile_shader, preprocess_shader, webgl2,
+};
 
 uniform_set! {
     ComputeUniformSet {
@@ -11,6 +20,7 @@ uniform_set! {
         u_cohesion: (f32,),
         u_separation: (f32,),
         u_alignment: (f32,),
+        u_edge_behavior: (f32,),
         u_edge_avoidance: (f32,),
         u_avoidance_radius: (f32,),
         u_detection_radius: (f32,),
@@ -20,39 +30,86 @@ uniform_set! {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct BoidsRenderer {}
+/// What boids do when reaching the edge of the simulated space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[repr(u32)]
+pub enum EdgeBehavior {
+    /// Steer back towards the center, weighted by the edge avoidance
+    Avoid = 0,
+    /// Leave the space and come back in on the opposite side
+    Wrap = 1,
+    /// Bounce off the edges
+    Bounce = 2,
+}
+
+impl Display for EdgeBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                EdgeBehavior::Avoid => "Avoid",
+                EdgeBehavior::Wrap => "Wrap",
+                EdgeBehavior::Bounce => "Bounce",
+            }
+        )
+    }
+}
+
+/// The options of the edge behavior setting
+pub const EDGE_BEHAVIORS: &[SelectionOption<EdgeBehavior>] = &[
+    SelectionOption::new(EdgeBehavior::Avoid),
+    SelectionOption::new(EdgeBehavior::Wrap),
+    SelectionOption::new(EdgeBehavior::Bounce),
+];
+
+/// The amount of simulated boids
+const BOID_COUNT: usize = 100;
+/// The amount of simulated boids in low power mode, each boid checks all others every step
+const LOW_POWER_BOID_COUNT: usize = 50;
+/// The width of the compute textures, holding one boid per texel
+const BOIDS_PER_ROW: usize = 10;
+
+/// The amount of values sampled from the separation falloff curve, stored in the first row of the
+/// third compute input. The compute shader interpolates linearly between them.
+const SEPARATION_FALLOFF_SAMPLES: u32 = 10;
+
+/// Declares the position and velocity outputs of the compute shader in webgl2
+const COMPUTE_OUTPUTS_WEBGL2: &str = "#version 300 es
+#define texture2D texture
+layout(location = 0) out highp vec4 position_output;
+layout(location = 1) out highp vec4 velocity_output;
+";
+
+/// Declares the position and velocity outputs of the compute shader in webgl1
+const COMPUTE_OUTPUTS_WEBGL1: &str = "#extension GL_EXT_draw_buffers : require
+#define position_output gl_FragData[0]
+#define velocity_output gl_FragData[1]
+";
+
+/// The amount of colors sampled from the speed gradient
+const SPEED_COLORS_WIDTH: usize = 64;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoidsRenderer {
+    /// Measures the compute and render passes
+    gpu_timer: GpuTimer,
+}
 
 #[derive(Debug)]
 pub struct BoidsRenderState {
     compute_program: ComputeProgram<ComputeUniformSet>,
     render_program: WebGlProgram,
-    render_vertex_buffer: WebGlBuffer,
+    render_geometry: Geometry,
     render_dimensions_uniform: Uniform<(f32, f32)>,
-    render_input_uniform: Uniform<(i32,)>,
+    render_input_uniform: TextureUniform,
+    render_velocities_uniform: TextureUniform,
     render_aspect_uniform: Uniform<(f32,)>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub struct BoidsRenderInput {
-    /// Weight for boids being attracted to the group center of mass
-    pub cohesion: f32,
-    /// Weight for boids being repelled by each other
-    pub separation: f32,
-    /// Weight for boids aligning to the same direction
-    pub alignment: f32,
-    /// Weight for boids avoiding edges
-    pub edge_avoidance: f32,
-    /// Radius for boids avoiding each other
-    pub avoidance_radius: f32,
-    /// Radius for boids vision
-    pub detection_radius: f32,
-    /// Minimum boid velocity
-    pub min_velocity: f32,
-    /// Maximum boid velocity
-    pub max_velocity: f32,
-    /// Maximum boid acceleration
-    pub max_acceleration: f32,
+    render_speed_range_uniform: Uniform<(f32, f32)>,
+    render_speed_colors_uniform: TextureUniform,
+    speed_colors_texture: WebGlTexture,
+    /// The amount of simulated boids, depending on the power mode the state was created in
+    boid_count: usize,
 }
 
 impl CanvasRenderer for BoidsRenderer {
@@ -60,139 +117,283 @@ impl CanvasRenderer for BoidsRenderer {
 
     type RenderInput = BoidsRenderInput;
 
-    fn render(
+    type RenderOutput = ();
+
+    fn on_resize(
         &self,
         state: &mut Self::RenderState,
-        _input: &Self::RenderInput,
-        gl: &GL,
-        RenderData {
-            width,
-            height,
-            resized,
-            input_changed,
-            ..
-        }: RenderData,
+        _gl: &GL,
+        RenderData { width, height, .. }: RenderData,
     ) {
-        // if resized {
-        //     gl.viewport(0, 0, width.try_into().unwrap(), height.try_into().unwrap());
-        // }
-
-        if input_changed {
-            log::info!("Input changed");
-        }
-
         let aspect = height as f32 / width as f32;
 
         state
             .compute_program
             .set_uniform::<{ ComputeUniformSet::u_space }>((1.0 / aspect, 1.0));
+    }
+
+    fn on_input_changed(
+        &self,
+        state: &mut Self::RenderState,
+        input: &Self::RenderInput,
+        gl: &GL,
+        _render_data: RenderData,
+    ) {
+        state
+            .compute_program
+            .set_uniform::<{ ComputeUniformSet::u_cohesion }>((input.cohesion,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_cohesion }>((_input.cohesion,));
+            .set_uniform::<{ ComputeUniformSet::u_separation }>((input.separation,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_separation }>((_input.separation,));
+            .set_uniform::<{ ComputeUniformSet::u_alignment }>((input.alignment,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_alignment }>((_input.alignment,));
+            .set_uniform::<{ ComputeUniformSet::u_edge_behavior }>((
+                input.edge_behavior as u32 as f32,
+            ));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_edge_avoidance }>((_input.edge_avoidance,));
+            .set_uniform::<{ ComputeUniformSet::u_edge_avoidance }>((input.edge_avoidance,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_detection_radius }>((_input.detection_radius,));
+            .set_uniform::<{ ComputeUniformSet::u_detection_radius }>((input.detection_radius,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_avoidance_radius }>((_input.avoidance_radius,));
+            .set_uniform::<{ ComputeUniformSet::u_avoidance_radius }>((input.avoidance_radius,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_min_velocity }>((_input.min_velocity,));
+            .set_uniform::<{ ComputeUniformSet::u_min_velocity }>((input.velocity.0,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_max_velocity }>((_input.max_velocity,));
+            .set_uniform::<{ ComputeUniformSet::u_max_velocity }>((input.velocity.1,));
         state
             .compute_program
-            .set_uniform::<{ ComputeUniformSet::u_max_acceleration }>((_input.max_acceleration,));
+            .set_uniform::<{ ComputeUniformSet::u_max_acceleration }>((input.max_acceleration,));
+        let falloff: Vec<_> = input
+            .separation_falloff
+            .samples(SEPARATION_FALLOFF_SAMPLES as usize)
+            .into_iter()
+            .flat_map(|sample| [sample, 0.0, 0.0, 0.0])
+            .collect();
+        state.compute_program.write_input_region(
+            gl,
+            2,
+            (0, 0),
+            (SEPARATION_FALLOFF_SAMPLES, 1),
+            &falloff,
+        );
 
-        state.compute_program.compute(gl);
-        state.compute_program.copy_output_to_input(gl, 0);
+        state.render_speed_range_uniform.set_data(input.velocity);
+        update_ramp_texture(
+            gl,
+            &state.speed_colors_texture,
+            &input.speed_colors.ramp(SPEED_COLORS_WIDTH),
+        );
+    }
 
-        gl.use_program(Some(&state.render_program));
-        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&state.render_vertex_buffer));
-        gl.active_texture(GL::TEXTURE0);
-        gl.bind_texture(GL::TEXTURE_2D, Some(state.compute_program.output_texture()));
+    fn on_destroy(&self, state: Self::RenderState, gl: &GL) {
+        state.compute_program.delete(gl);
+        state.render_geometry.delete(gl);
+        gl.delete_program(Some(&state.render_program));
+        gl.delete_texture(Some(&state.speed_colors_texture));
+    }
+
+    fn step(
+        &self,
+        state: &mut Self::RenderState,
+        _input: &Self::RenderInput,
+        gl: &GL,
+        _render_data: RenderData,
+    ) {
+        self.gpu_timer.measure(gl, "compute", || {
+            state.compute_program.compute(gl);
+            state.compute_program.swap();
+        });
+    }
+
+    fn render(
+        &self,
+        state: &mut Self::RenderState,
+        _input: &Self::RenderInput,
+        gl: &GL,
+        RenderData {
+            width,
+            height,
+            colors,
+            ..
+        }: RenderData,
+    ) {
+        let aspect = height as f32 / width as f32;
 
-        let position = gl
-            .get_attrib_location(&state.render_program, "a_index")
-            .try_into()
-            .unwrap();
-        gl.vertex_attrib_pointer_with_i32(position, 1, GL::FLOAT, false, 0, 0);
-        gl.enable_vertex_attrib_array(position);
+        gl.use_program(Some(&state.render_program));
+        state.render_geometry.bind(gl);
 
         state.render_dimensions_uniform.apply(gl);
-        state.render_input_uniform.apply(gl);
+        // The latest result is read from the inputs, since they were swapped after the pass
+        state
+            .render_input_uniform
+            .apply_texture(gl, Some(state.compute_program.input_texture(0).clone()));
+        state
+            .render_velocities_uniform
+            .apply_texture(gl, Some(state.compute_program.input_texture(1).clone()));
         state.render_aspect_uniform.apply_data(gl, (aspect,));
+        state.render_speed_range_uniform.apply(gl);
+        state.render_speed_colors_uniform.apply(gl);
 
         gl.viewport(0, 0, width.try_into().unwrap(), height.try_into().unwrap());
-        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        let [r, g, b, a] = colors.background;
+        gl.clear_color(r, g, b, a);
         gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
 
-        gl.draw_arrays(GL::TRIANGLES, 0, 300);
+        self.gpu_timer.measure(gl, "render", || {
+            state
+                .render_geometry
+                .draw_instanced(gl, GL::TRIANGLES, state.boid_count)
+        });
+        state.render_geometry.unbind(gl);
     }
 
     fn initial_render_state(
         &self,
-        _input: &Self::RenderInput,
+        input: &Self::RenderInput,
         gl: &GL,
-        _render_data: RenderData,
-    ) -> Self::RenderState {
+        render_data: RenderData,
+    ) -> Result<Self::RenderState, WebglError> {
         const COMPUTE_FRAG_SOURCE: &str = include_str!("./compute.frag");
         const RENDER_VERT_SOURCE: &str = include_str!("./render.vert");
         const RENDER_FRAG_SOURCE: &str = include_str!("./render.frag");
 
         log::info!("Starting initial setup");
 
-        let compute_program = ComputeProgram::new(10, 10, 1, gl, COMPUTE_FRAG_SOURCE);
-        let initial_data: Vec<_> = (0..100)
-            .flat_map(|_| {
-                [
-                    (2.0 * random() - 1.0) as f32,
-                    (2.0 * random() - 1.0) as f32,
-                    (2.0 * random() - 1.0) as f32,
-                    (2.0 * random() - 1.0) as f32,
-                ]
-            })
-            .collect();
-        compute_program.write_input(gl, 0, initial_data.as_slice());
+        if !instancing_supported(gl) {
+            return Err(WebglError::MissingExtension("ANGLE_instanced_arrays"));
+        }
 
-        let render_vertex_shader =
-            compile_shader(gl, GL::VERTEX_SHADER, RENDER_VERT_SOURCE).unwrap();
-        let render_fragment_shader =
-            compile_shader(gl, GL::FRAGMENT_SHADER, RENDER_FRAG_SOURCE).unwrap();
-        let render_program =
-            create_program(gl, &render_vertex_shader, &render_fragment_shader).unwrap();
+        let boid_count = if render_data.low_power {
+            LOW_POWER_BOID_COUNT
+        } else {
+            BOID_COUNT
+        };
+        let (columns, rows) = (BOIDS_PER_ROW, boid_count.div_ceil(BOIDS_PER_ROW));
+        // Positions and velocities are written to separate render targets
+        let compute_outputs = if webgl2(gl).is_some() {
+            COMPUTE_OUTPUTS_WEBGL2
+        } else {
+            COMPUTE_OUTPUTS_WEBGL1
+        };
+        let compute_program = ComputeProgram::new(
+            columns as u32,
+            rows as u32,
+            3,
+            2,
+            gl,
+            preprocess_shader(
+                &format!("{compute_outputs}{COMPUTE_FRAG_SOURCE}"),
+                &[
+                    ("BOID_COUNT", &boid_count),
+                    ("FALLOFF_SAMPLES", &SEPARATION_FALLOFF_SAMPLES),
+                ],
+            ),
+        )?;
+        let mut rng = SeededRng::new(render_data.seed);
+        let (positions, velocities): (Vec<_>, Vec<_>) = (0..boid_count)
+            .map(|_| {
+                (
+                    [
+                        2.0 * rng.next_f32() - 1.0,
+                        2.0 * rng.next_f32() - 1.0,
+                        0.0,
+                        1.0,
+                    ],
+                    [
+                        2.0 * rng.next_f32() - 1.0,
+                        2.0 * rng.next_f32() - 1.0,
+                        0.0,
+                        1.0,
+                    ],
+                )
+            })
+            .unzip();
+        compute_program.write_input(gl, 0, positions.as_flattened());
+        compute_program.write_input(gl, 1, velocities.as_flattened());
 
-        let render_dimensions_uniform =
-            Uniform::new(gl, &render_program, "u_dimensions", (10.0, 10.0));
-        let render_input_uniform = Uniform::new(gl, &render_program, "u_input", (0,));
-        let render_aspect_uniform = Uniform::new(gl, &render_program, "u_aspect", (0.0,));
+        let render_vertex_shader = compile_shader(
+            gl,
+            GL::VERTEX_SHADER,
+            preprocess_shader(RENDER_VERT_SOURCE, &[]),
+        )?;
+        let render_fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, RENDER_FRAG_SOURCE)?;
+        let render_program = create_program(gl, &render_vertex_shader, &render_fragment_shader)?;
 
-        let vertices: Vec<f32> = (0..300).map(|i| i as f32).collect();
-        let verts = web_sys::js_sys::Float32Array::from(vertices.as_slice());
-        let render_vertex_buffer = gl.create_buffer().unwrap();
+        let render_dimensions_uniform = Uniform::new(
+            gl,
+            &render_program,
+            "u_dimensions",
+            (columns as f32, rows as f32),
+        )?;
+        let mut texture_units = TextureUnits::new();
+        let render_input_uniform = texture_units.uniform(
+            gl,
+            &render_program,
+            "u_input",
+            Some(compute_program.input_texture(0).clone()),
+        )?;
+        let render_velocities_uniform = texture_units.uniform(
+            gl,
+            &render_program,
+            "u_velocities",
+            Some(compute_program.input_texture(1).clone()),
+        )?;
+        let render_aspect_uniform = Uniform::new(gl, &render_program, "u_aspect", (0.0,))?;
+        let render_speed_range_uniform =
+            Uniform::new(gl, &render_program, "u_speed_range", input.velocity)?;
+        let speed_colors_texture =
+            create_ramp_texture(gl, &input.speed_colors.ramp(SPEED_COLORS_WIDTH))?;
+        let render_speed_colors_uniform = texture_units.uniform(
+            gl,
+            &render_program,
+            "u_speed_colors",
+            Some(speed_colors_texture.clone()),
+        )?;
 
-        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&render_vertex_buffer));
-        gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &verts, GL::STATIC_DRAW);
+        // A single boid triangle, drawn once per boid
+        let instances: Vec<f32> = (0..BOID_COUNT).map(|i| i as f32).collect();
+        let render_geometry = Geometry::new(
+            gl,
+            &render_program,
+            vec![
+                (
+                    VertexLayout::new().attribute("a_position", AttributeType::Vec2),
+                    &[0.0, 0.5, -0.25, -0.25, 0.25, -0.25],
+                ),
+                (
+                    VertexLayout::new()
+                        .attribute("a_instance", AttributeType::Float)
+                        .per_instance(),
+                    &instances,
+                ),
+            ],
+        )?;
 
         log::info!("Initial setup complete");
 
-        BoidsRenderState {
+        Ok(BoidsRenderState {
             compute_program,
             render_program,
-            render_vertex_buffer,
+            render_geometry,
             render_dimensions_uniform,
             render_input_uniform,
+            render_velocities_uniform,
             render_aspect_uniform,
-        }
+            render_speed_range_uniform,
+            render_speed_colors_uniform,
+            speed_colors_texture,
+            boid_count,
+        })
+    }
+    fn gpu_timer(&self) -> Option<&GpuTimer> {
+        Some(&self.gpu_timer)
     }
 }
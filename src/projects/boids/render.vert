@@ -1,36 +1,27 @@
 precision mediump float;
 
-attribute float a_index;
+attribute vec2 a_position;
+attribute float a_instance;
+// The boid positions and velocities
 uniform sampler2D u_input;
+uniform sampler2D u_velocities;
 uniform vec2 u_dimensions;
 uniform float u_aspect;
+uniform vec2 u_speed_range;
 
-vec4 getValueFrom2DTextureAs1DArray(sampler2D tex, vec2 dimensions, float index) {
-    float y = floor(index / dimensions.x);
-    float x = mod(index, dimensions.x);
-    vec2 texcoord = (vec2(x, y) + 0.5) / dimensions;
-    return texture2D(tex, texcoord);
-}
+varying float v_speed;
+
+#include "texture_array.glsl"
 
 void main() {
-    float index = floor(a_index / 3.0);
-    vec4 data = getValueFrom2DTextureAs1DArray(u_input, u_dimensions, index);
-    vec2 dir = normalize(data.zw);
-    mat2 rotation_matrix = mat2(vec2(dir.x, -dir.y), vec2(dir.y, dir.x));
+    vec2 position = getValueFrom2DTextureAs1DArray(u_input, u_dimensions, a_instance).xy;
+    vec2 velocity = getValueFrom2DTextureAs1DArray(u_velocities, u_dimensions, a_instance).xy;
+    vec2 dir = normalize(velocity);
+    float speed_span = max(u_speed_range.y - u_speed_range.x, 0.0001);
+    v_speed = clamp((length(velocity) - u_speed_range.x) / speed_span, 0.0, 1.0);
+    mat2 rotation_matrix = mat2(vec2(dir.y, -dir.x), vec2(dir.x, dir.y));
 
-    vec2 VERTS[3];
-    VERTS[0] = vec2(0.0, 0.5);
-    VERTS[1] = vec2(-0.25, -0.25);
-    VERTS[2] = vec2(0.25, -0.25);
     float SCALE = 0.1;
-    vec2 vertex;
-    if (mod(a_index, 3.0) == 0.0) {
-        vertex = VERTS[0];
-    } else if (mod(a_index, 3.0) == 1.0) {
-        vertex = VERTS[1];
-    } else {
-        vertex = VERTS[2];
-    }
 
-    gl_Position = vec4((data.xy + SCALE * (rotation_matrix * vertex)) * vec2(u_aspect, 1.0), 0.0, 1.0);
+    gl_Position = vec4((position + SCALE * (rotation_matrix * a_position)) * vec2(u_aspect, 1.0), 0.0, 1.0);
 }
@@ -0,0 +1,83 @@
+//! Projects hosted elsewhere, registered through a remote manifest
+
+use std::rc::Rc;
+
+use gloo::net::http::Request;
+use yew::{platform::spawn_local, prelude::*};
+
+use crate::{
+    asset_version::versioned,
+    projects::{PreviewCard, PreviewTarget},
+};
+
+/// The path of the manifest listing all external projects
+pub const EXTERNAL_PROJECTS_MANIFEST_PATH: &str = "/assets/external_projects.json";
+
+/// A project hosted outside of this website
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExternalProject {
+    /// The title
+    pub title: String,
+    /// A short description
+    pub description: String,
+    /// The url of the preview image
+    pub preview: String,
+    /// The url the project is hosted at
+    pub url: String,
+}
+
+/// Fetch the external projects manifest
+async fn fetch_external_projects() -> Result<Vec<ExternalProject>, gloo::net::Error> {
+    Request::get(&versioned(EXTERNAL_PROJECTS_MANIFEST_PATH))
+        .send()
+        .await?
+        .json()
+        .await
+}
+
+/// A hook fetching the external projects manifest when the component is mounted.
+///
+/// Returns an empty list until the manifest is loaded, or if it could not be loaded.
+#[hook]
+pub fn use_external_projects() -> Rc<[ExternalProject]> {
+    let projects = use_state(|| Rc::<[ExternalProject]>::from([]));
+
+    use_effect_with((), {
+        let projects = projects.setter();
+        move |_| {
+            spawn_local(async move {
+                match fetch_external_projects().await {
+                    Ok(loaded) => projects.set(loaded.into()),
+                    Err(error) => log::warn!("Failed to load external projects: {error}"),
+                }
+            });
+        }
+    });
+
+    (*projects).clone()
+}
+
+/// Properties for the [`ExternalProjectPreview`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct ExternalProjectPreviewProperties {
+    pub project: ExternalProject,
+    /// Whether to show a compact card for a grid
+    #[prop_or_default]
+    pub grid: bool,
+}
+
+/// A preview for an [`ExternalProject`], linking to where it is hosted
+#[function_component(ExternalProjectPreview)]
+pub fn external_project_preview(
+    ExternalProjectPreviewProperties { project, grid }: &ExternalProjectPreviewProperties,
+) -> Html {
+    html! {
+        <PreviewCard
+            title={project.title.clone()}
+            description={project.description.clone()}
+            image={project.preview.clone()}
+            target={PreviewTarget::External(project.url.clone().into())}
+            grid={*grid}
+        />
+    }
+}
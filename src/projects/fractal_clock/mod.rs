@@ -1,438 +1,426 @@
-use std::{collections::HashMap, rc::Rc};
+use std::rc::Rc;
 
 use crate::{
-    navigation::{Route, Section},
+    about::Author,
+    dev::Tracked,
     projects::{
-        CodeExample, Note, ProjectSite,
+        ChangeDate, ChangelogEntry, ExampleId, Project, ProjectDescriptor, ProjectMeta,
+        article::{Article, ArticleView, article},
         fractal_clock::render::{
-            BLEND_EQUATIONS, BLEND_MULTIPLIERS, BlendConstant, FractalClockRenderInput,
-            FractalClockRenderer, MAX_RECURSION_DEPTH,
+            BLEND_EQUATIONS, BlendConstant, DESTINATION_BLEND_MULTIPLIERS, FractalClockRenderInput,
+            FractalClockRenderer, MAX_RECURSION_DEPTH, SOURCE_BLEND_MULTIPLIERS,
+        },
+        interactive::{
+            AngleDialControl, AutoplayPolicy, CheckboxControl, ColorControl, ExampleSettings,
+            Gradient, GradientControl, GradientStop, InteractiveExample, Preset, SelectionControl,
+            SettingDescriptor, SettingsDescriptor, SettingsPresets, SettingsTransfer, XyPad,
         },
-        interactive::{Checkbox, ColorPicker, InteractiveExample, Selection, Slider},
     },
+    theme::{AccentOverride, ThemeColor},
+    webgl::use_renderer,
 };
 
-use color::AlphaColor;
+use color::{AlphaColor, Srgb};
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
-use yew_router::prelude::Link;
 
 mod render;
 
-const HOUR_ANGLE_SETTING: &str = "Hour angle";
-const MINUTE_ANGLE_SETTING: &str = "Minute angle";
-const ANIMATE_SETTING: &str = "Animate";
-const SIZE_SETTING: &str = "Size";
-const HOUR_RATIO_SETTING: &str = "Hour ratio";
-const RECURSION_DEPTH_SETTING: &str = "Recursion depth";
-const SIZE_FACTOR_SETTING: &str = "Size factor";
-const COLOR_SETTING: &str = "Color";
-const ALPHA_SETTING: &str = "Alpha";
-const RGB_BLEND_SETTING: &str = "RGB blend";
-const ALPHA_BLEND_SETTING: &str = "Alpha blend";
-const SOURCE_RGB_SETTING: &str = "Source RGB";
-const SOURCE_ALPHA_SETTING: &str = "Source Alpha";
-const DESTINATION_RGB_SETTING: &str = "Destination RGB";
-const DESTINATION_ALPHA_SETTING: &str = "Destination Alpha";
+/// The shared settings of all fractal clock examples
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ExampleSettings)]
+#[settings(
+    storage_key = "fractal_clock",
+    hook = use_fractal_clock_settings,
+    handles = FractalClockSettingsHandles,
+    key = ClockSetting,
+)]
+struct FractalClockSettings {
+    #[setting(key = HourAngle, label = "Hour angle", control = AngleDialControl { step: 0.1 }, default = 310.0)]
+    #[setting(active = |settings| !settings.animate)]
+    pub hour_angle: f32,
+    #[setting(key = MinuteAngle, label = "Minute angle", control = AngleDialControl { step: 0.1 }, default = 60.0)]
+    #[setting(active = |settings| !settings.animate)]
+    pub minute_angle: f32,
+    #[setting(key = Animate, label = "Animate", control = CheckboxControl, default = true)]
+    pub animate: bool,
+    #[setting(key = Size, label = "Size", min = 1.0, max = 10.0, step = 0.1, default = 1.0)]
+    pub size: f32,
+    #[setting(key = HourRatio, label = "Hour ratio", min = 0.0, max = 1.0, step = 0.01, default = 0.75)]
+    pub hour_ratio: f32,
+    #[setting(key = RecursionDepth, label = "Recursion depth", min = 1, max = MAX_RECURSION_DEPTH, step = 1, default = 8)]
+    #[setting(low_power = 6)]
+    pub recursion_depth: u32,
+    #[setting(key = SizeFactor, label = "Size factor", min = 0.0, max = 0.99, step = 0.01, default = 0.75)]
+    pub size_factor: f32,
+    #[setting(key = Color, label = "Color", control = ColorControl, default = AlphaColor::from_rgb8(0x40, 0xff, 0x20).with_alpha(0.5))]
+    pub color: AlphaColor<Srgb>,
+    #[setting(key = ColorByDepth, label = "Color by depth", control = CheckboxControl, default = false)]
+    pub color_by_depth: bool,
+    #[setting(key = DepthColors, label = "Depth colors", control = GradientControl)]
+    #[setting(
+        description = "The colors of the lines from the first to the last recursion, drawn with the opacity of the color"
+    )]
+    #[setting(active = |settings| settings.color_by_depth)]
+    #[setting(default = Gradient::new(vec![
+        GradientStop::new(0.0, [0xff, 0x80, 0x20]),
+        GradientStop::new(1.0, [0x40, 0x20, 0xff]),
+    ]))]
+    pub depth_colors: Gradient,
+    #[setting(key = RgbBlend, label = "RGB blend", control = SelectionControl { options: BLEND_EQUATIONS }, default = BlendConstant::Addition)]
+    #[setting(
+        description = "How the color of each new line is combined with the color already drawn, after both were scaled by their multipliers"
+    )]
+    pub blend_equation_1: BlendConstant,
+    #[setting(key = AlphaBlend, label = "Alpha blend", control = SelectionControl { options: BLEND_EQUATIONS }, default = BlendConstant::Addition)]
+    #[setting(
+        description = "How the opacity of each new line is combined with the opacity already drawn, after both were scaled by their multipliers"
+    )]
+    pub blend_equation_2: BlendConstant,
+    #[setting(key = SourceRgb, label = "Source RGB", control = SelectionControl { options: SOURCE_BLEND_MULTIPLIERS }, default = BlendConstant::SourceAlpha)]
+    #[setting(description = "Scales the color of each new line before blending")]
+    pub blend_multiplier_1: BlendConstant,
+    #[setting(key = SourceAlpha, label = "Source Alpha", control = SelectionControl { options: SOURCE_BLEND_MULTIPLIERS }, default = BlendConstant::DestinationAlpha)]
+    #[setting(description = "Scales the opacity of each new line before blending")]
+    pub blend_multiplier_2: BlendConstant,
+    #[setting(key = DestinationRgb, label = "Destination RGB", control = SelectionControl { options: DESTINATION_BLEND_MULTIPLIERS }, default = BlendConstant::One)]
+    #[setting(description = "Scales the color already drawn before blending")]
+    pub blend_multiplier_3: BlendConstant,
+    #[setting(key = DestinationAlpha, label = "Destination Alpha", control = SelectionControl { options: DESTINATION_BLEND_MULTIPLIERS }, default = BlendConstant::One)]
+    #[setting(description = "Scales the opacity already drawn before blending")]
+    pub blend_multiplier_4: BlendConstant,
+    #[setting(key = ClockFace, label = "Clock face", control = CheckboxControl, default = false)]
+    pub clock_face: bool,
+    #[setting(key = ClockFaceOpacity, label = "Clock face opacity", min = 0.0, max = 1.0, step = 0.05, default = 0.3)]
+    #[setting(description = "The clock face is drawn in the text color of the website theme")]
+    #[setting(active = |settings| settings.clock_face)]
+    pub clock_face_opacity: f32,
+}
 
-#[function_component(FractalClockPage)]
-pub fn fractal_clock_page() -> Html {
-    // Define shared example settings
-    let hour_angle = use_state(|| 310.0);
-    let minute_angle = use_state(|| 60.0);
-    let animate = use_state(|| true);
-    let size = use_state(|| 1.0);
-    let recursion_depth = use_state(|| 8);
-    let hour_ratio = use_state(|| 0.75);
-    let size_factor = use_state(|| 0.75);
-    let color = use_state(|| "#40ff20".to_owned());
-    let alpha = use_state(|| 0.5);
-    let blend_equations: Box<[_]> = BLEND_EQUATIONS.iter().copied().collect();
-    let blend_multipliers: Box<[_]> = BLEND_MULTIPLIERS.iter().copied().collect();
-    let blend_equation_1 = use_state(|| BlendConstant::Addition);
-    let blend_equation_2 = use_state(|| BlendConstant::Addition);
-    let blend_multiplier_1 = use_state(|| BlendConstant::SourceAlpha);
-    let blend_multiplier_2 = use_state(|| BlendConstant::DestinationAlpha);
-    let blend_multiplier_3 = use_state(|| BlendConstant::One);
-    let blend_multiplier_4 = use_state(|| BlendConstant::One);
+/// The curated presets of the fractal clock settings
+const PRESETS: &[Preset<FractalClockSettings>] = &[
+    Preset {
+        name: "Galaxy",
+        settings: || FractalClockSettings {
+            size: 2.0,
+            hour_ratio: 0.5,
+            size_factor: 0.8,
+            color: AlphaColor::from_rgb8(0x80, 0x60, 0xff).with_alpha(0.3),
+            ..FractalClockSettings::default()
+        },
+    },
+    Preset {
+        name: "Snowflake",
+        settings: || FractalClockSettings {
+            hour_angle: 0.0,
+            minute_angle: 60.0,
+            animate: false,
+            hour_ratio: 1.0,
+            size_factor: 0.6,
+            color: AlphaColor::from_rgb8(0xa0, 0xe0, 0xff).with_alpha(0.6),
+            ..FractalClockSettings::default()
+        },
+    },
+];
 
-    let settings: Rc<HashMap<_, _>> = Rc::new([
-            (
-                "Hour angle".to_string(),
-                html! { <Slider<f32> active={!*animate} min={0.0} max={360.0} step={0.1} value={hour_angle.clone()}/> },
-            ),
-            (
-                "Minute angle".to_string(),
-                html! { <Slider<f32> active={!*animate} min={0.0} max={360.0} step={0.1} value={minute_angle.clone()}/> },
-            ),
-            (
-                "Animate".to_string(),
-                html! { <Checkbox value={animate.clone()}/> },
-            ),
-            (
-                "Size".to_string(),
-                html! { <Slider<f32> min={1.0} max={10.0} step={0.1} value={size.clone()}/> },
-            ),
-            (
-                "Hour ratio".to_string(),
-                html! { <Slider<f32> min={0.0} max={1.0} step={0.01} value={hour_ratio.clone()}/> },
-            ),
-            (
-                "Recursion depth".to_string(),
-                html! { <Slider<u32> min={1} max={MAX_RECURSION_DEPTH} step={1} value={recursion_depth.clone()}/> },
-            ),
-            (
-                "Size factor".to_string(),
-                html! { <Slider<f32> min={0.0} max={0.99} step={0.01} value={size_factor.clone()}/> },
-            ),
-            (
-                "Color".to_string(),
-                html! { <ColorPicker value={color.clone()}/> },
-            ),
-            (
-                "Alpha".to_string(),
-                html! { <Slider<f32> min={0.0} max={1.0} step={0.01} value={alpha.clone()}/> },
-            ),
-            (
-                "RGB blend".to_string(),
-                html! { <Selection<BlendConstant> value={blend_equation_1.clone()} values={blend_equations.clone()}/> },
-            ),
-            (
-                "Alpha blend".to_string(),
-                html! { <Selection<BlendConstant> value={blend_equation_2.clone()} values={blend_equations.clone()}/> },
-            ),
-            (
-                "Source RGB".to_string(),
-                html! { <Selection<BlendConstant> value={blend_multiplier_1.clone()} values={blend_multipliers.clone()}/> },
-            ),
-            (
-                "Source Alpha".to_string(),
-                html! { <Selection<BlendConstant> value={blend_multiplier_2.clone()} values={blend_multipliers.clone()}/> },
-            ),
-            (
-                "Destination RGB".to_string(),
-                html! { <Selection<BlendConstant> value={blend_multiplier_3.clone()} values={blend_multipliers.clone()}/> },
-            ),
-            (
-                "Destination Alpha".to_string(),
-                html! { <Selection<BlendConstant> value={blend_multiplier_4.clone()} values={blend_multipliers.clone()}/> },
-            ),
-        ].into_iter().collect());
+/// The embeddable fractal clock examples
+pub const EXAMPLES: &[ExampleId] = &[
+    ExampleId::new("trivial"),
+    ExampleId::new("trivial-recursive"),
+    ExampleId::new("recursive"),
+    ExampleId::new("without-blending"),
+    ExampleId::new("complete"),
+];
+
+/// Returns the html of a single embedded example
+pub fn embed(example: ExampleId, show_settings: bool) -> Option<Html> {
+    let version = ExampleVersion::from_example_id(example)?;
+
+    Some(html! { <FractalClockEmbed {version} {show_settings}/> })
+}
+
+/// The shared example settings, their transfer and the final render input derived from them
+struct SharedExampleInputs {
+    settings: FractalClockSettingsHandles,
+    settings_transfer: SettingsTransfer,
+    reset_settings: Callback<()>,
+    randomize_settings: Callback<()>,
+    final_render_input: Rc<FractalClockRenderInput>,
+}
 
-    let col = color::parse_color(&color)
-        .unwrap()
-        .to_alpha_color::<color::Srgb>()
-        .with_alpha(*alpha);
+/// A hook creating the [`SharedExampleInputs`]
+#[hook]
+fn use_shared_example_inputs() -> SharedExampleInputs {
+    let settings_handles = use_fractal_clock_settings();
+    let settings_transfer = settings_handles.transfer();
+    let reset_settings = settings_handles.reset_callback();
+    let randomize_settings = settings_handles.randomize_callback();
+    let FractalClockSettings {
+        hour_angle,
+        minute_angle,
+        animate,
+        size,
+        hour_ratio,
+        recursion_depth,
+        size_factor,
+        color,
+        color_by_depth,
+        depth_colors,
+        blend_equation_1,
+        blend_equation_2,
+        blend_multiplier_1,
+        blend_multiplier_2,
+        blend_multiplier_3,
+        blend_multiplier_4,
+        clock_face,
+        clock_face_opacity,
+    } = settings_handles.value();
 
     let final_render_input = Rc::new(FractalClockRenderInput {
-        hour_angle: *hour_angle,
-        minute_angle: *minute_angle,
-        animate: *animate,
-        size: *size,
-        recursion_depth: *recursion_depth,
-        hour_ratio: *hour_ratio,
-        size_factor: *size_factor,
-        color: col,
-        blend_equations: (*blend_equation_1, *blend_equation_2),
+        hour_angle,
+        minute_angle,
+        animate,
+        size,
+        recursion_depth,
+        hour_ratio,
+        size_factor,
+        color,
+        depth_colors: color_by_depth.then_some(depth_colors),
+        blend_equations: (blend_equation_1, blend_equation_2),
         blend_multipliers: (
-            *blend_multiplier_1,
-            *blend_multiplier_2,
-            *blend_multiplier_3,
-            *blend_multiplier_4,
+            blend_multiplier_1,
+            blend_multiplier_2,
+            blend_multiplier_3,
+            blend_multiplier_4,
         ),
+        clock_face: clock_face.then_some(clock_face_opacity),
     });
 
+    SharedExampleInputs {
+        settings: settings_handles,
+        settings_transfer,
+        reset_settings,
+        randomize_settings,
+        final_render_input,
+    }
+}
+
+/// Properties for the [`FractalClockEmbed`] component
+#[derive(Debug, PartialEq, Properties)]
+struct FractalClockEmbedProperties {
+    version: ExampleVersion,
+    show_settings: bool,
+}
+
+/// A single embedded fractal clock example with its own settings
+#[function_component(FractalClockEmbed)]
+fn fractal_clock_embed(
+    FractalClockEmbedProperties {
+        version,
+        show_settings,
+    }: &FractalClockEmbedProperties,
+) -> Html {
+    let SharedExampleInputs {
+        settings,
+        settings_transfer,
+        reset_settings,
+        randomize_settings,
+        final_render_input,
+    } = use_shared_example_inputs();
+
     html! {
-        <ProjectSite title="Fractal Clock">
-            <Section title="Introduction">
-                <p>
-                    {"
-                        Some while ago I stumbled upon a
-                    "}
-                    <a href="https://www.youtube.com/watch?v=4SH_-YhN15A">{"Video"}</a>
-                    {"
-                        by Code Parade about a particular way to visualize a clock. Since it looked
-                        cool I thought why not give implementing it a try? I took this challenge as
-                        an opportunity to learn about
-                    "}
-                    <a href="https://wgpu.rs/">{"wgpu.rs"}</a>
-                    {"
-                        although I will mostly focus on the clock and not dive into detail about
-                        shaders etc.
-                    "}
-                </p>
-                <p>
-                    {"
-                        Conceptually the visualization recursively draws analogue clocks at the
-                        end of each clock's pointer (The previously mentioned video does a good job
-                        explaining the concept at the beginning). Another way to think about it is
-                        imagining each pointer as a
-                    "}
-                    <a href="https://en.wikipedia.org/wiki/Fractal_canopy">{"Fractal canopy"}</a>
-                    {"."}
-                </p>
-                <Note>
-                    <p>
-                        {"
-                            Just a quick note: The source code of this website is accessible to anyone
-                            interested in the implementation used throughout the explanation, see 
-                        "}
-                        <Link<Route> to={Route::About}>{"About"}</Link<Route>>
-                        {"."}
-                    </p>
-                </Note>
-            </Section>
-            <Section title="Implementation Basics">
-                <p>
-                    {"
-                        Starting off we will be working with basic vector math. We will treat each
-                        base pointer as a vector of length 1 (and a shorter length for the hour
-                        pointer), with an appropriate angle derived from the current time (Not
-                        accurate to the actual time for demonstration purposes).
-                    "}
-                </p>
-                <CodeExample lang="Rust">
-                    {indoc::indoc! {
-                        r#"
-                            let (hour_angle, hour_ratio, minute_angle) = // Implementation details
-                            let hour_pointer = (hour_angle.cos() * hour_ratio, hour_angle.sin() * hour_ratio);
-                            let minute_pointer = (minute_angle.cos(), minute_angle.sin());
-                        "#
-                    }}
-                </CodeExample>
-                <FractalClockExample
-                    version={ExampleVersion::Trivial}
-                    final_render_input={final_render_input.clone()}
-                    settings={settings.clone()}
-                    initially_active=true
-                />
-                <p>
-                    {"
-                        So far so good, this already looks like a minimal analogue clock. Now comes
-                        the interesting part: recursively computing hour and minute pointers. To
-                        draw a line of course, we need two points but since the starting point
-                        is always the tip of a previous pointer (or the origin), we don't need to
-                        compute them again.  So how do we compute the next pointer recursively on
-                        top of a previous one? Technically the following should work:
-                    "}
-                </p>
-                <CodeExample lang="Rust">
-                    {indoc::indoc! {
-                        r#"
-                            let (prev_pointer_x, prev_pointer_y, prev_pointer_angle) = // ...
-                            let next_pointer_origin = (prev_pointer_x, prev_pointer_y);
-                            let next_hour_pointer_angle =
-                                prev_pointer_angle + hour_angle; // Since each recursive clock is rotated
-                            let next_hour_pointer = (
-                                next_pointer_origin + next_hour_pointer_angle.cos(),
-                                next_pointer_origin + next_hour_pointer_angle.sin()
-                            )
-                            // Similar for the minute pointer ...
-                        "#
-                    }}
-                </CodeExample>
-                <p>
-                    {"
-                        However we need to carry the pointer angles around, and repeatedly calculate
-                        the sinus and cosinus functions. To avoid those hassles, we can use
-                    "}
-                    <a href="https://en.wikipedia.org/wiki/Complex_number">{"Complex Numbers"}</a>
-                    {"
-                        :)
-                    "}
-                </p>
-            </Section>
-            <Section title="Complex numbers">
-                <p>
-                    {"
-                        I have sneakily already defined our vectors in a way that resembles a
-                        complex number, derived from it's polar form. We can now use the property,
-                        that multiplying two complex numbers is equivalent to adding their angles in
-                        polar form and multiplying their lengths.
-                    "}
-                </p>
-                <p>
-                    {"
-                        Additionally we get a property for free that I forgot to mention before:
-                        Each subsequent pointer should have a smaller length. The following is an
-                        example of drawing the first recursive set of pointers (Don't be confused by
-                        the size factor scaling the entire clock to fit it on the canvas):
-                    "}
-                </p>
-                <CodeExample lang="Rust">
-                    {indoc::indoc! {
-                        r#"
-                            let (hour_angle_x, hour_angle_y, minute_angle_x, minute_angle_y) = //...
-                            let (
-                                prev_pointer_x, prev_pointer_y,
-                                prev_pointer_angle_x, prev_pointer_angle_y,
-                            ) = // ...
-                            // Calculate the next pointer offset
-                            let next_hour_pointer_angle = (
-                                prev_pointer_angle_x * hour_angle_x - prev_pointer_angle_y * hour_angle_y,
-                                prev_pointer_angle_x * hour_angle_y + prev_pointer_angle_y * hour_angle_x,
-                            );
-                            // Sum previous offsets to get the actual vertex coordinate
-                            let next_hour_pointer = (
-                                prev_pointer_x + next_hour_pointer_angle.0,
-                                prev_pointer_y + next_hour_pointer_angle.1,
-                            )
-                            // Similar for the minute pointer ...
-                        "#
-                    }}
-                </CodeExample>
-                <FractalClockExample
-                    version={ExampleVersion::TrivialRecursive(false)}
-                    final_render_input={final_render_input.clone()}
-                    settings={settings.clone()}
-                />
-            </Section>
-            <Section title="Recursion">
-                <p>
-                    {"
-                        Not that we know how to calculate deeper pointers, it's time to do it
-                        recursively right? I mentioned in the beginning, that I used WebGPU to
-                        compute the fractal clock on a graphics card. This allows a lot of vertices
-                        to be efficiently computed, however shader code does not support recursion.
-                        To get rid of the recursion, we can iteratively compute each recursion-layer
-                        where each time the number of vertices (pointers) computed is doubled.
-                    "}
-                </p>
-                <p>
-                    {"
-                        Structurally we are working with a slightly modified array representation
-                        of a binary tree, where each node is the translation from the previous
-                        pointer end to the next pointer end (minute or hour respectively). The
-                        following is a rather accurate translation of the actual code used for the
-                        cpu computation.
-                    "}
-                 </p>
-                <CodeExample lang="Rust">
-                    {indoc::indoc! {r#"
-                        array[0] = (
-                            hour_angle_x, hour_angle_y, // Actual coordinate
-                            hour_angle_x, hour_angle_y, // Pointer angle
-                        );
-                        array[1] = (
-                            minute_angle_x, minute_angle_y, // Actual coordinate
-                            minute_angle_x, minute_angle_y, // Pointer angle
-                        );
+        <FractalClockExample
+            version={*version}
+            {final_render_input}
+            {settings}
+            {settings_transfer}
+            {reset_settings}
+            {randomize_settings}
+            initially_active=true
+            embedded=true
+            show_settings={*show_settings}
+        />
+    }
+}
+
+/// The registration of this project, see [`Project::descriptor`]
+pub const DESCRIPTOR: ProjectDescriptor = ProjectDescriptor {
+    meta: ProjectMeta {
+        title: "Fractal Clock",
+        description: indoc::indoc! {"
+            When drawing an analogue clock recursively at each pointer tip, beautiful
+            patterns emerge. We will explore how to optimize and render this efficiently
+            using webgl rendering.
+        "},
+        authors: &[Author::Ciklon],
+        tags: &["webgl", "fractal", "animation"],
+        order: 0,
+        featured: true,
+        // Green, like the default color of the clock
+        accents: &[AccentOverride {
+            color: ThemeColor::Base0D,
+            with: ThemeColor::Base0B,
+        }],
+        changelog: &[
+            ChangelogEntry {
+                date: ChangeDate {
+                    year: 2026,
+                    month: 10,
+                    day: 16,
+                },
+                summary: "Added an optional classic clock face beneath the fractal",
+            },
+            ChangelogEntry {
+                date: ChangeDate {
+                    year: 2026,
+                    month: 10,
+                    day: 16,
+                },
+                summary: "Settings can be dragged from one example onto another",
+            },
+            ChangelogEntry {
+                date: ChangeDate {
+                    year: 2026,
+                    month: 10,
+                    day: 16,
+                },
+                summary: "Lines can be colored by their recursion depth with a gradient",
+            },
+        ],
+    },
+    page: || html! { <FractalClockPage/> },
+    examples: EXAMPLES,
+    embed,
+};
 
-                        for i in 2..NUM_POINTERS {
-                            let parent = i / 2 - 1; // Calculate parent index
-                            let position = (
-                                array[parent].0, array[parent].1,
-                            );
-                            let angle = (
-                                array[parent].2, array[parent].3,
-                            );
-                            // Use the corresponding default angle
-                            let mut new_angle = if i % 2 == 0 { hour } else { minute };
-                            // Compute the new angle
-                            new_angle = (
-                                angle.0 * new_angle.0 - angle.1 * new_angle.1,
-                                angle.0 * new_angle.1 + angle.1 * new_angle.0,
-                            );
-                            // Set update the data for the current pointer
-                            array[i] = (
-                                position.0 + new_angle.0,
-                                position.1 + new_angle.1,
-                                new_angle.0,
-                                new_angle.1,
-                            )
-                        }
-                    "#}}
-                </CodeExample>
-                <Note>
-                    <p>
-                        {"
-                            Note: Since the first layers contain only a few vertices, it is faster to
-                            compute them on the CPU before sending them to the GPU, since each layer
-                            needs a seperate pass to the GPU. Also I've scaled the clock depending on
-                            the recursion depth to completely fit on screen.
-                        "}
-                    </p>
-                </Note>
-                <FractalClockExample
-                    version={ExampleVersion::TrivialRecursive(true)}
-                    final_render_input={final_render_input.clone()}
-                    settings={settings.clone()}
-                />
-            </Section>
-            <Section title="Colors">
-                <p>
-                    {"
-                        The previous example already looks functionally correct, now it's time to
-                        make it pretty :3 The rendering pipeline offers a few screws we can turn,
-                        starting with the actual drawing color.
-                    "}
-                </p>
-                <p>
-                    {"
-                        Additionally I've added a size slider to zoom into the clock in case someone
-                        want's to inspect some clock states in more detail.
-                    "}
-                </p>
-                <FractalClockExample
-                    version={ExampleVersion::CompleteWithoutBlending}
-                    final_render_input={final_render_input.clone()}
-                    settings={settings.clone()}
-                />
-            </Section>
-            <Section title="Blending">
-                <p>
-                    {"
-                        Finally the render pipeline allows us to play with the blending of colors.
-                        The color and alpha part of the final result is calculated seperately: Both
-                        use a function (like addition or subtraction) and some factors with which
-                        to multiply the source value (from the data that is being drawn) and the
-                        destination value (from the data that's already there).
-                    "}
-                </p>
-                <p>
-                    {"
-                        The default blend settings I chose could be written as follows:
-                    "}
-                </p>
-                <CodeExample lang="Rust">
-                    {indoc::indoc! {
-                        r#"
-                            // Function is addition so we have formulas of the form
-                            // FACTOR * src_component + FACTOR * dst_component
-                            let final_rgb = src_alpha * src_rgb + 1 * dst_rgb;
-                            let final_alpha = dst_alpha * src_alpha + 1 * dst_alpha;
-                        "#
-                    }}
-                </CodeExample>
-                <FractalClockExample
-                    version={ExampleVersion::Complete}
-                    final_render_input={final_render_input.clone()}
-                    settings={settings.clone()}
-                />
-            </Section>
-            <Section title="Conclusion">
-                <p>
-                    {"
-                        And that's it for this little Codling :) I hope maybe this inspires you to expand
-                        on the idea of a fractal clock, since I only implemented the basic functionality.
-                        Consider the fun challenge of expanding the clock to include a seconds pointer:
-                        How difficult would that be? What would you need to change?
-                    "}
-                </p>
-                <p>
-                    {"
-                        I also want to thank
-                    "}
-                    <a href="https://www.youtube.com/c/codeparade">{"Code Parade"}</a>
-                    {"
-                        again for introducing me to this idea and also many of his other videos, which I
-                        would recommend you to watch if you found this interesting.
-                    "}
-                </p>
-            </Section>
-        </ProjectSite>
+/// The fractal clock article
+pub const ARTICLE: Article = article!("articles/fractal_clock.md");
+
+#[function_component(FractalClockPage)]
+pub fn fractal_clock_page() -> Html {
+    // Define shared example settings
+    let SharedExampleInputs {
+        settings,
+        settings_transfer,
+        reset_settings,
+        randomize_settings,
+        final_render_input,
+    } = use_shared_example_inputs();
+
+    let examples = Callback::from(move |id| {
+        let Some(version) = ExampleVersion::from_example_id(id) else {
+            return Html::default();
+        };
+        html! {
+            <FractalClockExample
+                {version}
+                final_render_input={final_render_input.clone()}
+                settings={settings.clone()}
+                settings_transfer={settings_transfer.clone()}
+                reset_settings={reset_settings.clone()}
+                randomize_settings={randomize_settings.clone()}
+                initially_active={version == ExampleVersion::Trivial}
+            />
+        }
+    });
+    html! {
+        <ArticleView article={ARTICLE} {examples}/>
     }
 }
 
+/// The settings shown by the fractal clock examples, grouped by what they change
+const SETTINGS: SettingsDescriptor<FractalClockSettingsHandles, ExampleVersion> = {
+    use ClockSetting::*;
+    use ExampleVersion::*;
+
+    /// Returns the descriptor of the given field, shown by every version
+    const fn setting(
+        key: ClockSetting,
+    ) -> SettingDescriptor<FractalClockSettingsHandles, ExampleVersion> {
+        FractalClockSettingsHandles::descriptor(key)
+    }
+
+    /// Both angles can also be picked at once
+    const ANGLES: SettingDescriptor<FractalClockSettingsHandles, ExampleVersion> =
+        SettingDescriptor {
+            label: "Angles",
+            description: Some(
+                "Drag horizontally to change the hour angle and vertically to change the minute \
+                 angle",
+            ),
+            visible: |version| version == Complete,
+            active: |settings| !settings.animate,
+            control: |settings, active| {
+                html! {
+                    <XyPad
+                        {active}
+                        x_range={(0.0, 360.0)}
+                        y_range={(0.0, 360.0)}
+                        x={settings.hour_angle.clone()}
+                        y={settings.minute_angle.clone()}
+                    />
+                }
+            },
+        };
+
+    SettingsDescriptor {
+        settings: &[],
+        groups: &[
+            (
+                "Time",
+                &[
+                    setting(HourAngle),
+                    setting(MinuteAngle),
+                    ANGLES,
+                    setting(Animate),
+                ],
+            ),
+            (
+                "Appearance",
+                &[
+                    setting(Size).visible_in(|version| {
+                        matches!(version, CompleteWithoutBlending | Complete)
+                    }),
+                    setting(HourRatio),
+                    setting(SizeFactor).visible_in(|version| {
+                        matches!(
+                            version,
+                            TrivialRecursive(_) | CompleteWithoutBlending | Complete
+                        )
+                    }),
+                    setting(RecursionDepth).visible_in(|version| {
+                        matches!(
+                            version,
+                            TrivialRecursive(true) | CompleteWithoutBlending | Complete
+                        )
+                    }),
+                    setting(Color).visible_in(|version| {
+                        matches!(version, CompleteWithoutBlending | Complete)
+                    }),
+                    setting(ColorByDepth).visible_in(|version| {
+                        matches!(version, CompleteWithoutBlending | Complete)
+                    }),
+                    setting(DepthColors).visible_in(|version| {
+                        matches!(version, CompleteWithoutBlending | Complete)
+                    }),
+                    setting(ClockFace).visible_in(|version| matches!(version, Trivial | Complete)),
+                    setting(ClockFaceOpacity).visible_in(|version| version == Complete),
+                ],
+            ),
+            (
+                "Blending",
+                &[
+                    setting(RgbBlend).visible_in(|version| version == Complete),
+                    setting(AlphaBlend).visible_in(|version| version == Complete),
+                    setting(SourceRgb).visible_in(|version| version == Complete),
+                    setting(SourceAlpha).visible_in(|version| version == Complete),
+                    setting(DestinationRgb).visible_in(|version| version == Complete),
+                    setting(DestinationAlpha).visible_in(|version| version == Complete),
+                ],
+            ),
+        ],
+    }
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ExampleVersion {
     Trivial,
@@ -441,23 +429,54 @@ enum ExampleVersion {
     Complete,
 }
 
+impl ExampleVersion {
+    /// All versions, in the same order as [`EXAMPLES`]
+    const ALL: [ExampleVersion; 5] = [
+        ExampleVersion::Trivial,
+        ExampleVersion::TrivialRecursive(false),
+        ExampleVersion::TrivialRecursive(true),
+        ExampleVersion::CompleteWithoutBlending,
+        ExampleVersion::Complete,
+    ];
+
+    /// Returns the id used to embed this version
+    fn example_id(self) -> ExampleId {
+        EXAMPLES[Self::ALL.iter().position(|v| *v == self).unwrap()]
+    }
+
+    /// Returns the version embedded with the given id
+    fn from_example_id(id: ExampleId) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.example_id() == id)
+    }
+}
+
 #[derive(Debug, PartialEq, Properties)]
 struct FractalClockExampleProperties {
     version: ExampleVersion,
     final_render_input: Rc<FractalClockRenderInput>,
-    settings: Rc<HashMap<String, Html>>,
+    settings: FractalClockSettingsHandles,
+    settings_transfer: SettingsTransfer,
+    reset_settings: Callback<()>,
+    randomize_settings: Callback<()>,
     #[prop_or_default]
     initially_active: bool,
+    #[prop_or_default]
+    embedded: bool,
+    #[prop_or(true)]
+    show_settings: bool,
 }
 
 #[function_component(FractalClockExample)]
 fn fractal_clock_example(props: &FractalClockExampleProperties) -> Html {
+    let renderer = use_renderer(FractalClockRenderer::default);
+    let presets = use_memo((), |_| SettingsPresets::new(PRESETS));
     let render_input = match props.version {
         ExampleVersion::Trivial => FractalClockRenderInput {
             size: 1.0,
             recursion_depth: 1,
             size_factor: 0.75,
             color: AlphaColor::from_rgba8(255, 255, 255, 255),
+            depth_colors: None,
             blend_equations: (BlendConstant::Addition, BlendConstant::Addition),
             blend_multipliers: (
                 BlendConstant::One,
@@ -465,7 +484,7 @@ fn fractal_clock_example(props: &FractalClockExampleProperties) -> Html {
                 BlendConstant::One,
                 BlendConstant::Zero,
             ),
-            ..*props.final_render_input
+            ..(*props.final_render_input).clone()
         },
         ExampleVersion::TrivialRecursive(custom_recursion) => FractalClockRenderInput {
             size: 1.0,
@@ -475,6 +494,7 @@ fn fractal_clock_example(props: &FractalClockExampleProperties) -> Html {
                 2
             },
             color: AlphaColor::from_rgba8(255, 255, 255, 255),
+            depth_colors: None,
             blend_equations: (BlendConstant::Addition, BlendConstant::Addition),
             blend_multipliers: (
                 BlendConstant::One,
@@ -482,7 +502,7 @@ fn fractal_clock_example(props: &FractalClockExampleProperties) -> Html {
                 BlendConstant::One,
                 BlendConstant::Zero,
             ),
-            ..*props.final_render_input
+            ..(*props.final_render_input).clone()
         },
         ExampleVersion::CompleteWithoutBlending => FractalClockRenderInput {
             blend_equations: (BlendConstant::Addition, BlendConstant::Addition),
@@ -492,63 +512,31 @@ fn fractal_clock_example(props: &FractalClockExampleProperties) -> Html {
                 BlendConstant::One,
                 BlendConstant::Zero,
             ),
-            ..*props.final_render_input
+            ..(*props.final_render_input).clone()
         },
         ExampleVersion::Complete => (*props.final_render_input).clone(),
     };
-    const TRIVIAL_SETTINGS: &[&str] = &[
-        HOUR_ANGLE_SETTING,
-        MINUTE_ANGLE_SETTING,
-        ANIMATE_SETTING,
-        HOUR_RATIO_SETTING,
-    ];
-    const TRIVIAL_RECURSION_SETTINGS: &[&str] = &[
-        HOUR_ANGLE_SETTING,
-        MINUTE_ANGLE_SETTING,
-        ANIMATE_SETTING,
-        HOUR_RATIO_SETTING,
-        SIZE_FACTOR_SETTING,
-        RECURSION_DEPTH_SETTING,
-    ];
-    const COMPLETE_SETTINGS: &[&str] = &[
-        HOUR_ANGLE_SETTING,
-        MINUTE_ANGLE_SETTING,
-        ANIMATE_SETTING,
-        HOUR_RATIO_SETTING,
-        SIZE_SETTING,
-        SIZE_FACTOR_SETTING,
-        RECURSION_DEPTH_SETTING,
-        COLOR_SETTING,
-        ALPHA_SETTING,
-        RGB_BLEND_SETTING,
-        ALPHA_BLEND_SETTING,
-        SOURCE_RGB_SETTING,
-        SOURCE_ALPHA_SETTING,
-        DESTINATION_RGB_SETTING,
-        DESTINATION_ALPHA_SETTING,
-    ];
-    let settings_filter: &[&str] = match props.version {
-        ExampleVersion::Trivial => TRIVIAL_SETTINGS,
-        ExampleVersion::TrivialRecursive(false) => &TRIVIAL_RECURSION_SETTINGS[..5],
-        ExampleVersion::TrivialRecursive(true) => TRIVIAL_RECURSION_SETTINGS,
-        ExampleVersion::CompleteWithoutBlending => &COMPLETE_SETTINGS[..8],
-        ExampleVersion::Complete => COMPLETE_SETTINGS,
-    };
-    let settings: Vec<_> = settings_filter
-        .iter()
-        .map(|&setting| {
-            (
-                setting.to_owned(),
-                props.settings.get(setting).unwrap().clone(),
-            )
-        })
-        .collect();
     html! {
-        <InteractiveExample<FractalClockRenderer>
-            renderer={FractalClockRenderer::default()}
+        <Tracked<InteractiveExample<FractalClockRenderer>>
+            {renderer}
             {render_input}
             initially_active={props.initially_active}
-            {settings}
+            settings={SETTINGS.panel(&props.settings, props.version)}
+            title={Project::FractalClock.meta().title}
+            show_settings={props.show_settings}
+            height={if props.embedded { "100vh" } else { "500px" }}
+            example={props.version.example_id()}
+            embed_route={
+                (!props.embedded)
+                    .then(|| Project::FractalClock.embed_route(props.version.example_id()))
+            }
+            settings_transfer={props.settings_transfer.clone()}
+            on_reset_settings={props.reset_settings.clone()}
+            on_randomize_settings={props.randomize_settings.clone()}
+            presets={(*presets).clone()}
+            autoplay={
+                if props.embedded { AutoplayPolicy::Always } else { AutoplayPolicy::for_device() }
+            }
         />
     }
 }
@@ -4,17 +4,23 @@
 use std::fmt::Display;
 
 use color::{AlphaColor, Srgb};
-use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext as GL};
+use web_sys::{WebGlProgram, WebGlRenderingContext as GL, WebGlTexture};
 
 use crate::{
+    projects::interactive::{Gradient, SelectionOption},
     uniform_set,
     webgl::{
-        CanvasRenderer, ComputeProgram, RenderData, Uniform, compile_shader,
-        create_program,
+        AttributeType, CanvasRenderer, ChunkedTask, ComputeProgram, Geometry, GpuTimer,
+        IndexBuffer, RenderData, TextAnchor, TextRenderer, TextureUniform, TextureUnits, Uniform,
+        VertexLayout, WebglError, compile_shader, create_program, create_ramp_texture,
+        instancing_supported, preprocess_shader, update_ramp_texture,
     },
 };
 
 pub const MAX_RECURSION_DEPTH: u32 = 16;
+/// The highest recursion depth drawn in low power mode, which needs a single gpu pass after the
+/// cpu pre-pass
+const LOW_POWER_MAX_RECURSION_DEPTH: u32 = COMPUTE_TEXTURE_RECURSION_WIDTH;
 
 const COMPUTE_TEXTURE_RECURSION_WIDTH: u32 = 10;
 const COMPUTE_TEXTURE_RECURSION_HEIGHT: u32 =
@@ -22,6 +28,12 @@ const COMPUTE_TEXTURE_RECURSION_HEIGHT: u32 =
 const COMPUTE_TEXTURE_WIDTH: u32 = 2_u32.pow(COMPUTE_TEXTURE_RECURSION_WIDTH);
 const COMPUTE_TEXTURE_HEIGHT: u32 = 2_u32.pow(COMPUTE_TEXTURE_RECURSION_HEIGHT);
 
+/// The amount of colors sampled from the depth gradient
+const DEPTH_COLORS_WIDTH: usize = 64;
+
+/// The amount of pointers computed by the cpu pre-pass, filling the first row of the compute texture
+const PREPASS_POINTERS: usize = COMPUTE_TEXTURE_WIDTH as usize;
+
 const COMPUTE_FRAGMENT_SOURCE: &str = "
     precision highp float;
     uniform sampler2D u_input_0;
@@ -31,12 +43,7 @@ const COMPUTE_FRAGMENT_SOURCE: &str = "
     uniform vec2 u_hour;
     uniform vec2 u_minute;
 
-    vec4 getValueFrom2DTextureAs1DArray(sampler2D tex, vec2 dimensions, float index) {
-        float y = floor(index / dimensions.x);
-        float x = mod(index, dimensions.x);
-        vec2 texcoord = (vec2(x, y) + 0.5) / dimensions;
-        return texture2D(tex, texcoord);
-    }
+    #include \"texture_array.glsl\"
 
     void main() {
         float index = floor(u_dimensions.x) * floor(gl_FragCoord.y) + floor(gl_FragCoord.x);
@@ -63,21 +70,25 @@ const COMPUTE_FRAGMENT_SOURCE: &str = "
 const VERTEX_RENDER_VERTEX_SOURCE: &str = "
     precision mediump float;
 
-    attribute float a_index;
+    attribute float a_endpoint;
+    attribute float a_instance;
     uniform sampler2D u_input;
     uniform vec2 u_dimensions;
     uniform vec2 u_scale;
+    uniform float u_recursion_depth;
 
-    vec4 getValueFrom2DTextureAs1DArray(sampler2D tex, vec2 dimensions, float index) {
-        float y = floor(index / dimensions.x);
-        float x = mod(index, dimensions.x);
-        vec2 texcoord = (vec2(x, y) + 0.5) / dimensions;
-        return texture2D(tex, texcoord);
-    }
+    varying float v_depth;
+
+    #include \"texture_array.glsl\"
 
     void main() {
-        float vertex_index = floor(a_index / 2.0);
-        if (mod(a_index, 2.0) == 0.0) {
+        // The recursion of the pointer, pointers 2^n - 2 to 2^(n + 1) - 3 belong to the n-th one
+        float depth = floor(log2(a_instance + 2.0) + 0.001) - 1.0;
+        v_depth = clamp(depth / max(u_recursion_depth - 1.0, 1.0), 0.0, 1.0);
+
+        // Each instance is a segment from the parent pointer to the pointer of the instance
+        float vertex_index = a_instance;
+        if (a_endpoint == 0.0) {
             vertex_index = floor(vertex_index / 2.0) - 1.0;
         }
         if (vertex_index == -1.0) {
@@ -91,6 +102,31 @@ const VERTEX_RENDER_VERTEX_SOURCE: &str = "
 const VERTEX_RENDER_FRAGMENT_SOURCE: &str = "
     precision mediump float;
 
+    uniform vec4 u_color;
+    uniform sampler2D u_depth_colors;
+    uniform float u_color_by_depth;
+
+    varying float v_depth;
+
+    void main() {
+        vec3 depth_color = texture2D(u_depth_colors, vec2(v_depth, 0.5)).rgb;
+        gl_FragColor = vec4(mix(u_color.rgb, depth_color, u_color_by_depth), u_color.a);
+    }
+";
+
+const CLOCK_FACE_VERTEX_SOURCE: &str = "
+    precision mediump float;
+
+    attribute vec2 a_position;
+    uniform vec2 u_scale;
+
+    void main() {
+        gl_Position = vec4(a_position.y * u_scale.x, a_position.x * u_scale.y, 0.0, 1.0);
+    }
+";
+const CLOCK_FACE_FRAGMENT_SOURCE: &str = "
+    precision mediump float;
+
     uniform vec4 u_color;
 
     void main() {
@@ -98,7 +134,44 @@ const VERTEX_RENDER_FRAGMENT_SOURCE: &str = "
     }
 ";
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The amount of line segments approximating the clock face rim
+const CLOCK_FACE_RIM_SEGMENTS: u32 = 120;
+/// The distance of the numerals from the clock center, relative to the minute pointer
+const CLOCK_FACE_NUMERAL_RADIUS: f32 = 0.75;
+/// The font size of the numerals, relative to the minute pointer
+const CLOCK_FACE_NUMERAL_SIZE: f32 = 0.15;
+/// The font size the numerals are rasterized with
+const CLOCK_FACE_FONT_SIZE: u32 = 64;
+
+/// Returns the vertices of the clock face rim and tick marks, in the same coordinates as the
+/// clock pointers, together with the line indices
+fn clock_face_vertices() -> (Vec<f32>, Vec<u16>) {
+    let point = |angle: f32, radius: f32| {
+        let (y, x) = angle.sin_cos();
+        [x * radius, y * radius]
+    };
+
+    let rim = (0..CLOCK_FACE_RIM_SEGMENTS).map(|i| {
+        let angle = std::f32::consts::TAU / CLOCK_FACE_RIM_SEGMENTS as f32;
+        point(i as f32 * angle, 1.0)
+    });
+    let ticks = (0..60).flat_map(|i| {
+        let angle = (i as f32 * 6.0).to_radians();
+        let inner_radius = if i % 5 == 0 { 0.86 } else { 0.93 };
+        [point(angle, inner_radius), point(angle, 0.98)]
+    });
+    let vertices = rim.chain(ticks).flatten().collect();
+
+    // Adjacent rim segments share their end points
+    let segments = CLOCK_FACE_RIM_SEGMENTS as u16;
+    let rim_indices = (0..segments).flat_map(|i| [i, (i + 1) % segments]);
+    let tick_indices = segments..segments + 2 * 60;
+    let indices = rim_indices.chain(tick_indices).collect();
+
+    (vertices, indices)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 #[repr(u32)]
 pub enum BlendConstant {
     Addition = GL::FUNC_ADD,
@@ -148,24 +221,48 @@ impl BlendConstant {
     }
 }
 
-pub const BLEND_EQUATIONS: &[BlendConstant] = &[
-    BlendConstant::Addition,
-    BlendConstant::Subtraction,
-    BlendConstant::ReverseSubtraction,
-];
-pub const BLEND_MULTIPLIERS: &[BlendConstant] = &[
-    BlendConstant::Zero,
-    BlendConstant::One,
-    BlendConstant::SourceColor,
-    BlendConstant::OneMinusSourceColor,
-    BlendConstant::DestinationColor,
-    BlendConstant::OneMinusDestinationColor,
-    BlendConstant::SourceAlpha,
-    BlendConstant::OneMinusSourceAlpha,
-    BlendConstant::DestinationAlpha,
-    BlendConstant::OneMinusDestinationAlpha,
-    BlendConstant::SourceAlphaSaturate,
+/// The options of the blend equation settings
+pub const BLEND_EQUATIONS: &[SelectionOption<BlendConstant>] = &[
+    SelectionOption::new(BlendConstant::Addition).label("Source + Destination"),
+    SelectionOption::new(BlendConstant::Subtraction).label("Source - Destination"),
+    SelectionOption::new(BlendConstant::ReverseSubtraction).label("Destination - Source"),
 ];
+/// The options of the source blend multiplier settings
+pub const SOURCE_BLEND_MULTIPLIERS: &[SelectionOption<BlendConstant>] = &blend_multipliers(true);
+/// The options of the destination blend multiplier settings
+pub const DESTINATION_BLEND_MULTIPLIERS: &[SelectionOption<BlendConstant>] =
+    &blend_multipliers(false);
+
+/// Returns the blend multipliers grouped by what they scale with.
+///
+/// [`BlendConstant::SourceAlphaSaturate`] is only a valid source factor, so it is disabled for the
+/// destination.
+const fn blend_multipliers(source: bool) -> [SelectionOption<BlendConstant>; 11] {
+    [
+        SelectionOption::new(BlendConstant::Zero).group("Constant"),
+        SelectionOption::new(BlendConstant::One).group("Constant"),
+        SelectionOption::new(BlendConstant::SourceColor).group("Source"),
+        SelectionOption::new(BlendConstant::OneMinusSourceColor)
+            .label("1 - Source Color")
+            .group("Source"),
+        SelectionOption::new(BlendConstant::SourceAlpha).group("Source"),
+        SelectionOption::new(BlendConstant::OneMinusSourceAlpha)
+            .label("1 - Source Alpha")
+            .group("Source"),
+        SelectionOption::new(BlendConstant::SourceAlphaSaturate)
+            .label("Saturated Source Alpha")
+            .group("Source")
+            .disabled(!source),
+        SelectionOption::new(BlendConstant::DestinationColor).group("Destination"),
+        SelectionOption::new(BlendConstant::OneMinusDestinationColor)
+            .label("1 - Destination Color")
+            .group("Destination"),
+        SelectionOption::new(BlendConstant::DestinationAlpha).group("Destination"),
+        SelectionOption::new(BlendConstant::OneMinusDestinationAlpha)
+            .label("1 - Destination Alpha")
+            .group("Destination"),
+    ]
+}
 
 uniform_set! {
     ComputeUniformSet {
@@ -177,7 +274,10 @@ uniform_set! {
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct FractalClockRenderer {}
+pub struct FractalClockRenderer {
+    /// Measures the compute and render passes
+    gpu_timer: GpuTimer,
+}
 
 #[derive(Debug)]
 pub struct FractalClockRenderState {
@@ -185,13 +285,30 @@ pub struct FractalClockRenderState {
     vertex_compute_program: ComputeProgram<ComputeUniformSet>,
     vertex_render_program: WebGlProgram,
     vertex_render_dimensions_uniform: Uniform<(f32, f32)>,
-    vertex_render_input_uniform: Uniform<(i32,)>,
+    vertex_render_input_uniform: TextureUniform,
     vertex_render_scale_uniform: Uniform<(f32, f32)>,
     vertex_render_color_uniform: Uniform<(f32, f32, f32, f32)>,
-    vertex_render_vertex_buffer: WebGlBuffer,
+    vertex_render_recursion_depth_uniform: Uniform<(f32,)>,
+    vertex_render_color_by_depth_uniform: Uniform<(f32,)>,
+    vertex_render_depth_colors_uniform: TextureUniform,
+    /// The colors of the lines from the first to the last recursion
+    depth_colors_texture: WebGlTexture,
+    vertex_render_geometry: Geometry,
+    clock_face_program: WebGlProgram,
+    clock_face_scale_uniform: Uniform<(f32, f32)>,
+    clock_face_color_uniform: Uniform<(f32, f32, f32, f32)>,
+    clock_face_geometry: Geometry,
+    clock_face_index_buffer: IndexBuffer,
+    clock_face_text: TextRenderer,
+    /// The cpu pre-pass computing the first row of pointers
+    prepass: ChunkedTask,
+    /// The hour and minute pointer of the running pre-pass
+    prepass_pointers: ((f32, f32), (f32, f32)),
+    /// Whether a pre-pass finished since the state was created, so the clock is drawn
+    prepared: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct FractalClockRenderInput {
     pub hour_angle: f32,
     pub minute_angle: f32,
@@ -201,8 +318,12 @@ pub struct FractalClockRenderInput {
     pub hour_ratio: f32,
     pub size_factor: f32,
     pub color: AlphaColor<Srgb>,
+    /// Colors the lines by their recursion depth instead, keeping the opacity of the color
+    pub depth_colors: Option<Gradient>,
     pub blend_equations: (BlendConstant, BlendConstant),
     pub blend_multipliers: (BlendConstant, BlendConstant, BlendConstant, BlendConstant),
+    /// The opacity of the clock face drawn beneath the fractal in the theme foreground, if any
+    pub clock_face: Option<f32>,
 }
 
 impl CanvasRenderer for FractalClockRenderer {
@@ -210,6 +331,8 @@ impl CanvasRenderer for FractalClockRenderer {
 
     type RenderInput = FractalClockRenderInput;
 
+    type RenderOutput = ();
+
     fn render(
         &self,
         state: &mut Self::RenderState,
@@ -221,16 +344,26 @@ impl CanvasRenderer for FractalClockRenderer {
             height,
             input_changed,
             time,
+            cpu_budget,
+            colors,
+            low_power,
             ..
         }: RenderData,
     ) {
-        if input_changed || initial_render || input.animate {
+        let recursion_depth = if low_power {
+            input.recursion_depth.min(LOW_POWER_MAX_RECURSION_DEPTH)
+        } else {
+            input.recursion_depth
+        };
+
+        // While animating, the next pre-pass only starts once the previous one is finished
+        if input_changed || initial_render || (input.animate && state.prepass.finished()) {
             let (hour_angle, minute_angle) = if input.animate {
-                const COMPLETE_TIME_ROTATION: u32 = 12 * 60 * 60 * 10;
-                const ONE_HOUR_TIME_ROTATION: u32 = COMPLETE_TIME_ROTATION / 12;
+                const COMPLETE_TIME_ROTATION: f64 = 12.0 * 60.0 * 60.0 * 10.0;
+                const ONE_HOUR_TIME_ROTATION: f64 = COMPLETE_TIME_ROTATION / 12.0;
                 (
-                    (time % COMPLETE_TIME_ROTATION) as f32 / COMPLETE_TIME_ROTATION as f32 * 360.0,
-                    (time % ONE_HOUR_TIME_ROTATION) as f32 / ONE_HOUR_TIME_ROTATION as f32 * 360.0,
+                    (time % COMPLETE_TIME_ROTATION / COMPLETE_TIME_ROTATION * 360.0) as f32,
+                    (time % ONE_HOUR_TIME_ROTATION / ONE_HOUR_TIME_ROTATION * 360.0) as f32,
                 )
             } else {
                 (input.hour_angle, input.minute_angle)
@@ -277,74 +410,93 @@ impl CanvasRenderer for FractalClockRenderer {
             state.vertex_compute_input_buffer[6] = minute_start.0;
             state.vertex_compute_input_buffer[7] = minute_start.1;
 
-            for i in 2..1024 {
+            state.prepass_pointers = (hour, minute);
+            state.prepass.restart(PREPASS_POINTERS - 2);
+        }
+
+        // The cpu pre-pass fills the first row of pointers, spread over frames if necessary
+        if !state.prepass.finished() {
+            let (hour, minute) = state.prepass_pointers;
+            let buffer = &mut state.vertex_compute_input_buffer;
+            let finished = state.prepass.run(cpu_budget, |step| {
+                let i = step + 2;
                 let parent = i / 2 - 1;
-                let position = (
-                    state.vertex_compute_input_buffer[parent * 4],
-                    state.vertex_compute_input_buffer[parent * 4 + 1],
-                );
-                let angle = (
-                    state.vertex_compute_input_buffer[parent * 4 + 2],
-                    state.vertex_compute_input_buffer[parent * 4 + 3],
-                );
+                let position = (buffer[parent * 4], buffer[parent * 4 + 1]);
+                let angle = (buffer[parent * 4 + 2], buffer[parent * 4 + 3]);
                 let mut new_angle = if i % 2 == 0 { hour } else { minute };
                 new_angle = (
                     angle.0 * new_angle.0 - angle.1 * new_angle.1,
                     angle.0 * new_angle.1 + angle.1 * new_angle.0,
                 );
-                state.vertex_compute_input_buffer[i * 4] = position.0 + new_angle.0;
-                state.vertex_compute_input_buffer[i * 4 + 1] = position.1 + new_angle.1;
-                state.vertex_compute_input_buffer[i * 4 + 2] = new_angle.0;
-                state.vertex_compute_input_buffer[i * 4 + 3] = new_angle.1;
-            }
-            state
-                .vertex_compute_program
-                .write_input(gl, 0, &state.vertex_compute_input_buffer);
-
-            for _ in 0..(input
-                .recursion_depth
-                .saturating_sub(COMPUTE_TEXTURE_RECURSION_WIDTH)
-                + 1)
-            {
-                state.vertex_compute_program.compute(gl);
-                state.vertex_compute_program.copy_output_to_input(gl, 0);
+                buffer[i * 4] = position.0 + new_angle.0;
+                buffer[i * 4 + 1] = position.1 + new_angle.1;
+                buffer[i * 4 + 2] = new_angle.0;
+                buffer[i * 4 + 3] = new_angle.1;
+            });
+
+            if finished {
+                state.prepared = true;
+                state
+                    .vertex_compute_program
+                    .write_input(gl, 0, &state.vertex_compute_input_buffer);
+
+                self.gpu_timer.measure(gl, "compute", || {
+                    for _ in
+                        0..(recursion_depth.saturating_sub(COMPUTE_TEXTURE_RECURSION_WIDTH) + 1)
+                    {
+                        state.vertex_compute_program.compute(gl);
+                        state.vertex_compute_program.swap();
+                    }
+                });
             }
         }
 
         gl.use_program(Some(&state.vertex_render_program));
-        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&state.vertex_render_vertex_buffer));
-        gl.active_texture(GL::TEXTURE0);
-        gl.bind_texture(
-            GL::TEXTURE_2D,
-            Some(state.vertex_compute_program.output_texture()),
-        );
-
-        let position = gl
-            .get_attrib_location(&state.vertex_render_program, "a_index")
-            .try_into()
-            .unwrap();
-        gl.vertex_attrib_pointer_with_i32(position, 1, GL::FLOAT, false, 0, 0);
-        gl.enable_vertex_attrib_array(position);
+        gl.viewport(0, 0, width.try_into().unwrap(), height.try_into().unwrap());
+        // The blending assumes a transparent black destination, the themed background of the canvas
+        // element shows through instead
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
 
-        state.vertex_render_dimensions_uniform.apply(gl);
-        state.vertex_render_input_uniform.apply(gl);
         let scale = input.size
-            / ((1.0
-                - input
-                    .size_factor
-                    .powi(input.recursion_depth.try_into().unwrap()))
+            / ((1.0 - input.size_factor.powi(recursion_depth.try_into().unwrap()))
                 / (1.0 - input.size_factor));
-        state
-            .vertex_render_scale_uniform
-            .apply_data(gl, (height as f32 / width as f32 * scale, scale));
+        let scale = (height as f32 / width as f32 * scale, scale);
+
+        if let Some(opacity) = input.clock_face {
+            let [r, g, b, _] = colors.foreground;
+            let color = [r, g, b, opacity];
+            self.gpu_timer.measure(gl, "clock face", || {
+                render_clock_face(state, gl, color, scale, width, height)
+            });
+        }
+
+        gl.use_program(Some(&state.vertex_render_program));
+        state.vertex_render_geometry.bind(gl);
+
+        state.vertex_render_dimensions_uniform.apply(gl);
+        // The latest result is read from the input, since it was swapped after each pass
+        state.vertex_render_input_uniform.apply_texture(
+            gl,
+            Some(state.vertex_compute_program.input_texture(0).clone()),
+        );
+        state.vertex_render_scale_uniform.apply_data(gl, scale);
         let [r, g, b, a] = input.color.components;
         state
             .vertex_render_color_uniform
             .apply_data(gl, (r, g, b, a));
-
-        gl.viewport(0, 0, width.try_into().unwrap(), height.try_into().unwrap());
-        gl.clear_color(0.0, 0.0, 0.0, 0.0);
-        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+        state
+            .vertex_render_recursion_depth_uniform
+            .apply_data(gl, (recursion_depth as f32,));
+        state.vertex_render_color_by_depth_uniform.apply_data(
+            gl,
+            (if input.depth_colors.is_some() {
+                1.0
+            } else {
+                0.0
+            },),
+        );
+        state.vertex_render_depth_colors_uniform.apply(gl);
 
         gl.get_extension("EXT_float_blend").unwrap();
         gl.enable(GL::BLEND);
@@ -359,31 +511,62 @@ impl CanvasRenderer for FractalClockRenderer {
             input.blend_multipliers.3.value(),
         );
 
-        let x = 2 * 2 * (2_i32.pow(input.recursion_depth) - 1);
-        gl.draw_arrays(GL::LINES, 0, x);
+        let segments = 2 * (2_usize.pow(recursion_depth) - 1);
+        self.gpu_timer.measure(gl, "render", || {
+            state
+                .vertex_render_geometry
+                .draw_instanced(gl, GL::LINES, segments)
+        });
         gl.disable(GL::BLEND);
+        state.vertex_render_geometry.unbind(gl);
+    }
+
+    fn on_input_changed(
+        &self,
+        state: &mut Self::RenderState,
+        input: &Self::RenderInput,
+        gl: &GL,
+        _render_data: RenderData,
+    ) {
+        if let Some(depth_colors) = &input.depth_colors {
+            update_ramp_texture(
+                gl,
+                &state.depth_colors_texture,
+                &depth_colors.ramp(DEPTH_COLORS_WIDTH),
+            );
+        }
     }
 
     fn initial_render_state(
         &self,
-        _: &Self::RenderInput,
+        input: &Self::RenderInput,
         gl: &GL,
         _: RenderData,
-    ) -> FractalClockRenderState {
+    ) -> Result<FractalClockRenderState, WebglError> {
         let max_texture_size = gl
             .get_parameter(GL::MAX_TEXTURE_SIZE)
-            .unwrap()
-            .as_f64()
-            .unwrap() as u32;
-        assert!(max_texture_size >= std::cmp::max(COMPUTE_TEXTURE_WIDTH, COMPUTE_TEXTURE_HEIGHT));
+            .ok()
+            .and_then(|max_texture_size| max_texture_size.as_f64())
+            .unwrap_or_default() as u32;
+        let size = std::cmp::max(COMPUTE_TEXTURE_WIDTH, COMPUTE_TEXTURE_HEIGHT);
+        if max_texture_size < size {
+            return Err(WebglError::TextureTooLarge {
+                size,
+                max_size: max_texture_size,
+            });
+        }
+        if !instancing_supported(gl) {
+            return Err(WebglError::MissingExtension("ANGLE_instanced_arrays"));
+        }
 
         let vertex_compute_program = ComputeProgram::new(
             COMPUTE_TEXTURE_WIDTH,
             COMPUTE_TEXTURE_HEIGHT,
             1,
+            1,
             gl,
-            COMPUTE_FRAGMENT_SOURCE,
-        );
+            preprocess_shader(COMPUTE_FRAGMENT_SOURCE, &[]),
+        )?;
         let vertex_compute_input_buffer = vec![
             0.0;
             (4 * COMPUTE_TEXTURE_WIDTH * COMPUTE_TEXTURE_HEIGHT)
@@ -391,39 +574,98 @@ impl CanvasRenderer for FractalClockRenderer {
                 .unwrap()
         ];
 
-        let vertex_render_vertex_shader =
-            compile_shader(gl, GL::VERTEX_SHADER, VERTEX_RENDER_VERTEX_SOURCE).unwrap();
+        let vertex_render_vertex_shader = compile_shader(
+            gl,
+            GL::VERTEX_SHADER,
+            preprocess_shader(VERTEX_RENDER_VERTEX_SOURCE, &[]),
+        )?;
         let vertex_render_fragment_shader =
-            compile_shader(gl, GL::FRAGMENT_SHADER, VERTEX_RENDER_FRAGMENT_SOURCE).unwrap();
+            compile_shader(gl, GL::FRAGMENT_SHADER, VERTEX_RENDER_FRAGMENT_SOURCE)?;
         let vertex_render_program = create_program(
             gl,
             &vertex_render_vertex_shader,
             &vertex_render_fragment_shader,
-        )
-        .unwrap();
+        )?;
 
         let vertex_render_dimensions_uniform = Uniform::new(
             gl,
             &vertex_render_program,
             "u_dimensions",
             (COMPUTE_TEXTURE_WIDTH as f32, COMPUTE_TEXTURE_HEIGHT as f32),
-        );
-        let vertex_render_input_uniform = Uniform::new(gl, &vertex_render_program, "u_input", (0,));
+        )?;
+        let mut texture_units = TextureUnits::new();
+        let vertex_render_input_uniform = texture_units.uniform(
+            gl,
+            &vertex_render_program,
+            "u_input",
+            Some(vertex_compute_program.input_texture(0).clone()),
+        )?;
         let vertex_render_scale_uniform =
-            Uniform::new(gl, &vertex_render_program, "u_scale", (1.0, 1.0));
+            Uniform::new(gl, &vertex_render_program, "u_scale", (1.0, 1.0))?;
         let vertex_render_color_uniform =
-            Uniform::new(gl, &vertex_render_program, "u_color", (1.0, 1.0, 1.0, 1.0));
+            Uniform::new(gl, &vertex_render_program, "u_color", (1.0, 1.0, 1.0, 1.0))?;
+        let vertex_render_recursion_depth_uniform =
+            Uniform::new(gl, &vertex_render_program, "u_recursion_depth", (1.0,))?;
+        let vertex_render_color_by_depth_uniform =
+            Uniform::new(gl, &vertex_render_program, "u_color_by_depth", (0.0,))?;
+        let depth_colors_texture = create_ramp_texture(
+            gl,
+            &input
+                .depth_colors
+                .as_ref()
+                .map(|depth_colors| depth_colors.ramp(DEPTH_COLORS_WIDTH))
+                .unwrap_or_else(|| vec![[255; 4]]),
+        )?;
+        let vertex_render_depth_colors_uniform = texture_units.uniform(
+            gl,
+            &vertex_render_program,
+            "u_depth_colors",
+            Some(depth_colors_texture.clone()),
+        )?;
 
-        let vertices: Vec<f32> = (0..2_u32.pow(MAX_RECURSION_DEPTH + 2))
+        // A single segment, drawn once per pointer
+        let instances: Vec<f32> = (0..2_u32.pow(MAX_RECURSION_DEPTH + 1))
             .map(|i| i as f32)
             .collect();
-        let verts = web_sys::js_sys::Float32Array::from(vertices.as_slice());
-        let vertex_render_vertex_buffer = gl.create_buffer().unwrap();
-
-        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&vertex_render_vertex_buffer));
-        gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &verts, GL::STATIC_DRAW);
-
-        FractalClockRenderState {
+        let vertex_render_geometry = Geometry::new(
+            gl,
+            &vertex_render_program,
+            vec![
+                (
+                    VertexLayout::new().attribute("a_endpoint", AttributeType::Float),
+                    &[0.0, 1.0],
+                ),
+                (
+                    VertexLayout::new()
+                        .attribute("a_instance", AttributeType::Float)
+                        .per_instance(),
+                    &instances,
+                ),
+            ],
+        )?;
+
+        let clock_face_vertex_shader =
+            compile_shader(gl, GL::VERTEX_SHADER, CLOCK_FACE_VERTEX_SOURCE)?;
+        let clock_face_fragment_shader =
+            compile_shader(gl, GL::FRAGMENT_SHADER, CLOCK_FACE_FRAGMENT_SOURCE)?;
+        let clock_face_program =
+            create_program(gl, &clock_face_vertex_shader, &clock_face_fragment_shader)?;
+        let clock_face_scale_uniform =
+            Uniform::new(gl, &clock_face_program, "u_scale", (1.0, 1.0))?;
+        let clock_face_color_uniform =
+            Uniform::new(gl, &clock_face_program, "u_color", (1.0, 1.0, 1.0, 1.0))?;
+
+        let (clock_face_vertices, clock_face_indices) = clock_face_vertices();
+        let clock_face_geometry = Geometry::new(
+            gl,
+            &clock_face_program,
+            vec![(
+                VertexLayout::new().attribute("a_position", AttributeType::Vec2),
+                &clock_face_vertices,
+            )],
+        )?;
+
+        Ok(FractalClockRenderState {
             vertex_compute_program,
             vertex_compute_input_buffer,
             vertex_render_program,
@@ -431,7 +673,70 @@ impl CanvasRenderer for FractalClockRenderer {
             vertex_render_input_uniform,
             vertex_render_scale_uniform,
             vertex_render_color_uniform,
-            vertex_render_vertex_buffer,
-        }
+            vertex_render_recursion_depth_uniform,
+            vertex_render_color_by_depth_uniform,
+            vertex_render_depth_colors_uniform,
+            depth_colors_texture,
+            vertex_render_geometry,
+            clock_face_program,
+            clock_face_scale_uniform,
+            clock_face_color_uniform,
+            clock_face_geometry,
+            clock_face_index_buffer: IndexBuffer::new(gl, &clock_face_indices)?,
+            clock_face_text: TextRenderer::new(gl, CLOCK_FACE_FONT_SIZE)?,
+            prepass: ChunkedTask::default(),
+            prepass_pointers: Default::default(),
+            prepared: false,
+        })
+    }
+    fn loading_progress(&self, state: &Self::RenderState) -> Option<f32> {
+        (!state.prepared).then(|| state.prepass.progress())
+    }
+
+    fn gpu_timer(&self) -> Option<&GpuTimer> {
+        Some(&self.gpu_timer)
+    }
+}
+
+/// Draw the clock face rim, tick marks and numerals with the given RGBA color and pointer scale
+fn render_clock_face(
+    state: &mut FractalClockRenderState,
+    gl: &GL,
+    [r, g, b, a]: [f32; 4],
+    scale: (f32, f32),
+    width: u32,
+    height: u32,
+) {
+    gl.use_program(Some(&state.clock_face_program));
+    state.clock_face_geometry.bind(gl);
+
+    state.clock_face_scale_uniform.apply_data(gl, scale);
+    state.clock_face_color_uniform.apply_data(gl, (r, g, b, a));
+
+    gl.enable(GL::BLEND);
+    gl.blend_equation(GL::FUNC_ADD);
+    gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
+    state.clock_face_index_buffer.draw(gl, GL::LINES);
+    gl.disable(GL::BLEND);
+
+    state.clock_face_geometry.unbind(gl);
+
+    // Numerals, converted from pointer coordinates to canvas pixels
+    let size = CLOCK_FACE_NUMERAL_SIZE * scale.1 * height as f32 / 2.0;
+    for hour in 1..=12 {
+        let (y, x) = (hour as f32 * 30.0).to_radians().sin_cos();
+        let (x, y) = (x * CLOCK_FACE_NUMERAL_RADIUS, y * CLOCK_FACE_NUMERAL_RADIUS);
+        let pixel = (
+            (y * scale.0 + 1.0) / 2.0 * width as f32,
+            (1.0 - x * scale.1) / 2.0 * height as f32,
+        );
+        state.clock_face_text.queue(
+            hour.to_string(),
+            pixel,
+            TextAnchor::Center,
+            [r, g, b, a],
+            size,
+        );
     }
+    state.clock_face_text.render(gl, width, height);
 }
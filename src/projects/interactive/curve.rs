@@ -0,0 +1,324 @@
+//! Response curves, edited by the [`CurveEditor`] and sampled by simulations
+
+use std::{cell::Cell, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+use stylist::yew::use_style;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::{projects::interactive::use_frame_setter, theme::ThemeColor, use_theme};
+
+/// The size of the [`CurveEditor`] in pixels, matching its view box
+const EDITOR_SIZE: (f32, f32) = (160.0, 100.0);
+
+/// The margin around the curve inside the [`CurveEditor`], so points at the edges can be grabbed
+const EDITOR_MARGIN: f32 = 6.0;
+
+/// The distance in pixels within which a point of the [`CurveEditor`] is grabbed
+const GRAB_DISTANCE: f32 = 8.0;
+
+/// The least horizontal distance between two points of a curve
+const MIN_POINT_DISTANCE: f32 = 0.01;
+
+/// A smooth curve through control points in `[0, 1] x [0, 1]`.
+///
+/// The first and last point always lie at 0 and 1 horizontally, so the curve maps every value in
+/// `[0, 1]`, e.g. a distance relative to a radius, to a response in `[0, 1]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<(f32, f32)>", into = "Vec<(f32, f32)>")]
+pub struct Curve {
+    /// The control points, sorted horizontally
+    points: Vec<(f32, f32)>,
+}
+
+impl Curve {
+    /// Create a curve through the given points.
+    ///
+    /// The points are sorted and clamped to `[0, 1]`, the outer points are moved to 0 and 1
+    /// horizontally.
+    ///
+    /// # Panics
+    /// If there are less than two points
+    pub fn new(mut points: Vec<(f32, f32)>) -> Self {
+        assert!(points.len() >= 2, "A curve needs at least 2 points");
+        for (x, y) in &mut points {
+            *x = x.clamp(0.0, 1.0);
+            *y = y.clamp(0.0, 1.0);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.first_mut().unwrap().0 = 0.0;
+        points.last_mut().unwrap().0 = 1.0;
+
+        Self { points }
+    }
+
+    /// Returns the control points, sorted horizontally
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points
+    }
+
+    /// Returns the value of the curve at the given position in `[0, 1]`.
+    ///
+    /// Between two points the curve is a cubic Hermite spline with Catmull-Rom tangents, clamped to
+    /// `[0, 1]`.
+    pub fn sample(&self, x: f32) -> f32 {
+        let points = &self.points;
+        let x = x.clamp(0.0, 1.0);
+        let index = points
+            .windows(2)
+            .position(|pair| x <= pair[1].0)
+            .unwrap_or(points.len() - 2);
+        let ((x0, y0), (x1, y1)) = (points[index], points[index + 1]);
+        let span = x1 - x0;
+        if span <= 0.0 {
+            return y1;
+        }
+
+        // The tangents through the neighbouring points, scaled to the span of this segment
+        let tangent = |before: usize, after: usize| {
+            let ((xa, ya), (xb, yb)) = (points[before], points[after]);
+            if xb > xa {
+                (yb - ya) / (xb - xa) * span
+            } else {
+                0.0
+            }
+        };
+        let m0 = tangent(index.saturating_sub(1), index + 1);
+        let m1 = tangent(index, (index + 2).min(points.len() - 1));
+
+        let t = (x - x0) / span;
+        let (t2, t3) = (t * t, t * t * t);
+        let y = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+            + (t3 - 2.0 * t2 + t) * m0
+            + (-2.0 * t3 + 3.0 * t2) * y1
+            + (t3 - t2) * m1;
+        y.clamp(0.0, 1.0)
+    }
+
+    /// Returns the given amount of values sampled evenly from the curve, including both ends
+    pub fn samples(&self, count: usize) -> Vec<f32> {
+        (0..count)
+            .map(|index| self.sample(index as f32 / (count.max(2) - 1) as f32))
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<(f32, f32)>> for Curve {
+    type Error = String;
+
+    fn try_from(points: Vec<(f32, f32)>) -> Result<Self, Self::Error> {
+        if points.len() < 2 {
+            return Err("A curve needs at least 2 points".to_owned());
+        }
+        Ok(Self::new(points))
+    }
+}
+
+impl From<Curve> for Vec<(f32, f32)> {
+    fn from(curve: Curve) -> Self {
+        curve.points
+    }
+}
+
+/// Returns the position of a curve point inside the [`CurveEditor`] view box
+fn to_view((x, y): (f32, f32)) -> (f32, f32) {
+    let (width, height) = EDITOR_SIZE;
+    (
+        EDITOR_MARGIN + x * (width - 2.0 * EDITOR_MARGIN),
+        EDITOR_MARGIN + (1.0 - y) * (height - 2.0 * EDITOR_MARGIN),
+    )
+}
+
+/// Returns the curve point at a position inside the [`CurveEditor`] view box
+fn from_view((x, y): (f32, f32)) -> (f32, f32) {
+    let (width, height) = EDITOR_SIZE;
+    (
+        ((x - EDITOR_MARGIN) / (width - 2.0 * EDITOR_MARGIN)).clamp(0.0, 1.0),
+        (1.0 - (y - EDITOR_MARGIN) / (height - 2.0 * EDITOR_MARGIN)).clamp(0.0, 1.0),
+    )
+}
+
+/// Properties for the [`CurveEditor`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct CurveEditorProperties {
+    /// Whether the editor is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The edited curve
+    pub value: UseStateHandle<Curve>,
+}
+
+/// A component used to edit a [`Curve`] by dragging its control points.
+///
+/// Pressing next to the curve adds a point, double clicking a point removes it again. The outer
+/// points can only be moved vertically.
+#[function_component(CurveEditor)]
+pub fn curve_editor(CurveEditorProperties { active, value }: &CurveEditorProperties) -> Html {
+    let theme = use_theme();
+    let style = use_style!(
+        r#"
+            width: ${width}px;
+            height: ${height}px;
+            margin: 5px 0px;
+            border: 1px solid ${fg};
+            border-radius: 5px;
+            background-color: ${bg};
+            touch-action: none;
+            cursor: crosshair;
+
+            &.inactive {
+                opacity: 0.5;
+                cursor: default;
+            }
+        "#,
+        width = EDITOR_SIZE.0,
+        height = EDITOR_SIZE.1,
+        fg = ThemeColor::Base04.var(),
+        bg = ThemeColor::Base01.var(),
+    );
+    // The index of the dragged point
+    let dragged = use_memo((), |_| Rc::new(Cell::new(None::<usize>)));
+
+    // Returns the pointer position inside the view box
+    let view_position = |event: &PointerEvent| {
+        let editor = event
+            .current_target()
+            .and_then(|target| target.dyn_into::<Element>().ok())?;
+        let rect = editor.get_bounding_client_rect();
+        Some((
+            ((f64::from(event.client_x()) - rect.left()) / rect.width()) as f32 * EDITOR_SIZE.0,
+            ((f64::from(event.client_y()) - rect.top()) / rect.height()) as f32 * EDITOR_SIZE.1,
+        ))
+    };
+    // Move the given point, keeping it between its neighbours
+    let set_value = use_frame_setter(value);
+    let move_point = {
+        let value = value.clone();
+        move |index: usize, (x, y): (f32, f32)| {
+            let mut points = value.points.clone();
+            let last = points.len() - 1;
+            let x = if index == 0 || index == last {
+                points[index].0
+            } else {
+                x.clamp(
+                    points[index - 1].0 + MIN_POINT_DISTANCE,
+                    points[index + 1].0 - MIN_POINT_DISTANCE,
+                )
+            };
+            points[index] = (x, y);
+            set_value.emit(Curve::new(points));
+        }
+    };
+
+    let on_pointer_down = Callback::from({
+        let value = value.clone();
+        let dragged = dragged.clone();
+        let active = *active;
+        move |event: PointerEvent| {
+            if !active {
+                return;
+            }
+            let Some(position) = view_position(&event) else {
+                return;
+            };
+            if let Some(editor) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+            {
+                let _ = editor.set_pointer_capture(event.pointer_id());
+            }
+
+            let grabbed = value.points.iter().position(|&point| {
+                let (x, y) = to_view(point);
+                (x - position.0).hypot(y - position.1) <= GRAB_DISTANCE
+            });
+            match grabbed {
+                Some(index) => dragged.set(Some(index)),
+                None => {
+                    // Add a point between its neighbours and drag it right away
+                    let point = from_view(position);
+                    let index = value
+                        .points
+                        .iter()
+                        .position(|&(x, _)| x > point.0)
+                        .unwrap_or(value.points.len() - 1)
+                        .max(1);
+                    let mut points = value.points.clone();
+                    let x = point.0.clamp(
+                        points[index - 1].0 + MIN_POINT_DISTANCE,
+                        points[index].0 - MIN_POINT_DISTANCE,
+                    );
+                    if x > points[index - 1].0 && x < points[index].0 {
+                        points.insert(index, (x, point.1));
+                        value.set(Curve::new(points));
+                        dragged.set(Some(index));
+                    }
+                }
+            }
+        }
+    });
+    let on_pointer_move = Callback::from({
+        let dragged = dragged.clone();
+        move |event: PointerEvent| {
+            if let (Some(index), Some(position)) = (dragged.get(), view_position(&event)) {
+                move_point(index, from_view(position));
+            }
+        }
+    });
+    let on_pointer_up = Callback::from({
+        let dragged = dragged.clone();
+        move |_: PointerEvent| dragged.set(None)
+    });
+    let remove_point = |index: usize| {
+        let value = value.clone();
+        let active = *active;
+        Callback::from(move |_: MouseEvent| {
+            let mut points = value.points.clone();
+            if active && index > 0 && index < points.len() - 1 {
+                points.remove(index);
+                value.set(Curve::new(points));
+            }
+        })
+    };
+
+    let curve_points = (0..=64)
+        .map(|index| {
+            let x = index as f32 / 64.0;
+            let (x, y) = to_view((x, value.sample(x)));
+            format!("{x},{y}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let points = value.points.iter().enumerate().map(|(index, &point)| {
+        let (x, y) = to_view(point);
+        html! {
+            <circle
+                cx={x.to_string()}
+                cy={y.to_string()}
+                r="4"
+                fill={theme.base0D.to_string()}
+                ondblclick={remove_point(index)}
+            />
+        }
+    });
+
+    html! {
+        <svg
+            class={classes!(style, (!active).then_some("inactive"))}
+            viewBox={format!("0 0 {} {}", EDITOR_SIZE.0, EDITOR_SIZE.1)}
+            onpointerdown={on_pointer_down}
+            onpointermove={on_pointer_move}
+            onpointerup={on_pointer_up}
+        >
+            <polyline
+                points={curve_points}
+                fill="none"
+                stroke={theme.base04.to_string()}
+                stroke-width="2"
+            />
+            {for points}
+        </svg>
+    }
+}
@@ -0,0 +1,219 @@
+//! Exporting interactive examples as files
+
+use gloo::{events::EventListener, timers::callback::Timeout, utils::document};
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Blob, BlobEvent, BlobPropertyBag, CanvasRenderingContext2d, HtmlAnchorElement,
+    HtmlCanvasElement, HtmlImageElement, MediaRecorder, MediaRecorderOptions, RecordingState, Url,
+    js_sys::{Array, encode_uri_component},
+};
+
+use crate::{feeds::WEBSITE_URL, fonts, theme::Theme};
+
+/// The path to the logo drawn onto watermarks
+pub const WATERMARK_LOGO_PATH: &str = "/assets/images/cod_64.png";
+
+/// The mime type of recorded videos
+const VIDEO_MIME_TYPE: &str = "video/webm";
+
+/// The padding around watermark contents in pixels
+const WATERMARK_PADDING: f64 = 8.0;
+/// The size of the watermark logo in pixels
+const WATERMARK_LOGO_SIZE: f64 = 32.0;
+/// The font size of the watermark text in pixels
+const WATERMARK_FONT_SIZE: f64 = 14.0;
+
+/// An attribution watermark composited onto exported images
+pub struct Watermark<'a> {
+    /// The title of the exported example
+    pub title: &'a str,
+    /// The (already loaded) logo image
+    pub logo: &'a HtmlImageElement,
+    /// The theme used for coloring
+    pub theme: &'a Theme,
+}
+
+impl Watermark<'_> {
+    /// Draw this watermark into the bottom right corner of the given context
+    fn draw(
+        &self,
+        context: &CanvasRenderingContext2d,
+        width: f64,
+        height: f64,
+    ) -> Result<(), JsValue> {
+        context.set_font(&format!("{WATERMARK_FONT_SIZE}px {}", fonts::font_family()));
+        let text_width = context
+            .measure_text(self.title)?
+            .width()
+            .max(context.measure_text(WEBSITE_URL)?.width());
+
+        let box_width = WATERMARK_LOGO_SIZE + text_width + 3.0 * WATERMARK_PADDING;
+        let box_height = WATERMARK_LOGO_SIZE + 2.0 * WATERMARK_PADDING;
+        let x = width - box_width - WATERMARK_PADDING;
+        let y = height - box_height - WATERMARK_PADDING;
+
+        context.set_global_alpha(0.8);
+        context.set_fill_style_str(self.theme.base00);
+        context.fill_rect(x, y, box_width, box_height);
+        context.set_global_alpha(1.0);
+
+        let logo_x = x + WATERMARK_PADDING;
+        let logo_y = y + WATERMARK_PADDING;
+        context.set_fill_style_str(self.theme.base0D);
+        context.fill_rect(logo_x, logo_y, WATERMARK_LOGO_SIZE, WATERMARK_LOGO_SIZE);
+        if self.logo.complete() {
+            context.draw_image_with_html_image_element_and_dw_and_dh(
+                self.logo,
+                logo_x,
+                logo_y,
+                WATERMARK_LOGO_SIZE,
+                WATERMARK_LOGO_SIZE,
+            )?;
+        }
+
+        let text_x = logo_x + WATERMARK_LOGO_SIZE + WATERMARK_PADDING;
+        context.set_fill_style_str(self.theme.base06);
+        context.fill_text(self.title, text_x, logo_y + WATERMARK_FONT_SIZE)?;
+        context.set_fill_style_str(self.theme.base04);
+        context.fill_text(WEBSITE_URL, text_x, logo_y + WATERMARK_LOGO_SIZE)?;
+
+        Ok(())
+    }
+}
+
+/// Export the current contents of a canvas as a png download, optionally adding a watermark
+///
+/// The canvas contents are copied onto a separate 2D canvas first, so the watermark never ends up
+/// on the displayed canvas.
+pub fn export_png(
+    canvas: &HtmlCanvasElement,
+    file_name: &str,
+    watermark: Option<Watermark>,
+) -> Result<(), JsValue> {
+    let export: HtmlCanvasElement = document().create_element("canvas")?.dyn_into()?;
+    export.set_width(canvas.width());
+    export.set_height(canvas.height());
+
+    let context: CanvasRenderingContext2d = export
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("2d context unavailable"))?
+        .dyn_into()?;
+    context.draw_image_with_html_canvas_element(canvas, 0.0, 0.0)?;
+
+    if let Some(watermark) = watermark {
+        watermark.draw(&context, canvas.width().into(), canvas.height().into())?;
+    }
+
+    download(&export.to_data_url()?, file_name)
+}
+
+/// Export a value as a json file download
+pub fn export_json<T: Serialize>(value: &T, file_name: &str) -> Result<(), JsValue> {
+    let json =
+        serde_json::to_string(value).map_err(|error| JsValue::from_str(&error.to_string()))?;
+
+    download(
+        &format!(
+            "data:application/json;charset=utf-8,{}",
+            encode_uri_component(&json)
+        ),
+        file_name,
+    )
+}
+
+/// Records the contents of a canvas as a webm video, downloaded once the recording stops
+pub struct VideoRecorder {
+    /// The recorder of the canvas stream
+    recorder: MediaRecorder,
+    /// Listeners collecting the recorded data and downloading it once stopped
+    _listeners: [EventListener; 2],
+    /// Stops the recording after the requested duration
+    _timeout: Timeout,
+}
+
+impl VideoRecorder {
+    /// Start recording the canvas for at most `duration` milliseconds.
+    ///
+    /// Once stopped, the video is downloaded and `on_stop` is called.
+    pub fn start(
+        canvas: &HtmlCanvasElement,
+        file_name: String,
+        duration: u32,
+        on_stop: impl FnOnce() + 'static,
+    ) -> Result<Self, JsValue> {
+        let options = MediaRecorderOptions::new();
+        options.set_mime_type(VIDEO_MIME_TYPE);
+        let recorder = MediaRecorder::new_with_media_stream_and_media_recorder_options(
+            &canvas.capture_stream()?,
+            &options,
+        )?;
+
+        let chunks = Array::new();
+        let data_listener = EventListener::new(&recorder, "dataavailable", {
+            let chunks = chunks.clone();
+            move |event| {
+                if let Some(data) = event.unchecked_ref::<BlobEvent>().data() {
+                    chunks.push(&data);
+                }
+            }
+        });
+        let mut on_stop = Some(on_stop);
+        let stop_listener = EventListener::new(&recorder, "stop", move |_| {
+            if let Err(error) = download_video(&chunks, &file_name) {
+                log::error!("Failed to export video: {error:?}");
+            }
+            if let Some(on_stop) = on_stop.take() {
+                on_stop();
+            }
+        });
+        let timeout = Timeout::new(duration, {
+            let recorder = recorder.clone();
+            move || {
+                let _ = recorder.stop();
+            }
+        });
+
+        recorder.start()?;
+
+        Ok(Self {
+            recorder,
+            _listeners: [data_listener, stop_listener],
+            _timeout: timeout,
+        })
+    }
+
+    /// Stop the recording early, which downloads the video
+    pub fn stop(&self) {
+        if self.recorder.state() == RecordingState::Recording {
+            let _ = self.recorder.stop();
+        }
+    }
+}
+
+/// Download the recorded chunks of a video
+fn download_video(chunks: &Array, file_name: &str) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type(VIDEO_MIME_TYPE);
+    let video = Blob::new_with_blob_sequence_and_options(chunks, &options)?;
+
+    let url = Url::create_object_url_with_blob(&video)?;
+    download(&url, file_name)?;
+    // The download reads the url asynchronously, so it is only revoked afterwards
+    Timeout::new(0, move || {
+        let _ = Url::revoke_object_url(&url);
+    })
+    .forget();
+
+    Ok(())
+}
+
+/// Let the browser download the given url
+fn download(url: &str, file_name: &str) -> Result<(), JsValue> {
+    let link: HtmlAnchorElement = document().create_element("a")?.dyn_into()?;
+    link.set_href(url);
+    link.set_download(file_name);
+    link.click();
+
+    Ok(())
+}
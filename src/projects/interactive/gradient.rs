@@ -0,0 +1,281 @@
+//! Color gradients, edited by the [`GradientPicker`] and uploaded as color ramps
+
+use serde::{Deserialize, Serialize};
+use stylist::yew::use_style;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{projects::interactive::use_frame_setter, theme::ThemeColor};
+
+/// The least amount of stops a gradient keeps
+const MIN_STOPS: usize = 2;
+
+/// A color at a position of a [`Gradient`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    /// The position of the stop in `[0, 1]`
+    pub position: f32,
+    /// The RGB color of the stop
+    pub color: [u8; 3],
+}
+
+impl GradientStop {
+    /// Create a stop with the given position and color
+    pub const fn new(position: f32, color: [u8; 3]) -> Self {
+        Self { position, color }
+    }
+
+    /// Returns the color as a css hex string
+    pub fn hex_color(&self) -> String {
+        let [r, g, b] = self.color;
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    /// Parses a css hex string like `#40ff20`, returns `None` if it is invalid
+    fn parse_hex_color(hex: &str) -> Option<[u8; 3]> {
+        let hex = hex.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+
+        Some([channel(0)?, channel(2)?, channel(4)?])
+    }
+}
+
+/// A color gradient made of stops, which are kept sorted by their position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<GradientStop>", into = "Vec<GradientStop>")]
+pub struct Gradient {
+    /// The stops, sorted by their position
+    stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    /// Create a gradient from the given stops in any order
+    ///
+    /// # Panics
+    /// If there are less than two stops
+    pub fn new(mut stops: Vec<GradientStop>) -> Self {
+        assert!(
+            stops.len() >= MIN_STOPS,
+            "A gradient needs at least {MIN_STOPS} stops"
+        );
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self { stops }
+    }
+
+    /// Returns the stops, sorted by their position
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    /// Returns the color at the given position in `[0, 1]`, interpolated linearly between stops
+    pub fn sample(&self, position: f32) -> [u8; 3] {
+        let next = self.stops.iter().position(|stop| stop.position >= position);
+        let (from, to) = match next {
+            Some(0) => return self.stops[0].color,
+            Some(index) => (self.stops[index - 1], self.stops[index]),
+            None => return self.stops[self.stops.len() - 1].color,
+        };
+        let span = to.position - from.position;
+        let t = if span > 0.0 {
+            (position - from.position) / span
+        } else {
+            0.0
+        };
+
+        std::array::from_fn(|channel| {
+            let (from, to) = (f32::from(from.color[channel]), f32::from(to.color[channel]));
+            (from + (to - from) * t).round() as u8
+        })
+    }
+
+    /// Returns the given amount of opaque colors sampled evenly from the gradient, e.g. for
+    /// [`create_ramp_texture`](crate::webgl::create_ramp_texture)
+    pub fn ramp(&self, width: usize) -> Vec<[u8; 4]> {
+        (0..width)
+            .map(|index| {
+                let [r, g, b] = self.sample(index as f32 / (width.max(2) - 1) as f32);
+                [r, g, b, 255]
+            })
+            .collect()
+    }
+
+    /// Returns the gradient as a css `linear-gradient`, from left to right
+    fn css(&self) -> String {
+        let stops: Vec<_> = self
+            .stops
+            .iter()
+            .map(|stop| format!("{} {}%", stop.hex_color(), stop.position * 100.0))
+            .collect();
+        format!("linear-gradient(to right, {})", stops.join(", "))
+    }
+}
+
+impl TryFrom<Vec<GradientStop>> for Gradient {
+    type Error = String;
+
+    fn try_from(stops: Vec<GradientStop>) -> Result<Self, Self::Error> {
+        if stops.len() < MIN_STOPS {
+            return Err(format!("A gradient needs at least {MIN_STOPS} stops"));
+        }
+        Ok(Self::new(stops))
+    }
+}
+
+impl From<Gradient> for Vec<GradientStop> {
+    fn from(gradient: Gradient) -> Self {
+        gradient.stops
+    }
+}
+
+/// Properties for the [`GradientPicker`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct GradientPickerProperties {
+    /// Whether the gradient picker is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The selected gradient
+    pub value: UseStateHandle<Gradient>,
+}
+
+/// A component used to edit the stops of a color gradient, previewing the result
+#[function_component(GradientPicker)]
+pub fn gradient_picker(
+    GradientPickerProperties { active, value }: &GradientPickerProperties,
+) -> Html {
+    let style = use_style!(
+        r#"
+            display: grid;
+            grid-template-columns: max-content auto max-content;
+            column-gap: 10px;
+            row-gap: 5px;
+            align-items: center;
+            margin: 5px 0px;
+
+            .preview {
+                grid-column: 1 / -1;
+                height: 20px;
+                border: 1px solid ${fg};
+                border-radius: 5px;
+                background: ${preview};
+            }
+
+            button {
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+
+            button:disabled {
+                cursor: default;
+                opacity: 0.5;
+            }
+
+            .add {
+                grid-column: 1 / -1;
+                justify-self: start;
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        preview = value.css(),
+    );
+
+    // Replace a stop, keeping the stops sorted
+    let set_value = use_frame_setter(value);
+    let set_stop = {
+        let value = value.clone();
+        move |index: usize, stop: GradientStop| {
+            let mut stops = value.stops.clone();
+            stops[index] = stop;
+            set_value.emit(Gradient::new(stops));
+        }
+    };
+    let stops = value.stops.iter().enumerate().map(|(index, &stop)| {
+        let set_color = Callback::from({
+            let set_stop = set_stop.clone();
+            move |event: InputEvent| {
+                let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+                if let Some(color) = GradientStop::parse_hex_color(&input.value()) {
+                    set_stop(index, GradientStop { color, ..stop });
+                }
+            }
+        });
+        let set_position = Callback::from({
+            let set_stop = set_stop.clone();
+            move |event: Event| {
+                let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+                if let Ok(position) = input.value().parse::<f32>() {
+                    let position = position.clamp(0.0, 1.0);
+                    set_stop(index, GradientStop { position, ..stop });
+                }
+            }
+        });
+        let remove = Callback::from({
+            let value = value.clone();
+            move |_: MouseEvent| {
+                let mut stops = value.stops.clone();
+                stops.remove(index);
+                value.set(Gradient::new(stops));
+            }
+        });
+
+        html! {
+            <>
+                <input
+                    type="color"
+                    disabled={!active}
+                    value={stop.hex_color()}
+                    oninput={set_color}
+                />
+                <input
+                    type="range"
+                    disabled={!active}
+                    min="0"
+                    max="1"
+                    step="0.01"
+                    value={stop.position.to_string()}
+                    onchange={set_position}
+                />
+                <button
+                    title="Remove this color"
+                    disabled={!active || value.stops.len() <= MIN_STOPS}
+                    onclick={remove}
+                >
+                    <i class="iconoir-xmark"/>
+                </button>
+            </>
+        }
+    });
+    let add_stop = Callback::from({
+        let value = value.clone();
+        move |_: MouseEvent| {
+            // Fill the widest gap between two stops
+            let (index, _) = value
+                .stops
+                .windows(2)
+                .enumerate()
+                .max_by(|(_, a), (_, b)| {
+                    (a[1].position - a[0].position).total_cmp(&(b[1].position - b[0].position))
+                })
+                .unwrap();
+            let position = (value.stops[index].position + value.stops[index + 1].position) / 2.0;
+            let mut stops = value.stops.clone();
+            stops.push(GradientStop::new(position, value.sample(position)));
+            value.set(Gradient::new(stops));
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <div class="preview"/>
+            {for stops}
+            <button class="add" title="Add a color" disabled={!active} onclick={add_stop}>
+                <i class="iconoir-plus"/>
+                {" Add color"}
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,2435 @@
+//! Components for creating interactive interfaces
+
+use std::{
+    cell::LazyCell,
+    fmt::Display,
+    rc::Rc,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use color::{AlphaColor, Srgb};
+use convert_case::{Case, Casing};
+use gloo::{
+    events::EventListener,
+    file::callbacks::{FileReader, read_as_text},
+    render::request_animation_frame,
+    utils::window,
+};
+use stylist::yew::use_style;
+use wasm_bindgen::{JsCast, prelude::Closure};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Element, HtmlCanvasElement, HtmlElement, HtmlImageElement, HtmlInputElement, HtmlSelectElement,
+    IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit, js_sys::Array,
+};
+use yew::{platform::spawn_local, prelude::*};
+use yew_router::Routable;
+
+pub use website_macros::ExampleSettings;
+
+mod curve;
+pub(crate) mod export;
+mod gradient;
+mod presets;
+mod settings;
+mod stats;
+
+pub use curve::{Curve, CurveEditor};
+pub use gradient::{Gradient, GradientPicker, GradientStop};
+use presets::PresetSelection;
+pub use presets::{Preset, SettingsPresets};
+use settings::Setting;
+pub use settings::{
+    AngleDialControl, CheckboxControl, ColorControl, CurveControl, GradientControl,
+    RangeSliderControl, SelectionControl, SettingControl, SettingDescriptor, SettingsDescriptor,
+    SettingsHandles, SettingsPanel, SettingsTransfer, SliderControl, ToggleGroupControl,
+    shared_settings, store_collapsed_groups, store_settings, stored_collapsed_groups,
+    stored_settings,
+};
+use stats::FrameStatsOverlay;
+
+use crate::{
+    dev::Tracked,
+    feeds::WEBSITE_URL,
+    intl::format_number,
+    motion::use_pause,
+    navigation::Route,
+    power::is_mobile,
+    projects::ExampleId,
+    theme::ThemeColor,
+    toast::use_toasts,
+    use_theme,
+    webgl::{Canvas, CanvasRenderer, FrameStats, RenderLoopState, ReplayController},
+};
+
+/// A scroll event listener, notifying a list of callbacks
+struct ScrollEventListener {
+    _listener: EventListener,
+    callbacks: Rc<Mutex<Vec<Callback<Event>>>>,
+}
+
+impl ScrollEventListener {
+    /// Create a new `ScrollEventListener`, registering it for the window
+    fn new() -> Self {
+        let callbacks: Rc<Mutex<Vec<Callback<Event>>>> = Rc::new(Mutex::new(Vec::default()));
+
+        ScrollEventListener {
+            _listener: EventListener::new(&window(), "scroll", {
+                let callbacks = callbacks.clone();
+                move |event| {
+                    for cb in callbacks.lock().unwrap().iter() {
+                        cb.emit(event.clone());
+                    }
+                }
+            }),
+            callbacks,
+        }
+    }
+}
+
+thread_local! {
+    /// The unique scroll listener used throughout this website
+    static SCROLL_EVENT_LISTENER: LazyCell<ScrollEventListener> = LazyCell::new(ScrollEventListener::new);
+}
+
+/// Register a callback to the unique [`ScrollEventListener`].
+///
+/// The callback gets unregistered automatically when the hook is no more in use.
+#[hook]
+pub fn use_scroll_event_listener(callback: impl Fn(Event) + 'static) {
+    let callback = Callback::from(callback);
+
+    use_effect_with(callback, |callback| {
+        let callback = callback.clone();
+        let callback_clone = callback.clone();
+
+        SCROLL_EVENT_LISTENER.with(move |listener| {
+            let mut callbacks = listener.callbacks.lock().unwrap();
+            callbacks.push(callback_clone);
+        });
+
+        move || {
+            SCROLL_EVENT_LISTENER.with(|listener| {
+                listener
+                    .callbacks
+                    .lock()
+                    .unwrap()
+                    .retain(|cb| *cb != callback)
+            });
+        }
+    });
+}
+
+/// How far outside of the viewport elements already count as visible, so examples start rendering
+/// shortly before they are scrolled into view
+const VISIBILITY_MARGIN: &str = "50% 0px";
+
+/// Returns whether the referenced element is in or close to the viewport, `None` until the first
+/// observation.
+///
+/// Unlike checking the position on scroll, the [`IntersectionObserver`] also notices resizes,
+/// layout changes and scrolling within nested containers.
+#[hook]
+pub fn use_visibility(node_ref: NodeRef) -> Option<bool> {
+    let visible = use_state(|| None);
+
+    use_effect_with(node_ref, {
+        let visible = visible.setter();
+        move |node_ref| {
+            let callback = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+                // Entries are in chronological order, the last one is the current state
+                if let Some(entry) = entries.iter().last() {
+                    let entry: IntersectionObserverEntry = entry.unchecked_into();
+                    visible.set(Some(entry.is_intersecting()));
+                }
+            });
+            let options = IntersectionObserverInit::new();
+            options.set_root_margin(VISIBILITY_MARGIN);
+            let observer =
+                IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options)
+                    .unwrap();
+            if let Some(element) = node_ref.cast::<Element>() {
+                observer.observe(&element);
+            }
+
+            move || {
+                observer.disconnect();
+                drop(callback);
+            }
+        }
+    });
+
+    *visible
+}
+
+/// Returns a callback setting the given state at most once per animation frame.
+///
+/// Dragging a control changes its value with every input event, each rerendering the example
+/// holding the setting. Setting it through this callback coalesces those changes, only the latest
+/// one is applied before the next frame.
+#[hook]
+pub fn use_frame_setter<T>(value: &UseStateHandle<T>) -> Callback<T>
+where
+    T: 'static,
+{
+    // The value to apply with the requested frame
+    let latest = use_mut_ref(|| None);
+    // The requested frame, dropping it cancels the update
+    let frame = use_mut_ref(|| None);
+    let setter = value.setter();
+
+    (*use_memo((), move |_| {
+        Callback::from(move |value: T| {
+            if latest.borrow_mut().replace(value).is_none() {
+                let latest = latest.clone();
+                let setter = setter.clone();
+                *frame.borrow_mut() = Some(request_animation_frame(move |_| {
+                    if let Some(value) = latest.borrow_mut().take() {
+                        setter.set(value);
+                    }
+                }));
+            }
+        })
+    }))
+    .clone()
+}
+
+/// When an [`InteractiveExample`] starts rendering
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoplayPolicy {
+    /// Render all the time, even when scrolled out of view
+    Always,
+    /// Render while the example is on screen
+    WhenVisible,
+    /// Show a static first frame until the user presses play
+    Never,
+}
+
+impl AutoplayPolicy {
+    /// Returns the policy suited for the current device class.
+    ///
+    /// Mobile devices only start examples on request, to save battery and data.
+    pub fn for_device() -> Self {
+        if is_mobile() {
+            AutoplayPolicy::Never
+        } else {
+            AutoplayPolicy::WhenVisible
+        }
+    }
+}
+
+/// The rendering quality of an [`InteractiveExample`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quality {
+    /// Render at the resolution of the screen
+    Standard,
+    /// Render at twice the resolution, smoothing aliased edges
+    High,
+    /// Render at four times the resolution
+    Ultra,
+}
+
+impl Quality {
+    /// All qualities, in ascending order
+    pub const ALL: [Quality; 3] = [Quality::Standard, Quality::High, Quality::Ultra];
+
+    /// Returns the supersampling factor of the canvas
+    pub fn supersampling(self) -> u32 {
+        match self {
+            Quality::Standard => 1,
+            Quality::High => 2,
+            Quality::Ultra => 4,
+        }
+    }
+}
+
+impl Display for Quality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Quality::Standard => "Standard",
+                Quality::High => "High (2x supersampling)",
+                Quality::Ultra => "Ultra (4x supersampling)",
+            }
+        )
+    }
+}
+
+/// Properties for the [`InteractiveExample`] component
+#[derive(Clone, Properties, PartialEq)]
+pub struct InteractiveExampleProperties<R: CanvasRenderer> {
+    /// The renderer used on this [Canvas]
+    pub renderer: R,
+    /// Input to the renderer
+    pub render_input: R::RenderInput,
+    #[prop_or_default]
+    /// Whether this example is initially active
+    pub initially_active: bool,
+    /// The settings shown for this example, rendered as labeled controls
+    #[prop_or_default]
+    pub settings: Option<SettingsPanel>,
+    /// The title used for exported images
+    #[prop_or(AttrValue::from("Example"))]
+    pub title: AttrValue,
+    /// Whether to show the settings panel
+    #[prop_or(true)]
+    pub show_settings: bool,
+    /// The height of the canvas, valid css
+    #[prop_or(AttrValue::from("500px"))]
+    pub height: AttrValue,
+    /// The id of this example, remembers which setting groups are collapsed if given
+    #[prop_or_default]
+    pub example: Option<ExampleId>,
+    /// The route embedding this example, enables copying the embed code if given
+    #[prop_or_default]
+    pub embed_route: Option<Route>,
+    /// The serialized settings, enables dragging them onto examples using the same renderer
+    #[prop_or_default]
+    pub settings_transfer: Option<SettingsTransfer>,
+    /// Restores all settings to their defaults, enables the reset button if given
+    #[prop_or_default]
+    pub on_reset_settings: Option<Callback<()>>,
+    /// Sets all settings to random values, enables the randomize button if given
+    #[prop_or_default]
+    pub on_randomize_settings: Option<Callback<()>>,
+    /// Curated presets of the settings, offered next to the presets saved by the user.
+    ///
+    /// Presets are only available with a [`settings_transfer`](Self::settings_transfer).
+    #[prop_or_default]
+    pub presets: SettingsPresets,
+    /// When to start rendering, defaults to [`AutoplayPolicy::for_device`]
+    #[prop_or_else(AutoplayPolicy::for_device)]
+    pub autoplay: AutoplayPolicy,
+    /// The fixed timestep of the simulation, see
+    /// [`CanvasProperties::fixed_timestep`](crate::webgl::CanvasProperties::fixed_timestep)
+    #[prop_or_default]
+    pub fixed_timestep: Option<u32>,
+    /// The maximum length of recorded videos in seconds
+    #[prop_or(10)]
+    pub video_duration: u32,
+    /// Whether to show buttons freezing the example and stepping through it frame by frame
+    #[prop_or_default]
+    pub frame_controls: bool,
+    /// Whether to show a button restarting the example, e.g. to randomize a simulation again
+    #[prop_or_default]
+    pub resettable: bool,
+    /// Called whenever the [output](CanvasRenderer::output) of the renderer changes, only while
+    /// rendering with webgl
+    #[prop_or_default]
+    pub on_output: Callback<R::RenderOutput>,
+}
+
+#[cfg(debug_assertions)]
+impl<R: CanvasRenderer> crate::dev::PropDiff for InteractiveExampleProperties<R> {
+    fn changed_props(&self, old: &Self) -> Vec<&'static str> {
+        crate::changed_props!(
+            self,
+            old,
+            [
+                renderer,
+                render_input,
+                initially_active,
+                settings,
+                title,
+                show_settings,
+                height,
+                example,
+                embed_route,
+                settings_transfer,
+                on_reset_settings,
+                on_randomize_settings,
+                presets,
+                autoplay,
+                fixed_timestep,
+                video_duration,
+                frame_controls,
+                resettable,
+                on_output,
+            ]
+        )
+    }
+}
+
+/// An interactive example.
+///
+/// This is mostly a wrapper around a [`Canvas`]
+#[function_component(InteractiveExample)]
+pub fn interactive_example<R: CanvasRenderer>(props: &InteractiveExampleProperties<R>) -> Html {
+    let canvas_node_ref = use_node_ref();
+    let example_node_ref = use_node_ref();
+    // Observes the whole example, keeping it running while only its settings are in view
+    let visible = use_visibility(example_node_ref.clone()).unwrap_or(props.initially_active);
+
+    let full_screen_canvas = Callback::from({
+        let canvas_node_ref = canvas_node_ref.clone();
+
+        move |_| {
+            if let Some(canvas) = canvas_node_ref.cast::<HtmlCanvasElement>() {
+                canvas.request_fullscreen().unwrap();
+            } else {
+                panic!("Canvas should exist");
+            }
+        }
+    });
+
+    let frame_stats = use_memo((), |_| FrameStats::default());
+    let show_stats = use_state(|| false);
+    let toggle_stats = Callback::from({
+        let show_stats = show_stats.clone();
+        move |_| show_stats.set(!*show_stats)
+    });
+
+    let watermark = use_state(|| true);
+    let quality = use_state(|| Quality::Standard);
+    let watermark_logo = use_memo((), |_| {
+        let logo = HtmlImageElement::new().unwrap();
+        logo.set_src(export::WATERMARK_LOGO_PATH);
+        logo
+    });
+    let theme = use_theme();
+
+    let export_canvas = Callback::from({
+        let canvas_node_ref = canvas_node_ref.clone();
+        let watermark = watermark.clone();
+        let title = props.title.clone();
+        let theme = theme.clone();
+
+        move |_| {
+            if let Some(canvas) = canvas_node_ref.cast::<HtmlCanvasElement>() {
+                let file_name = format!("{}.png", title.to_case(Case::Kebab));
+                let watermark = watermark.then(|| export::Watermark {
+                    title: &title,
+                    logo: &watermark_logo,
+                    theme: &theme,
+                });
+                if let Err(error) = export::export_png(&canvas, &file_name, watermark) {
+                    log::error!("Failed to export canvas: {error:?}");
+                }
+            } else {
+                panic!("Canvas should exist");
+            }
+        }
+    });
+
+    let video_recorder = use_mut_ref(|| None::<export::VideoRecorder>);
+    let recording_video = use_state(|| false);
+    let toggle_video = Callback::from({
+        let canvas_node_ref = canvas_node_ref.clone();
+        let video_recorder = video_recorder.clone();
+        let recording_video = recording_video.clone();
+        let title = props.title.clone();
+        let duration = props.video_duration;
+
+        move |_| {
+            if *recording_video {
+                if let Some(recorder) = &*video_recorder.borrow() {
+                    recorder.stop();
+                }
+                return;
+            }
+
+            if let Some(canvas) = canvas_node_ref.cast::<HtmlCanvasElement>() {
+                let file_name = format!("{}.webm", title.to_case(Case::Kebab));
+                let on_stop = {
+                    let recording_video = recording_video.setter();
+                    move || recording_video.set(false)
+                };
+                match export::VideoRecorder::start(&canvas, file_name, duration * 1000, on_stop) {
+                    Ok(recorder) => {
+                        *video_recorder.borrow_mut() = Some(recorder);
+                        recording_video.set(true);
+                    }
+                    Err(error) => log::error!("Failed to record video: {error:?}"),
+                }
+            } else {
+                panic!("Canvas should exist");
+            }
+        }
+    });
+
+    let replay = use_memo((), |_| ReplayController::<R::RenderInput>::default());
+    let recording = use_state(|| false);
+    let toggle_recording = Callback::from({
+        let replay = replay.clone();
+        let recording = recording.clone();
+        let title = props.title.clone();
+
+        move |_| {
+            if let Some(recorded) = replay.stop_recording() {
+                let file_name = format!("{}-replay.json", title.to_case(Case::Kebab));
+                if let Err(error) = export::export_json(&recorded, &file_name) {
+                    log::error!("Failed to export replay: {error:?}");
+                }
+                recording.set(false);
+            } else {
+                replay.start_recording();
+                recording.set(true);
+            }
+        }
+    });
+
+    let recording_title = if *recording {
+        "Stop recording and save the replay"
+    } else {
+        "Record a replay"
+    };
+
+    // The reader needs to be kept alive until the file is loaded
+    let replay_reader = use_mut_ref(|| None::<FileReader>);
+    let load_replay = Callback::from({
+        let replay = replay.clone();
+        let recording = recording.clone();
+
+        move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let replay = replay.clone();
+                let recording = recording.clone();
+                *replay_reader.borrow_mut() = Some(read_as_text(&file.into(), move |text| {
+                    let loaded = text.map_err(|error| error.to_string()).and_then(|text| {
+                        serde_json::from_str(&text).map_err(|error| error.to_string())
+                    });
+                    match loaded {
+                        Ok(loaded) => {
+                            replay.play(loaded);
+                            recording.set(false);
+                        }
+                        Err(error) => log::error!("Failed to load replay: {error}"),
+                    }
+                }));
+            }
+            input.set_value("");
+        }
+    });
+
+    let copy_embed_code = props.embed_route.map(|route| {
+        Callback::from(move |_| {
+            let embed_code = format!(
+                r#"<iframe src="{WEBSITE_URL}{}" width="100%" height="500" style="border: none;"></iframe>"#,
+                route.to_path()
+            );
+            let _ = window().navigator().clipboard().write_text(&embed_code);
+        })
+    });
+
+    let toasts = use_toasts();
+    let copy_settings_link = props
+        .settings_transfer
+        .as_ref()
+        .and_then(SettingsTransfer::share_url)
+        .map(|url| {
+            Callback::from(move |_: MouseEvent| {
+                let _ = window().navigator().clipboard().write_text(&url);
+                if let Some(toasts) = &toasts {
+                    toasts.show("Copied a link to these settings");
+                }
+            })
+        });
+
+    // Settings can only be dropped onto examples and share presets using the same renderer
+    let renderer_name = use_memo((), |_| {
+        let renderer_name = std::any::type_name::<R>().rsplit("::").next().unwrap();
+        AttrValue::from(renderer_name.to_case(Case::Kebab))
+    });
+    let settings_format = use_memo(renderer_name.clone(), |renderer_name| {
+        format!("application/x-settings.{renderer_name}")
+    });
+    let drag_over = use_state(|| false);
+    let drag_settings = props.settings_transfer.as_ref().map(|transfer| {
+        let settings_format = settings_format.clone();
+        let serialized = transfer.serialized.clone();
+        Callback::from(move |event: DragEvent| {
+            if let Some(data_transfer) = event.data_transfer() {
+                let _ = data_transfer.set_data(&settings_format, &serialized);
+                data_transfer.set_effect_allowed("copy");
+            }
+        })
+    });
+    let accepts_drop = {
+        let settings_format = settings_format.clone();
+        let droppable = props.settings_transfer.is_some();
+        move |event: &DragEvent| {
+            droppable
+                && event.data_transfer().is_some_and(|data_transfer| {
+                    data_transfer
+                        .types()
+                        .includes(&settings_format.as_str().into(), 0)
+                })
+        }
+    };
+    let settings_drag_over = Callback::from({
+        let drag_over = drag_over.clone();
+        let accepts_drop = accepts_drop.clone();
+        move |event: DragEvent| {
+            if accepts_drop(&event) {
+                event.prevent_default();
+                drag_over.set(true);
+            }
+        }
+    });
+    let settings_drag_leave = Callback::from({
+        let drag_over = drag_over.clone();
+        move |_: DragEvent| drag_over.set(false)
+    });
+    let drop_settings = Callback::from({
+        let drag_over = drag_over.clone();
+        let apply = props
+            .settings_transfer
+            .as_ref()
+            .map(|transfer| transfer.apply.clone());
+        move |event: DragEvent| {
+            drag_over.set(false);
+            if !accepts_drop(&event) {
+                return;
+            }
+            event.prevent_default();
+            let serialized = event
+                .data_transfer()
+                .and_then(|data_transfer| data_transfer.get_data(&settings_format).ok());
+            if let (Some(apply), Some(serialized)) = (&apply, serialized)
+                && let Err(error) = apply.emit(serialized)
+            {
+                log::warn!("Dropped settings are invalid: {error}");
+            }
+        }
+    });
+
+    // Distinguishes the ids of the controls of this example from other examples on the page
+    let example_id = use_memo((), |_| EXAMPLE_COUNT.fetch_add(1, Ordering::Relaxed));
+    let control_id = |name: &str| AttrValue::from(format!("example-{example_id}-{name}"));
+
+    // The names of the setting groups collapsed in this example, remembered like its settings
+    let collapsed_groups = use_state({
+        let example = props.example;
+        move || example.map(stored_collapsed_groups).unwrap_or_default()
+    });
+
+    // Why the last pasted settings could not be applied
+    let paste_error = use_state(|| None::<AttrValue>);
+    let copy_settings = props.settings_transfer.as_ref().map(|transfer| {
+        let serialized = transfer.serialized.clone();
+        let paste_error = paste_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let _ = window().navigator().clipboard().write_text(&serialized);
+            paste_error.set(None);
+        })
+    });
+    let paste_settings = props.settings_transfer.as_ref().map(|transfer| {
+        let apply = transfer.apply.clone();
+        let paste_error = paste_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let apply = apply.clone();
+            let paste_error = paste_error.clone();
+            spawn_local(async move {
+                let read = JsFuture::from(window().navigator().clipboard().read_text()).await;
+                let result = match read.ok().and_then(|text| text.as_string()) {
+                    Some(json) => apply
+                        .emit(json)
+                        .map_err(|error| format!("The pasted settings are invalid: {error}")),
+                    None => Err("The clipboard could not be read".to_owned()),
+                };
+                paste_error.set(result.err().map(AttrValue::from));
+            });
+        })
+    });
+
+    let pause = use_pause();
+    let resume_animations = Callback::from({
+        let pause = pause.clone();
+        move |_| pause.set(false)
+    });
+
+    let started = use_state(|| props.autoplay != AutoplayPolicy::Never);
+    let start = Callback::from({
+        let started = started.clone();
+        move |_| started.set(true)
+    });
+
+    let frozen = use_state(|| false);
+    // The amount of single frames requested while frozen
+    let frame_steps = use_state(|| 0);
+    let freeze = Callback::from({
+        let frozen = frozen.clone();
+        let frame_steps = frame_steps.clone();
+        move |_| {
+            frame_steps.set(0);
+            frozen.set(true);
+        }
+    });
+    let step_frame = Callback::from({
+        let frame_steps = frame_steps.clone();
+        move |_| frame_steps.set(*frame_steps + 1)
+    });
+    let resume = Callback::from({
+        let frozen = frozen.clone();
+        move |_| frozen.set(false)
+    });
+
+    let resets = use_state(|| 0);
+    let reset = Callback::from({
+        let resets = resets.clone();
+        move |_| resets.set(*resets + 1)
+    });
+
+    let render_loop_state = match (props.autoplay, *started, visible) {
+        (AutoplayPolicy::Always, _, _) | (_, true, true) => RenderLoopState::Rendering,
+        (_, false, true) => RenderLoopState::Paused,
+        (_, _, false) => RenderLoopState::Finished,
+    };
+    let render_loop_state = match (render_loop_state, *frozen, *frame_steps) {
+        (RenderLoopState::Rendering, true, 0) => RenderLoopState::Paused,
+        (RenderLoopState::Rendering, true, steps) => RenderLoopState::StepOnce(steps),
+        (render_loop_state, _, _) => render_loop_state,
+    };
+
+    let style = use_style!(
+        r#"
+            display: grid;
+            row-gap: 0;
+            position: relative;
+        
+            .settings {
+                display: grid;
+                grid-template-columns: max-content auto max-content auto;
+                column-gap: 20px;
+                background-color: ${bg};
+                padding: 10px 20px;
+                align-items: center;
+            }
+
+            .settings * {
+                font-size: 13px;
+            }
+
+            .canvas-buttons {
+                position: absolute;
+                top: 10px;
+                right: 10px;
+            }
+
+            .canvas-buttons button,
+            .canvas-buttons label {
+                color: ${canvas_button_fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+
+            .canvas-buttons button:hover,
+            .canvas-buttons label:hover {
+                color: ${canvas_button_fg_hover};
+            }
+
+            .canvas-buttons i {
+                font-size: 32px;
+            }
+
+            .canvas-buttons button.recording {
+                color: ${recording_fg};
+            }
+
+            &.drag-over {
+                outline: 3px dashed ${drag_over_fg};
+            }
+
+            .play-overlay {
+                position: absolute;
+                top: 50%;
+                left: 50%;
+                transform: translate(-50%, -50%);
+                font-size: 64px;
+                color: ${canvas_button_fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+
+            .play-overlay:hover {
+                color: ${canvas_button_fg_hover};
+            }
+
+            .paused-badge {
+                position: absolute;
+                top: 10px;
+                left: 10px;
+                padding: 2px 10px;
+                border: none;
+                border-radius: 10px;
+                color: ${chip_fg};
+                background-color: ${chip_bg};
+                cursor: pointer;
+            }
+
+            .settings-header {
+                grid-column: 1 / -1;
+                display: flex;
+                flex-wrap: wrap;
+                column-gap: 10px;
+                row-gap: 5px;
+                margin: 5px 0px;
+            }
+
+            .settings-chip {
+                padding: 2px 10px;
+                border-radius: 10px;
+                color: ${chip_fg};
+                background-color: ${chip_bg};
+                cursor: grab;
+            }
+
+            .settings-group {
+                grid-column: 1 / -1;
+                justify-self: start;
+                margin: 5px 0px;
+                padding: 0px;
+                border: none;
+                color: ${chip_bg};
+                background-color: transparent;
+                font-weight: bold;
+                cursor: pointer;
+            }
+
+            .setting-label {
+                display: flex;
+                align-items: center;
+                column-gap: 5px;
+            }
+
+            .setting-info {
+                position: relative;
+                color: ${chip_bg};
+                cursor: help;
+            }
+
+            .setting-tooltip {
+                display: none;
+                position: absolute;
+                left: 0px;
+                top: 100%;
+                z-index: 2;
+                width: max-content;
+                max-width: 250px;
+                padding: 5px 10px;
+                border: 1px solid ${chip_bg};
+                border-radius: 5px;
+                color: ${tooltip_fg};
+                background-color: ${tooltip_bg};
+            }
+
+            .setting-info:hover .setting-tooltip,
+            .setting-info:focus .setting-tooltip {
+                display: block;
+            }
+
+            .settings-error {
+                grid-column: 1 / -1;
+                margin: 0px 0px 5px 0px;
+                color: ${error_fg};
+            }
+
+            .settings-presets {
+                padding: 2px 5px;
+                border-radius: 10px;
+            }
+
+            .settings-action {
+                padding: 2px 10px;
+                border: 1px solid ${chip_bg};
+                border-radius: 10px;
+                color: ${chip_bg};
+                background-color: transparent;
+                cursor: pointer;
+            }
+        "#,
+        bg = ThemeColor::Base00.var(),
+        canvas_button_fg = ThemeColor::Base04.var(),
+        canvas_button_fg_hover = ThemeColor::Base07.var(),
+        drag_over_fg = ThemeColor::Base0D.var(),
+        chip_fg = ThemeColor::Base00.var(),
+        chip_bg = ThemeColor::Base0D.var(),
+        recording_fg = ThemeColor::Base08.var(),
+        error_fg = ThemeColor::Base08.var(),
+        tooltip_fg = ThemeColor::Base05.var(),
+        tooltip_bg = ThemeColor::Base01.var(),
+    );
+    #[cfg(debug_assertions)]
+    let gpu_timing_overlay = props
+        .renderer
+        .gpu_timer()
+        .map_or_else(Html::default, |timer| {
+            html! { <crate::dev::GpuTimingOverlay timer={timer.clone()}/> }
+        });
+    #[cfg(not(debug_assertions))]
+    let gpu_timing_overlay = Html::default();
+
+    let settings_html = |settings: &[Setting]| {
+        settings
+            .iter()
+            .map(|setting| {
+                let id = control_id(&setting.label.to_case(Case::Kebab));
+                let description_id = format!("{id}-description");
+                html! {
+                    <>
+                        <span class="setting-label">
+                            <label id={label_id(&id)} for={id.clone()}>{&setting.label}</label>
+                            if let Some(description) = &setting.description {
+                                <span
+                                    class="setting-info"
+                                    tabindex="0"
+                                    aria-label={format!("About {}", setting.label)}
+                                    aria-describedby={description_id.clone()}
+                                >
+                                    <i class="iconoir-info-circle"/>
+                                    <span
+                                        class="setting-tooltip"
+                                        id={description_id}
+                                        role="tooltip"
+                                    >
+                                        {description}
+                                    </span>
+                                </span>
+                            }
+                        </span>
+                        <ContextProvider<ControlId> context={ControlId(id)}>
+                            {setting.html.clone()}
+                        </ContextProvider<ControlId>>
+                    </>
+                }
+            })
+            .collect::<Html>()
+    };
+    let settings = props
+        .settings
+        .as_ref()
+        .map(|panel| settings_html(&panel.settings()));
+    let setting_groups = props.settings.iter().flat_map(|panel| {
+        panel.groups().into_iter().map(|(group, name)| {
+            let name = AttrValue::Static(name);
+            let collapsed = collapsed_groups.contains(&name);
+            let toggle = Callback::from({
+                let collapsed_groups = collapsed_groups.clone();
+                let example = props.example;
+                let name = name.clone();
+                move |_: MouseEvent| {
+                    let mut updated = (*collapsed_groups).clone();
+                    if !updated.remove(&name) {
+                        updated.insert(name.clone());
+                    }
+                    if let Some(example) = example {
+                        store_collapsed_groups(example, &updated);
+                    }
+                    collapsed_groups.set(updated);
+                }
+            });
+            html! {
+                <>
+                    <button
+                        class="settings-group"
+                        aria-expanded={(!collapsed).to_string()}
+                        onclick={toggle}
+                    >
+                        if collapsed {
+                            <i class="iconoir-nav-arrow-right"/>
+                        } else {
+                            <i class="iconoir-nav-arrow-down"/>
+                        }
+                        {" "}{name}
+                    </button>
+                    if !collapsed {
+                        {settings_html(&panel.group_settings(group))}
+                    }
+                </>
+            }
+        })
+    });
+    html! {
+        <div
+            ref={example_node_ref}
+            class={classes!(style, drag_over.then_some("drag-over"))}
+            ondragover={settings_drag_over}
+            ondragleave={settings_drag_leave}
+            ondrop={drop_settings}
+        >
+            if pause.paused() {
+                <button
+                    class="paused-badge"
+                    title="Animations are paused site-wide, click to resume them"
+                    onclick={resume_animations}
+                >
+                    <i class="iconoir-play"/>
+                    {" Paused"}
+                </button>
+            }
+            <div class="canvas-buttons">
+                if let Some(copy_embed_code) = copy_embed_code {
+                    <button title="Copy embed code" onclick={copy_embed_code}>
+                        <i class="iconoir-code-brackets-square"/>
+                    </button>
+                }
+                <button title={recording_title} onclick={toggle_recording}>
+                    if *recording {
+                        <i class="iconoir-video-camera-off"/>
+                    } else {
+                        <i class="iconoir-video-camera"/>
+                    }
+                </button>
+                <label title="Play back a replay">
+                    <i class="iconoir-upload-square"/>
+                    <input type="file" accept=".json" hidden=true onchange={load_replay}/>
+                </label>
+                <button
+                    class={classes!(recording_video.then_some("recording"))}
+                    title={if *recording_video { "Stop recording the video" } else { "Record a video" }}
+                    onclick={toggle_video}
+                >
+                    <i class="iconoir-media-video"/>
+                </button>
+                if props.resettable {
+                    <button title="Restart the example" onclick={reset}>
+                        <i class="iconoir-refresh-double"/>
+                    </button>
+                }
+                if props.frame_controls && *frozen {
+                    <button title="Step forward one frame" onclick={step_frame}>
+                        <i class="iconoir-skip-next"/>
+                    </button>
+                    <button title="Resume" onclick={resume}>
+                        <i class="iconoir-play"/>
+                    </button>
+                } else if props.frame_controls {
+                    <button title="Pause" onclick={freeze}>
+                        <i class="iconoir-pause"/>
+                    </button>
+                }
+                <button title="Toggle frame rate stats" onclick={toggle_stats}>
+                    <i class="iconoir-graph-up"/>
+                </button>
+                <button title="Save a screenshot" onclick={export_canvas}>
+                    <i class="iconoir-camera"/>
+                </button>
+                <button title="Full screen" onclick={full_screen_canvas}>
+                    <i class="iconoir-plus-square"/>
+                </button>
+            </div>
+            if !*started {
+                <button class="play-overlay" title="Start the example" onclick={start}>
+                    <i class="iconoir-play"/>
+                </button>
+            }
+            <Tracked<Canvas<R>>
+                canvas_node_ref={canvas_node_ref.clone()}
+                renderer={props.renderer.clone()}
+                render_input={props.render_input.clone()}
+                width="100%"
+                height={props.height.clone()}
+                replay={(*replay).clone()}
+                frame_stats={(*frame_stats).clone()}
+                fixed_timestep={props.fixed_timestep}
+                supersampling={quality.supersampling()}
+                {render_loop_state}
+                reset={*resets}
+                on_output={props.on_output.clone()}
+            />
+            if *show_stats {
+                <FrameStatsOverlay stats={(*frame_stats).clone()}/>
+            }
+            {gpu_timing_overlay}
+            if props.show_settings {
+                <div class="settings">
+                    if drag_settings.is_some()
+                        || props.on_reset_settings.is_some()
+                        || props.on_randomize_settings.is_some()
+                    {
+                        <div class="settings-header">
+                            if let Some(drag_settings) = drag_settings {
+                                <span
+                                    class="settings-chip"
+                                    title="Drag onto another example to copy these settings"
+                                    draggable="true"
+                                    ondragstart={drag_settings}
+                                >
+                                    <i class="iconoir-drag-hand-gesture"/>
+                                    {" Settings"}
+                                </span>
+                            }
+                            if let Some(copy_settings) = copy_settings {
+                                <button
+                                    class="settings-action"
+                                    title="Copy these settings as json"
+                                    onclick={copy_settings}
+                                >
+                                    <i class="iconoir-copy"/>
+                                    {" Copy"}
+                                </button>
+                            }
+                            if let Some(paste_settings) = paste_settings {
+                                <button
+                                    class="settings-action"
+                                    title="Apply settings copied as json"
+                                    onclick={paste_settings}
+                                >
+                                    <i class="iconoir-paste-clipboard"/>
+                                    {" Paste"}
+                                </button>
+                            }
+                            if let Some(copy_settings_link) = copy_settings_link {
+                                <button
+                                    class="settings-action"
+                                    title="Copy a link restoring these settings"
+                                    onclick={copy_settings_link}
+                                >
+                                    <i class="iconoir-link"/>
+                                    {" Share"}
+                                </button>
+                            }
+                            if let Some(transfer) = &props.settings_transfer {
+                                <PresetSelection
+                                    storage_key={(*renderer_name).clone()}
+                                    presets={props.presets.clone()}
+                                    transfer={transfer.clone()}
+                                />
+                            }
+                            if let Some(on_reset_settings) = &props.on_reset_settings {
+                                <button
+                                    class="settings-action"
+                                    title="Restore the default settings and forget the remembered ones"
+                                    onclick={on_reset_settings.reform(|_| ())}
+                                >
+                                    <i class="iconoir-undo"/>
+                                    {" Reset"}
+                                </button>
+                            }
+                            if let Some(on_randomize_settings) = &props.on_randomize_settings {
+                                <button
+                                    class="settings-action"
+                                    title="Pick random settings"
+                                    onclick={on_randomize_settings.reform(|_| ())}
+                                >
+                                    <i class="iconoir-sparks"/>
+                                    {" Randomize"}
+                                </button>
+                            }
+                        </div>
+                    }
+                    if let Some(paste_error) = &*paste_error {
+                        <p class="settings-error">{paste_error}</p>
+                    }
+                    {settings}
+                    {for setting_groups}
+                    <label for={control_id("quality")}>{"Quality"}</label>
+                    <Selection<Quality>
+                        id={control_id("quality")}
+                        value={quality}
+                        options={Quality::ALL.iter().copied().map(SelectionOption::new).collect::<Box<[_]>>()}
+                    />
+                    <label for={control_id("watermark")}>{"Export watermark"}</label>
+                    <Checkbox id={control_id("watermark")} value={watermark}/>
+                </div>
+            }
+        </div>
+    }
+}
+
+/// The amount of interactive examples created so far, used to give their controls unique ids
+static EXAMPLE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// The id of the control of a setting, provided by the [`InteractiveExample`] showing it
+#[derive(Debug, Clone, PartialEq)]
+struct ControlId(AttrValue);
+
+/// Returns the id of the label of the control with the given id
+fn label_id(control_id: &str) -> String {
+    format!("{control_id}-label")
+}
+
+/// Returns the given id of a control, falling back to the id of the setting it is shown for
+#[hook]
+fn use_control_id(id: Option<AttrValue>) -> Option<AttrValue> {
+    let setting_id = use_context::<ControlId>();
+    id.or(setting_id.map(|ControlId(id)| id))
+}
+
+/// Allows a type to be used with [`Slider`]
+pub trait SliderValue
+where
+    Self: Clone + PartialEq + PartialOrd + 'static,
+{
+    /// The value one
+    const ONE: Self;
+
+    /// Converts self to a js number
+    fn to_js_number_string(&self) -> String;
+
+    /// Converts self to a float, for displaying it
+    fn to_f64(&self) -> f64;
+
+    /// Converts to self from a js number, returns `None` if it is not a valid value
+    fn from_js_number_string(value: String) -> Option<Self>;
+
+    /// Converts to self from a float, rounding if necessary
+    fn from_f64(value: f64) -> Self;
+}
+
+/// Implement [`SliderValue`] for primitive numbers.
+///
+/// Input is parsed as a float for all of them, so out of range or fractional input is rounded and
+/// saturated instead of being rejected.
+macro_rules! impl_slider_value {
+    (@int $($type:ty),* $(,)?) => {
+        $(
+            impl SliderValue for $type {
+                const ONE: Self = 1;
+
+                fn to_js_number_string(&self) -> String {
+                    self.to_string()
+                }
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn from_js_number_string(value: String) -> Option<Self> {
+                    f64::from_js_number_string(value).map(Self::from_f64)
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value.round() as $type
+                }
+            }
+        )*
+    };
+    (@float $($type:ty),* $(,)?) => {
+        $(
+            impl SliderValue for $type {
+                const ONE: Self = 1.0;
+
+                fn to_js_number_string(&self) -> String {
+                    self.to_string()
+                }
+
+                fn to_f64(&self) -> f64 {
+                    f64::from(*self)
+                }
+
+                fn from_js_number_string(value: String) -> Option<Self> {
+                    value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|value: &$type| value.is_finite())
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value as $type
+                }
+            }
+        )*
+    };
+}
+
+impl_slider_value!(@int u8, u32, i32, usize);
+impl_slider_value!(@float f32, f64);
+
+/// Implement [`SliderValue`] for a newtype around a number, delegating to the wrapped value.
+///
+/// The newtype needs to derive `Clone`, `PartialEq` and `PartialOrd`.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+/// struct BoidCount(u32);
+///
+/// slider_value!(BoidCount(u32));
+/// ```
+#[macro_export]
+macro_rules! slider_value {
+    ($name:ident($inner:ty)) => {
+        impl $crate::projects::interactive::SliderValue for $name {
+            const ONE: Self = Self(<$inner as $crate::projects::interactive::SliderValue>::ONE);
+
+            fn to_js_number_string(&self) -> String {
+                $crate::projects::interactive::SliderValue::to_js_number_string(&self.0)
+            }
+
+            fn to_f64(&self) -> f64 {
+                $crate::projects::interactive::SliderValue::to_f64(&self.0)
+            }
+
+            fn from_js_number_string(value: String) -> Option<Self> {
+                <$inner as $crate::projects::interactive::SliderValue>::from_js_number_string(value)
+                    .map(Self)
+            }
+
+            fn from_f64(value: f64) -> Self {
+                Self(<$inner as $crate::projects::interactive::SliderValue>::from_f64(value))
+            }
+        }
+    };
+}
+
+/// Properties for the [`Slider`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct SliderProperties<T: SliderValue> {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the component is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The minimum value
+    pub min: T,
+    /// The maximum value
+    pub max: T,
+    /// The step value
+    #[prop_or(T::ONE)]
+    pub step: T,
+    /// The selected value
+    pub value: UseStateHandle<T>,
+}
+
+/// A slider component used to select a value in a range.
+///
+/// Next to the slider, the current value can be read and typed into a number field. Typed values
+/// are clamped to the range, invalid entries are reverted to the current value.
+#[function_component(Slider)]
+pub fn slider<T: SliderValue>(
+    SliderProperties {
+        id,
+        active,
+        min,
+        max,
+        value,
+        step,
+    }: &SliderProperties<T>,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let style = use_style!(
+        r#"
+            width: 100%;
+            display: grid;
+            grid-template-columns: max-content auto max-content max-content;
+            column-gap: 10px;
+            align-items: center;
+
+            p {
+                color: ${fg};
+            }
+
+            input[type="number"] {
+                width: 6em;
+                color: ${fg};
+                background-color: ${bg};
+                border: 1px solid ${fg};
+                border-radius: 5px;
+                padding: 2px 5px;
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        bg = ThemeColor::Base01.var(),
+    );
+    let set_value = use_frame_setter(value);
+    let on_input = Callback::from(move |event: InputEvent| {
+        if let Some(new_value) =
+            T::from_js_number_string(event.target_dyn_into::<HtmlInputElement>().unwrap().value())
+        {
+            set_value.emit(new_value);
+        }
+    });
+    let on_entry = Callback::from({
+        let value = value.clone();
+        let (min, max) = (min.clone(), max.clone());
+
+        move |event: Event| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            match T::from_js_number_string(input.value()) {
+                Some(new_value) => {
+                    let new_value = if new_value < min {
+                        min.clone()
+                    } else if new_value > max {
+                        max.clone()
+                    } else {
+                        new_value
+                    };
+                    // The state may not change, so the field is corrected directly
+                    input.set_value(&new_value.to_js_number_string());
+                    value.set(new_value);
+                }
+                None => input.set_value(&value.to_js_number_string()),
+            }
+        }
+    });
+    html! {
+        <div class={style}>
+            <p aria-hidden="true">{format_number(min.to_f64())}</p>
+            <input
+                type="range"
+                id={id.clone()}
+                disabled={!active}
+                min={min.to_js_number_string()}
+                max={max.to_js_number_string()}
+                step={step.to_js_number_string()}
+                value={value.to_js_number_string()}
+                aria-valuemin={min.to_js_number_string()}
+                aria-valuemax={max.to_js_number_string()}
+                aria-valuenow={value.to_js_number_string()}
+                oninput={on_input}
+            />
+            <p aria-hidden="true">{format_number(max.to_f64())}</p>
+            <input
+                type="number"
+                aria-labelledby={id.as_deref().map(label_id)}
+                disabled={!active}
+                min={min.to_js_number_string()}
+                max={max.to_js_number_string()}
+                step={step.to_js_number_string()}
+                value={value.to_js_number_string()}
+                onchange={on_entry}
+            />
+        </div>
+    }
+}
+
+/// Properties for the [`RangeSlider`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct RangeSliderProperties<T: SliderValue> {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the component is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The minimum value
+    pub min: T,
+    /// The maximum value
+    pub max: T,
+    /// The step value
+    #[prop_or(T::ONE)]
+    pub step: T,
+    /// The selected lower and upper value
+    pub value: UseStateHandle<(T, T)>,
+}
+
+/// A slider component with two handles, used to select a range within a range.
+///
+/// The handles cannot cross, so the lower value never exceeds the upper one.
+#[function_component(RangeSlider)]
+pub fn range_slider<T: SliderValue>(
+    RangeSliderProperties {
+        id,
+        active,
+        min,
+        max,
+        value,
+        step,
+    }: &RangeSliderProperties<T>,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let style = use_style!(
+        r#"
+            width: 100%;
+            display: grid;
+            grid-template-columns: max-content auto max-content max-content;
+            column-gap: 10px;
+            align-items: center;
+
+            p {
+                color: ${fg};
+            }
+
+            .track {
+                position: relative;
+                height: 20px;
+            }
+
+            .track::before {
+                content: "";
+                position: absolute;
+                left: 0px;
+                right: 0px;
+                top: 8px;
+                height: 4px;
+                border-radius: 2px;
+                background-color: ${track};
+            }
+
+            .track input {
+                position: absolute;
+                left: 0px;
+                width: 100%;
+                height: 20px;
+                margin: 0px;
+                appearance: none;
+                -webkit-appearance: none;
+                background: none;
+                pointer-events: none;
+            }
+
+            .track input.on-top {
+                z-index: 1;
+            }
+
+            .track input::-webkit-slider-thumb {
+                appearance: none;
+                -webkit-appearance: none;
+                width: 16px;
+                height: 16px;
+                border-radius: 50%;
+                background-color: ${thumb};
+                pointer-events: auto;
+                cursor: pointer;
+            }
+
+            .track input::-moz-range-thumb {
+                width: 16px;
+                height: 16px;
+                border: none;
+                border-radius: 50%;
+                background-color: ${thumb};
+                pointer-events: auto;
+                cursor: pointer;
+            }
+
+            .track input:disabled::-webkit-slider-thumb {
+                background-color: ${track};
+            }
+
+            .track input:disabled::-moz-range-thumb {
+                background-color: ${track};
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        track = ThemeColor::Base02.var(),
+        thumb = ThemeColor::Base0D.var(),
+    );
+    let (low, high) = &**value;
+
+    let set_value = use_frame_setter(value);
+    let on_low_input = Callback::from({
+        let value = value.clone();
+        let set_value = set_value.clone();
+
+        move |event: InputEvent| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            if let Some(new_low) = T::from_js_number_string(input.value()) {
+                let high = value.1.clone();
+                let new_low = if new_low > high {
+                    high.clone()
+                } else {
+                    new_low
+                };
+                // The state may not change, so the handle is corrected directly
+                input.set_value(&new_low.to_js_number_string());
+                set_value.emit((new_low, high));
+            }
+        }
+    });
+    let on_high_input = Callback::from({
+        let value = value.clone();
+
+        move |event: InputEvent| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            if let Some(new_high) = T::from_js_number_string(input.value()) {
+                let low = value.0.clone();
+                let new_high = if new_high < low {
+                    low.clone()
+                } else {
+                    new_high
+                };
+                input.set_value(&new_high.to_js_number_string());
+                set_value.emit((low, new_high));
+            }
+        }
+    });
+
+    // The upper handle is drawn on top, unless both are stuck at the maximum
+    let low_on_top = high == max;
+
+    html! {
+        <div class={style}>
+            <p aria-hidden="true">{format_number(min.to_f64())}</p>
+            <div class="track">
+                <input
+                    type="range"
+                    id={id.clone()}
+                    disabled={!active}
+                    min={min.to_js_number_string()}
+                    max={max.to_js_number_string()}
+                    step={step.to_js_number_string()}
+                    value={low.to_js_number_string()}
+                    aria-valuemin={min.to_js_number_string()}
+                    aria-valuemax={high.to_js_number_string()}
+                    aria-valuenow={low.to_js_number_string()}
+                    class={classes!(low_on_top.then_some("on-top"))}
+                    oninput={on_low_input}
+                />
+                <input
+                    type="range"
+                    aria-labelledby={id.as_deref().map(label_id)}
+                    disabled={!active}
+                    min={min.to_js_number_string()}
+                    max={max.to_js_number_string()}
+                    step={step.to_js_number_string()}
+                    value={high.to_js_number_string()}
+                    aria-valuemin={low.to_js_number_string()}
+                    aria-valuemax={max.to_js_number_string()}
+                    aria-valuenow={high.to_js_number_string()}
+                    oninput={on_high_input}
+                />
+            </div>
+            <p aria-hidden="true">{format_number(max.to_f64())}</p>
+            <p>{format!("{} - {}", format_number(low.to_f64()), format_number(high.to_f64()))}</p>
+        </div>
+    }
+}
+
+/// Properties for the [`Checkbox`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct CheckboxProperties {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the checkbox is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The checked state of the checkbox
+    pub value: UseStateHandle<bool>,
+}
+
+/// A checkbox component to enable or disable stuff
+#[function_component(Checkbox)]
+pub fn checkbox(CheckboxProperties { id, active, value }: &CheckboxProperties) -> Html {
+    let id = use_control_id(id.clone());
+    let style = use_style!(
+        r#"
+            height: 20px;
+            width: 20px;
+        "#
+    );
+
+    let on_input = Callback::from({
+        let value = value.clone();
+
+        move |event: InputEvent| {
+            value.set(
+                event
+                    .target_dyn_into::<HtmlInputElement>()
+                    .unwrap()
+                    .checked(),
+            )
+        }
+    });
+
+    html! {
+        <input
+            class={style}
+            type="checkbox"
+            {id}
+            disabled={!active}
+            value={value.to_string()}
+            checked={**value}
+            oninput={on_input}
+        />
+    }
+}
+
+/// Properties for the [`ColorPicker`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct ColorPickerProperties {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the color picker is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The selected color
+    pub value: UseStateHandle<AlphaColor<Srgb>>,
+}
+
+/// A color picker component used to select a color including its alpha channel.
+///
+/// The color can be picked, typed as any css color (e.g. a hex string like `#40ff2080`) or have
+/// its alpha adjusted by a slider. Typed colors which can not be parsed are reverted to the
+/// current color.
+#[function_component(ColorPicker)]
+pub fn color_picker(ColorPickerProperties { id, active, value }: &ColorPickerProperties) -> Html {
+    let id = use_control_id(id.clone());
+    let style = use_style!(
+        r#"
+            display: flex;
+            align-items: center;
+            column-gap: 10px;
+
+            input[type="text"] {
+                width: 7em;
+                color: ${fg};
+                background-color: ${bg};
+                border: 1px solid ${fg};
+                border-radius: 5px;
+                padding: 2px 5px;
+                font-family: monospace;
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        bg = ThemeColor::Base01.var(),
+    );
+    let parse = |css: &str| {
+        color::parse_color(css.trim())
+            .ok()
+            .map(|color| color.to_alpha_color::<Srgb>())
+    };
+    let rgba = value.to_rgba8();
+    let alpha = value.components[3];
+
+    let set_value = use_frame_setter(value);
+    let on_color_input = Callback::from({
+        let set_value = set_value.clone();
+
+        move |event: InputEvent| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            // The native picker has no alpha channel, so the current one is kept
+            if let Some(color) = parse(&input.value()) {
+                set_value.emit(color.with_alpha(alpha));
+            }
+        }
+    });
+    let on_hex_entry = Callback::from({
+        let value = value.clone();
+
+        move |event: Event| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            match parse(&input.value()) {
+                Some(color) => value.set(color),
+                None => input.set_value(&format!("{:x}", value.to_rgba8())),
+            }
+        }
+    });
+    let on_alpha_input = Callback::from({
+        let value = value.clone();
+
+        move |event: InputEvent| {
+            let input = event.target_dyn_into::<HtmlInputElement>().unwrap();
+            if let Some(alpha) = f32::from_js_number_string(input.value()) {
+                set_value.emit(value.with_alpha(alpha.clamp(0.0, 1.0)));
+            }
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <input
+                type="color"
+                {id}
+                disabled={!active}
+                value={format!("#{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b)}
+                oninput={on_color_input}
+            />
+            <input
+                type="text"
+                aria-label="Hex color"
+                disabled={!active}
+                value={format!("{rgba:x}")}
+                onchange={on_hex_entry}
+            />
+            <input
+                type="range"
+                aria-label="Alpha"
+                disabled={!active}
+                min="0"
+                max="1"
+                step="0.01"
+                value={alpha.to_string()}
+                oninput={on_alpha_input}
+            />
+        </div>
+    }
+}
+
+/// An option of a [`Selection`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectionOption<T> {
+    /// The selected value
+    pub value: T,
+    /// The shown label, defaults to the value's [`ToString`]
+    pub label: Option<&'static str>,
+    /// The group the option is listed under, consecutive options of a group are shown together
+    pub group: Option<&'static str>,
+    /// Whether the option can not be selected
+    pub disabled: bool,
+}
+
+impl<T> SelectionOption<T> {
+    /// Create an enabled option without label or group
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            label: None,
+            group: None,
+            disabled: false,
+        }
+    }
+
+    /// Show the option with the given label
+    pub const fn label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// List the option under the given group
+    pub const fn group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Set whether the option can not be selected
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl<T: ToString> SelectionOption<T> {
+    /// Returns the shown label of the option
+    fn text(&self) -> String {
+        self.label
+            .map_or_else(|| self.value.to_string(), str::to_owned)
+    }
+}
+
+/// Properties for the [`Selection`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct SelectionProperties<T: ToString + PartialEq + Clone + 'static> {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the component is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The currently selected value
+    pub value: UseStateHandle<T>,
+    /// The possible options, in the order they are listed
+    pub options: Box<[SelectionOption<T>]>,
+}
+
+/// A component used for selecting values from a list of possible options, which may be grouped,
+/// labeled or disabled
+#[function_component(Selection)]
+pub fn selection<T: ToString + PartialEq + Clone + 'static>(
+    SelectionProperties {
+        id,
+        active,
+        value,
+        options,
+    }: &SelectionProperties<T>,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let option = |option: &SelectionOption<T>| {
+        html! {
+            <option selected={option.value == **value} disabled={option.disabled}>
+                {option.text()}
+            </option>
+        }
+    };
+    // The options are listed in order, so the selected index still indexes into `options`
+    let groups = options
+        .chunk_by(|a, b| a.group == b.group)
+        .map(|chunk| match chunk[0].group {
+            Some(group) => {
+                html! { <optgroup label={group}>{for chunk.iter().map(option)}</optgroup> }
+            }
+            None => html! { for chunk.iter().map(option) },
+        });
+    let style = use_style!(
+        r#"
+            height: 30px;
+            margin: 10px 0px;
+        "#
+    );
+
+    let on_input = Callback::from({
+        let value = value.clone();
+        let options = options.clone();
+
+        move |event: InputEvent| {
+            value.set(
+                options[usize::try_from(
+                    event
+                        .target_dyn_into::<HtmlSelectElement>()
+                        .unwrap()
+                        .selected_index(),
+                )
+                .unwrap()]
+                .value
+                .clone(),
+            );
+        }
+    });
+
+    html! {
+        <select {id} disabled={!active} oninput={on_input} class={style}>
+            {for groups}
+        </select>
+    }
+}
+
+/// Properties for the [`ToggleGroup`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct ToggleGroupProperties<T: ToString + PartialEq + Clone + 'static> {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the component is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The currently selected value
+    pub value: UseStateHandle<T>,
+    /// The possible options, shown side by side. Their groups are ignored
+    pub options: Box<[SelectionOption<T>]>,
+}
+
+/// A segmented group of toggle buttons, of which exactly one is selected.
+///
+/// Meant for small sets of options which are switched often, where a [`Selection`] would hide
+/// them behind a dropdown. When focused, the arrow keys select the previous or next option.
+#[function_component(ToggleGroup)]
+pub fn toggle_group<T: ToString + PartialEq + Clone + 'static>(
+    ToggleGroupProperties {
+        id,
+        active,
+        value,
+        options,
+    }: &ToggleGroupProperties<T>,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let style = use_style!(
+        r#"
+            display: inline-flex;
+            margin: 5px 0px;
+            border: 1px solid ${fg};
+            border-radius: 5px;
+            overflow: hidden;
+
+            button {
+                padding: 5px 10px;
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+
+            button + button {
+                border-left: 1px solid ${fg};
+            }
+
+            button[aria-checked="true"] {
+                color: ${bg};
+                background-color: ${accent};
+            }
+
+            button:disabled {
+                cursor: default;
+                opacity: 0.5;
+            }
+
+            button:focus-visible {
+                outline: 2px solid ${accent};
+                outline-offset: -2px;
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        bg = ThemeColor::Base00.var(),
+        accent = ThemeColor::Base0D.var(),
+    );
+    let selected = options.iter().position(|option| option.value == **value);
+
+    let on_key_down = Callback::from({
+        let value = value.clone();
+        let options = options.clone();
+        let active = *active;
+        move |event: KeyboardEvent| {
+            let direction = match event.key().as_str() {
+                "ArrowRight" | "ArrowDown" => 1,
+                "ArrowLeft" | "ArrowUp" => options.len() - 1,
+                _ => return,
+            };
+            event.prevent_default();
+            if !active {
+                return;
+            }
+            // Move to the next enabled option in the given direction, wrapping around
+            let start = selected.unwrap_or(0);
+            let Some(next) = (1..options.len())
+                .map(|offset| (start + offset * direction) % options.len())
+                .find(|&index| !options[index].disabled)
+            else {
+                return;
+            };
+            value.set(options[next].value.clone());
+            if let Some(button) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .and_then(|group| group.children().item(next as u32))
+                .and_then(|button| button.dyn_into::<HtmlElement>().ok())
+            {
+                let _ = button.focus();
+            }
+        }
+    });
+    let buttons = options.iter().enumerate().map(|(index, option)| {
+        let checked = selected == Some(index);
+        let onclick = Callback::from({
+            let value = value.clone();
+            let option = option.value.clone();
+            move |_: MouseEvent| value.set(option.clone())
+        });
+        // Only the selected option can be reached by tabbing, the arrow keys move between them
+        let tabindex = if checked || (selected.is_none() && index == 0) {
+            "0"
+        } else {
+            "-1"
+        };
+
+        html! {
+            <button
+                type="button"
+                role="radio"
+                aria-checked={checked.to_string()}
+                {tabindex}
+                disabled={!active || option.disabled}
+                {onclick}
+            >
+                {option.text()}
+            </button>
+        }
+    });
+
+    let label = id.as_deref().map(label_id);
+
+    html! {
+        <div
+            class={style}
+            {id}
+            role="radiogroup"
+            aria-labelledby={label}
+            onkeydown={on_key_down}
+        >
+            {for buttons}
+        </div>
+    }
+}
+
+/// Properties for the [`XyPad`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct XyPadProperties {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the pad is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The range of the horizontal value, from left to right
+    pub x_range: (f32, f32),
+    /// The range of the vertical value, from bottom to top
+    pub y_range: (f32, f32),
+    /// The selected horizontal value
+    pub x: UseStateHandle<f32>,
+    /// The selected vertical value
+    pub y: UseStateHandle<f32>,
+}
+
+/// A square pad used to select two correlated values at once by dragging a point.
+///
+/// When focused, the arrow keys move the point by a hundredth of the ranges, or by a tenth while
+/// holding shift.
+#[function_component(XyPad)]
+pub fn xy_pad(
+    XyPadProperties {
+        id,
+        active,
+        x_range,
+        y_range,
+        x,
+        y,
+    }: &XyPadProperties,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let theme = use_theme();
+    let style = use_style!(
+        r#"
+            width: 120px;
+            height: 120px;
+            margin: 5px 0px;
+            overflow: visible;
+            touch-action: none;
+            cursor: crosshair;
+
+            &:focus-visible {
+                outline: 2px solid ${accent};
+                border-radius: 5px;
+            }
+
+            &.inactive {
+                opacity: 0.5;
+                cursor: default;
+            }
+        "#,
+        accent = ThemeColor::Base0D.var(),
+    );
+
+    // Map the pointer position inside the pad onto both ranges
+    let (set_x, set_y) = (use_frame_setter(x), use_frame_setter(y));
+    let select = {
+        let (x_range, y_range) = (*x_range, *y_range);
+        move |event: &PointerEvent| {
+            let Some(pad) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+            else {
+                return;
+            };
+            let rect = pad.get_bounding_client_rect();
+            let fraction_x =
+                ((f64::from(event.client_x()) - rect.left()) / rect.width()).clamp(0.0, 1.0);
+            let fraction_y =
+                ((f64::from(event.client_y()) - rect.top()) / rect.height()).clamp(0.0, 1.0);
+            set_x.emit(x_range.0 + (x_range.1 - x_range.0) * fraction_x as f32);
+            set_y.emit(y_range.0 + (y_range.1 - y_range.0) * (1.0 - fraction_y as f32));
+        }
+    };
+    let on_pointer_down = Callback::from({
+        let select = select.clone();
+        let active = *active;
+        move |event: PointerEvent| {
+            if !active {
+                return;
+            }
+            if let Some(pad) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+            {
+                let _ = pad.set_pointer_capture(event.pointer_id());
+            }
+            select(&event);
+        }
+    });
+    let on_pointer_move = Callback::from({
+        let active = *active;
+        move |event: PointerEvent| {
+            if active && event.buttons() & 0b1 == 0b1 {
+                select(&event);
+            }
+        }
+    });
+    let on_key_down = Callback::from({
+        let (x, y) = (x.clone(), y.clone());
+        let (x_range, y_range) = (*x_range, *y_range);
+        let active = *active;
+        move |event: KeyboardEvent| {
+            let (value, (min, max), direction) = match event.key().as_str() {
+                "ArrowRight" => (&x, x_range, 1.0),
+                "ArrowLeft" => (&x, x_range, -1.0),
+                "ArrowUp" => (&y, y_range, 1.0),
+                "ArrowDown" => (&y, y_range, -1.0),
+                _ => return,
+            };
+            event.prevent_default();
+            if active {
+                let fraction = if event.shift_key() { 0.1 } else { 0.01 };
+                let (low, high) = (min.min(max), min.max(max));
+                value.set((**value + direction * (max - min) * fraction).clamp(low, high));
+            }
+        }
+    });
+
+    let fraction = |value: f32, (min, max): (f32, f32)| {
+        if max == min {
+            0.0
+        } else {
+            ((value - min) / (max - min)).clamp(0.0, 1.0)
+        }
+    };
+    let label = id.as_deref().map(label_id);
+    let (point_x, point_y) = (
+        fraction(**x, *x_range) * 100.0,
+        (1.0 - fraction(**y, *y_range)) * 100.0,
+    );
+
+    html! {
+        <svg
+            class={classes!(style, (!active).then_some("inactive"))}
+            viewBox="0 0 100 100"
+            {id}
+            role="slider"
+            tabindex={if *active { "0" } else { "-1" }}
+            aria-labelledby={label}
+            aria-valuetext={format!(
+                "{}, {}",
+                format_number(f64::from(**x)),
+                format_number(f64::from(**y)),
+            )}
+            onpointerdown={on_pointer_down}
+            onpointermove={on_pointer_move}
+            onkeydown={on_key_down}
+        >
+            <rect
+                x="0.5"
+                y="0.5"
+                width="99"
+                height="99"
+                rx="4"
+                fill={theme.base01.to_string()}
+                stroke={theme.base04.to_string()}
+                stroke-width="1"
+            />
+            <circle
+                cx={point_x.to_string()}
+                cy={point_y.to_string()}
+                r="5"
+                fill={theme.base0D.to_string()}
+            />
+        </svg>
+    }
+}
+
+/// Properties for the [`AngleDial`] component
+#[derive(Debug, Properties, PartialEq)]
+pub struct AngleDialProperties {
+    /// The id of the control, defaults to the id of the setting it is shown for
+    #[prop_or_default]
+    pub id: Option<AttrValue>,
+    /// Whether the dial is active
+    #[prop_or(true)]
+    pub active: bool,
+    /// The angle the value snaps to, in degrees
+    #[prop_or(1.0)]
+    pub step: f32,
+    /// The selected angle in degrees, in `[0, 360)`
+    pub value: UseStateHandle<f32>,
+}
+
+/// A circular dial used to select an angle by dragging its hand around.
+///
+/// Dragging while holding shift snaps to multiples of 15 degrees. When focused, the arrow keys
+/// turn the dial by one step, or by ten steps while holding shift.
+#[function_component(AngleDial)]
+pub fn angle_dial(
+    AngleDialProperties {
+        id,
+        active,
+        step,
+        value,
+    }: &AngleDialProperties,
+) -> Html {
+    let id = use_control_id(id.clone());
+    let theme = use_theme();
+    let style = use_style!(
+        r#"
+            display: flex;
+            align-items: center;
+            column-gap: 10px;
+            margin: 5px 0px;
+
+            svg {
+                width: 60px;
+                height: 60px;
+                touch-action: none;
+                cursor: grab;
+                border-radius: 50%;
+            }
+
+            svg:focus-visible {
+                outline: 2px solid ${accent};
+            }
+
+            &.inactive svg {
+                opacity: 0.5;
+                cursor: default;
+            }
+
+            p {
+                color: ${fg};
+            }
+        "#,
+        fg = ThemeColor::Base04.var(),
+        accent = ThemeColor::Base0D.var(),
+    );
+
+    let snap = {
+        let step = *step;
+        move |angle: f32, step_multiple: f32| snap_angle(angle, step * step_multiple)
+    };
+    // Turn the dial towards the pointer, zero degrees pointing up and increasing clockwise
+    let set_value = use_frame_setter(value);
+    let select = {
+        let step = *step;
+        move |event: &PointerEvent| {
+            let Some(dial) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+            else {
+                return;
+            };
+            let rect = dial.get_bounding_client_rect();
+            let dx = f64::from(event.client_x()) - (rect.left() + rect.width() / 2.0);
+            let dy = f64::from(event.client_y()) - (rect.top() + rect.height() / 2.0);
+            let angle = dx.atan2(-dy).to_degrees() as f32;
+            let step_multiple = if event.shift_key() {
+                15.0 / step.max(f32::EPSILON)
+            } else {
+                1.0
+            };
+            set_value.emit(snap(angle, step_multiple));
+        }
+    };
+    let on_pointer_down = Callback::from({
+        let select = select.clone();
+        let active = *active;
+        move |event: PointerEvent| {
+            if !active {
+                return;
+            }
+            if let Some(dial) = event
+                .current_target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+            {
+                let _ = dial.set_pointer_capture(event.pointer_id());
+            }
+            select(&event);
+        }
+    });
+    let on_pointer_move = Callback::from({
+        let active = *active;
+        move |event: PointerEvent| {
+            if active && event.buttons() & 0b1 == 0b1 {
+                select(&event);
+            }
+        }
+    });
+    let on_key_down = Callback::from({
+        let value = value.clone();
+        let active = *active;
+        let step = *step;
+        move |event: KeyboardEvent| {
+            let direction = match event.key().as_str() {
+                "ArrowRight" | "ArrowUp" => 1.0,
+                "ArrowLeft" | "ArrowDown" => -1.0,
+                _ => return,
+            };
+            event.prevent_default();
+            if active {
+                let step_multiple = if event.shift_key() { 10.0 } else { 1.0 };
+                value.set(snap(*value + direction * step * step_multiple, 1.0));
+            }
+        }
+    });
+
+    let label = id.as_deref().map(label_id);
+    let (sin, cos) = value.to_radians().sin_cos();
+    let (hand_x, hand_y) = (50.0 + 38.0 * sin, 50.0 - 38.0 * cos);
+
+    html! {
+        <div class={classes!(style, (!active).then_some("inactive"))}>
+            <svg
+                viewBox="0 0 100 100"
+                {id}
+                role="slider"
+                aria-labelledby={label}
+                tabindex={if *active { "0" } else { "-1" }}
+                aria-valuemin="0"
+                aria-valuemax="360"
+                aria-valuenow={value.to_string()}
+                onpointerdown={on_pointer_down}
+                onpointermove={on_pointer_move}
+                onkeydown={on_key_down}
+            >
+                <circle
+                    cx="50"
+                    cy="50"
+                    r="46"
+                    fill={theme.base01.to_string()}
+                    stroke={theme.base04.to_string()}
+                    stroke-width="2"
+                />
+                <line
+                    x1="50"
+                    y1="50"
+                    x2={hand_x.to_string()}
+                    y2={hand_y.to_string()}
+                    stroke={theme.base0D.to_string()}
+                    stroke-width="4"
+                    stroke-linecap="round"
+                />
+                <circle
+                    cx={hand_x.to_string()}
+                    cy={hand_y.to_string()}
+                    r="7"
+                    fill={theme.base0D.to_string()}
+                />
+            </svg>
+            <p>{format!("{}°", format_number(f64::from(**value)))}</p>
+        </div>
+    }
+}
+
+/// Snap the given angle in degrees to a multiple of the step and wrap it into `[0, 360)`
+fn snap_angle(angle: f32, step: f32) -> f32 {
+    let angle = if step > 0.0 {
+        (angle / step).round() * step
+    } else {
+        angle
+    };
+    // Tiny negative angles wrap to 360 due to rounding
+    let angle = angle.rem_euclid(360.0);
+    if angle < 360.0 { angle } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_slider_values_round_and_saturate() {
+        assert_eq!(u32::from_js_number_string("42".to_owned()), Some(42));
+        assert_eq!(u32::from_js_number_string(" 2.6 ".to_owned()), Some(3));
+        assert_eq!(u8::from_js_number_string("300".to_owned()), Some(255));
+        assert_eq!(u32::from_js_number_string("-5".to_owned()), Some(0));
+        assert_eq!(i32::from_js_number_string("-5".to_owned()), Some(-5));
+    }
+
+    #[test]
+    fn float_slider_values_parse() {
+        assert_eq!(f32::from_js_number_string("0.25".to_owned()), Some(0.25));
+        assert_eq!(
+            f64::from_js_number_string(" -1e3 ".to_owned()),
+            Some(-1000.0)
+        );
+    }
+
+    #[test]
+    fn invalid_slider_values_are_rejected() {
+        assert_eq!(f64::from_js_number_string(String::new()), None);
+        assert_eq!(f64::from_js_number_string("abc".to_owned()), None);
+        assert_eq!(f32::from_js_number_string("NaN".to_owned()), None);
+        assert_eq!(f32::from_js_number_string("inf".to_owned()), None);
+        assert_eq!(u32::from_js_number_string("1e400".to_owned()), None);
+    }
+
+    #[test]
+    fn angles_snap_to_the_step() {
+        assert_eq!(snap_angle(44.0, 15.0), 45.0);
+        assert_eq!(snap_angle(37.4, 1.0), 37.0);
+        assert_eq!(snap_angle(37.4, 0.0), 37.4);
+    }
+
+    #[test]
+    fn angles_wrap_into_a_turn() {
+        assert_eq!(snap_angle(-90.0, 1.0), 270.0);
+        assert_eq!(snap_angle(360.0, 1.0), 0.0);
+        assert_eq!(snap_angle(725.0, 1.0), 5.0);
+        assert_eq!(snap_angle(359.6, 1.0), 0.0);
+        assert_eq!(snap_angle(-1e-6, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,142 @@
+//! Named presets for the settings of interactive examples
+
+use std::rc::Rc;
+
+use gloo_storage::Storage;
+use serde::Serialize;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::projects::interactive::SettingsTransfer;
+
+/// The prefix of the local storage keys holding the presets saved by the user
+const PRESETS_STORAGE_KEY_PREFIX: &str = "SettingsPresets";
+
+/// A named set of settings shipped with an example
+#[derive(Debug, Clone, Copy)]
+pub struct Preset<T> {
+    /// The name shown in the preset selection
+    pub name: &'static str,
+    /// Returns the settings of the preset
+    pub settings: fn() -> T,
+}
+
+/// The curated presets of an example, serialized like a [`SettingsTransfer`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsPresets(Rc<[(AttrValue, AttrValue)]>);
+
+impl SettingsPresets {
+    /// Serialize the given presets
+    pub fn new<T: Serialize>(presets: &[Preset<T>]) -> Self {
+        Self(
+            presets
+                .iter()
+                .map(|preset| {
+                    (
+                        AttrValue::from(preset.name),
+                        AttrValue::from(serde_json::to_string(&(preset.settings)()).unwrap()),
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Properties for the [`PresetSelection`] component
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct PresetSelectionProperties {
+    /// Identifies the saved presets, shared by all examples with compatible settings
+    pub storage_key: AttrValue,
+    /// The curated presets
+    pub presets: SettingsPresets,
+    /// The current settings, used to apply presets and to save new ones
+    pub transfer: SettingsTransfer,
+}
+
+/// A dropdown applying curated or saved presets, next to a button saving the current settings
+#[function_component(PresetSelection)]
+pub fn preset_selection(
+    PresetSelectionProperties {
+        storage_key,
+        presets,
+        transfer,
+    }: &PresetSelectionProperties,
+) -> Html {
+    let storage_key = format!("{PRESETS_STORAGE_KEY_PREFIX}/{storage_key}");
+    // The presets saved by the user as (name, settings json)
+    let saved = use_state({
+        let storage_key = storage_key.clone();
+        move || {
+            gloo_storage::LocalStorage::get::<Vec<(String, String)>>(storage_key)
+                .unwrap_or_default()
+        }
+    });
+
+    let apply_preset = Callback::from({
+        let presets = presets.clone();
+        let saved = saved.clone();
+        let apply = transfer.apply.clone();
+
+        move |event: Event| {
+            let select = event.target_dyn_into::<HtmlSelectElement>().unwrap();
+            // The first option only labels the dropdown
+            let Some(index) = usize::try_from(select.selected_index())
+                .ok()
+                .and_then(|index| index.checked_sub(1))
+            else {
+                return;
+            };
+            let settings = match presets.0.get(index) {
+                Some((_, settings)) => settings.to_string(),
+                None => saved[index - presets.0.len()].1.clone(),
+            };
+            if let Err(error) = apply.emit(settings) {
+                log::warn!("The preset is invalid: {error}");
+            }
+            select.set_selected_index(0);
+        }
+    });
+
+    let save_preset = Callback::from({
+        let saved = saved.clone();
+        let serialized = transfer.serialized.clone();
+
+        move |_: MouseEvent| {
+            let Some(name) = gloo::dialogs::prompt("Name of the preset", None) else {
+                return;
+            };
+            let name = name.trim().to_owned();
+            if name.is_empty() {
+                return;
+            }
+
+            let mut updated = (*saved).clone();
+            updated.retain(|(saved_name, _)| *saved_name != name);
+            updated.push((name, serialized.to_string()));
+            gloo_storage::LocalStorage::set(&storage_key, &updated).unwrap();
+            saved.set(updated);
+        }
+    });
+
+    html! {
+        <>
+            <select class="settings-presets" title="Apply a preset" onchange={apply_preset}>
+                <option selected=true>{"Presets"}</option>
+                {for presets.0.iter().map(|(name, _)| html! { <option>{name}</option> })}
+                if !saved.is_empty() {
+                    <optgroup label="Saved">
+                        {for saved.iter().map(|(name, _)| html! { <option>{name}</option> })}
+                    </optgroup>
+                }
+            </select>
+            <button
+                class="settings-action"
+                title="Save the current settings as a preset"
+                onclick={save_preset}
+            >
+                <i class="iconoir-bookmark"/>
+                {" Save"}
+            </button>
+        </>
+    }
+}
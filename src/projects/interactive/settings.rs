@@ -0,0 +1,589 @@
+//! Declarative settings for interactive examples
+
+use std::{any::Any, collections::HashSet, rc::Rc};
+
+use color::{AlphaColor, Srgb};
+use gloo::utils::window;
+use gloo_storage::Storage;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use web_sys::js_sys::encode_uri_component;
+use yew::prelude::*;
+use yew_router::history::Location;
+
+use crate::{
+    projects::{
+        ExampleId,
+        interactive::{
+            AngleDial, Checkbox, ColorPicker, Curve, CurveEditor, Gradient, GradientPicker,
+            GradientStop, RangeSlider, Selection, SelectionOption, Slider, SliderValue,
+            ToggleGroup,
+        },
+    },
+    webgl::SeededRng,
+};
+
+/// A control which can edit a value of type `T` held in a [`UseStateHandle`]
+pub trait SettingControl<T> {
+    /// Render the control bound to the given value
+    fn html(&self, value: UseStateHandle<T>, active: bool) -> Html;
+
+    /// Returns a random value inside the range of the control
+    fn random_value(&self, rng: &mut SeededRng) -> T;
+}
+
+/// Returns a random multiple of `step` above `min`, not exceeding `max`
+fn random_step<T: SliderValue>(min: &T, max: &T, step: &T, rng: &mut SeededRng) -> T {
+    let (min, max, step) = (min.to_f64(), max.to_f64(), step.to_f64());
+    let steps = if step > 0.0 {
+        ((max - min) / step).floor().max(0.0)
+    } else {
+        0.0
+    };
+    let index = (f64::from(rng.next_f32()) * (steps + 1.0))
+        .floor()
+        .min(steps);
+
+    T::from_f64(min + index * step)
+}
+
+/// A [`Slider`] setting control
+#[derive(Debug, Clone, PartialEq)]
+pub struct SliderControl<T> {
+    /// The minimum value
+    pub min: T,
+    /// The maximum value
+    pub max: T,
+    /// The step value
+    pub step: T,
+}
+
+impl<T: SliderValue + Clone> SettingControl<T> for SliderControl<T> {
+    fn html(&self, value: UseStateHandle<T>, active: bool) -> Html {
+        html! {
+            <Slider<T>
+                {active}
+                min={self.min.clone()}
+                max={self.max.clone()}
+                step={self.step.clone()}
+                {value}
+            />
+        }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> T {
+        random_step(&self.min, &self.max, &self.step, rng)
+    }
+}
+
+/// A [`RangeSlider`] setting control, selecting a lower and upper value
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeSliderControl<T> {
+    /// The minimum value
+    pub min: T,
+    /// The maximum value
+    pub max: T,
+    /// The step value
+    pub step: T,
+}
+
+impl<T: SliderValue + Clone> SettingControl<(T, T)> for RangeSliderControl<T> {
+    fn html(&self, value: UseStateHandle<(T, T)>, active: bool) -> Html {
+        html! {
+            <RangeSlider<T>
+                {active}
+                min={self.min.clone()}
+                max={self.max.clone()}
+                step={self.step.clone()}
+                {value}
+            />
+        }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> (T, T) {
+        let first = random_step(&self.min, &self.max, &self.step, rng);
+        let second = random_step(&self.min, &self.max, &self.step, rng);
+        if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+}
+
+/// An [`AngleDial`] setting control, selecting an angle in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AngleDialControl {
+    /// The angle the value snaps to, in degrees
+    pub step: f32,
+}
+
+impl SettingControl<f32> for AngleDialControl {
+    fn html(&self, value: UseStateHandle<f32>, active: bool) -> Html {
+        html! { <AngleDial {active} step={self.step} {value}/> }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> f32 {
+        random_step(&0.0, &(360.0 - self.step), &self.step, rng)
+    }
+}
+
+/// A [`Checkbox`] setting control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckboxControl;
+
+impl SettingControl<bool> for CheckboxControl {
+    fn html(&self, value: UseStateHandle<bool>, active: bool) -> Html {
+        html! { <Checkbox {active} {value}/> }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> bool {
+        rng.next_f32() < 0.5
+    }
+}
+
+/// A [`ColorPicker`] setting control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorControl;
+
+impl SettingControl<AlphaColor<Srgb>> for ColorControl {
+    fn html(&self, value: UseStateHandle<AlphaColor<Srgb>>, active: bool) -> Html {
+        html! { <ColorPicker {active} {value}/> }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> AlphaColor<Srgb> {
+        AlphaColor::new(std::array::from_fn(|_| rng.next_f32()))
+    }
+}
+
+/// A [`GradientPicker`] setting control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientControl;
+
+impl SettingControl<Gradient> for GradientControl {
+    fn html(&self, value: UseStateHandle<Gradient>, active: bool) -> Html {
+        html! { <GradientPicker {active} {value}/> }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> Gradient {
+        let mut color = || std::array::from_fn(|_| (rng.next_f32() * 256.0) as u8);
+        Gradient::new(vec![
+            GradientStop::new(0.0, color()),
+            GradientStop::new(0.5, color()),
+            GradientStop::new(1.0, color()),
+        ])
+    }
+}
+
+/// A [`CurveEditor`] setting control
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveControl;
+
+impl SettingControl<Curve> for CurveControl {
+    fn html(&self, value: UseStateHandle<Curve>, active: bool) -> Html {
+        html! { <CurveEditor {active} {value}/> }
+    }
+
+    fn random_value(&self, rng: &mut SeededRng) -> Curve {
+        Curve::new(vec![
+            (0.0, rng.next_f32()),
+            (0.5, rng.next_f32()),
+            (1.0, rng.next_f32()),
+        ])
+    }
+}
+
+/// A [`Selection`] setting control choosing from a fixed list of options
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelectionControl<T: 'static> {
+    /// The possible options
+    pub options: &'static [SelectionOption<T>],
+}
+
+impl<T: ToString + PartialEq + Clone + 'static> SettingControl<T> for SelectionControl<T> {
+    fn html(&self, value: UseStateHandle<T>, active: bool) -> Html {
+        let options: Box<[SelectionOption<T>]> = self.options.to_vec().into_boxed_slice();
+        html! { <Selection<T> {active} {value} {options}/> }
+    }
+
+    /// # Panics
+    /// If there are no enabled options to choose from
+    fn random_value(&self, rng: &mut SeededRng) -> T {
+        let enabled: Vec<_> = self
+            .options
+            .iter()
+            .filter(|option| !option.disabled)
+            .collect();
+        let index = (rng.next_f32() * enabled.len() as f32) as usize;
+        enabled[index.min(enabled.len() - 1)].value.clone()
+    }
+}
+
+/// A [`ToggleGroup`] setting control choosing from a few options shown side by side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToggleGroupControl<T: 'static> {
+    /// The possible options
+    pub options: &'static [SelectionOption<T>],
+}
+
+impl<T: ToString + PartialEq + Clone + 'static> SettingControl<T> for ToggleGroupControl<T> {
+    fn html(&self, value: UseStateHandle<T>, active: bool) -> Html {
+        let options: Box<[SelectionOption<T>]> = self.options.to_vec().into_boxed_slice();
+        html! { <ToggleGroup<T> {active} {value} {options}/> }
+    }
+
+    /// # Panics
+    /// If there are no enabled options to choose from
+    fn random_value(&self, rng: &mut SeededRng) -> T {
+        SelectionControl {
+            options: self.options,
+        }
+        .random_value(rng)
+    }
+}
+
+/// The serialized settings of an example, which can be dragged onto compatible examples
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsTransfer {
+    /// The current settings as json
+    pub serialized: AttrValue,
+    /// Applies settings given as json, returns why they are invalid otherwise
+    pub apply: Callback<String, Result<(), String>>,
+}
+
+impl SettingsTransfer {
+    /// Create a new transfer from the current settings and a callback applying dropped settings,
+    /// see [`SettingsTransfer::apply_callback`]
+    pub fn new<T: Serialize>(value: &T, apply: Callback<String, Result<(), String>>) -> Self {
+        Self {
+            serialized: serde_json::to_string(value).unwrap().into(),
+            apply,
+        }
+    }
+
+    /// Create the callback applying settings given as json with the given function.
+    ///
+    /// The callback should be created once, e.g. with [`use_memo`], so transfers of unchanged
+    /// settings compare equal and don't rerender the examples holding them.
+    pub fn apply_callback<T: DeserializeOwned + 'static>(
+        apply: impl Fn(T) + 'static,
+    ) -> Callback<String, Result<(), String>> {
+        Callback::from(move |json: String| {
+            serde_json::from_str(&json)
+                .map(&apply)
+                .map_err(|error| error.to_string())
+        })
+    }
+
+    /// Returns a link to the current page restoring the current settings, see [`shared_settings`]
+    pub fn share_url(&self) -> Option<String> {
+        let location = window().location();
+        let encoded = encode_base64(&self.serialized)?;
+        Some(format!(
+            "{}{}?{}={}",
+            location.origin().ok()?,
+            location.pathname().ok()?,
+            SharedSettingsQuery::PARAMETER,
+            encode_uri_component(&encoded),
+        ))
+    }
+}
+
+/// The state handles of settings derived with
+/// [`ExampleSettings`](crate::projects::interactive::ExampleSettings)
+pub trait SettingsHandles: Clone + PartialEq + 'static {
+    /// The settings held by the handles
+    type Settings;
+
+    /// Returns the current settings
+    fn value(&self) -> Self::Settings;
+}
+
+/// Describes a setting held by the handles `H`, shown by some of the versions `V` of an example
+pub struct SettingDescriptor<H: SettingsHandles, V> {
+    /// The label shown next to the control
+    pub label: &'static str,
+    /// Explains the setting, shown in a tooltip next to the label
+    pub description: Option<&'static str>,
+    /// Whether the given version of the example shows the setting
+    pub visible: fn(V) -> bool,
+    /// Whether the control is enabled for the current settings
+    pub active: fn(&H::Settings) -> bool,
+    /// Renders the control of the setting, which determines its kind and range, bound to the
+    /// handles. The second argument is whether the control is enabled.
+    pub control: fn(&H, bool) -> Html,
+}
+
+impl<H: SettingsHandles, V> SettingDescriptor<H, V> {
+    /// Show the setting only in the versions of the example accepted by the given function
+    pub const fn visible_in(self, visible: fn(V) -> bool) -> Self {
+        Self { visible, ..self }
+    }
+}
+
+impl<H: SettingsHandles, V> Clone for SettingDescriptor<H, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H: SettingsHandles, V> Copy for SettingDescriptor<H, V> {}
+
+/// Describes which settings the versions `V` of an example show and how they are grouped
+pub struct SettingsDescriptor<H: SettingsHandles, V: 'static> {
+    /// The settings shown before all groups, in display order
+    pub settings: &'static [SettingDescriptor<H, V>],
+    /// The names of the collapsible groups and their settings, in display order
+    pub groups: &'static [(&'static str, &'static [SettingDescriptor<H, V>])],
+}
+
+impl<H: SettingsHandles, V: Copy + PartialEq + 'static> SettingsDescriptor<H, V> {
+    /// Bind the settings shown by the given version of the example to the given handles
+    pub fn panel(&'static self, handles: &H, version: V) -> SettingsPanel {
+        SettingsPanel(Rc::new(BoundSettings {
+            descriptor: self,
+            handles: handles.clone(),
+            version,
+        }))
+    }
+}
+
+/// The settings shown by an example bound to their handles, which the
+/// [`InteractiveExample`](crate::projects::interactive::InteractiveExample) renders as labeled
+/// controls. Created with [`SettingsDescriptor::panel`].
+#[derive(Clone)]
+pub struct SettingsPanel(Rc<dyn PanelSettings>);
+
+impl SettingsPanel {
+    /// Returns the visible settings shown before all groups
+    pub fn settings(&self) -> Vec<Setting> {
+        self.0.settings(None)
+    }
+
+    /// Returns the indices and names of the groups with visible settings
+    pub fn groups(&self) -> Vec<(usize, &'static str)> {
+        self.0.groups()
+    }
+
+    /// Returns the visible settings of the group with the given index
+    pub fn group_settings(&self, group: usize) -> Vec<Setting> {
+        self.0.settings(Some(group))
+    }
+}
+
+impl PartialEq for SettingsPanel {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_dyn(other.0.as_any())
+    }
+}
+
+impl std::fmt::Debug for SettingsPanel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SettingsPanel").finish_non_exhaustive()
+    }
+}
+
+/// The settings of a [`SettingsPanel`], independent of the type of their handles
+trait PanelSettings {
+    /// Render the visible settings of the given group, or of the ungrouped ones
+    fn settings(&self, group: Option<usize>) -> Vec<Setting>;
+
+    /// Returns the indices and names of the groups with visible settings
+    fn groups(&self) -> Vec<(usize, &'static str)>;
+
+    /// Returns self for comparing it
+    fn as_any(&self) -> &dyn Any;
+
+    /// Whether the given settings are the same as these
+    fn eq_dyn(&self, other: &dyn Any) -> bool;
+}
+
+/// A [`SettingsDescriptor`] bound to the handles of the settings, for one version of the example
+struct BoundSettings<H: SettingsHandles, V: 'static> {
+    /// The descriptor of the settings
+    descriptor: &'static SettingsDescriptor<H, V>,
+    /// The handles of the settings
+    handles: H,
+    /// The version of the example showing the settings
+    version: V,
+}
+
+impl<H: SettingsHandles, V: Copy + PartialEq + 'static> PanelSettings for BoundSettings<H, V> {
+    fn settings(&self, group: Option<usize>) -> Vec<Setting> {
+        let settings = match group {
+            Some(group) => self.descriptor.groups[group].1,
+            None => self.descriptor.settings,
+        };
+        // The current settings are only needed once for all controls
+        let current = self.handles.value();
+        settings
+            .iter()
+            .filter(|setting| (setting.visible)(self.version))
+            .map(|setting| Setting {
+                label: setting.label.into(),
+                description: setting.description.map(AttrValue::from),
+                html: (setting.control)(&self.handles, (setting.active)(&current)),
+            })
+            .collect()
+    }
+
+    fn groups(&self) -> Vec<(usize, &'static str)> {
+        self.descriptor
+            .groups
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, settings))| {
+                settings
+                    .iter()
+                    .any(|setting| (setting.visible)(self.version))
+            })
+            .map(|(index, &(name, _))| (index, name))
+            .collect()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_dyn(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().is_some_and(|other| {
+            std::ptr::eq(self.descriptor, other.descriptor)
+                && self.handles == other.handles
+                && self.version == other.version
+        })
+    }
+}
+
+/// A visible setting of a [`SettingsPanel`], with its control rendered
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    /// The label of the setting
+    pub label: AttrValue,
+    /// Explains the setting, shown in a tooltip next to the label
+    pub description: Option<AttrValue>,
+    /// The control of the setting
+    pub html: Html,
+}
+
+/// The query of a link sharing settings, holding them as base64 encoded json
+#[derive(Debug, Deserialize)]
+struct SharedSettingsQuery {
+    /// The encoded settings
+    s: String,
+}
+
+impl SharedSettingsQuery {
+    /// The name of the query parameter
+    const PARAMETER: &str = "s";
+}
+
+/// Returns the settings shared through the query of the given location, if they are valid
+pub fn shared_settings<T: DeserializeOwned>(location: Option<&Location>) -> Option<T> {
+    let query = location?.query::<SharedSettingsQuery>().ok()?;
+    let json = decode_base64(&query.s)?;
+    serde_json::from_str(&json)
+        .inspect_err(|error| log::warn!("Shared settings are invalid: {error}"))
+        .ok()
+}
+
+/// Encode the utf-8 bytes of the given text as base64, since `btoa` only accepts latin-1 text
+fn encode_base64(text: &str) -> Option<String> {
+    let binary: String = text.bytes().map(char::from).collect();
+    window().btoa(&binary).ok()
+}
+
+/// Decode base64 encoded utf-8 text, see [`encode_base64`]. Returns `None` if the decoded bytes
+/// are no valid utf-8.
+fn decode_base64(encoded: &str) -> Option<String> {
+    let binary = window().atob(encoded).ok()?;
+    let bytes: Option<Vec<u8>> = binary.chars().map(|c| u8::try_from(c).ok()).collect();
+    String::from_utf8(bytes?).ok()
+}
+
+/// The prefix of the local storage keys remembering the settings of each example
+const SETTINGS_STORAGE_KEY_PREFIX: &str = "ExampleSettings";
+
+/// Returns the settings remembered under the given key from the last visit, if they are valid
+pub fn stored_settings<T: DeserializeOwned>(storage_key: &str) -> Option<T> {
+    gloo_storage::LocalStorage::get(format!("{SETTINGS_STORAGE_KEY_PREFIX}/{storage_key}"))
+        .inspect_err(|error| {
+            if !matches!(error, gloo_storage::errors::StorageError::KeyNotFound(_)) {
+                log::warn!("Stored settings are invalid: {error}");
+            }
+        })
+        .ok()
+}
+
+/// Remember the given settings under the given key, forgetting them if they are the defaults
+pub fn store_settings<T: Serialize + PartialEq>(storage_key: &str, value: &T, defaults: &T) {
+    let storage_key = format!("{SETTINGS_STORAGE_KEY_PREFIX}/{storage_key}");
+    if value == defaults {
+        gloo_storage::LocalStorage::delete(storage_key);
+    } else if let Err(error) = gloo_storage::LocalStorage::set(storage_key, value) {
+        log::warn!("Failed to store settings: {error}");
+    }
+}
+
+/// The prefix of the local storage keys remembering the collapsed setting groups of each example
+const COLLAPSED_GROUPS_STORAGE_KEY_PREFIX: &str = "CollapsedSettingGroups";
+
+/// Returns the names of the setting groups collapsed in the given example during the last visit
+pub fn stored_collapsed_groups(example: ExampleId) -> HashSet<AttrValue> {
+    gloo_storage::LocalStorage::get::<Vec<String>>(format!(
+        "{COLLAPSED_GROUPS_STORAGE_KEY_PREFIX}/{example}"
+    ))
+    .map(|groups| groups.into_iter().map(AttrValue::from).collect())
+    .unwrap_or_default()
+}
+
+/// Remember the given collapsed setting groups of the given example, forgetting them if there are
+/// none
+pub fn store_collapsed_groups(example: ExampleId, groups: &HashSet<AttrValue>) {
+    let storage_key = format!("{COLLAPSED_GROUPS_STORAGE_KEY_PREFIX}/{example}");
+    let groups: Vec<&str> = groups.iter().map(AttrValue::as_str).collect();
+    if groups.is_empty() {
+        gloo_storage::LocalStorage::delete(storage_key);
+    } else if let Err(error) = gloo_storage::LocalStorage::set(storage_key, groups) {
+        log::warn!("Failed to store the collapsed setting groups: {error}");
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn base64_round_trips_utf8() {
+        for text in ["", "plain ascii", r#"{"angle":45.0}"#, "Grüße 🐟 ∑"] {
+            let encoded = encode_base64(text).unwrap();
+            assert_eq!(decode_base64(&encoded).as_deref(), Some(text));
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn base64_rejects_invalid_utf8() {
+        // A lone latin-1 byte, as encoded from latin-1 text directly
+        assert_eq!(decode_base64("/w=="), None);
+        assert_eq!(decode_base64("not base64!"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn collapsed_groups_round_trip() {
+        let example = ExampleId::new("collapsed-groups-test");
+        let groups: HashSet<AttrValue> = ["Colors", "Motion"].map(AttrValue::from).into();
+        store_collapsed_groups(example, &groups);
+        assert_eq!(stored_collapsed_groups(example), groups);
+
+        store_collapsed_groups(example, &HashSet::new());
+        assert!(stored_collapsed_groups(example).is_empty());
+        assert!(
+            gloo_storage::LocalStorage::raw()
+                .get_item(&format!("{COLLAPSED_GROUPS_STORAGE_KEY_PREFIX}/{example}"))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! An overlay showing the frame rate of an interactive example
+
+use gloo::timers::callback::Interval;
+use stylist::yew::use_style;
+use yew::prelude::*;
+
+use crate::{intl::format_decimal, theme::ThemeColor, webgl::FrameStats};
+
+/// The interval in which the [`FrameStatsOverlay`] is refreshed, in milliseconds
+const REFRESH_INTERVAL: u32 = 250;
+
+/// The size of the frame time graph in pixels
+const GRAPH_SIZE: (u32, u32) = (120, 32);
+
+/// The lowest frame time the graph scales to in milliseconds, so 30 fps fill the graph
+const GRAPH_MIN_SCALE: f64 = 33.0;
+
+/// Properties for the [`FrameStatsOverlay`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct FrameStatsOverlayProperties {
+    /// The stats of the measured canvas
+    pub stats: FrameStats,
+}
+
+/// An overlay showing the frame rate, frame time and a graph of the recent frame times
+#[function_component(FrameStatsOverlay)]
+pub fn frame_stats_overlay(
+    FrameStatsOverlayProperties { stats }: &FrameStatsOverlayProperties,
+) -> Html {
+    let style = use_style!(
+        r#"
+            position: absolute;
+            top: 60px;
+            right: 10px;
+            padding: 4px 10px;
+            color: ${fg};
+            background-color: ${bg};
+            font-family: monospace;
+            font-size: 12px;
+            opacity: 0.8;
+            pointer-events: none;
+
+            svg {
+                display: block;
+                margin-top: 4px;
+            }
+
+            polyline {
+                fill: none;
+                stroke: ${graph};
+                stroke-width: 1;
+            }
+        "#,
+        fg = ThemeColor::Base05.var(),
+        bg = ThemeColor::Base00.var(),
+        graph = ThemeColor::Base0B.var(),
+    );
+    let frame_times = use_state(Vec::new);
+
+    use_effect_with(stats.clone(), {
+        let frame_times = frame_times.setter();
+        move |stats| {
+            let stats = stats.clone();
+            let interval = Interval::new(REFRESH_INTERVAL, move || {
+                frame_times.set(stats.frame_times());
+            });
+
+            move || drop(interval)
+        }
+    });
+
+    let text = if frame_times.is_empty() {
+        "No frames rendered".to_owned()
+    } else {
+        let average = frame_times.iter().sum::<f64>() / frame_times.len() as f64;
+        format!(
+            "{} fps · {} ms",
+            format_decimal(1000.0 / average.max(1.0), 0),
+            format_decimal(average, 1),
+        )
+    };
+
+    let (width, height) = GRAPH_SIZE;
+    let scale = frame_times.iter().copied().fold(GRAPH_MIN_SCALE, f64::max);
+    let step = width as f64 / (FrameStats::HISTORY - 1) as f64;
+    let points = frame_times
+        .iter()
+        .enumerate()
+        .map(|(index, frame_time)| {
+            let x = index as f64 * step;
+            let y = height as f64 * (1.0 - frame_time / scale);
+            format!("{x:.1},{y:.1}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    html! {
+        <div class={style}>
+            {text}
+            <svg
+                width={width.to_string()}
+                height={height.to_string()}
+                viewBox={format!("0 0 {width} {height}")}
+            >
+                <polyline {points}/>
+            </svg>
+        </div>
+    }
+}
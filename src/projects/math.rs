@@ -0,0 +1,266 @@
+//! Rendering formulas written in TeX notation as MathML.
+//!
+//! Only the subset of TeX used by the articles is understood: numbers, identifiers and operators,
+//! groups, sub- and superscripts, fractions, roots, text and the common symbols below. Unknown
+//! commands are shown as errors within the formula, instead of failing the whole article.
+
+use std::{iter::Peekable, str::Chars};
+
+use yew::prelude::*;
+
+/// Commands for identifiers, mostly greek letters
+const IDENTIFIERS: &[(&str, char)] = &[
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ε'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("rho", 'ρ'),
+    ("sigma", 'σ'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'φ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+    ("infty", '∞'),
+    ("partial", '∂'),
+    ("nabla", '∇'),
+    ("ell", 'ℓ'),
+];
+
+/// Commands for operators and relations
+const OPERATORS: &[(&str, char)] = &[
+    ("cdot", '⋅'),
+    ("times", '×'),
+    ("div", '÷'),
+    ("pm", '±'),
+    ("mp", '∓'),
+    ("circ", '∘'),
+    ("le", '≤'),
+    ("leq", '≤'),
+    ("ge", '≥'),
+    ("geq", '≥'),
+    ("ne", '≠'),
+    ("neq", '≠'),
+    ("approx", '≈'),
+    ("equiv", '≡'),
+    ("in", '∈'),
+    ("to", '→'),
+    ("rightarrow", '→'),
+    ("leftarrow", '←'),
+    ("Rightarrow", '⇒'),
+    ("mapsto", '↦'),
+    ("sum", '∑'),
+    ("prod", '∏'),
+    ("int", '∫'),
+    ("ldots", '…'),
+    ("cdots", '⋯'),
+    ("mid", '∣'),
+    ("langle", '⟨'),
+    ("rangle", '⟩'),
+];
+
+/// Commands for functions, written upright
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "exp", "log", "ln", "min", "max", "arg", "Re", "Im",
+];
+
+/// Render the given formula, either displayed on its own line or inline with the text
+pub fn render(tex: &str, display: bool) -> Html {
+    let mut parser = TexParser {
+        chars: tex.chars().peekable(),
+    };
+    let mut row = Vec::new();
+    // Unbalanced closing braces are skipped
+    loop {
+        row.extend(parser.row());
+        if parser.chars.next().is_none() {
+            break;
+        }
+    }
+
+    html! {
+        <math display={if display { "block" } else { "inline" }}>
+            <mrow>{for row}</mrow>
+        </math>
+    }
+}
+
+/// A recursive descent parser turning TeX into MathML elements
+struct TexParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl TexParser<'_> {
+    /// Parse atoms with their scripts, up to the end of the input or a closing brace
+    fn row(&mut self) -> Vec<Html> {
+        let mut row = Vec::new();
+        while let Some(atom) = self.scripted_atom() {
+            row.push(atom);
+        }
+        row
+    }
+
+    /// Parse an atom with its optional sub- and superscript
+    fn scripted_atom(&mut self) -> Option<Html> {
+        let base = self.atom()?;
+        let (mut sub, mut sup) = (None, None);
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('_') if sub.is_none() => {
+                    self.chars.next();
+                    sub = Some(self.argument());
+                }
+                Some('^') if sup.is_none() => {
+                    self.chars.next();
+                    sup = Some(self.argument());
+                }
+                _ => break,
+            }
+        }
+
+        Some(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => html! { <msub>{base}{sub}</msub> },
+            (None, Some(sup)) => html! { <msup>{base}{sup}</msup> },
+            (Some(sub), Some(sup)) => html! { <msubsup>{base}{sub}{sup}</msubsup> },
+        })
+    }
+
+    /// Parse a single element, returns `None` at the end of the input or of a group
+    fn atom(&mut self) -> Option<Html> {
+        self.skip_whitespace();
+        let next = self.chars.next_if(|&next| next != '}')?;
+        Some(match next {
+            '{' => {
+                let row = self.row();
+                self.chars.next();
+                html! { <mrow>{for row}</mrow> }
+            }
+            '\\' => self.command(),
+            '0'..='9' | '.' => {
+                let mut number = next.to_string();
+                while let Some(digit) = self
+                    .chars
+                    .next_if(|next| next.is_ascii_digit() || *next == '.')
+                {
+                    number.push(digit);
+                }
+                html! { <mn>{number}</mn> }
+            }
+            '^' | '_' => error(&next.to_string()),
+            next if next.is_alphabetic() => html! { <mi>{next.to_string()}</mi> },
+            '\'' => html! { <mo>{"′"}</mo> },
+            next => html! { <mo>{next.to_string()}</mo> },
+        })
+    }
+
+    /// Parse the argument of a command or script, either a group or a single element
+    fn argument(&mut self) -> Html {
+        self.atom().unwrap_or_else(|| html! { <mrow/> })
+    }
+
+    /// Parse a command following a backslash
+    fn command(&mut self) -> Html {
+        let mut name = String::new();
+        while let Some(letter) = self.chars.next_if(char::is_ascii_alphabetic) {
+            name.push(letter);
+        }
+        if name.is_empty() {
+            // A single symbol, spacing or escaped character
+            return match self.chars.next() {
+                Some(',') => html! { <mspace width="0.17em"/> },
+                Some(':') => html! { <mspace width="0.22em"/> },
+                Some(';') => html! { <mspace width="0.28em"/> },
+                Some(' ') => html! { <mspace width="0.25em"/> },
+                Some(symbol @ ('{' | '}' | '|' | '%' | '$' | '#' | '&')) => {
+                    html! { <mo>{symbol.to_string()}</mo> }
+                }
+                symbol => error(&format!(
+                    "\\{}",
+                    symbol.map(String::from).unwrap_or_default()
+                )),
+            };
+        }
+
+        match name.as_str() {
+            "frac" => {
+                let (numerator, denominator) = (self.argument(), self.argument());
+                html! { <mfrac>{numerator}{denominator}</mfrac> }
+            }
+            "sqrt" => html! { <msqrt>{self.argument()}</msqrt> },
+            "text" => html! { <mtext>{self.text_argument()}</mtext> },
+            "mathrm" => html! { <mi mathvariant="normal">{self.text_argument()}</mi> },
+            "quad" => html! { <mspace width="1em"/> },
+            "qquad" => html! { <mspace width="2em"/> },
+            // Delimiters stretch by default
+            "left" | "right" => {
+                self.skip_whitespace();
+                match self.chars.next() {
+                    Some('.') | None => html! { <mrow/> },
+                    Some('\\') => self.command(),
+                    Some(delimiter) => html! { <mo>{delimiter.to_string()}</mo> },
+                }
+            }
+            name => {
+                if let Some((_, identifier)) = IDENTIFIERS.iter().find(|(key, _)| *key == name) {
+                    html! { <mi>{identifier.to_string()}</mi> }
+                } else if let Some((_, operator)) = OPERATORS.iter().find(|(key, _)| *key == name) {
+                    html! { <mo>{operator.to_string()}</mo> }
+                } else if FUNCTIONS.contains(&name) {
+                    html! { <mi>{name.to_owned()}</mi> }
+                } else {
+                    error(&format!("\\{name}"))
+                }
+            }
+        }
+    }
+
+    /// Parse the verbatim content of a group, e.g. the argument of `\text`
+    fn text_argument(&mut self) -> String {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'{').is_none() {
+            return self.chars.next().map(String::from).unwrap_or_default();
+        }
+        self.chars
+            .by_ref()
+            .take_while(|&next| next != '}')
+            .collect()
+    }
+
+    /// Skip whitespace, which TeX ignores within formulas
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|next| next.is_whitespace()).is_some() {}
+    }
+}
+
+/// Returns an element marking unsupported notation
+fn error(notation: &str) -> Html {
+    html! {
+        <merror>
+            <mtext>{notation.to_owned()}</mtext>
+        </merror>
+    }
+}
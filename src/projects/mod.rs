@@ -1,34 +1,193 @@
 //! Individual project pages
 
-use stylist::{css, yew::use_style};
+use std::{fmt::Display, str::FromStr};
+
+use gloo::utils::window;
+use strum::IntoEnumIterator;
+use stylist::{css, manager::StyleManager, yew::use_style};
 use syntect::{
     easy::HighlightLines,
     highlighting::{FontStyle, Style},
     util::LinesWithEndings,
 };
+use web_sys::{Element, HtmlImageElement};
 use yew::prelude::*;
-use yew_router::prelude::Link;
+use yew_router::prelude::{Link, use_location};
 
 use crate::{
+    NotFoundPage,
     about::Author,
+    asset_version::versioned,
+    csp::{manager_class, use_style_manager},
+    effects::use_celebrate,
+    intl::{format_date, format_relative_date},
     navigation::Route,
-    projects::{boids::BoidsPage, fractal_clock::FractalClockPage},
-    theme::use_theme,
-    theme::{HighlightSet, use_highlight_set},
+    projects::interactive::use_scroll_event_listener,
+    theme::{AccentOverride, HighlightSet, use_highlight_set},
+    theme::{ThemeColor, use_theme},
 };
 
-pub mod boids;
-pub mod fractal_clock;
-mod interactive;
+pub mod article;
+pub mod external;
+pub(crate) mod interactive;
+mod math;
+mod palette;
+
+/// Declares the project modules and the [`Project`] enum listing them.
+///
+/// Each project module exposes a `DESCRIPTOR` of type [`ProjectDescriptor`], so adding a project
+/// only takes its module and a single line here.
+macro_rules! projects {
+    ($($(#[$attr:meta])* $variant:ident => $module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// An enum of all projects
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, strum::EnumIter,
+        )]
+        #[strum(serialize_all = "kebab-case")]
+        pub enum Project {
+            $(
+                $(#[$attr])*
+                $variant,
+            )*
+        }
+
+        impl Project {
+            /// Returns the descriptor registered by the project module
+            pub const fn descriptor(self) -> &'static ProjectDescriptor {
+                match self {
+                    $(Project::$variant => &$module::DESCRIPTOR,)*
+                }
+            }
+        }
+    };
+}
 
-/// An enum of all projects
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, strum::EnumIter)]
-#[strum(serialize_all = "kebab-case")]
-pub enum Project {
+projects! {
     /// Fractal clock
-    FractalClock,
+    FractalClock => fractal_clock,
     /// Boids
-    Boids,
+    Boids => boids,
+}
+
+/// Everything a project module registers about itself, see [`Project::descriptor`]
+pub struct ProjectDescriptor {
+    /// The metadata
+    pub meta: ProjectMeta,
+    /// Renders the project page
+    pub page: fn() -> Html,
+    /// The ids of all embeddable examples
+    pub examples: &'static [ExampleId],
+    /// Renders a single embedded example and whether its settings are shown, `None` if there is
+    /// no such example
+    pub embed: fn(ExampleId, bool) -> Option<Html>,
+}
+
+/// The local storage key of the chosen [`ProjectLayout`]
+pub const PROJECT_LAYOUT_STORAGE_KEY: &str = "ProjectLayout";
+
+/// The layout projects are listed in on the home page
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    strum::EnumIter,
+    strum::Display,
+)]
+pub enum ProjectLayout {
+    /// One wide preview per row
+    #[default]
+    List,
+    /// A responsive grid of compact cards
+    Grid,
+}
+
+/// A calendar date, ordered chronologically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ChangeDate {
+    /// The year
+    pub year: u16,
+    /// The month, starting at 1
+    pub month: u8,
+    /// The day of the month, starting at 1
+    pub day: u8,
+}
+
+impl ChangeDate {
+    /// The english month names
+    const MONTH_NAMES: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    /// The abbreviated english weekday names, starting with sunday
+    const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+    /// Returns the english name of the month
+    pub fn month_name(self) -> &'static str {
+        Self::MONTH_NAMES[usize::from(self.month - 1)]
+    }
+
+    /// Returns the day of the week, starting with 0 for sunday
+    pub fn weekday(self) -> u8 {
+        // Sakamoto's method
+        const OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = u32::from(self.year) - u32::from(self.month < 3);
+        let day = year + year / 4 - year / 100
+            + year / 400
+            + OFFSETS[usize::from(self.month - 1)]
+            + u32::from(self.day);
+        (day % 7) as u8
+    }
+
+    /// Returns the start of this day as an RFC 822 date, as used by RSS
+    pub fn to_rfc822(self) -> String {
+        format!(
+            "{}, {:02} {} {} 00:00:00 GMT",
+            Self::WEEKDAY_NAMES[usize::from(self.weekday())],
+            self.day,
+            &self.month_name()[..3],
+            self.year,
+        )
+    }
+}
+
+/// Serialized as an ISO 8601 date, e.g. `2026-10-16`
+impl serde::Serialize for ChangeDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Display for ChangeDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A single entry of a projects changelog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    /// When the change was published
+    pub date: ChangeDate,
+    /// A short summary of the change
+    pub summary: &'static str,
 }
 
 /// Project metadata
@@ -40,30 +199,30 @@ pub struct ProjectMeta {
     pub description: &'static str,
     /// The authors
     pub authors: &'static [Author],
+    /// Short tags categorizing the project
+    pub tags: &'static [&'static str],
+    /// The position among all projects on the home page, lower comes first
+    pub order: u32,
+    /// Whether the project is pinned to the featured row on the home page
+    pub featured: bool,
+    /// Notable changes to the project, listed on the changes page and in the changes feed
+    pub changelog: &'static [ChangelogEntry],
+    /// Overrides applied over the current theme on the project pages, giving the project its own
+    /// accent
+    pub accents: &'static [AccentOverride],
 }
 
 impl Project {
     /// Returns the projects metadata
     pub const fn meta(self) -> ProjectMeta {
-        match self {
-            Project::FractalClock => ProjectMeta {
-                title: "Fractal Clock",
-                description: indoc::indoc! {"
-                    When drawing an analogue clock recursively at each pointer tip, beautiful
-                    patterns emerge. We will explore how to optimize and render this efficiently
-                    using webgl rendering.
-                "},
-                authors: &[Author::Ciklon],
-            },
-            Project::Boids => ProjectMeta {
-                title: "Boids",
-                description: indoc::indoc! {"
-                    This interactive tutorial guides you through implementing the Boids algorithm,
-                    originally developed by Craig Reynolds in 1986, using a compute shader.
-                "},
-                authors: &[Author::DawnFirefly],
-            },
-        }
+        self.descriptor().meta
+    }
+
+    /// Returns all projects sorted by their [`order`](ProjectMeta::order)
+    pub fn sorted() -> Vec<Project> {
+        let mut projects: Vec<_> = Project::iter().collect();
+        projects.sort_by_key(|project| project.meta().order);
+        projects
     }
 
     /// Returns the route that leads to the project page
@@ -71,30 +230,144 @@ impl Project {
         Route::Project { project: self }
     }
 
-    /// Returns the path to the project preview image
+    /// Returns the versioned path to the project preview image
     pub fn preview_image_path(self) -> String {
-        format!("assets/images/preview/{self}.png")
+        versioned(&format!("assets/images/preview/{self}.png"))
     }
 
     /// Returns the project page html
     pub fn html(self) -> Html {
-        match self {
-            Project::FractalClock => html! { <FractalClockPage/> },
-            Project::Boids => html! { <BoidsPage/> },
+        (self.descriptor().page)()
+    }
+
+    /// Returns the ids of all embeddable examples of this project
+    pub const fn example_ids(self) -> &'static [ExampleId] {
+        self.descriptor().examples
+    }
+
+    /// Returns the html of a single embedded example, `None` if there is no such example
+    pub fn embed_html(self, example: ExampleId, show_settings: bool) -> Option<Html> {
+        (self.descriptor().embed)(example, show_settings)
+    }
+
+    /// Returns the route that embeds the given example
+    pub fn embed_route(self, example: ExampleId) -> Route {
+        Route::Embed {
+            project: self,
+            example,
         }
     }
 }
 
-/// Properties for the [`ProjectPreview`] component
+/// The id of an embeddable example, see [`Project::example_ids`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExampleId(&'static str);
+
+impl ExampleId {
+    /// Create a new example id, it should be written in kebab-case
+    pub const fn new(id: &'static str) -> Self {
+        Self(id)
+    }
+}
+
+impl Display for ExampleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ExampleId {
+    type Err = String;
+
+    /// Parse any example id known by some [`Project`]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Project::iter()
+            .flat_map(|project| project.example_ids())
+            .find(|id| id.0 == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown example '{s}'"))
+    }
+}
+
+/// Query parameters of the embed route
+#[derive(Debug, Default, serde::Deserialize)]
+struct EmbedQuery {
+    /// Whether to hide the settings panel
+    #[serde(default)]
+    hide_settings: bool,
+}
+
+/// Properties for the [`EmbedPage`] component
 #[derive(Debug, PartialEq, Properties)]
-pub struct ProjectPreviewProperties {
+pub struct EmbedPageProperties {
+    /// The project of the example
     pub project: Project,
+    /// The embedded example
+    pub example: ExampleId,
 }
 
-/// A project preview component showing an image next to a description
-#[function_component(ProjectPreview)]
-pub fn project_preview(ProjectPreviewProperties { project }: &ProjectPreviewProperties) -> Html {
-    let theme = use_theme();
+/// A page showing nothing but a single interactive example, meant to be used in an iframe.
+///
+/// The settings can be hidden with the `hide_settings=true` query parameter.
+#[function_component(EmbedPage)]
+pub fn embed_page(EmbedPageProperties { project, example }: &EmbedPageProperties) -> Html {
+    let query = use_location()
+        .and_then(|location| location.query::<EmbedQuery>().ok())
+        .unwrap_or_default();
+
+    match project.embed_html(*example, !query.hide_settings) {
+        Some(example) => example,
+        None => html! { <NotFoundPage/> },
+    }
+}
+
+/// Where a [`PreviewCard`] leads to
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewTarget {
+    /// A page of this website
+    Route(Route),
+    /// An external url
+    External(AttrValue),
+}
+
+/// Properties for the [`PreviewCard`] component
+#[derive(Debug, PartialEq, Properties)]
+pub struct PreviewCardProperties {
+    /// The title
+    pub title: AttrValue,
+    /// A short description
+    pub description: AttrValue,
+    /// The path to the preview image
+    pub image: AttrValue,
+    /// Where the image and title link to
+    pub target: PreviewTarget,
+    /// Whether to show a larger card, used for featured projects
+    #[prop_or_default]
+    pub large: bool,
+    /// Whether to show a compact card for a grid, see [`ProjectLayout::Grid`]
+    #[prop_or_default]
+    pub grid: bool,
+    /// Additional content shown below the description
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// A preview card showing an image next to a description.
+///
+/// The card is accented with the dominant color of its image once it is loaded.
+#[function_component(PreviewCard)]
+pub fn preview_card(
+    PreviewCardProperties {
+        title,
+        description,
+        image,
+        target,
+        large,
+        grid,
+        children,
+    }: &PreviewCardProperties,
+) -> Html {
+    let accent = use_state(|| None::<String>);
     let style = use_style!(
         r#"
             display: flex;
@@ -104,6 +377,34 @@ pub fn project_preview(ProjectPreviewProperties { project }: &ProjectPreviewProp
             height: 350px;
             width: 900px;
             margin: 0 auto;
+            border: 2px solid ${accent};
+            transition: box-shadow 0.3s;
+
+            :hover {
+                box-shadow: 0px 0px 20px ${accent};
+            }
+
+            &.large {
+                height: 450px;
+                width: 1100px;
+            }
+
+            &.grid {
+                flex-direction: column;
+                justify-content: start;
+                height: 100%;
+                width: auto;
+                box-sizing: border-box;
+            }
+
+            &.grid > a, &.grid > div {
+                width: auto;
+            }
+
+            &.grid a img {
+                height: auto;
+                width: 100%;
+            }
 
             a {
                 margin: 10px;
@@ -141,34 +442,151 @@ pub fn project_preview(ProjectPreviewProperties { project }: &ProjectPreviewProp
                 justify-content: center;
             }
         "#,
-        container_bg = theme.base02,
-        heading_fg = theme.base06,
+        container_bg = ThemeColor::Base02.var(),
+        heading_fg = ThemeColor::Base06.var(),
+        accent = accent.as_deref().unwrap_or(ThemeColor::Base02.var()),
+    );
+    let extract_accent = Callback::from({
+        let accent = accent.clone();
+        move |event: Event| {
+            let image: HtmlImageElement = event.target_unchecked_into();
+            accent.set(palette::dominant_color(&image));
+        }
+    });
+    let link = |content: Html| match target {
+        PreviewTarget::Route(route) => html! {
+            <Link<Route> to={*route}>{content}</Link<Route>>
+        },
+        PreviewTarget::External(url) => html! {
+            <a href={url.clone()}>{content}</a>
+        },
+    };
+    html! {
+        <div class={classes!(style, large.then_some("large"), grid.then_some("grid"))}>
+            {link(html! { <img src={image.clone()} onload={extract_accent}/> })}
+            <div>
+                {link(html! { <h3>{title.clone()}</h3> })}
+                <p>{description.clone()}</p>
+                {children.clone()}
+            </div>
+        </div>
+    }
+}
+
+/// Properties for the [`ProjectMetadata`] component
+#[derive(Debug, PartialEq, Properties)]
+struct ProjectMetadataProperties {
+    project: Project,
+}
+
+/// The authors and tags of a project, shown the same way on every kind of card
+#[function_component(ProjectMetadata)]
+fn project_metadata(ProjectMetadataProperties { project }: &ProjectMetadataProperties) -> Html {
+    let style = use_style!(
+        r#"
+            .tags {
+                margin: 0px;
+                padding: 0px;
+                text-align: center;
+                color: ${tag_fg};
+                font-size: 14px;
+            }
+
+            .tags span {
+                margin: 0px 5px;
+            }
+
+            .updated {
+                margin: 0px;
+                text-align: center;
+                color: ${tag_fg};
+                font-size: 14px;
+            }
+
+            .author {
+                margin: 0px 10px;
+            }
+        "#,
+        tag_fg = ThemeColor::Base04.var(),
     );
     let authors = project.meta().authors.iter().map(|author| {
         html! {
-            <div class={css!("margin: 0px 10px;")}>
+            <div class="author">
                 {author.badge()}
             </div>
         }
     });
+    let tags = project
+        .meta()
+        .tags
+        .iter()
+        .map(|tag| html! { <span>{format!("#{tag}")}</span> });
+    let updated = project
+        .meta()
+        .changelog
+        .iter()
+        .map(|entry| entry.date)
+        .max();
     html! {
         <div class={style}>
-            <Link<Route> to={project.route()}>
-                <img src={project.preview_image_path()}/>
-            </Link<Route>>
-            <div>
-                <Link<Route> to={project.route()}>
-                    <h3>{project.meta().title}</h3>
-                </Link<Route>>
-                <p>{project.meta().description}</p>
-                <div class="authors">
-                    {for authors}
-                </div>
+            <div class="authors">
+                {for authors}
             </div>
+            <p class="tags">
+                {for tags}
+            </p>
+            if let Some(updated) = updated {
+                <p class="updated" title={format_date(updated)}>
+                    {format!("Updated {}", format_relative_date(updated))}
+                </p>
+            }
         </div>
     }
 }
 
+/// Properties for the [`ProjectPreview`] and [`ProjectCard`] components
+#[derive(Debug, PartialEq, Properties)]
+pub struct ProjectPreviewProperties {
+    pub project: Project,
+    /// Whether to show a larger card
+    #[prop_or_default]
+    pub large: bool,
+}
+
+/// A project preview component showing an image next to a description
+#[function_component(ProjectPreview)]
+pub fn project_preview(
+    ProjectPreviewProperties { project, large }: &ProjectPreviewProperties,
+) -> Html {
+    html! {
+        <PreviewCard
+            title={project.meta().title}
+            description={project.meta().description}
+            image={project.preview_image_path()}
+            target={PreviewTarget::Route(project.route())}
+            large={*large}
+        >
+            <ProjectMetadata project={*project}/>
+        </PreviewCard>
+    }
+}
+
+/// A compact project card showing an image above a description, meant for a grid
+#[function_component(ProjectCard)]
+pub fn project_card(ProjectPreviewProperties { project, .. }: &ProjectPreviewProperties) -> Html {
+    html! {
+        <PreviewCard
+            title={project.meta().title}
+            description={project.meta().description}
+            image={project.preview_image_path()}
+            target={PreviewTarget::Route(project.route())}
+            grid=true
+        >
+            <ProjectMetadata project={*project}/>
+        </PreviewCard>
+    }
+}
+
 /// Properties for the [`ProjectSite`] component
 #[derive(Debug, Properties, PartialEq)]
 pub struct ProjectSiteProperties {
@@ -178,10 +596,12 @@ pub struct ProjectSiteProperties {
     children: Children,
 }
 
+/// The id of the last section of an article, reading past it completes the article
+const CONCLUSION_SECTION_ID: &str = "conclusion";
+
 /// Wraps project content in a page (mainly for styling)
 #[function_component(ProjectSite)]
 pub fn project_site(ProjectSiteProperties { title, children }: &ProjectSiteProperties) -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             width: 900px;
@@ -200,11 +620,39 @@ pub fn project_site(ProjectSiteProperties { title, children }: &ProjectSitePrope
                 color: ${link_fg};
             }
         "#,
-        bg = theme.base02,
-        link_fg = theme.base0C,
+        bg = ThemeColor::Base02.var(),
+        link_fg = ThemeColor::Base0C.var(),
     );
+    let node = use_node_ref();
+    let celebrate = use_celebrate();
+    let celebrated = use_mut_ref(|| false);
+
+    // Celebrate once the reader scrolls past the conclusion of the article, if it has one
+    use_scroll_event_listener({
+        let node = node.clone();
+        move |_| {
+            if *celebrated.borrow() {
+                return;
+            }
+            let Some(conclusion) = node.cast::<Element>().and_then(|element| {
+                element
+                    .query_selector(&format!("section#{CONCLUSION_SECTION_ID}"))
+                    .ok()
+                    .flatten()
+            }) else {
+                return;
+            };
+            let width = window().inner_width().unwrap().as_f64().unwrap();
+            let height = window().inner_height().unwrap().as_f64().unwrap();
+            if conclusion.get_bounding_client_rect().bottom() <= height + 1.0 {
+                *celebrated.borrow_mut() = true;
+                celebrate.emit((width / 2.0, height));
+            }
+        }
+    });
+
     html! {
-        <div class={style}>
+        <div class={style} ref={node}>
             <h1>{title}</h1>
             {children}
         </div>
@@ -219,13 +667,12 @@ pub struct NoteProperties {
 /// Displays a note box around some content
 #[function_component(Note)]
 pub fn note(props: &NoteProperties) -> Html {
-    let theme = use_theme();
     let style = use_style!(
         r#"
             padding: 10px 20px;
             background-color: ${bg};
         "#,
-        bg = theme.base03,
+        bg = ThemeColor::Base03.var(),
     );
     html! {
         <div class={style}>
@@ -262,6 +709,7 @@ pub fn code_example(props: &CodeExampleProperties) -> Html {
 #[function_component(CodeExampleInner)]
 fn code_example_inner(props: &CodeExampleProperties) -> HtmlResult {
     let theme = use_theme();
+    let style_manager = use_style_manager();
     let highlight_set = use_highlight_set()?;
     let style = use_style!(
         r#"
@@ -270,7 +718,7 @@ fn code_example_inner(props: &CodeExampleProperties) -> HtmlResult {
             font-family: monospace;
             font-size: 15px;
         "#,
-        bg = theme.base00,
+        bg = ThemeColor::Base00.var(),
     );
     let highlighted = highlight_code(
         &props.lang,
@@ -280,10 +728,10 @@ fn code_example_inner(props: &CodeExampleProperties) -> HtmlResult {
             .theme
             .as_ref()
             .map(AttrValue::as_str)
-            .unwrap_or(theme.syntax_theme),
+            .unwrap_or_else(|| theme.code_theme(&highlight_set)),
     );
     let content = match highlighted {
-        Ok(highlighted) => highlight_to_html(&highlighted),
+        Ok(highlighted) => highlight_to_html(&highlighted, &style_manager),
         Err(error) => html! { {error} },
     };
     Ok(html! {
@@ -303,7 +751,11 @@ fn highlight_code<'code>(
     theme_name: &str,
 ) -> Result<Vec<(Style, &'code str)>, syntect::Error> {
     let theme = &highlight_set.themes().themes[theme_name];
-    let syntax = highlight_set.syntaxes().find_syntax_by_name(lang).unwrap();
+    // The fallback highlight set only knows plain text
+    let syntax = highlight_set
+        .syntaxes()
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| highlight_set.syntaxes().find_syntax_plain_text());
     let mut highlighter = HighlightLines::new(syntax, theme);
     let mut result = Vec::default();
     for line in LinesWithEndings::from(code) {
@@ -314,25 +766,31 @@ fn highlight_code<'code>(
     Ok(result)
 }
 
-/// Converts a sequence of highlighted strings to html
-fn highlight_to_html(highlight: &[(Style, &str)]) -> Html {
-    fn to_css_style(style: &Style) -> String {
-        format!(
-            "{underline}{bold}{italic}color: {color}",
+/// Converts a sequence of highlighted strings to html, styled with classes of the given manager
+fn highlight_to_html(highlight: &[(Style, &str)], style_manager: &StyleManager) -> Html {
+    // Classes instead of inline style attributes, which a strict CSP would block
+    let to_css_style = |style: &Style| {
+        let css = css!(
+            r#"
+                text-decoration: ${underline};
+                font-weight: ${bold};
+                font-style: ${italic};
+                color: ${color};
+            "#,
             underline = if style.font_style.contains(FontStyle::UNDERLINE) {
-                "text-decoration: underline;"
+                "underline"
             } else {
-                ""
+                "inherit"
             },
             bold = if style.font_style.contains(FontStyle::BOLD) {
-                "font-weight: bold;"
+                "bold"
             } else {
-                ""
+                "inherit"
             },
             italic = if style.font_style.contains(FontStyle::ITALIC) {
-                "font-style: italic;"
+                "italic"
             } else {
-                ""
+                "inherit"
             },
             color = {
                 let col = style.foreground;
@@ -347,9 +805,10 @@ fn highlight_to_html(highlight: &[(Style, &str)]) -> Html {
                         "".to_owned()
                     }
                 )
-            }
-        )
-    }
+            },
+        );
+        manager_class(style_manager, css)
+    };
     let mut output: Vec<Html> = vec![];
     let mut prev_style: Option<&Style> = None;
     let mut prev_text: Vec<&str> = vec![];
@@ -365,7 +824,7 @@ fn highlight_to_html(highlight: &[(Style, &str)]) -> Html {
             if let Some(style) = prev_style {
                 let css = to_css_style(style);
                 output.push(html! {
-                    <span style={css}>
+                    <span class={css}>
                         {for prev_text.iter()}
                     </span>
                 });
@@ -378,7 +837,7 @@ fn highlight_to_html(highlight: &[(Style, &str)]) -> Html {
     if let Some(style) = prev_style {
         let css = to_css_style(style);
         output.push(html! {
-            <span style={css}>
+            <span class={css}>
                 {for prev_text.iter()}
             </span>
         });
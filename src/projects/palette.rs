@@ -0,0 +1,56 @@
+//! Color palette extraction from images
+
+use std::collections::HashMap;
+
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+/// The width and height images are scaled down to before sampling
+const SAMPLE_SIZE: u32 = 32;
+
+/// The minimum difference between the largest and smallest channel of a sampled pixel, so mostly
+/// gray backgrounds do not dominate
+const MIN_CHROMA: u8 = 48;
+
+/// Returns the dominant color of a loaded image as a css color.
+///
+/// Pixels are grouped into coarse color buckets, the average color of the largest bucket is
+/// returned. Returns `None` if the image contains no colorful pixels or can not be read, e.g.
+/// because it is served from another origin.
+pub fn dominant_color(image: &HtmlImageElement) -> Option<String> {
+    let canvas: HtmlCanvasElement = document().create_element("canvas").ok()?.dyn_into().ok()?;
+    canvas.set_width(SAMPLE_SIZE);
+    canvas.set_height(SAMPLE_SIZE);
+    let context: CanvasRenderingContext2d = canvas.get_context("2d").ok()??.dyn_into().ok()?;
+
+    let size = f64::from(SAMPLE_SIZE);
+    context
+        .draw_image_with_html_image_element_and_dw_and_dh(image, 0.0, 0.0, size, size)
+        .ok()?;
+    let pixels = context.get_image_data(0.0, 0.0, size, size).ok()?.data();
+
+    // Bucket key -> (pixel count, channel sums)
+    let mut buckets: HashMap<(u8, u8, u8), (u32, [u32; 3])> = HashMap::new();
+    for pixel in pixels.chunks_exact(4) {
+        let [r, g, b, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+        let chroma = r.max(g).max(b) - r.min(g).min(b);
+        if a < 128 || chroma < MIN_CHROMA {
+            continue;
+        }
+
+        let (count, sums) = buckets.entry((r >> 5, g >> 5, b >> 5)).or_default();
+        *count += 1;
+        for (sum, channel) in sums.iter_mut().zip([r, g, b]) {
+            *sum += u32::from(channel);
+        }
+    }
+
+    let (count, [r, g, b]) = buckets.into_values().max_by_key(|(count, _)| *count)?;
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        r / count,
+        g / count,
+        b / count
+    ))
+}
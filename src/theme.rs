@@ -1,23 +1,48 @@
 //! Website theming
 
+use gloo::{events::EventListener, timers::callback::Timeout, utils::window};
 use gloo_storage::Storage;
 use std::{
+    cell::{Cell, RefCell},
     collections::HashSet,
-    ops::{Deref, Index},
+    fmt::Display,
+    ops::{Deref, Index, IndexMut},
     rc::Rc,
 };
 use strum::IntoEnumIterator;
-use stylist::{css, yew::use_style};
-use syntect::{highlighting::ThemeSet, parsing::SyntaxSet};
-use web_sys::HtmlSelectElement;
+use stylist::{Style, css, yew::use_style};
+use syntect::{
+    highlighting::ThemeSet,
+    parsing::{SyntaxSet, SyntaxSetBuilder},
+};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlSelectElement, StorageEvent};
 use yew::{
-    Callback, Children, ContextProvider, Html, InputEvent, Properties, TargetCast, UseStateHandle,
-    function_component, hook, html, platform::spawn_local, use_state,
+    AttrValue, Callback, Children, ContextProvider, Html, HtmlResult, InputEvent, Properties,
+    TargetCast, UseStateHandle, classes, function_component, hook, html, platform::spawn_local,
+    use_effect_with, use_memo, use_state,
 };
 use yew_agent::prelude::*;
 
+use crate::toast::use_toasts;
+
 const THEME_STORAGE_KEY: &str = "Theme";
 
+/// The local storage key of the syntax theme chosen independently of the website theme
+const SYNTAX_THEME_STORAGE_KEY: &str = "SyntaxTheme";
+
+/// The media query matching if the user asked the system for a light color scheme
+const LIGHT_SCHEME_QUERY: &str = "(prefers-color-scheme: light)";
+
+/// How long theme switches fade between the colors, in milliseconds
+const THEME_TRANSITION_DURATION: u32 = 300;
+
+/// The class of elements providing their own theme colors, which fade like the root ones
+const THEME_SCOPE_CLASS: &str = "theme-scope";
+
+/// How long to wait for the syntax highlighting webworker before falling back, in milliseconds
+const WORKER_TIMEOUT: u32 = 5000;
+
 /// An enum which can be used to index [`Theme`] colors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThemeColor {
@@ -39,13 +64,83 @@ pub enum ThemeColor {
     Base0F,
 }
 
+impl ThemeColor {
+    /// All colors, in the order of the base16 palette
+    pub const ALL: [ThemeColor; 16] = [
+        ThemeColor::Base00,
+        ThemeColor::Base01,
+        ThemeColor::Base02,
+        ThemeColor::Base03,
+        ThemeColor::Base04,
+        ThemeColor::Base05,
+        ThemeColor::Base06,
+        ThemeColor::Base07,
+        ThemeColor::Base08,
+        ThemeColor::Base09,
+        ThemeColor::Base0A,
+        ThemeColor::Base0B,
+        ThemeColor::Base0C,
+        ThemeColor::Base0D,
+        ThemeColor::Base0E,
+        ThemeColor::Base0F,
+    ];
+
+    /// Returns the name of the css custom property holding this color of the current theme
+    pub const fn property(self) -> &'static str {
+        match self {
+            ThemeColor::Base00 => "--base00",
+            ThemeColor::Base01 => "--base01",
+            ThemeColor::Base02 => "--base02",
+            ThemeColor::Base03 => "--base03",
+            ThemeColor::Base04 => "--base04",
+            ThemeColor::Base05 => "--base05",
+            ThemeColor::Base06 => "--base06",
+            ThemeColor::Base07 => "--base07",
+            ThemeColor::Base08 => "--base08",
+            ThemeColor::Base09 => "--base09",
+            ThemeColor::Base0A => "--base0a",
+            ThemeColor::Base0B => "--base0b",
+            ThemeColor::Base0C => "--base0c",
+            ThemeColor::Base0D => "--base0d",
+            ThemeColor::Base0E => "--base0e",
+            ThemeColor::Base0F => "--base0f",
+        }
+    }
+
+    /// Returns a css value referencing this color of the current theme.
+    ///
+    /// Styles using it follow theme switches without being recomputed.
+    pub const fn var(self) -> &'static str {
+        match self {
+            ThemeColor::Base00 => "var(--base00)",
+            ThemeColor::Base01 => "var(--base01)",
+            ThemeColor::Base02 => "var(--base02)",
+            ThemeColor::Base03 => "var(--base03)",
+            ThemeColor::Base04 => "var(--base04)",
+            ThemeColor::Base05 => "var(--base05)",
+            ThemeColor::Base06 => "var(--base06)",
+            ThemeColor::Base07 => "var(--base07)",
+            ThemeColor::Base08 => "var(--base08)",
+            ThemeColor::Base09 => "var(--base09)",
+            ThemeColor::Base0A => "var(--base0a)",
+            ThemeColor::Base0B => "var(--base0b)",
+            ThemeColor::Base0C => "var(--base0c)",
+            ThemeColor::Base0D => "var(--base0d)",
+            ThemeColor::Base0E => "var(--base0e)",
+            ThemeColor::Base0F => "var(--base0f)",
+        }
+    }
+}
+
 /// A general base 16 theme in combination with a syntax theme
 #[allow(non_snake_case)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Theme {
     name: &'static str,
     author: &'static str,
-    link: &'static str,
+    link: Option<&'static str>,
+    /// Whether the background colors are dark
+    pub dark: bool,
     /// base00 color css code
     pub base00: &'static str,
     /// base01 color css code
@@ -107,86 +202,137 @@ impl Index<ThemeColor> for Theme {
     }
 }
 
-/// The collection of available themes
-#[derive(
-    Debug,
-    Default,
-    Clone,
-    Copy,
-    PartialEq,
-    serde::Serialize,
-    serde::Deserialize,
-    strum::EnumIter,
-    strum::Display,
-)]
-pub enum ThemeKind {
-    #[default]
-    Dark,
-    Light,
+impl Theme {
+    /// Returns css declarations setting the custom properties of all colors, see
+    /// [`ThemeColor::var`]
+    pub fn css_properties(&self) -> String {
+        ThemeColor::ALL
+            .iter()
+            .map(|&color| format!("{}: {};", color.property(), self[color]))
+            .collect()
+    }
 }
 
-impl ThemeKind {
-    /// Returns the current [`Theme`] for this kind
-    pub fn current(self) -> &'static Theme {
-        #[allow(non_snake_case)]
-        const DARK: Theme = Theme {
-            name: "Twilight",
-            author: "David Hart",
-            link: "https://github.com/hartbit/base16-twilight-scheme",
-            base00: "#1e1e1e",
-            base01: "#323537",
-            base02: "#464b50",
-            base03: "#5f5a60",
-            base04: "#838184",
-            base05: "#a7a7a7",
-            base06: "#c3c3c3",
-            base07: "#ffffff",
-            base08: "#cf6a4c",
-            base09: "#cda869",
-            base0A: "#f9ee98",
-            base0B: "#8f9d6a",
-            base0C: "#afc4db",
-            base0D: "#7587a6",
-            base0E: "#9b859d",
-            base0F: "#9b703f",
-            syntax_theme: "base16-eighties.dark",
-        };
+impl IndexMut<ThemeColor> for Theme {
+    fn index_mut(&mut self, index: ThemeColor) -> &mut Self::Output {
+        match index {
+            ThemeColor::Base00 => &mut self.base00,
+            ThemeColor::Base01 => &mut self.base01,
+            ThemeColor::Base02 => &mut self.base02,
+            ThemeColor::Base03 => &mut self.base03,
+            ThemeColor::Base04 => &mut self.base04,
+            ThemeColor::Base05 => &mut self.base05,
+            ThemeColor::Base06 => &mut self.base06,
+            ThemeColor::Base07 => &mut self.base07,
+            ThemeColor::Base08 => &mut self.base08,
+            ThemeColor::Base09 => &mut self.base09,
+            ThemeColor::Base0A => &mut self.base0A,
+            ThemeColor::Base0B => &mut self.base0B,
+            ThemeColor::Base0C => &mut self.base0C,
+            ThemeColor::Base0D => &mut self.base0D,
+            ThemeColor::Base0E => &mut self.base0E,
+            ThemeColor::Base0F => &mut self.base0F,
+        }
+    }
+}
 
-        #[allow(non_snake_case)]
-        const LIGHT: Theme = Theme {
-            name: "Classic Light",
-            author: "Jason Heeris",
-            link: "https://github.com/detly/base16-classic-scheme",
-            base00: "#f5f5f5",
-            base01: "#e0e0e0",
-            base02: "#d0d0d0",
-            base03: "#b0b0b0",
-            base04: "#505050",
-            base05: "#303030",
-            base06: "#202020",
-            base07: "#151515",
-            base08: "#ac4142",
-            base09: "#d28445",
-            base0A: "#f4bf75",
-            base0B: "#90a959",
-            base0C: "#75b5aa",
-            base0D: "#6a9fb5",
-            base0E: "#aa759f",
-            base0F: "#8f5536",
-            syntax_theme: "InspiredGitHub",
-        };
+/// Replaces a color of the current theme with another color of the same theme, e.g. to give a
+/// project page its own accent while keeping the selected theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccentOverride {
+    /// The replaced color
+    pub color: ThemeColor,
+    /// The color used in its place
+    pub with: ThemeColor,
+}
 
-        match self {
-            ThemeKind::Dark => &DARK,
-            ThemeKind::Light => &LIGHT,
+/// Declares the [`ThemeKind`] enum, each variant using the [`Theme`] generated by the build script
+/// from the base16 scheme `assets/themes/<file>.yaml`.
+///
+/// Adding a theme only takes its scheme and a single line here.
+macro_rules! themes {
+    ($($(#[$attr:meta])* $variant:ident => $file:literal),* $(,)?) => {
+        /// The collection of available themes, dark themes first
+        #[derive(
+            Debug,
+            Default,
+            Clone,
+            Copy,
+            PartialEq,
+            serde::Serialize,
+            serde::Deserialize,
+            strum::EnumIter,
+        )]
+        pub enum ThemeKind {
+            $(
+                $(#[$attr])*
+                $variant,
+            )*
+        }
+
+        impl ThemeKind {
+            /// Returns the current [`Theme`] for this kind
+            pub fn current(self) -> &'static Theme {
+                match self {
+                    $(
+                        ThemeKind::$variant => {
+                            const THEME: Theme =
+                                include!(concat!(env!("OUT_DIR"), "/themes/", $file, ".rs"));
+                            &THEME
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+
+themes! {
+    #[default]
+    #[serde(alias = "Dark")]
+    Twilight => "twilight",
+    GruvboxDark => "gruvbox-dark",
+    SolarizedDark => "solarized-dark",
+    CatppuccinMocha => "catppuccin-mocha",
+    Nord => "nord",
+    #[serde(alias = "Light")]
+    ClassicLight => "classic-light",
+    GruvboxLight => "gruvbox-light",
+    SolarizedLight => "solarized-light",
+    CatppuccinLatte => "catppuccin-latte",
+}
+
+impl Display for ThemeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.current().name)
+    }
+}
+
+impl ThemeKind {
+    /// Returns the kind matching the color scheme the user prefers, [`ThemeKind::Twilight`] if
+    /// unknown
+    pub fn preferred() -> Self {
+        let light = window()
+            .match_media(LIGHT_SCHEME_QUERY)
+            .ok()
+            .flatten()
+            .is_some_and(|query| query.matches());
+        if light {
+            Self::ClassicLight
+        } else {
+            Self::Twilight
         }
     }
 
     pub fn credits(self) -> Html {
         html! {
             <p>
-                <h4 style="display: inline;">{self.to_string()}{": "}</h4>
-                <a href={self.current().link}>{self.current().name}</a>
+                <strong>{self.to_string()}{": "}</strong>
+                if let Some(link) = self.current().link {
+                    <a href={link}>{self.current().name}</a>
+                } else {
+                    {self.current().name}
+                }
                 {" by "}
                 {self.current().author}
             </p>
@@ -201,6 +347,10 @@ pub struct ThemeContext {
     inner: UseStateHandle<ThemeKind>,
     /// Global highlight set
     highlight: UseStateHandle<Option<Rc<HighlightSet>>>,
+    /// The name of the syntax theme chosen by the user, if it differs from the website theme
+    syntax: UseStateHandle<Option<AttrValue>>,
+    /// The current theme with accent overrides applied, if there are any
+    accented: Option<Rc<Theme>>,
 }
 
 impl ThemeContext {
@@ -208,8 +358,30 @@ impl ThemeContext {
     pub fn new(
         inner: UseStateHandle<ThemeKind>,
         highlight: UseStateHandle<Option<Rc<HighlightSet>>>,
+        syntax: UseStateHandle<Option<AttrValue>>,
     ) -> Self {
-        Self { inner, highlight }
+        Self {
+            inner,
+            highlight,
+            syntax,
+            accented: None,
+        }
+    }
+
+    /// Returns this context with the given accent overrides applied over the current theme
+    pub fn with_accents(&self, accents: &[AccentOverride]) -> Self {
+        if accents.is_empty() {
+            return self.clone();
+        }
+        let mut theme = (**self).clone();
+        for accent in accents {
+            theme[accent.color] = self[accent.with];
+        }
+
+        Self {
+            accented: Some(Rc::new(theme)),
+            ..self.clone()
+        }
     }
 
     /// Set the current theme
@@ -222,13 +394,44 @@ impl ThemeContext {
     pub fn kind(&self) -> ThemeKind {
         *self.inner
     }
+
+    /// Set the syntax theme used for code, `None` follows the website theme
+    pub fn set_syntax_theme(&self, name: Option<AttrValue>) {
+        match &name {
+            Some(name) => {
+                if let Err(error) =
+                    gloo_storage::LocalStorage::set(SYNTAX_THEME_STORAGE_KEY, name.as_str())
+                {
+                    log::warn!("Failed to store the syntax theme: {error}");
+                }
+            }
+            None => gloo_storage::LocalStorage::delete(SYNTAX_THEME_STORAGE_KEY),
+        }
+        // The theme still applies to this visit if it could not be remembered
+        self.syntax.set(name)
+    }
+
+    /// Returns the name of the syntax theme chosen by the user, `None` if it follows the website
+    /// theme
+    pub fn chosen_syntax_theme(&self) -> Option<&str> {
+        self.syntax.as_deref()
+    }
+
+    /// Returns the name of the syntax theme used for code, if the given highlight set has it
+    pub fn code_theme(&self, highlight_set: &HighlightSet) -> &str {
+        self.chosen_syntax_theme()
+            .filter(|name| highlight_set.themes().themes.contains_key(*name))
+            .unwrap_or(self.syntax_theme)
+    }
 }
 
 impl Deref for ThemeContext {
     type Target = Theme;
 
     fn deref(&self) -> &Self::Target {
-        self.inner.current()
+        self.accented
+            .as_deref()
+            .unwrap_or_else(|| self.inner.current())
     }
 }
 
@@ -238,13 +441,63 @@ pub(crate) struct ThemeProviderProps {
     pub children: Children,
 }
 
-/// A context provider for the [`ThemeContext`]
+/// A context provider for the [`ThemeContext`].
+///
+/// Follows the preferred color scheme of the system until the user selects a theme explicitly, and
+/// the theme selected in other tabs of the website.
 #[function_component(ThemeProvider)]
 pub(crate) fn theme_provider(props: &ThemeProviderProps) -> Html {
-    let theme_kind =
-        use_state(|| gloo_storage::LocalStorage::get(THEME_STORAGE_KEY).unwrap_or_default());
+    let theme_kind = use_state(|| {
+        gloo_storage::LocalStorage::get(THEME_STORAGE_KEY)
+            .unwrap_or_else(|_| ThemeKind::preferred())
+    });
     let highlight = use_state(|| None);
-    let theme_ctx = ThemeContext::new(theme_kind, highlight);
+    let syntax = use_state(|| {
+        gloo_storage::LocalStorage::get::<String>(SYNTAX_THEME_STORAGE_KEY)
+            .ok()
+            .map(AttrValue::from)
+    });
+
+    use_effect_with((), {
+        let theme_kind = theme_kind.setter();
+        move |_| {
+            let listener = window()
+                .match_media(LIGHT_SCHEME_QUERY)
+                .ok()
+                .flatten()
+                .map(|query| {
+                    EventListener::new(&query, "change", move |_| {
+                        if gloo_storage::LocalStorage::get::<ThemeKind>(THEME_STORAGE_KEY).is_err()
+                        {
+                            theme_kind.set(ThemeKind::preferred());
+                        }
+                    })
+                });
+
+            move || drop(listener)
+        }
+    });
+    // Other tabs store the theme the user selected there, which is adopted here as well
+    use_effect_with((), {
+        let theme_kind = theme_kind.setter();
+        move |_| {
+            let listener = EventListener::new(&window(), "storage", move |event| {
+                let Some(event) = event.dyn_ref::<StorageEvent>() else {
+                    return;
+                };
+                // A missing key means the whole storage was cleared
+                if event.key().is_none_or(|key| key == THEME_STORAGE_KEY) {
+                    theme_kind.set(
+                        gloo_storage::LocalStorage::get(THEME_STORAGE_KEY)
+                            .unwrap_or_else(|_| ThemeKind::preferred()),
+                    );
+                }
+            });
+
+            move || drop(listener)
+        }
+    });
+    let theme_ctx = ThemeContext::new(theme_kind, highlight, syntax);
 
     html! {
         <ContextProvider<ThemeContext> context={theme_ctx}>
@@ -253,6 +506,143 @@ pub(crate) fn theme_provider(props: &ThemeProviderProps) -> Html {
     }
 }
 
+/// Properties for the [`ThemeScope`]
+#[derive(Debug, PartialEq, Properties)]
+pub struct ThemeScopeProps {
+    /// The theme provided to the children
+    pub kind: ThemeKind,
+    pub children: Children,
+}
+
+/// Provides a fixed theme to its children, independent of the selected one.
+///
+/// The kind is read when mounting, key the scope by its kind to change it.
+#[function_component(ThemeScope)]
+pub fn theme_scope(props: &ThemeScopeProps) -> Html {
+    let parent = use_theme();
+    let theme_kind = use_state(|| props.kind);
+    let theme_ctx = ThemeContext::new(theme_kind, parent.highlight.clone(), parent.syntax.clone());
+    let style = use_scope_style(&theme_ctx);
+
+    html! {
+        <ContextProvider<ThemeContext> context={theme_ctx}>
+            <div class={classes!(THEME_SCOPE_CLASS, style)}>
+                {props.children.clone()}
+            </div>
+        </ContextProvider<ThemeContext>>
+    }
+}
+
+/// Properties for the [`ThemeAccent`]
+#[derive(Debug, PartialEq, Properties)]
+pub struct ThemeAccentProps {
+    /// The overrides applied over the current theme
+    pub accents: &'static [AccentOverride],
+    pub children: Children,
+}
+
+/// Provides the current theme with accent overrides applied to its children
+#[function_component(ThemeAccent)]
+pub fn theme_accent(props: &ThemeAccentProps) -> Html {
+    let theme_ctx = use_theme().with_accents(props.accents);
+    let style = use_scope_style(&theme_ctx);
+
+    html! {
+        <ContextProvider<ThemeContext> context={theme_ctx}>
+            <div class={classes!(THEME_SCOPE_CLASS, style)}>
+                {props.children.clone()}
+            </div>
+        </ContextProvider<ThemeContext>>
+    }
+}
+
+/// A hook creating the style of a theme scope, providing the colors of the given theme as css
+/// custom properties to its children without a box of its own
+#[hook]
+fn use_scope_style(theme: &Theme) -> Style {
+    use_style!(
+        r#"
+            display: contents;
+            --base00: ${base00};
+            --base01: ${base01};
+            --base02: ${base02};
+            --base03: ${base03};
+            --base04: ${base04};
+            --base05: ${base05};
+            --base06: ${base06};
+            --base07: ${base07};
+            --base08: ${base08};
+            --base09: ${base09};
+            --base0a: ${base0a};
+            --base0b: ${base0b};
+            --base0c: ${base0c};
+            --base0d: ${base0d};
+            --base0e: ${base0e};
+            --base0f: ${base0f};
+        "#,
+        base00 = theme.base00,
+        base01 = theme.base01,
+        base02 = theme.base02,
+        base03 = theme.base03,
+        base04 = theme.base04,
+        base05 = theme.base05,
+        base06 = theme.base06,
+        base07 = theme.base07,
+        base08 = theme.base08,
+        base09 = theme.base09,
+        base0a = theme.base0A,
+        base0b = theme.base0B,
+        base0c = theme.base0C,
+        base0d = theme.base0D,
+        base0e = theme.base0E,
+        base0f = theme.base0F,
+    )
+}
+
+/// Provides the colors of the current theme as css custom properties on the document root, see
+/// [`ThemeColor::var`].
+///
+/// The properties are registered as colors, so switching themes fades between them instead of
+/// snapping, unless the user prefers reduced motion.
+#[function_component(ThemeProperties)]
+pub fn theme_properties() -> Html {
+    let theme = use_theme();
+    // The registrations and transitions are the same for every theme
+    let rules = use_memo((), |_| {
+        let registrations: String = ThemeColor::ALL
+            .iter()
+            .map(|color| {
+                format!(
+                    "@property {} {{ syntax: '<color>'; inherits: true; initial-value: transparent; }}\n",
+                    color.property(),
+                )
+            })
+            .collect();
+        let transitions = ThemeColor::ALL
+            .iter()
+            .map(|color| format!("{} {THEME_TRANSITION_DURATION}ms ease", color.property()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "{registrations}\
+            :root, .{THEME_SCOPE_CLASS} {{ transition: {transitions}; }}\n\
+            @media (prefers-reduced-motion: reduce) {{ \
+                :root, .{THEME_SCOPE_CLASS} {{ transition: none; }} \
+            }}"
+        )
+    });
+
+    // Plain style elements, since stylist does not know about registered properties
+    let nonce = crate::csp::host_nonce();
+    html! {
+        <>
+            <style nonce={nonce.clone()}>{(*rules).clone()}</style>
+            <style {nonce}>{format!(":root {{ {} }}", theme.css_properties())}</style>
+        </>
+    }
+}
+
 /// A convenient hook for accessing the current theme context.
 ///
 /// # Panics
@@ -289,7 +679,7 @@ pub fn theme_selector() -> Html {
                 background-color: ${bg_hover};
             }
 
-            select option {
+            select option, select optgroup {
                 background-color: ${bg};
                 border: none;
             }
@@ -309,33 +699,43 @@ pub fn theme_selector() -> Html {
                 margin: 0px 5px;
             }
         "#,
-        fg = theme.base00,
-        bg = theme.base0D,
-        bg_hover = theme.base0C,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
     );
-    let themes = ThemeKind::iter().map(|kind| {
+    // The themes in the order they are listed, dark themes first
+    let (dark, light): (Vec<_>, Vec<_>) = ThemeKind::iter().partition(|kind| kind.current().dark);
+    let group = |label: &'static str, kinds: &[ThemeKind]| {
         html! {
-            <option selected={kind == theme.kind()}>
-                {kind.to_string()}
-            </option>
+            <optgroup {label}>
+                {for kinds.iter().map(|&kind| html! {
+                    <option selected={kind == theme.kind()}>
+                        {kind.to_string()}
+                    </option>
+                })}
+            </optgroup>
         }
-    });
+    };
+    let themes = html! {
+        <>
+            {group("Dark", &dark)}
+            {group("Light", &light)}
+        </>
+    };
     let on_input = Callback::from({
         let theme = theme.clone();
+        let kinds: Vec<_> = dark.iter().chain(&light).copied().collect();
         move |event: InputEvent| {
-            theme.set(
-                ThemeKind::iter()
-                    .nth(
-                        usize::try_from(
-                            event
-                                .target_dyn_into::<HtmlSelectElement>()
-                                .unwrap()
-                                .selected_index(),
-                        )
-                        .unwrap(),
-                    )
-                    .unwrap(),
-            );
+            let index = event
+                .target_dyn_into::<HtmlSelectElement>()
+                .unwrap()
+                .selected_index();
+            if let Some(&kind) = usize::try_from(index)
+                .ok()
+                .and_then(|index| kinds.get(index))
+            {
+                theme.set(kind);
+            }
         }
     });
     html! {
@@ -344,12 +744,67 @@ pub fn theme_selector() -> Html {
                 <i class="iconoir-brightness"/>
             </div>
             <select oninput={on_input}>
-                {for themes}
+                {themes}
             </select>
         </div>
     }
 }
 
+/// A selection of the syntax theme used for code, independent of the website theme.
+///
+/// Suspends until the [`HighlightSet`] is loaded.
+#[function_component(SyntaxThemeSelector)]
+pub fn syntax_theme_selector() -> HtmlResult {
+    let theme = use_theme();
+    let highlight_set = use_highlight_set()?;
+    let style = use_style!(
+        r#"
+            height: 30px;
+            margin: 10px 0px;
+        "#
+    );
+    // The first option follows the website theme
+    let names: Vec<AttrValue> = highlight_set
+        .themes()
+        .themes
+        .keys()
+        .map(|name| AttrValue::from(name.clone()))
+        .collect();
+    let options: Html = names
+        .iter()
+        .map(|name| {
+            html! {
+                <option selected={theme.chosen_syntax_theme() == Some(name.as_str())}>
+                    {name}
+                </option>
+            }
+        })
+        .collect();
+    let on_input = Callback::from({
+        let theme = theme.clone();
+        move |event: InputEvent| {
+            let index = event
+                .target_dyn_into::<HtmlSelectElement>()
+                .unwrap()
+                .selected_index();
+            let name = usize::try_from(index)
+                .ok()
+                .and_then(|index| index.checked_sub(1))
+                .and_then(|index| names.get(index).cloned());
+            theme.set_syntax_theme(name);
+        }
+    });
+
+    Ok(html! {
+        <select class={style} title="Syntax theme used for code" oninput={on_input}>
+            <option selected={theme.chosen_syntax_theme().is_none()}>
+                {format!("Match the website theme ({})", theme.syntax_theme)}
+            </option>
+            {options}
+        </select>
+    })
+}
+
 /// The syntax theme
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct HighlightSet {
@@ -383,13 +838,35 @@ impl HighlightSet {
     }
 }
 
+impl HighlightSet {
+    /// A minimal set loaded on the main thread, in case the webworker is unavailable.
+    ///
+    /// Contains all themes but only the plain text syntax, so code is shown without highlighting.
+    fn fallback() -> Self {
+        let mut syntaxes = SyntaxSetBuilder::new();
+        syntaxes.add_plain_text_syntax();
+
+        HighlightSet {
+            syntaxes: syntaxes.build(),
+            themes: ThemeSet::load_defaults(),
+        }
+    }
+}
+
+thread_local! {
+    /// Whether the user was already warned about the webworker failing to load
+    static WORKER_FAILURE_SHOWN: Cell<bool> = const { Cell::new(false) };
+}
+
 /// A hook for accessing the current SyntaxTheme.
 ///
 /// This hook leverages a webworker to load the available syntaxes asynchronously. This load time is
-/// only expected when first calling this hook.
+/// only expected when first calling this hook. If the webworker does not respond in time, a
+/// [fallback](HighlightSet::fallback) is used until it does.
 #[hook]
 pub fn use_highlight_set() -> yew::suspense::SuspensionResult<Rc<HighlightSet>> {
     let theme = use_theme();
+    let toasts = use_toasts();
     let load_task = use_oneshot_runner::<LoadSyntaxTheme>();
 
     // If the theme is already loaded, return a reference
@@ -398,13 +875,36 @@ pub fn use_highlight_set() -> yew::suspense::SuspensionResult<Rc<HighlightSet>>
     }
 
     let (s, handle) = yew::suspense::Suspension::new();
+    // Resumed by whichever finishes first, the webworker or the fallback
+    let handle = Rc::new(RefCell::new(Some(handle)));
 
     // Otherwise load the theme
-    spawn_local(async move {
-        let loaded = load_task.run(()).await;
-        theme.highlight.set(Some(Rc::new(loaded)));
-        handle.resume();
+    spawn_local({
+        let highlight = theme.highlight.clone();
+        let handle = handle.clone();
+        async move {
+            let loaded = load_task.run(()).await;
+            // Also replaces the fallback, if the webworker was just slow
+            highlight.set(Some(Rc::new(loaded)));
+            if let Some(handle) = handle.take() {
+                handle.resume();
+            }
+        }
     });
+    Timeout::new(WORKER_TIMEOUT, move || {
+        let Some(handle) = handle.take() else {
+            return;
+        };
+        if !WORKER_FAILURE_SHOWN.replace(true) {
+            log::warn!("The syntax highlighting webworker did not respond, using a fallback");
+            if let Some(toasts) = toasts {
+                toasts.show("Syntax highlighting is unavailable, code is shown as plain text.");
+            }
+        }
+        theme.highlight.set(Some(Rc::new(HighlightSet::fallback())));
+        handle.resume();
+    })
+    .forget();
 
     Err(s)
 }
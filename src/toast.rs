@@ -0,0 +1,171 @@
+//! Short, non-blocking notifications
+
+use std::rc::Rc;
+
+use gloo::timers::callback::Timeout;
+use stylist::yew::use_style;
+use yew::{
+    AttrValue, Callback, Children, ContextProvider, Html, Properties, Reducible, UseReducerHandle,
+    function_component, hook, html, use_context, use_effect_with, use_reducer_eq,
+};
+
+use crate::theme::ThemeColor;
+
+/// How long a toast is shown before it is dismissed automatically, in milliseconds
+const TOAST_DURATION: u32 = 8000;
+
+/// The toasts currently shown
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Toasts {
+    /// The id given to the next toast
+    next_id: usize,
+    /// The shown toasts (id, message), oldest first
+    toasts: Vec<(usize, AttrValue)>,
+}
+
+/// Actions for modifying the shown [`Toasts`]
+pub enum ToastAction {
+    /// Show a new toast with the given message
+    Show(AttrValue),
+    /// Dismiss the toast with the given id
+    Dismiss(usize),
+}
+
+impl Reducible for Toasts {
+    type Action = ToastAction;
+
+    fn reduce(mut self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let this = Rc::make_mut(&mut self);
+
+        match action {
+            ToastAction::Show(message) => {
+                this.toasts.push((this.next_id, message));
+                this.next_id += 1;
+            }
+            ToastAction::Dismiss(id) => this.toasts.retain(|(toast_id, _)| *toast_id != id),
+        }
+
+        self
+    }
+}
+
+/// A context used to show toasts from anywhere on the website
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastContext {
+    /// The shown toasts
+    inner: UseReducerHandle<Toasts>,
+}
+
+impl ToastContext {
+    /// Show a toast with the given message
+    pub fn show(&self, message: impl Into<AttrValue>) {
+        self.inner.dispatch(ToastAction::Show(message.into()))
+    }
+}
+
+/// Properties for the [`ToastProvider`]
+#[derive(Debug, PartialEq, Properties)]
+pub(crate) struct ToastProviderProps {
+    pub children: Children,
+}
+
+/// A context provider for the [`ToastContext`], also displaying the toasts
+#[function_component(ToastProvider)]
+pub(crate) fn toast_provider(props: &ToastProviderProps) -> Html {
+    let style = use_style!(
+        r#"
+            position: fixed;
+            bottom: 20px;
+            right: 20px;
+            z-index: 2;
+            display: flex;
+            flex-direction: column;
+            row-gap: 10px;
+        "#
+    );
+    let toasts = use_reducer_eq(Toasts::default);
+
+    let items = toasts.toasts.iter().map(|(id, message)| {
+        let dismiss = Callback::from({
+            let toasts = toasts.clone();
+            let id = *id;
+            move |_| toasts.dispatch(ToastAction::Dismiss(id))
+        });
+        html! {
+            <ToastItem key={*id} message={message.clone()} {dismiss}/>
+        }
+    });
+    let toast_ctx = ToastContext {
+        inner: toasts.clone(),
+    };
+
+    html! {
+        <ContextProvider<ToastContext> context={toast_ctx}>
+            {props.children.clone()}
+            <div class={style} role="status">
+                {for items}
+            </div>
+        </ContextProvider<ToastContext>>
+    }
+}
+
+/// Properties for the [`ToastItem`] component
+#[derive(Debug, PartialEq, Properties)]
+struct ToastItemProperties {
+    /// The shown message
+    message: AttrValue,
+    /// Dismisses the toast
+    dismiss: Callback<()>,
+}
+
+/// A single toast, dismissed by its button or after a while
+#[function_component(ToastItem)]
+fn toast_item(ToastItemProperties { message, dismiss }: &ToastItemProperties) -> Html {
+    let style = use_style!(
+        r#"
+            display: flex;
+            align-items: center;
+            column-gap: 10px;
+            max-width: 400px;
+            padding: 10px 15px;
+            color: ${fg};
+            background-color: ${bg};
+            border-left: 5px solid ${accent};
+
+            button {
+                font-size: 20px;
+                color: ${fg};
+                background-color: transparent;
+                border: none;
+                cursor: pointer;
+            }
+        "#,
+        fg = ThemeColor::Base06.var(),
+        bg = ThemeColor::Base03.var(),
+        accent = ThemeColor::Base0A.var(),
+    );
+
+    // Every dismiss callback of this toast does the same, so the first one can be kept
+    use_effect_with((), {
+        let dismiss = dismiss.clone();
+        move |_| {
+            let timeout = Timeout::new(TOAST_DURATION, move || dismiss.emit(()));
+            move || drop(timeout)
+        }
+    });
+
+    html! {
+        <div class={style}>
+            <span>{message}</span>
+            <button title="Dismiss" onclick={dismiss.reform(|_| ())}>
+                <i class="iconoir-xmark"/>
+            </button>
+        </div>
+    }
+}
+
+/// A convenient hook for accessing the toast context, if it has been provided
+#[hook]
+pub fn use_toasts() -> Option<ToastContext> {
+    use_context::<ToastContext>()
+}
@@ -5,56 +5,201 @@ use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::Mutex;
 
-use gloo::utils::window;
-use stylist::css;
+use color::Srgb;
+use gloo::events::{EventListener, EventListenerOptions};
+use gloo::utils::{document, window};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use stylist::{css, manager::StyleManager};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
+use web_sys::Element;
 use web_sys::HtmlCanvasElement;
+use web_sys::WebGlContextAttributes;
+use web_sys::WebGlPowerPreference;
 use web_sys::WebGlRenderingContext as GL;
+use web_sys::WheelEvent;
+use yew::context::ContextHandle;
 use yew::html;
 use yew::prelude::*;
 
+use crate::{
+    csp::manager_class,
+    theme::{Theme, ThemeColor, ThemeContext},
+    webgl::{
+        CanvasScheduler, FrameStats, GpuTimer, ReplayController, SeededRng, WebglError, get_context,
+    },
+};
+
+/// The maximum amount of fixed timestep steps per frame, so slow frames do not pile up more work
+const MAX_SUBSTEPS: u32 = 8;
+
+/// The highest supported supersampling factor, the drawing buffer is further limited to the
+/// maximum renderbuffer and texture size of the context
+const MAX_SUPERSAMPLING: u32 = 4;
+
+/// The pixels scrolled per line, for wheel events measured in lines
+const WHEEL_LINE_HEIGHT: f64 = 16.0;
+
+/// Renderer changes closer together than this many milliseconds are likely recreated renderers
+#[cfg(debug_assertions)]
+const RENDERER_CHURN_INTERVAL: f64 = 1000.0;
+
 /// The state of the rendering loop
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RenderLoopState {
     /// Currently rendering each frame
     Rendering,
-    /// Not rendering
+    /// Not rendering, apart from the first frame
     Paused,
+    /// Rendering a single frame, then pausing. The value tells consecutive requests apart, so each
+    /// one is a change of the property
+    StepOnce(u32),
     /// About to terminate the loop
     Finished,
 }
 
 /// Data about the last mouse state
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct MouseData {
     /// Whether mouse button 1 is down
     pub primary_button: bool,
     /// Whether mouse button 2 is down
     pub secondary_button: bool,
-    /// The mouse position relative to this canvas (None if not on the canvas)
+    /// The mouse position relative to this canvas in logical (css) pixels (None if not on the
+    /// canvas)
     pub position: Option<(u32, u32)>,
+    /// The horizontal and vertical scroll wheel movement in pixels since the last frame, only
+    /// reported with [`CanvasProperties::capture_wheel`]
+    pub wheel_delta: (i32, i32),
+    /// The horizontal and vertical mouse movement in pixels since the last frame, also reported
+    /// while the pointer is locked
+    pub movement: (i32, i32),
+    /// Whether the pointer is locked to this canvas, see [`CanvasProperties::pointer_lock`]
+    pub pointer_locked: bool,
 }
 
 /// Some additional rendering data
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct RenderData {
     /// Whether it's the initial render
     pub initial_render: bool,
-    /// The width of the canvas
+    /// The width of the canvas drawing buffer in physical pixels
     pub width: u32,
-    /// The height of the canvas
+    /// The height of the canvas drawing buffer in physical pixels
     pub height: u32,
+    /// The width of the canvas in logical (css) pixels
+    pub logical_width: u32,
+    /// The height of the canvas in logical (css) pixels
+    pub logical_height: u32,
     /// If the canvas was resized before this frame
     pub resized: bool,
     /// Whether any render input changed
     pub input_changed: bool,
-    /// The amount of milliseconds that passed since the beginning of rendering
-    pub time: u32,
-    /// The amount of milliseconds that passed since the last frame
-    pub delta_time: u32,
+    /// The amount of milliseconds that passed since the beginning of rendering, with sub
+    /// millisecond precision
+    pub time: f64,
+    /// The amount of milliseconds that passed since the last frame, with sub millisecond precision
+    pub delta_time: f64,
     /// Info about the mouse
     pub mouse_data: MouseData,
+    /// The seed for all randomness, see [`SeededRng`](crate::webgl::SeededRng)
+    pub seed: u32,
+    /// The amount of milliseconds CPU-side work may take this frame, see
+    /// [`ChunkedTask`](crate::webgl::ChunkedTask)
+    pub cpu_budget: u32,
+    /// The colors of the website theme, see [`RenderColors`]
+    pub colors: RenderColors,
+    /// Whether low power mode is enabled, renderers should then reduce their expensive work. The
+    /// render state is created again whenever it changes.
+    pub low_power: bool,
+    /// The progress of loading work spread over frames, between 0 and 1, as reported by
+    /// [`CanvasRenderer::loading_progress`] after the last frame. `None` once everything is loaded.
+    pub loading_progress: Option<f32>,
+}
+
+impl RenderData {
+    /// Returns the amount of seconds that passed since the beginning of rendering
+    pub fn seconds(&self) -> f64 {
+        self.time / 1000.0
+    }
+
+    /// Returns the amount of seconds that passed since the last frame
+    pub fn delta_seconds(&self) -> f64 {
+        self.delta_time / 1000.0
+    }
+}
+
+/// The colors of the website theme, so renderers can draw in a consistent way
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RenderColors {
+    /// The RGBA background color, e.g. used to clear the canvas
+    pub background: [f32; 4],
+    /// The RGBA foreground color, e.g. used for drawings without a color of their own
+    pub foreground: [f32; 4],
+}
+
+impl RenderColors {
+    /// Returns the background and foreground colors of the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        let parse = |color: ThemeColor| {
+            color::parse_color(theme[color])
+                .expect("Theme colors are valid css colors")
+                .to_alpha_color::<Srgb>()
+                .components
+        };
+
+        Self {
+            background: parse(ThemeColor::Base00),
+            foreground: parse(ThemeColor::Base05),
+        }
+    }
+}
+
+impl Default for RenderColors {
+    /// Black and white, as canvases were drawn before following the theme
+    fn default() -> Self {
+        Self {
+            background: [0.0, 0.0, 0.0, 1.0],
+            foreground: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+/// The attributes the webgl context of a [Canvas] is created with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextOptions {
+    /// Whether the drawing buffer is kept after presenting, so the canvas contents can be exported
+    /// at any time
+    pub preserve_drawing_buffer: bool,
+    /// Whether the drawing buffer has an alpha channel, blending the canvas with the page
+    pub alpha: bool,
+    /// Whether the drawing buffer is antialiased
+    pub antialias: bool,
+    /// Which GPU the browser should prefer on devices with several
+    pub power_preference: WebGlPowerPreference,
+}
+
+impl Default for ContextOptions {
+    fn default() -> Self {
+        Self {
+            preserve_drawing_buffer: true,
+            alpha: true,
+            antialias: true,
+            power_preference: WebGlPowerPreference::Default,
+        }
+    }
+}
+
+impl ContextOptions {
+    /// Returns the context attributes to request
+    fn attributes(&self) -> WebGlContextAttributes {
+        let attributes = WebGlContextAttributes::new();
+        attributes.set_preserve_drawing_buffer(self.preserve_drawing_buffer);
+        attributes.set_alpha(self.alpha);
+        attributes.set_antialias(self.antialias);
+        attributes.set_power_preference(self.power_preference);
+        attributes
+    }
 }
 
 /// A trait for rendering on a [Canvas]
@@ -62,7 +207,42 @@ pub trait CanvasRenderer: Clone + PartialEq + 'static {
     /// Internal state that can be modified each render
     type RenderState: 'static;
     /// External input that can not be modified from within the renderer
-    type RenderInput: Clone + PartialEq + 'static;
+    type RenderInput: Clone + PartialEq + Serialize + DeserializeOwned + 'static;
+    /// Values reported back to the page, e.g. for displaying live data. `()` for renderers without
+    /// any output.
+    type RenderOutput: Clone + PartialEq + 'static;
+
+    /// Called before stepping and rendering if the drawing buffer was resized, including the first
+    /// frame after the render state was created
+    fn on_resize(&self, _state: &mut Self::RenderState, _gl: &GL, _render_data: RenderData) {}
+
+    /// Called before stepping and rendering if the render input changed, including the first
+    /// frame after the render state was created
+    fn on_input_changed(
+        &self,
+        _state: &mut Self::RenderState,
+        _input: &Self::RenderInput,
+        _gl: &GL,
+        _render_data: RenderData,
+    ) {
+    }
+
+    /// Called when the render state is dropped while the context is still valid, e.g. when the
+    /// [Canvas] unmounts or the renderer changes, to delete the gl resources of the state
+    fn on_destroy(&self, _state: Self::RenderState, _gl: &GL) {}
+
+    /// Advance the simulation of the renderer by one step, before rendering.
+    ///
+    /// Called once per frame, or as often as the [fixed timestep](CanvasProperties::fixed_timestep)
+    /// fits into the elapsed time, with [`RenderData::delta_time`] set to the timestep.
+    fn step(
+        &self,
+        _state: &mut Self::RenderState,
+        _input: &Self::RenderInput,
+        _gl: &GL,
+        _render_data: RenderData,
+    ) {
+    }
 
     /// Called every frame to render to the [Canvas]
     fn render(
@@ -73,17 +253,52 @@ pub trait CanvasRenderer: Clone + PartialEq + 'static {
         render_data: RenderData,
     );
 
-    /// Create the initial render state
+    /// Create the initial render state.
+    ///
+    /// On failure, the [Canvas] pauses and shows the error instead, trying again once its
+    /// properties or the provided contexts change.
     fn initial_render_state(
         &self,
         input: &Self::RenderInput,
         gl: &GL,
         render_data: RenderData,
-    ) -> Self::RenderState;
+    ) -> Result<Self::RenderState, WebglError>;
+
+    /// Returns the progress of loading work spread over frames, between 0 and 1, e.g. of a
+    /// [`ChunkedTask`](crate::webgl::ChunkedTask). Called after each frame, the placeholder shows
+    /// the progress until `None` is returned.
+    fn loading_progress(&self, _state: &Self::RenderState) -> Option<f32> {
+        None
+    }
+
+    /// Returns the current output, called after each frame. The output is passed to
+    /// [`CanvasProperties::on_output`] whenever it changes.
+    fn output(&self, _state: &Self::RenderState) -> Option<Self::RenderOutput> {
+        None
+    }
+
+    /// The timer measuring the render passes, if the renderer supports it
+    fn gpu_timer(&self) -> Option<&GpuTimer> {
+        None
+    }
+}
+
+/// A hook keeping a renderer stable across re-renders.
+///
+/// The [`Canvas`] rebuilds its whole render state whenever the renderer prop changes, so pages
+/// should create their renderers through this hook instead of constructing a new value each
+/// render.
+#[hook]
+pub fn use_renderer<R, F>(init: F) -> R
+where
+    R: CanvasRenderer,
+    F: FnOnce() -> R,
+{
+    (*use_memo((), |_| init())).clone()
 }
 
 /// Properties for use in [Html]
-#[derive(Debug, Properties, PartialEq)]
+#[derive(Debug, Clone, Properties, PartialEq)]
 pub struct CanvasProperties<R>
 where
     R: CanvasRenderer + PartialEq,
@@ -105,6 +320,130 @@ where
     /// The render loop state
     #[prop_or(RenderLoopState::Rendering)]
     pub render_loop_state: RenderLoopState,
+    /// The highest device pixel ratio the drawing buffer is scaled by, limiting the cost of
+    /// rendering on high density screens
+    #[prop_or(2.0)]
+    pub max_pixel_ratio: f64,
+    /// The factor the drawing buffer is enlarged by on top of the pixel ratio, so the browser
+    /// scales a supersampled image down onto the page, smoothing aliased edges
+    #[prop_or(1)]
+    pub supersampling: u32,
+    /// Whether rendering pauses while the page is hidden, disable for simulations that should
+    /// keep running in the background
+    #[prop_or(true)]
+    pub pause_when_hidden: bool,
+    /// The controller used to record and play back the render loop
+    #[prop_or_default]
+    pub replay: ReplayController<R::RenderInput>,
+    /// Collects the frame times of the render loop
+    #[prop_or_default]
+    pub frame_stats: FrameStats,
+    /// The milliseconds [`CanvasRenderer::step`] advances the simulation by, so it runs at the
+    /// same speed regardless of the frame rate. `None` steps once per frame.
+    #[prop_or_default]
+    pub fixed_timestep: Option<u32>,
+    /// The maximum amount of frames rendered per second, in addition to the limit of the
+    /// [CanvasScheduler]
+    #[prop_or_default]
+    pub max_fps: Option<u32>,
+    /// Whether clicking the canvas locks the pointer to it, hiding the cursor and reporting only
+    /// the [mouse movement](MouseData::movement), e.g. for first-person camera controls
+    #[prop_or_default]
+    pub pointer_lock: bool,
+    /// Whether the scroll wheel over the canvas is reported as [wheel
+    /// movement](MouseData::wheel_delta) instead of scrolling the page, e.g. for zooming
+    #[prop_or_default]
+    pub capture_wheel: bool,
+    /// The attributes of the webgl context. A canvas keeps its context, so changes only apply to
+    /// newly created canvases.
+    #[prop_or_default]
+    pub context_options: ContextOptions,
+    /// Changing this value drops the render state, so the renderer starts over with a new seed
+    #[prop_or_default]
+    pub reset: u32,
+    /// Called after a frame if the [output](CanvasRenderer::output) of the renderer changed
+    #[prop_or_default]
+    pub on_output: Callback<R::RenderOutput>,
+}
+
+#[cfg(debug_assertions)]
+impl<R: CanvasRenderer> crate::dev::PropDiff for CanvasProperties<R> {
+    fn changed_props(&self, old: &Self) -> Vec<&'static str> {
+        crate::changed_props!(
+            self,
+            old,
+            [
+                canvas_node_ref,
+                renderer,
+                render_input,
+                width,
+                height,
+                render_loop_state,
+                max_pixel_ratio,
+                supersampling,
+                pause_when_hidden,
+                replay,
+                frame_stats,
+                fixed_timestep,
+                max_fps,
+                pointer_lock,
+                capture_wheel,
+                context_options,
+                reset,
+                on_output,
+            ]
+        )
+    }
+}
+
+/// Messages for the [Canvas] component
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasMessage {
+    /// The provided [CanvasScheduler] changed
+    SchedulerChanged(CanvasScheduler),
+    /// The website theme changed
+    ThemeChanged(ThemeContext),
+    /// The render state could not be created, so the render loop stopped
+    RenderError(WebglError),
+    /// The render state started or finished being created
+    Loading(bool),
+    /// The loading progress reported by the renderer changed
+    LoadingProgress(Option<f32>),
+    /// The lost webgl context was restored
+    ContextRestored,
+    /// A shader of the renderer failed to compile or link
+    #[cfg(debug_assertions)]
+    ShaderError(AttrValue),
+}
+
+#[cfg(debug_assertions)]
+thread_local! {
+    /// The canvases showing shader errors, with the callback displaying them
+    static SHADER_ERROR_LISTENERS: RefCell<Vec<(NodeRef, Callback<AttrValue>)>> =
+        RefCell::default();
+}
+
+/// Show a shader error on the canvas of the given context, if it is a mounted [Canvas].
+///
+/// The canvas is updated right away, as the renderer usually panics right after the error.
+#[cfg(debug_assertions)]
+pub(crate) fn report_shader_error(gl: &GL, error: String) {
+    let Some(canvas) = gl.canvas() else {
+        return;
+    };
+    let listener = SHADER_ERROR_LISTENERS.with_borrow(|listeners| {
+        listeners
+            .iter()
+            .find(|(node_ref, _)| {
+                node_ref
+                    .get()
+                    .is_some_and(|node| JsValue::from(node) == JsValue::from(&canvas))
+            })
+            .map(|(_, callback)| callback.clone())
+    });
+    if let Some(listener) = listener {
+        listener.emit(error.into());
+    }
 }
 
 /// A Canvas used for rendering with WebGL
@@ -118,6 +457,29 @@ where
     canvas_render_state: Arc<Mutex<CanvasRenderState<R>>>,
     /// Whether to initiate the gl render loop on the next render
     initiate_render_loop: bool,
+    /// Keeps the scheduler context subscription alive
+    _scheduler_handle: Option<ContextHandle<CanvasScheduler>>,
+    /// Keeps the theme context subscription alive
+    _theme_handle: Option<ContextHandle<ThemeContext>>,
+    /// The style manager provided to the website, mounting the canvas styles
+    style_manager: StyleManager,
+    /// The error that stopped the render loop
+    error: Option<WebglError>,
+    /// Whether the render state is being created, showing a placeholder
+    loading: bool,
+    /// The loading progress reported by the renderer, showing a placeholder until it is `None`
+    loading_progress: Option<f32>,
+    /// Listeners for the loss and restoration of the webgl context
+    _context_listeners: Option<[EventListener; 2]>,
+    /// Listener for scroll wheel events on the canvas
+    _wheel_listener: Option<EventListener>,
+    /// Listener for the page being hidden or shown again
+    _visibility_listener: EventListener,
+    /// Listener for the pointer being locked or released
+    _pointer_lock_listener: EventListener,
+    /// The last shader error of the renderer
+    #[cfg(debug_assertions)]
+    shader_error: Option<AttrValue>,
 }
 
 /// Internal rendering state
@@ -129,33 +491,122 @@ where
     renderer: R,
     /// The render state
     render_state: Option<R::RenderState>,
+    /// The context the render state belongs to, once the render loop was initiated
+    gl: Option<GL>,
     /// The render input
     render_input: R::RenderInput,
     /// Whether the render input changed last frame
     render_input_changed: bool,
     /// The render loop state
     render_loop_state: RenderLoopState,
+    /// Whether a render loop is requesting frames
+    running: bool,
+    /// Whether the render loop was suspended by an error or the loss of the context. It pauses
+    /// and stops requesting frames, until the component restarts it.
+    suspended: bool,
+    /// The highest device pixel ratio the drawing buffer is scaled by
+    max_pixel_ratio: f64,
+    /// The factor the drawing buffer is enlarged by on top of the pixel ratio
+    supersampling: u32,
+    /// Whether the webgl context was lost and not restored yet
+    context_lost: bool,
+    /// Whether rendering pauses while the page is hidden
+    pause_when_hidden: bool,
+    /// Whether the page is currently hidden
+    hidden: bool,
     /// Mouse data
     mouse_data: MouseData,
+    /// The site-wide scheduling constraints
+    scheduler: CanvasScheduler,
+    /// The colors of the current theme
+    colors: RenderColors,
+    /// The seed the render state was initialized with
+    seed: u32,
+    /// The replay controller
+    replay: ReplayController<R::RenderInput>,
+    /// Collects the frame times
+    frame_stats: FrameStats,
+    /// The milliseconds each simulation step advances by, `None` for one step per frame
+    fixed_timestep: Option<u32>,
+    /// The elapsed milliseconds not yet simulated with a fixed timestep
+    step_accumulator: f64,
+    /// The maximum amount of frames rendered per second of this canvas
+    max_fps: Option<u32>,
+    /// Reports errors creating the render state to the component
+    on_error: Callback<WebglError>,
+    /// Whether the placeholder is shown until the render state is created
+    loading: bool,
+    /// Reports creating the render state to the component
+    on_loading: Callback<bool>,
+    /// The last loading progress reported by the renderer
+    loading_progress: Option<f32>,
+    /// Reports changes of the loading progress to the component
+    on_loading_progress: Callback<Option<f32>>,
+    /// Reports changes of the renderer output to the page
+    on_output: Callback<R::RenderOutput>,
+    /// The last reported renderer output
+    last_output: Option<R::RenderOutput>,
+    /// When the render state was last rebuilt due to a renderer change, in milliseconds
+    #[cfg(debug_assertions)]
+    last_renderer_rebuild: Option<f64>,
 }
 
 impl<R> CanvasRenderState<R>
 where
     R: CanvasRenderer,
 {
-    /// Create a new [CanvasRenderState]
+    /// Create a new [CanvasRenderState] from the initial properties
     fn new(
-        renderer: R,
-        canvas_render_input: R::RenderInput,
-        render_loop_state: RenderLoopState,
+        props: &CanvasProperties<R>,
+        scheduler: CanvasScheduler,
+        colors: RenderColors,
+        on_error: Callback<WebglError>,
+        on_loading: Callback<bool>,
+        on_loading_progress: Callback<Option<f32>>,
     ) -> Self {
         Self {
-            renderer,
+            renderer: props.renderer.clone(),
             render_state: None,
-            render_input: canvas_render_input,
+            gl: None,
+            render_input: props.render_input.clone(),
             render_input_changed: false,
-            render_loop_state,
+            render_loop_state: props.render_loop_state,
+            running: false,
+            suspended: false,
+            max_pixel_ratio: props.max_pixel_ratio,
+            supersampling: props.supersampling,
+            context_lost: false,
+            pause_when_hidden: props.pause_when_hidden,
+            hidden: document().hidden(),
             mouse_data: MouseData::default(),
+            scheduler,
+            colors,
+            seed: SeededRng::random_seed(),
+            replay: props.replay.clone(),
+            frame_stats: props.frame_stats.clone(),
+            fixed_timestep: props.fixed_timestep,
+            step_accumulator: 0.0,
+            max_fps: props.max_fps,
+            on_error,
+            loading: false,
+            on_loading,
+            loading_progress: None,
+            on_loading_progress,
+            on_output: props.on_output.clone(),
+            last_output: None,
+            #[cfg(debug_assertions)]
+            last_renderer_rebuild: None,
+        }
+    }
+
+    /// Drop the render state, letting the renderer delete its resources unless the context was
+    /// lost
+    fn destroy_render_state(&mut self) {
+        if let Some(state) = self.render_state.take()
+            && let Some(gl) = &self.gl
+            && !self.context_lost
+        {
+            self.renderer.on_destroy(state, gl);
         }
     }
 }
@@ -166,39 +617,144 @@ where
     R::RenderInput: PartialEq + Clone + 'static,
     R::RenderState: 'static,
 {
-    type Message = ();
+    type Message = CanvasMessage;
     type Properties = CanvasProperties<R>;
 
     fn create(ctx: &Context<Self>) -> Self {
+        let (scheduler, scheduler_handle) = ctx
+            .link()
+            .context(ctx.link().callback(CanvasMessage::SchedulerChanged))
+            .unzip();
+        let (theme, theme_handle) = ctx
+            .link()
+            .context(ctx.link().callback(CanvasMessage::ThemeChanged))
+            .unzip();
+
+        #[cfg(debug_assertions)]
+        SHADER_ERROR_LISTENERS.with_borrow_mut(|listeners| {
+            listeners.push((
+                ctx.props().canvas_node_ref.clone(),
+                ctx.link().callback(CanvasMessage::ShaderError),
+            ))
+        });
+
+        let canvas_render_state = Arc::new(Mutex::new(CanvasRenderState::new(
+            ctx.props(),
+            scheduler.unwrap_or_default(),
+            theme.map_or_else(RenderColors::default, |theme: ThemeContext| {
+                RenderColors::from_theme(&theme)
+            }),
+            ctx.link().callback(CanvasMessage::RenderError),
+            ctx.link().callback(CanvasMessage::Loading),
+            ctx.link().callback(CanvasMessage::LoadingProgress),
+        )));
+        let visibility_listener = EventListener::new(&document(), "visibilitychange", {
+            let state = canvas_render_state.clone();
+            move |_| state.lock().unwrap().hidden = document().hidden()
+        });
+        let pointer_lock_listener = EventListener::new(&document(), "pointerlockchange", {
+            let state = canvas_render_state.clone();
+            let canvas_node_ref = ctx.props().canvas_node_ref.clone();
+            move |_| {
+                state.lock().unwrap().mouse_data.pointer_locked = document()
+                    .pointer_lock_element()
+                    .is_some_and(|element| canvas_node_ref.cast::<Element>() == Some(element));
+            }
+        });
+
         Self {
             canvas_node_ref: ctx.props().canvas_node_ref.clone(),
-            canvas_render_state: Arc::new(Mutex::new(CanvasRenderState::new(
-                ctx.props().renderer.clone(),
-                ctx.props().render_input.clone(),
-                ctx.props().render_loop_state,
-            ))),
+            canvas_render_state,
             initiate_render_loop: matches!(
                 ctx.props().render_loop_state,
-                RenderLoopState::Rendering | RenderLoopState::Paused
+                RenderLoopState::Rendering | RenderLoopState::Paused | RenderLoopState::StepOnce(_)
             ),
+            _scheduler_handle: scheduler_handle,
+            _theme_handle: theme_handle,
+            style_manager: ctx
+                .link()
+                .context::<StyleManager>(Callback::noop())
+                .map(|(manager, _)| manager)
+                .unwrap_or_default(),
+            error: None,
+            loading: false,
+            loading_progress: None,
+            _context_listeners: None,
+            _wheel_listener: None,
+            _visibility_listener: visibility_listener,
+            _pointer_lock_listener: pointer_lock_listener,
+            #[cfg(debug_assertions)]
+            shader_error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            CanvasMessage::SchedulerChanged(scheduler) => {
+                let mut render_state = self.canvas_render_state.lock().unwrap();
+                // Renderers size their work for the power mode when creating their state
+                if render_state.scheduler.low_power != scheduler.low_power {
+                    render_state.destroy_render_state();
+                }
+                render_state.scheduler = scheduler;
+                drop(render_state);
+
+                self.resume_render_loop(ctx.props())
+            }
+            CanvasMessage::ThemeChanged(theme) => {
+                self.canvas_render_state.lock().unwrap().colors = RenderColors::from_theme(&theme);
+
+                self.resume_render_loop(ctx.props())
+            }
+            CanvasMessage::ContextRestored => self.resume_render_loop(ctx.props()),
+            CanvasMessage::RenderError(error) => {
+                log::error!("Paused rendering: {error}");
+                self.error = Some(error);
+                true
+            }
+            CanvasMessage::Loading(loading) => {
+                self.loading = loading;
+                true
+            }
+            CanvasMessage::LoadingProgress(progress) => {
+                self.loading_progress = progress;
+                true
+            }
+            #[cfg(debug_assertions)]
+            CanvasMessage::ShaderError(error) => {
+                self.shader_error = Some(error);
+                true
+            }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let css = css!(
-            r#"
-                background-color: #000000;
-                width: ${w};
-                height: ${h};
-                user-select: none;
-            "#,
-            w = ctx.props().width,
-            h = ctx.props().height,
+        let css = manager_class(
+            &self.style_manager,
+            css!(
+                r#"
+                    background-color: ${bg};
+                    width: ${w};
+                    height: ${h};
+                    user-select: none;
+                "#,
+                bg = ThemeColor::Base00.var(),
+                w = ctx.props().width,
+                h = ctx.props().height,
+            ),
         );
 
         let onmousedown = Callback::from({
             let state: Arc<_> = self.canvas_render_state.clone();
+            let canvas_node_ref = self.canvas_node_ref.clone();
+            let pointer_lock = ctx.props().pointer_lock;
             move |event: MouseEvent| {
+                if pointer_lock
+                    && !state.lock().unwrap().mouse_data.pointer_locked
+                    && let Some(canvas) = canvas_node_ref.cast::<HtmlCanvasElement>()
+                {
+                    canvas.request_pointer_lock();
+                }
                 let buttons = event.buttons();
                 if buttons & 0b1 == 0b1 {
                     state.lock().unwrap().mouse_data.primary_button = true;
@@ -223,8 +779,10 @@ where
         let onmousemove = Callback::from({
             let state: Arc<_> = self.canvas_render_state.clone();
             move |event: MouseEvent| {
-                state.lock().unwrap().mouse_data.position =
-                    Some((event.offset_x() as u32, event.offset_y() as u32));
+                let mouse_data = &mut state.lock().unwrap().mouse_data;
+                mouse_data.position = Some((event.offset_x() as u32, event.offset_y() as u32));
+                mouse_data.movement.0 += event.movement_x();
+                mouse_data.movement.1 += event.movement_y();
             }
         });
         let onmouseleave = Callback::from({
@@ -235,35 +793,139 @@ where
         });
         let oncontextmenu = Callback::from(|e: MouseEvent| e.prevent_default());
 
+        #[cfg(debug_assertions)]
+        let shader_error = if let Some(error) = &self.shader_error {
+            let css = manager_class(
+                &self.style_manager,
+                css!(
+                    r#"
+                        position: absolute;
+                        inset: 0px;
+                        z-index: 1;
+                        margin: 0px;
+                        padding: 10px;
+                        overflow: auto;
+                        color: #ff6060;
+                        background-color: rgba(0, 0, 0, 0.85);
+                        font-size: 12px;
+                        white-space: pre;
+                    "#
+                ),
+            );
+            html! { <pre class={css}>{error}</pre> }
+        } else {
+            Html::default()
+        };
+        #[cfg(not(debug_assertions))]
+        let shader_error = Html::default();
+
+        let loading = if (self.loading || self.loading_progress.is_some()) && self.error.is_none() {
+            let css = manager_class(
+                &self.style_manager,
+                css!(
+                    r#"
+                        position: absolute;
+                        inset: 0px;
+                        display: flex;
+                        align-items: center;
+                        justify-content: center;
+                        gap: 8px;
+                        color: ${fg};
+                        background-color: ${bg};
+                        pointer-events: none;
+                    "#,
+                    fg = ThemeColor::Base05.var(),
+                    bg = ThemeColor::Base00.var(),
+                ),
+            );
+            let progress = self.loading_progress.map(|progress| {
+                html! { <progress max="1" value={progress.clamp(0.0, 1.0).to_string()}/> }
+            });
+            html! {
+                <div class={css}>
+                    <i class="iconoir-hourglass"/>
+                    {"Preparing the example..."}
+                    {progress}
+                </div>
+            }
+        } else {
+            Html::default()
+        };
+
+        let error = if let Some(error) = &self.error {
+            let css = manager_class(
+                &self.style_manager,
+                css!(
+                    r#"
+                        position: absolute;
+                        inset: 0px;
+                        display: flex;
+                        flex-direction: column;
+                        align-items: center;
+                        justify-content: center;
+                        padding: 10px;
+                        text-align: center;
+                        color: ${fg};
+                        background-color: ${bg};
+                    "#,
+                    fg = ThemeColor::Base05.var(),
+                    bg = ThemeColor::Base00.var(),
+                ),
+            );
+            html! {
+                <div class={css}>
+                    <p>{"This example could not be started."}</p>
+                    <small>{error.to_string()}</small>
+                </div>
+            }
+        } else {
+            Html::default()
+        };
+
         html! {
-            <canvas
-                class={css}
-                ref={self.canvas_node_ref.clone()}
-                {onmousedown}
-                {onmouseup}
-                {onmousemove}
-                {onmouseleave}
-                {oncontextmenu}
-            />
+            <>
+                <canvas
+                    class={css}
+                    ref={self.canvas_node_ref.clone()}
+                    {onmousedown}
+                    {onmouseup}
+                    {onmousemove}
+                    {onmouseleave}
+                    {oncontextmenu}
+                />
+                {loading}
+                {error}
+                {shader_error}
+            </>
         }
     }
 
-    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
         if !self.initiate_render_loop {
             return;
         }
+        self.initiate_render_loop = false;
 
         let canvas = self.canvas_node_ref.cast::<HtmlCanvasElement>().unwrap();
-        let gl: GL = canvas
-            .get_context("webgl")
-            .unwrap()
-            .unwrap()
-            .dyn_into()
-            .unwrap();
+        let Some(gl) = get_context(&canvas, &ctx.props().context_options.attributes()) else {
+            self.canvas_render_state.lock().unwrap().suspended = true;
+            ctx.link()
+                .send_message(CanvasMessage::RenderError(WebglError::MissingContext));
+            return;
+        };
 
-        Self::init_render_loop(gl, self.canvas_render_state.clone());
+        if self._context_listeners.is_none() {
+            self._context_listeners = Some(self.context_listeners(ctx, &canvas));
+        }
+        if ctx.props().capture_wheel && self._wheel_listener.is_none() {
+            self._wheel_listener = Some(self.wheel_listener(&canvas));
+        }
 
-        self.initiate_render_loop = false;
+        let mut render_state = self.canvas_render_state.lock().unwrap();
+        render_state.gl = Some(gl.clone());
+        render_state.running = true;
+        drop(render_state);
+        Self::init_render_loop(gl, self.canvas_render_state.clone());
     }
 
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
@@ -273,19 +935,82 @@ where
         if old_props.renderer != new_props.renderer {
             let mut render_state = self.canvas_render_state.lock().unwrap();
 
-            render_state.render_state = None;
-            render_state.renderer = new_props.renderer.clone();
+            // Switching renderers is fine, but not recreating them on every re-render of the page
+            #[cfg(debug_assertions)]
+            {
+                let now = web_sys::js_sys::Date::now();
+                if render_state
+                    .last_renderer_rebuild
+                    .replace(now)
+                    .is_some_and(|last| now - last < RENDERER_CHURN_INTERVAL)
+                {
+                    log::warn!(
+                        "Render state of {renderer} rebuilt repeatedly due to renderer prop \
+                         changes, consider keeping it stable with `use_renderer`",
+                        renderer = std::any::type_name::<R>(),
+                    );
+                }
+            }
 
-            drop(render_state);
+            render_state.destroy_render_state();
+            render_state.renderer = new_props.renderer.clone();
+        }
+        if old_props.reset != new_props.reset {
+            let mut render_state = self.canvas_render_state.lock().unwrap();
+            render_state.destroy_render_state();
+            render_state.seed = SeededRng::random_seed();
         }
         if old_props.render_input != new_props.render_input {
             let mut render_state = self.canvas_render_state.lock().unwrap();
 
-            render_state.render_input = new_props.render_input.clone();
-            render_state.render_input_changed = true;
+            if let Some(input) = render_state
+                .replay
+                .intercept_input(new_props.render_input.clone())
+            {
+                render_state.render_input = input;
+                render_state.render_input_changed = true;
+            }
 
             drop(render_state);
         }
+        if old_props.max_pixel_ratio != new_props.max_pixel_ratio {
+            self.canvas_render_state.lock().unwrap().max_pixel_ratio = new_props.max_pixel_ratio;
+        }
+        if old_props.supersampling != new_props.supersampling {
+            self.canvas_render_state.lock().unwrap().supersampling = new_props.supersampling;
+        }
+        if old_props.pause_when_hidden != new_props.pause_when_hidden {
+            self.canvas_render_state.lock().unwrap().pause_when_hidden =
+                new_props.pause_when_hidden;
+        }
+        if old_props.replay != new_props.replay {
+            self.canvas_render_state.lock().unwrap().replay = new_props.replay.clone();
+        }
+        if old_props.frame_stats != new_props.frame_stats {
+            self.canvas_render_state.lock().unwrap().frame_stats = new_props.frame_stats.clone();
+        }
+        if old_props.fixed_timestep != new_props.fixed_timestep {
+            self.canvas_render_state.lock().unwrap().fixed_timestep = new_props.fixed_timestep;
+        }
+        if old_props.max_fps != new_props.max_fps {
+            self.canvas_render_state.lock().unwrap().max_fps = new_props.max_fps;
+        }
+        if old_props.on_output != new_props.on_output {
+            self.canvas_render_state.lock().unwrap().on_output = new_props.on_output.clone();
+        }
+        if old_props.capture_wheel != new_props.capture_wheel {
+            self._wheel_listener = new_props
+                .capture_wheel
+                .then(|| self.canvas_node_ref.cast::<HtmlCanvasElement>())
+                .flatten()
+                .map(|canvas| self.wheel_listener(&canvas));
+        }
+        if old_props.pointer_lock && !new_props.pointer_lock {
+            let render_state = self.canvas_render_state.lock().unwrap();
+            if render_state.mouse_data.pointer_locked {
+                document().exit_pointer_lock();
+            }
+        }
         if old_props.render_loop_state != new_props.render_loop_state {
             self.canvas_render_state.lock().unwrap().render_loop_state =
                 new_props.render_loop_state;
@@ -299,11 +1024,20 @@ where
             changed = true;
         }
 
-        changed
+        // The changed properties may succeed where the last attempt failed
+        self.resume_render_loop(new_props) || changed
     }
 
     fn destroy(&mut self, _ctx: &Context<Self>) {
-        self.canvas_render_state.lock().unwrap().render_loop_state = RenderLoopState::Finished;
+        let mut render_state = self.canvas_render_state.lock().unwrap();
+        render_state.render_loop_state = RenderLoopState::Finished;
+        render_state.destroy_render_state();
+        drop(render_state);
+
+        #[cfg(debug_assertions)]
+        SHADER_ERROR_LISTENERS.with_borrow_mut(|listeners| {
+            listeners.retain(|(node_ref, _)| *node_ref != self.canvas_node_ref)
+        });
     }
 }
 
@@ -312,8 +1046,39 @@ where
     R: CanvasRenderer + 'static,
     R::RenderState: 'static,
 {
-    /// Resize the canvas size to fir it's actual size (not 100% accurate but good enough?)
-    fn resize_to_display_size(gl: &GL) -> (u32, u32, bool) {
+    /// Resume the render loop after it was suspended by an error or the loss of the context,
+    /// unless the context is still lost. The render state is created again on the next frame.
+    ///
+    /// Returns whether the canvas has to be rendered again to initiate the loop.
+    fn resume_render_loop(&mut self, props: &CanvasProperties<R>) -> bool {
+        let mut render_state = self.canvas_render_state.lock().unwrap();
+        if !render_state.suspended || render_state.context_lost {
+            return false;
+        }
+        render_state.suspended = false;
+        render_state.render_loop_state = props.render_loop_state;
+        self.error = None;
+
+        // A loop that did not notice the suspension yet just keeps running
+        if !render_state.running {
+            self.initiate_render_loop = true;
+        }
+        true
+    }
+
+    /// Resize the drawing buffer to the displayed size of the canvas, scaled by the device pixel
+    /// ratio (capped at `max_pixel_ratio`) so it stays sharp on high density screens, and by the
+    /// supersampling factor.
+    ///
+    /// The scale is reduced so neither side exceeds the maximum renderbuffer and texture size, which
+    /// the scene targets of post processing have to match.
+    ///
+    /// Returns the physical and the logical size, and whether the canvas was resized.
+    fn resize_to_display_size(
+        gl: &GL,
+        max_pixel_ratio: f64,
+        supersampling: u32,
+    ) -> ((u32, u32), (u32, u32), bool) {
         let canvas: HtmlCanvasElement = gl
             .canvas()
             .unwrap()
@@ -324,80 +1089,351 @@ where
             canvas.client_width().try_into().unwrap(),
             canvas.client_height().try_into().unwrap(),
         );
+        let pixel_ratio = window()
+            .device_pixel_ratio()
+            .clamp(1.0, max_pixel_ratio.max(1.0))
+            * f64::from(supersampling.clamp(1, MAX_SUPERSAMPLING));
+        let max_size = [GL::MAX_RENDERBUFFER_SIZE, GL::MAX_TEXTURE_SIZE]
+            .into_iter()
+            .filter_map(|parameter| gl.get_parameter(parameter).ok()?.as_f64())
+            .filter(|max_size| *max_size > 0.0)
+            .reduce(f64::min);
+        let pixel_ratio = match max_size {
+            Some(max_size) => {
+                pixel_ratio.min(max_size / f64::from(client_width.max(client_height).max(1)))
+            }
+            None => pixel_ratio,
+        };
+        let (width, height) = (
+            (client_width as f64 * pixel_ratio).round() as u32,
+            (client_height as f64 * pixel_ratio).round() as u32,
+        );
 
-        let resized = if client_width != canvas.width() || client_height != canvas.height() {
-            canvas.set_width(client_width);
-            canvas.set_height(client_height);
+        let resized = if width != canvas.width() || height != canvas.height() {
+            canvas.set_width(width);
+            canvas.set_height(height);
 
             true
         } else {
             false
         };
 
-        (canvas.width(), canvas.height(), resized)
+        (
+            (canvas.width(), canvas.height()),
+            (client_width, client_height),
+            resized,
+        )
+    }
+
+    /// Listen for the loss and restoration of the webgl context of the canvas.
+    ///
+    /// While the context is lost, the render loop is suspended. The render state belongs to the
+    /// lost context, so it is dropped and created again once the loop resumes after the restoration.
+    fn context_listeners(
+        &self,
+        ctx: &Context<Self>,
+        canvas: &HtmlCanvasElement,
+    ) -> [EventListener; 2] {
+        let lost = EventListener::new_with_options(
+            canvas,
+            "webglcontextlost",
+            EventListenerOptions::enable_prevent_default(),
+            {
+                let state = self.canvas_render_state.clone();
+                move |event| {
+                    // Signals that the context should be restored
+                    event.prevent_default();
+                    log::warn!("Webgl context lost");
+
+                    let mut state = state.lock().unwrap();
+                    state.context_lost = true;
+                    state.suspended = true;
+                    state.render_state = None;
+                    if let Some(timer) = state.renderer.gpu_timer() {
+                        timer.reset();
+                    }
+                }
+            },
+        );
+        let restored = EventListener::new(canvas, "webglcontextrestored", {
+            let state = self.canvas_render_state.clone();
+            let on_restored = ctx.link().callback(|_| CanvasMessage::ContextRestored);
+            move |_| {
+                log::info!("Webgl context restored");
+                state.lock().unwrap().context_lost = false;
+                on_restored.emit(());
+            }
+        });
+
+        [lost, restored]
+    }
+
+    /// Accumulate the scroll wheel movement over the canvas until the next frame.
+    ///
+    /// The page does not scroll while the mouse is over the canvas, so renderers can use the wheel
+    /// e.g. for zooming. The listener can not be passive for that, which is why it is not attached
+    /// in [`Component::view`], and only while [`CanvasProperties::capture_wheel`] is set.
+    fn wheel_listener(&self, canvas: &HtmlCanvasElement) -> EventListener {
+        EventListener::new_with_options(
+            canvas,
+            "wheel",
+            EventListenerOptions::enable_prevent_default(),
+            {
+                let state = self.canvas_render_state.clone();
+                let canvas = canvas.clone();
+                move |event| {
+                    let event = event.unchecked_ref::<WheelEvent>();
+                    event.prevent_default();
+
+                    let scale = match event.delta_mode() {
+                        WheelEvent::DOM_DELTA_LINE => WHEEL_LINE_HEIGHT,
+                        WheelEvent::DOM_DELTA_PAGE => canvas.client_height() as f64,
+                        _ => 1.0,
+                    };
+                    let wheel_delta = &mut state.lock().unwrap().mouse_data.wheel_delta;
+                    wheel_delta.0 += (event.delta_x() * scale).round() as i32;
+                    wheel_delta.1 += (event.delta_y() * scale).round() as i32;
+                }
+            },
+        )
     }
 
     /// Initiate the rendering loop to render each frame
     fn init_render_loop(gl: GL, rendering_state: Arc<Mutex<CanvasRenderState<R>>>) {
         type SelfOwnedSharedFunction<T> = Rc<RefCell<Option<Closure<dyn FnMut(T)>>>>;
-        let cb: SelfOwnedSharedFunction<u32> = Rc::new(RefCell::new(None));
+        let cb: SelfOwnedSharedFunction<f64> = Rc::new(RefCell::new(None));
 
         *cb.borrow_mut() = Some(Closure::wrap(Box::new({
             let cb = cb.clone();
-            let mut last_time = 0;
-            move |time: u32| {
+            let mut last_time = 0.0;
+            move |time: f64| {
+                // Reported once the state is unlocked, since the page may update the canvas
+                let mut output = None;
                 match &mut *rendering_state.lock().unwrap() {
+                    CanvasRenderState {
+                        render_loop_state: RenderLoopState::Finished,
+                        running,
+                        ..
+                    } => {
+                        *running = false;
+                        *cb.borrow_mut() = None;
+                        return;
+                    }
+                    // Pause after an error or the loss of the context, until the component resumes
+                    CanvasRenderState {
+                        suspended: true,
+                        render_loop_state,
+                        running,
+                        ..
+                    } => {
+                        *render_loop_state = RenderLoopState::Paused;
+                        *running = false;
+                        *cb.borrow_mut() = None;
+                        return;
+                    }
+                    // Hidden pages do not render, resuming without a jump in time afterwards
+                    CanvasRenderState {
+                        hidden: true,
+                        pause_when_hidden: true,
+                        ..
+                    } => last_time = time,
+                    // Paused canvases still render their first frame, so they are not left blank, and
+                    // resume without a jump in time
+                    CanvasRenderState {
+                        render_loop_state: RenderLoopState::Paused,
+                        render_state: Some(_),
+                        ..
+                    } => last_time = time,
+                    CanvasRenderState {
+                        render_loop_state: RenderLoopState::Rendering,
+                        render_state,
+                        scheduler,
+                        max_fps,
+                        ..
+                    } if (scheduler.paused && render_state.is_some())
+                        || time - last_time
+                            < scheduler
+                                .frame_interval()
+                                .max(max_fps.map_or(0, |fps| 1000 / fps.max(1)))
+                                as f64 => {}
+                    // Show the placeholder for a frame before the blocking creation of the render
+                    // state, so the page is painted with it instead of freezing with a black box
+                    CanvasRenderState {
+                        render_state: None,
+                        loading,
+                        on_loading,
+                        ..
+                    } if !*loading => {
+                        *loading = true;
+                        on_loading.emit(true);
+                    }
                     CanvasRenderState {
                         renderer,
                         render_state,
                         render_input: canvas_render_input,
                         render_input_changed,
-                        render_loop_state: RenderLoopState::Rendering,
                         mouse_data,
+                        seed,
+                        replay,
+                        scheduler,
+                        colors,
+                        on_error,
+                        max_pixel_ratio,
+                        supersampling,
+                        frame_stats,
+                        fixed_timestep,
+                        step_accumulator,
+                        render_loop_state,
+                        on_output,
+                        last_output,
+                        loading,
+                        on_loading,
+                        loading_progress,
+                        on_loading_progress,
+                        suspended,
+                        ..
                     } => {
-                        let (width, height, resized) = Self::resize_to_display_size(&gl);
-                        let render_data = RenderData {
-                            initial_render: render_state.is_none(),
-                            width,
-                            height,
-                            resized,
-                            input_changed: *render_input_changed,
-                            time,
-                            delta_time: time - last_time,
-                            mouse_data: *mouse_data,
-                        };
-
-                        let render_state = render_state.get_or_insert_with(|| {
-                            renderer.initial_render_state(canvas_render_input, &gl, render_data)
-                        });
+                        let stepping_once =
+                            matches!(render_loop_state, RenderLoopState::StepOnce(_));
+                        if let Some(new_seed) = replay.take_restart() {
+                            if let Some(state) = render_state.take() {
+                                renderer.on_destroy(state, &gl);
+                            }
+                            *seed = new_seed;
+                        }
+
+                        let ((width, height), (logical_width, logical_height), resized) =
+                            Self::resize_to_display_size(&gl, *max_pixel_ratio, *supersampling);
+                        let render_data = replay.frame(
+                            RenderData {
+                                initial_render: render_state.is_none(),
+                                width,
+                                height,
+                                logical_width,
+                                logical_height,
+                                resized,
+                                input_changed: *render_input_changed,
+                                time,
+                                delta_time: time - last_time,
+                                mouse_data: *mouse_data,
+                                seed: *seed,
+                                cpu_budget: scheduler.cpu_budget,
+                                colors: *colors,
+                                low_power: scheduler.low_power,
+                                loading_progress: *loading_progress,
+                            },
+                            canvas_render_input,
+                        );
+
+                        if render_state.is_none() {
+                            match renderer.initial_render_state(
+                                canvas_render_input,
+                                &gl,
+                                render_data,
+                            ) {
+                                Ok(initial_state) => {
+                                    *render_state = Some(initial_state);
+                                    *loading = false;
+                                    on_loading.emit(false);
+                                }
+                                Err(error) => {
+                                    *loading = false;
+                                    *suspended = true;
+                                    on_error.emit(error);
+                                    // Pauses with the next frame
+                                    Self::render_loop(cb.borrow().as_ref().unwrap());
+                                    return;
+                                }
+                            }
+                        }
+                        let render_state = render_state.as_mut().unwrap();
 
+                        if render_data.resized || render_data.initial_render {
+                            renderer.on_resize(render_state, &gl, render_data);
+                        }
+                        if render_data.input_changed || render_data.initial_render {
+                            renderer.on_input_changed(
+                                render_state,
+                                canvas_render_input,
+                                &gl,
+                                render_data,
+                            );
+                        }
+
+                        match *fixed_timestep {
+                            Some(timestep) if !render_data.initial_render => {
+                                let timestep = timestep.max(1) as f64;
+                                // A single requested frame advances by exactly one step
+                                let steps = if stepping_once {
+                                    1
+                                } else {
+                                    *step_accumulator += render_data.delta_time;
+                                    let steps = (*step_accumulator / timestep) as u32;
+                                    // Drop the time exceeding the substeps, instead of catching up
+                                    // for ever after a long frame
+                                    *step_accumulator = if steps > MAX_SUBSTEPS {
+                                        0.0
+                                    } else {
+                                        *step_accumulator % timestep
+                                    };
+                                    steps
+                                };
+                                let step_data = RenderData {
+                                    delta_time: timestep,
+                                    ..render_data
+                                };
+                                for _ in 0..steps.min(MAX_SUBSTEPS) {
+                                    renderer.step(
+                                        render_state,
+                                        canvas_render_input,
+                                        &gl,
+                                        step_data,
+                                    );
+                                }
+                            }
+                            _ => {
+                                *step_accumulator = 0.0;
+                                renderer.step(render_state, canvas_render_input, &gl, render_data);
+                            }
+                        }
                         renderer.render(render_state, canvas_render_input, &gl, render_data);
+                        if let Some(new_output) = renderer.output(render_state)
+                            && last_output.as_ref() != Some(&new_output)
+                        {
+                            *last_output = Some(new_output.clone());
+                            output = Some((on_output.clone(), new_output));
+                        }
+                        let new_loading_progress = renderer.loading_progress(render_state);
+                        if new_loading_progress != *loading_progress {
+                            *loading_progress = new_loading_progress;
+                            on_loading_progress.emit(new_loading_progress);
+                        }
+                        if !render_data.initial_render {
+                            frame_stats.record(render_data.delta_time);
+                        }
 
                         *render_input_changed = false;
+                        mouse_data.wheel_delta = (0, 0);
+                        mouse_data.movement = (0, 0);
                         last_time = time;
+                        if stepping_once {
+                            *render_loop_state = RenderLoopState::Paused;
+                        }
                     }
-                    CanvasRenderState {
-                        render_loop_state: RenderLoopState::Finished,
-                        ..
-                    } => {
-                        *cb.borrow_mut() = None;
-                        return;
-                    }
-                    CanvasRenderState {
-                        render_loop_state: RenderLoopState::Paused,
-                        ..
-                    } => {}
+                }
+                if let Some((on_output, output)) = output {
+                    on_output.emit(output);
                 }
 
                 Self::render_loop(cb.borrow().as_ref().unwrap());
             }
-        }) as Box<dyn FnMut(u32)>));
+        }) as Box<dyn FnMut(f64)>));
 
         Self::render_loop(cb.borrow().as_ref().unwrap());
     }
 
     /// Helper method for the rendering loop
-    fn render_loop(render_function: &Closure<dyn FnMut(u32)>) {
+    fn render_loop(render_function: &Closure<dyn FnMut(f64)>) {
         window()
             .request_animation_frame(render_function.as_ref().unchecked_ref())
             .unwrap();
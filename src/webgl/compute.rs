@@ -1,11 +1,18 @@
 //! Simulating compute shaders with webgl
 
+use gloo::utils::window;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlRenderingContext as GL, WebGlTexture,
-    js_sys::Float32Array,
+    WebGl2RenderingContext, WebGlBuffer, WebGlFramebuffer, WebGlProgram,
+    WebGlRenderingContext as GL, WebGlSync, WebGlTexture, WebglDrawBuffers,
+    js_sys::{Array, Float32Array, Promise},
 };
 
-use crate::webgl::{Uniform, UniformData, compile_shader, create_program};
+use crate::webgl::{
+    TextureUniform, TextureUnits, Uniform, UniformData, WebglError, compile_shader, create_program,
+    get_extension, webgl2,
+};
 
 // TODO: write docs
 
@@ -15,18 +22,84 @@ pub trait UniformConstAccess<const INDEX: u32> {
     fn access(&mut self) -> &mut Uniform<Self::UniformDataType>;
 }
 
-pub trait UniformSet {
-    fn initialize(gl: &GL, program: &WebGlProgram) -> Self;
+pub trait UniformSet: Sized {
+    fn initialize(gl: &GL, program: &WebGlProgram) -> Result<Self, WebglError>;
 
     fn apply_all(&self, gl: &GL);
 }
 
+/// The data of a group of uniforms sharing a prefix, like the members of a glsl struct.
+///
+/// Implemented by the group structs declared in [`uniform_set!`].
+pub trait UniformGroupData: Sized {
+    /// The uniforms of the members
+    type Uniforms: std::fmt::Debug;
+
+    /// Look up the members as `<prefix>.<member>` and initialize them with default data
+    fn initialize(
+        gl: &GL,
+        program: &WebGlProgram,
+        prefix: &str,
+    ) -> Result<Self::Uniforms, WebglError>;
+
+    /// Set the data of all members
+    fn set_data(uniforms: &mut Self::Uniforms, data: Self);
+
+    /// Apply all members
+    fn apply(uniforms: &Self::Uniforms, gl: &GL);
+}
+
+/// A group of uniforms sharing a prefix, set at once with a single typed value
+#[derive(Debug)]
+pub struct UniformGroup<Data: UniformGroupData> {
+    /// The uniforms of the members
+    uniforms: Data::Uniforms,
+}
+
+impl<Data: UniformGroupData> UniformGroup<Data> {
+    /// Create the uniforms of the group members in the given program
+    pub fn new(gl: &GL, program: &WebGlProgram, prefix: &str) -> Result<Self, WebglError> {
+        Ok(Self {
+            uniforms: Data::initialize(gl, program, prefix)?,
+        })
+    }
+
+    /// Sets the data of all members
+    pub fn set_data(&mut self, data: Data) {
+        Data::set_data(&mut self.uniforms, data);
+    }
+
+    /// Applies all members by sending the data to the graphics card
+    pub fn apply(&self, gl: &GL) {
+        Data::apply(&self.uniforms, gl);
+    }
+
+    /// A convenience wrapper for setting and then applying the group data
+    pub fn apply_data(&mut self, gl: &GL, data: Data) {
+        self.set_data(data);
+        self.apply(gl);
+    }
+}
+
+pub trait UniformGroupConstAccess<const INDEX: u32> {
+    type UniformGroupDataType: UniformGroupData;
+
+    fn access_group(&mut self) -> &mut UniformGroup<Self::UniformGroupDataType>;
+}
+
 /// # Example
 /// ```
 /// uniform_set! {
 ///     pub TestSet {
 ///         u_position: (f32, f32), // Uses default implemenation for initialization
 ///         u_aspect: (f32,) = (1.0,), // Initializes with value (1.0,)
+///         u_weights: [f32; 8], // Uploaded as `uniform float u_weights[8]`
+///     }
+///     // Declares `pub struct Light`, uploaded as `uniform Light u_light` with the members
+///     // `u_light.position` and `u_light.color`, initialized with default values
+///     struct u_light: Light {
+///         position: (f32, f32, f32),
+///         color: (f32, f32, f32),
 ///     }
 /// }
 /// ```
@@ -39,18 +112,64 @@ macro_rules! uniform_set {
             ),*
             $(,)?
         }
-
+        $(
+            struct $group:ident: $group_type:ident {
+                $(
+                    $member:ident: $member_type:ty
+                ),*
+                $(,)?
+            }
+        )*
     ) => {
         #[derive(Debug)]
         $set_visibility struct $set_name {
             $(
-                pub $location: Uniform<$type>
-            ),*
+                pub $location: Uniform<$type>,
+            )*
+            $(
+                pub $group: $crate::webgl::UniformGroup<$group_type>,
+            )*
         }
 
+        $(
+            #[derive(Debug, Clone, Default, PartialEq)]
+            $set_visibility struct $group_type {
+                $(
+                    pub $member: $member_type
+                ),*
+            }
+
+            impl $crate::webgl::UniformGroupData for $group_type {
+                type Uniforms = ($(Uniform<$member_type>,)*);
+
+                #[allow(unused_variables)]
+                fn initialize(gl: &GL, program: &WebGlProgram, prefix: &str) -> Result<Self::Uniforms, $crate::webgl::WebglError> {
+                    Ok(($(
+                        Uniform::new(gl, program, format!("{prefix}.{}", stringify!($member)), Default::default())?,
+                    )*))
+                }
+
+                #[allow(unused_variables)]
+                fn set_data(uniforms: &mut Self::Uniforms, data: Self) {
+                    let ($($member,)*) = uniforms;
+                    $(
+                        $member.set_data(data.$member);
+                    )*
+                }
+
+                #[allow(unused_variables)]
+                fn apply(uniforms: &Self::Uniforms, gl: &GL) {
+                    let ($($member,)*) = uniforms;
+                    $(
+                        $member.apply(gl);
+                    )*
+                }
+            }
+        )*
+
         #[allow(non_upper_case_globals, dead_code)]
         impl $set_name {
-            uniform_set!(@count_constants | $($location),*);
+            uniform_set!(@count_constants | $($location,)* $($group,)*);
 
             pub fn access<const UNIFORM_LOCATION: u32>(&mut self) -> &mut Uniform<<Self as $crate::webgl::UniformConstAccess<UNIFORM_LOCATION>>::UniformDataType>
             where
@@ -58,22 +177,35 @@ macro_rules! uniform_set {
             {
                 <Self as $crate::webgl::UniformConstAccess<UNIFORM_LOCATION>>::access(self)
             }
+
+            pub fn access_group<const UNIFORM_LOCATION: u32>(&mut self) -> &mut $crate::webgl::UniformGroup<<Self as $crate::webgl::UniformGroupConstAccess<UNIFORM_LOCATION>>::UniformGroupDataType>
+            where
+                Self: $crate::webgl::UniformGroupConstAccess<UNIFORM_LOCATION>
+            {
+                <Self as $crate::webgl::UniformGroupConstAccess<UNIFORM_LOCATION>>::access_group(self)
+            }
         }
 
         #[allow(unused_variables)]
         impl $crate::webgl::UniformSet for $set_name {
-            fn initialize(gl: &GL, program: &WebGlProgram) -> Self {
-                Self {
+            fn initialize(gl: &GL, program: &WebGlProgram) -> Result<Self, $crate::webgl::WebglError> {
+                Ok(Self {
                     $(
-                        $location: Uniform::new(gl, program, stringify!($location), uniform_set!(@val_or_default $($val)?))
-                    ),*
-                }
+                        $location: Uniform::new(gl, program, stringify!($location), uniform_set!(@val_or_default $($val)?))?,
+                    )*
+                    $(
+                        $group: $crate::webgl::UniformGroup::new(gl, program, stringify!($group))?,
+                    )*
+                })
             }
 
             fn apply_all(&self, gl: &GL) {
                 $(
-                    self.$location.apply(gl)
-                );*
+                    self.$location.apply(gl);
+                )*
+                $(
+                    self.$group.apply(gl);
+                )*
             }
         }
 
@@ -86,14 +218,21 @@ macro_rules! uniform_set {
                 }
             }
         )*
+
+        $(
+            impl $crate::webgl::UniformGroupConstAccess<{ $set_name::$group }> for $set_name {
+                type UniformGroupDataType = $group_type;
+
+                fn access_group(&mut self) -> &mut $crate::webgl::UniformGroup<Self::UniformGroupDataType> {
+                    &mut self.$group
+                }
+            }
+        )*
     };
-    (@count_constants | ) => {};
-    (@count_constants $($counted:ident),* | $first:ident, $($rest:ident),*) => {
+    (@count_constants $($counted:ident),* | ) => {};
+    (@count_constants $($counted:ident),* | $first:ident, $($rest:ident,)*) => {
         pub const $first: u32 = uniform_set!(@to_number $($counted),*);
-        uniform_set!(@count_constants $($counted,)* $first | $($rest),*);
-    };
-    (@count_constants $($counted:ident),* | $last:ident) => {
-        pub const $last: u32 = uniform_set!(@to_number $($counted),*);
+        uniform_set!(@count_constants $($counted,)* $first | $($rest,)*);
     };
     (@to_number $($id:ident),*) => {
         $(
@@ -112,10 +251,101 @@ macro_rules! uniform_set {
     };
 }
 
-/// A compute program, consisting of multiple input textures and an output textures.
+/// A readback started by [`ComputeProgram::read_output_async`]
+enum PendingReadback {
+    /// The pixels are read into a pixel pack buffer, done once the fence is signaled (webgl2)
+    PackBuffer(WebGlBuffer, WebGlSync),
+    /// The output was copied to a snapshot texture, read through the framebuffer (webgl1)
+    Snapshot(WebGlTexture, WebGlFramebuffer),
+}
+
+/// Resolves with the next animation frame
+async fn next_animation_frame() {
+    let frame = Promise::new(&mut |resolve, _| {
+        let _ = window().request_animation_frame(&resolve);
+    });
+    let _ = JsFuture::from(frame).await;
+}
+
+/// Textures rendered to by a compute program
+#[derive(Debug)]
+struct RenderTargets {
+    /// The textures, each with a framebuffer for reading from it
+    textures: Vec<(WebGlTexture, WebGlFramebuffer)>,
+    /// The framebuffer rendering to all textures
+    frame_buffer: WebGlFramebuffer,
+}
+
+impl RenderTargets {
+    /// Create the framebuffers for the given textures
+    fn new(gl: &GL, textures: Vec<WebGlTexture>) -> Result<Self, WebglError> {
+        let frame_buffer = Self::create_frame_buffer(gl, &textures.iter().collect::<Vec<_>>())?;
+        let textures = textures
+            .into_iter()
+            .map(|texture| {
+                let read_frame_buffer = Self::create_frame_buffer(gl, &[&texture])?;
+                Ok((texture, read_frame_buffer))
+            })
+            .collect::<Result<_, WebglError>>()?;
+
+        Ok(Self {
+            textures,
+            frame_buffer,
+        })
+    }
+
+    /// Delete the textures and framebuffers
+    fn delete(self, gl: &GL) {
+        for (texture, read_frame_buffer) in self.textures {
+            gl.delete_texture(Some(&texture));
+            gl.delete_framebuffer(Some(&read_frame_buffer));
+        }
+        gl.delete_framebuffer(Some(&self.frame_buffer));
+    }
+
+    /// Create a framebuffer rendering to the given textures, in order of their color attachments
+    fn create_frame_buffer(
+        gl: &GL,
+        textures: &[&WebGlTexture],
+    ) -> Result<WebGlFramebuffer, WebglError> {
+        let frame_buffer = gl
+            .create_framebuffer()
+            .ok_or(WebglError::CreationFailed("framebuffer"))?;
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&frame_buffer));
+        for (index, texture) in textures.iter().enumerate() {
+            gl.framebuffer_texture_2d(
+                GL::FRAMEBUFFER,
+                GL::COLOR_ATTACHMENT0 + index as u32,
+                GL::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+        }
+
+        if textures.len() > 1 {
+            let attachments: Array = (0..textures.len())
+                .map(|index| JsValue::from(GL::COLOR_ATTACHMENT0 + index as u32))
+                .collect();
+            if let Some(gl) = webgl2(gl) {
+                gl.draw_buffers(&attachments);
+            } else {
+                let draw_buffers: WebglDrawBuffers =
+                    get_extension(gl, "WEBGL_draw_buffers")?.unchecked_into();
+                draw_buffers.draw_buffers_webgl(&attachments);
+            }
+        }
+
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+        Ok(frame_buffer)
+    }
+}
+
+/// A compute program, consisting of multiple input textures and one or more output textures.
 ///
 /// All textures must have the same sizes.
-/// The actual computation is done using a fragment shader.
+/// The actual computation is done using a fragment shader. With multiple outputs, the shader writes
+/// to `gl_FragData[i]` using the `GL_EXT_draw_buffers` extension in webgl1, or to
+/// `layout(location = i)` outputs of a `#version 300 es` shader in webgl2.
 #[derive(Debug)]
 pub struct ComputeProgram<Set: UniformSet> {
     /// The width of the textures
@@ -123,13 +353,13 @@ pub struct ComputeProgram<Set: UniformSet> {
     /// The height of the textures
     height: u32,
     /// The input textures
-    inputs: Vec<(WebGlTexture, Uniform<(i32,)>)>,
-    /// The output texture
-    output_texture: WebGlTexture,
+    inputs: Vec<TextureUniform>,
+    /// The output textures
+    outputs: RenderTargets,
+    /// The input textures as render targets, swapped with the outputs by [`ComputeProgram::swap`]
+    swapped_outputs: Option<RenderTargets>,
     /// The program used to compute the actual data
     program: WebGlProgram,
-    /// The output framebuffer
-    frame_buffer: WebGlFramebuffer,
     /// The vertex buffer
     vertex_buffer: WebGlBuffer,
     /// The dimension uniform
@@ -148,83 +378,137 @@ impl<Set: UniformSet> ComputeProgram<Set> {
         }
     ";
 
+    /// Vertex shader for drawing the space filling quad, for `#version 300 es` fragment shaders
+    const VERTEX_SOURCE_300: &'static str = "#version 300 es
+        in vec2 a_position;
+
+        void main() {
+            gl_Position = vec4(a_position, 0.0, 1.0);
+        }
+    ";
+
     /// Vertex coordinates for a space filling quad
     const VERTICES: [f32; 12] = [
         -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
     ];
 
-    /// Creates a new compute shader with the given dimensions and uniforms and fragment shader source.
+    /// Creates a new compute shader with the given dimensions, amount of input and output textures
+    /// and fragment shader source.
+    ///
+    /// Fragment shaders starting with `#version 300 es` may use webgl2 features like `texelFetch`,
+    /// they require a webgl2 context (see [`webgl2`]).
+    ///
+    /// Fails if floating point textures, or multiple outputs are requested but not supported by
+    /// the context.
     pub fn new(
         width: u32,
         height: u32,
         inputs: usize,
+        outputs: usize,
         gl: &GL,
         fragment_source: impl AsRef<str>,
-    ) -> Self {
-        let output_texture = Self::create_texture(gl, width as i32, height as i32);
-
-        let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, Self::VERTEX_SOURCE).unwrap();
-        let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, fragment_source).unwrap();
-        let program = create_program(gl, &vertex_shader, &fragment_shader).unwrap();
-
+    ) -> Result<Self, WebglError> {
+        let outputs = RenderTargets::new(
+            gl,
+            (0..outputs)
+                .map(|_| Self::create_texture(gl, width as i32, height as i32))
+                .collect::<Result<_, _>>()?,
+        )?;
+
+        let fragment_source = fragment_source.as_ref();
+        let vertex_source = if fragment_source.trim_start().starts_with("#version 300 es") {
+            Self::VERTEX_SOURCE_300
+        } else {
+            Self::VERTEX_SOURCE
+        };
+        let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, vertex_source)?;
+        let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, fragment_source)?;
+        let program = create_program(gl, &vertex_shader, &fragment_shader)?;
+
+        let mut texture_units = TextureUnits::new();
         let inputs = (0..inputs)
             .map(|i| {
-                (
-                    Self::create_texture(gl, width as i32, height as i32),
-                    Uniform::new(gl, &program, format!("u_input_{i}"), (i as i32,)),
+                texture_units.uniform(
+                    gl,
+                    &program,
+                    format!("u_input_{i}"),
+                    Some(Self::create_texture(gl, width as i32, height as i32)?),
                 )
             })
-            .collect();
-
-        let frame_buffer = gl.create_framebuffer().unwrap();
-        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&frame_buffer));
-        gl.framebuffer_texture_2d(
-            GL::FRAMEBUFFER,
-            GL::COLOR_ATTACHMENT0,
-            GL::TEXTURE_2D,
-            Some(&output_texture),
-            0,
-        );
-        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
-
-        let vertex_buffer = gl.create_buffer().unwrap();
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Created up front, so swapping can not fail while rendering
+        let output_count = outputs.textures.len();
+        let swapped_outputs = if inputs.len() >= output_count {
+            Some(RenderTargets::new(
+                gl,
+                inputs
+                    .iter()
+                    .filter_map(TextureUniform::texture)
+                    .take(output_count)
+                    .cloned()
+                    .collect(),
+            )?)
+        } else {
+            None
+        };
+
+        let vertex_buffer = gl
+            .create_buffer()
+            .ok_or(WebglError::CreationFailed("buffer"))?;
         let verts = web_sys::js_sys::Float32Array::from(Self::VERTICES.as_slice());
         gl.bind_buffer(GL::ARRAY_BUFFER, Some(&vertex_buffer));
         gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &verts, GL::STATIC_DRAW);
         gl.bind_buffer(GL::ARRAY_BUFFER, None);
 
         let dimensions_uniform =
-            Uniform::new(gl, &program, "u_dimensions", (width as f32, height as f32));
+            Uniform::optional(gl, &program, "u_dimensions", (width as f32, height as f32));
 
-        let uniforms = Set::initialize(gl, &program);
+        let uniforms = Set::initialize(gl, &program)?;
 
-        Self {
+        Ok(Self {
             width,
             height,
             inputs,
-            output_texture,
+            outputs,
+            swapped_outputs,
             program,
-            frame_buffer,
             vertex_buffer,
             dimensions_uniform,
             uniforms,
+        })
+    }
+
+    /// Returns the internal format of the floating point textures.
+    ///
+    /// Webgl2 has sized floating point formats, webgl1 only supports them through extensions.
+    fn internal_format(gl: &GL) -> i32 {
+        if webgl2(gl).is_some() {
+            WebGl2RenderingContext::RGBA32F as i32
+        } else {
+            GL::RGBA as i32
         }
     }
 
     /// Convenient function for creating a floating point texture of the given size
-    fn create_texture(gl: &GL, width: i32, height: i32) -> WebGlTexture {
-        let texture = gl.create_texture().unwrap();
+    fn create_texture(gl: &GL, width: i32, height: i32) -> Result<WebGlTexture, WebglError> {
+        if webgl2(gl).is_some() {
+            // Floating point textures are core in webgl2, rendering to them is not
+            get_extension(gl, "EXT_color_buffer_float")?;
+        } else {
+            get_extension(gl, "OES_texture_float")?;
+            get_extension(gl, "WEBGL_color_buffer_float")?;
+        }
 
-        gl.get_extension("OES_texture_float").unwrap().unwrap();
-        gl.get_extension("WEBGL_color_buffer_float")
-            .unwrap()
-            .unwrap();
+        let texture = gl
+            .create_texture()
+            .ok_or(WebglError::CreationFailed("texture"))?;
 
         gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
         gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
             GL::TEXTURE_2D,
             0,
-            GL::RGBA as i32,
+            Self::internal_format(gl),
             width,
             height,
             0,
@@ -232,7 +516,7 @@ impl<Set: UniformSet> ComputeProgram<Set> {
             GL::FLOAT,
             None,
         )
-        .unwrap();
+        .map_err(|_| WebglError::CreationFailed("texture"))?;
         gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::NEAREST as i32);
         gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::NEAREST as i32);
         gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
@@ -240,7 +524,7 @@ impl<Set: UniformSet> ComputeProgram<Set> {
 
         gl.bind_texture(GL::TEXTURE_2D, None);
 
-        texture
+        Ok(texture)
     }
 
     /// Write the given data to the given input texture
@@ -249,11 +533,11 @@ impl<Set: UniformSet> ComputeProgram<Set> {
     /// If the data dimension does not match the texture dimension
     pub fn write_input(&self, gl: &GL, index: usize, data: &[f32]) {
         assert_eq!(data.len() as u32, self.width * self.height * 4);
-        gl.bind_texture(GL::TEXTURE_2D, Some(&self.inputs[index].0));
+        gl.bind_texture(GL::TEXTURE_2D, self.inputs[index].texture());
         gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_array_buffer_view(
             GL::TEXTURE_2D,
             0,
-            GL::RGBA as i32,
+            Self::internal_format(gl),
             self.width as i32,
             self.height as i32,
             0,
@@ -265,15 +549,44 @@ impl<Set: UniformSet> ComputeProgram<Set> {
         gl.bind_texture(GL::TEXTURE_2D, None);
     }
 
+    /// Write the given data to a rectangular region of the given input texture, leaving the rest
+    /// untouched. Cheaper than [`ComputeProgram::write_input`] if only a few texels change.
+    ///
+    /// # Panics
+    /// If the region exceeds the texture or the data dimension does not match the region
+    pub fn write_input_region(
+        &self,
+        gl: &GL,
+        index: usize,
+        (x, y): (u32, u32),
+        (width, height): (u32, u32),
+        data: &[f32],
+    ) {
+        assert!(x + width <= self.width && y + height <= self.height);
+        assert_eq!(data.len() as u32, width * height * 4);
+        gl.bind_texture(GL::TEXTURE_2D, self.inputs[index].texture());
+        gl.tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_opt_array_buffer_view(
+            GL::TEXTURE_2D,
+            0,
+            x as i32,
+            y as i32,
+            width as i32,
+            height as i32,
+            GL::RGBA,
+            GL::FLOAT,
+            Some(&Float32Array::from(data)),
+        )
+        .unwrap();
+        gl.bind_texture(GL::TEXTURE_2D, None);
+    }
+
     /// Apply the compute shader and render to the output texture
     pub fn compute(&self, gl: &GL) {
         gl.use_program(Some(&self.program));
-        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.frame_buffer));
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.outputs.frame_buffer));
 
-        for (index, (texture, uniform)) in self.inputs.iter().enumerate() {
-            gl.active_texture(GL::TEXTURE0 + index as u32);
-            gl.bind_texture(GL::TEXTURE_2D, Some(texture));
-            uniform.apply(gl);
+        for input in &self.inputs {
+            input.apply(gl);
         }
 
         gl.bind_buffer(GL::ARRAY_BUFFER, Some(&self.vertex_buffer));
@@ -290,40 +603,104 @@ impl<Set: UniformSet> ComputeProgram<Set> {
 
         gl.disable_vertex_attrib_array(position);
         gl.bind_buffer(GL::ARRAY_BUFFER, None);
-        gl.bind_texture(GL::TEXTURE_2D, None);
+        for input in &self.inputs {
+            input.unbind(gl);
+        }
         gl.bind_framebuffer(GL::FRAMEBUFFER, None);
         gl.use_program(None);
     }
 
-    /// Copy the output texture to the given texture
-    pub fn copy_output(&self, gl: &GL, texture: &WebGlTexture) {
-        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.frame_buffer));
+    /// Copy the output texture at the given index to the given texture
+    ///
+    /// With webgl2, the texture must already be a floating point texture of the same size, e.g.
+    /// one of the input textures.
+    pub fn copy_output(&self, gl: &GL, output_index: usize, texture: &WebGlTexture) {
+        gl.bind_framebuffer(
+            GL::FRAMEBUFFER,
+            Some(&self.outputs.textures[output_index].1),
+        );
         gl.active_texture(GL::TEXTURE0);
         gl.bind_texture(GL::TEXTURE_2D, Some(texture));
-        gl.copy_tex_image_2d(
-            GL::TEXTURE_2D,
-            0,
-            GL::RGBA,
-            0,
-            0,
-            self.width as i32,
-            self.height as i32,
-            0,
-        );
+        if webgl2(gl).is_some() {
+            // Sized floating point formats can not be used for reallocating copies
+            gl.copy_tex_sub_image_2d(
+                GL::TEXTURE_2D,
+                0,
+                0,
+                0,
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+            );
+        } else {
+            gl.copy_tex_image_2d(
+                GL::TEXTURE_2D,
+                0,
+                GL::RGBA,
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+                0,
+            );
+        }
         gl.bind_texture(GL::TEXTURE_2D, None);
         gl.bind_framebuffer(GL::FRAMEBUFFER, None);
     }
 
-    /// Copy the output texture to the given input texture
-    pub fn copy_output_to_input(&self, gl: &GL, input_index: usize) {
-        self.copy_output(gl, &self.inputs[input_index].0);
+    /// Copy the output texture at the given index to the given input texture
+    pub fn copy_output_to_input(&self, gl: &GL, output_index: usize, input_index: usize) {
+        self.copy_output(gl, output_index, self.input_texture(input_index));
+    }
+
+    /// Copy every output texture to the input texture with the same index
+    pub fn copy_outputs_to_inputs(&self, gl: &GL) {
+        for index in 0..self.outputs.textures.len().min(self.inputs.len()) {
+            self.copy_output_to_input(gl, index, index);
+        }
     }
 
-    /// Read the output texture into an array
-    pub fn read_output(&self, gl: &GL) -> Float32Array {
+    /// Swap the output textures with the input textures of the same index, instead of copying them.
+    ///
+    /// Afterwards the previous outputs are read as inputs, while the next computation renders to
+    /// the previous inputs.
+    ///
+    /// # Panics
+    /// If there are less inputs than outputs
+    pub fn swap(&mut self) {
+        let outputs = self.outputs.textures.len();
+        let mut swapped_outputs = self.swapped_outputs.take().unwrap_or_else(|| {
+            panic!("Swapping requires an input for each of the {outputs} outputs")
+        });
+        std::mem::swap(&mut self.outputs, &mut swapped_outputs);
+        for (input, (texture, _)) in self.inputs.iter_mut().zip(&swapped_outputs.textures) {
+            input.set_texture(Some(texture.clone()));
+        }
+        self.swapped_outputs = Some(swapped_outputs);
+    }
+
+    /// Delete all textures, framebuffers and the program, e.g. once the render state is destroyed
+    pub fn delete(self, gl: &GL) {
+        for input in &self.inputs {
+            gl.delete_texture(input.texture());
+        }
+        self.outputs.delete(gl);
+        if let Some(swapped_outputs) = self.swapped_outputs {
+            swapped_outputs.delete(gl);
+        }
+        gl.delete_buffer(Some(&self.vertex_buffer));
+        gl.delete_program(Some(&self.program));
+    }
+
+    /// Read the output texture at the given index into an array
+    pub fn read_output(&self, gl: &GL, output_index: usize) -> Float32Array {
         let output = Float32Array::new_with_length(self.width * self.height * 4);
 
-        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.frame_buffer));
+        gl.bind_framebuffer(
+            GL::FRAMEBUFFER,
+            Some(&self.outputs.textures[output_index].1),
+        );
         gl.read_pixels_with_opt_array_buffer_view(
             0,
             0,
@@ -339,19 +716,115 @@ impl<Set: UniformSet> ComputeProgram<Set> {
         output
     }
 
+    /// Read the output texture at the given index into an array, without stalling the pipeline.
+    ///
+    /// With webgl2, the pixels are read into a pixel pack buffer, which is copied once a fence
+    /// signals that the gpu is done with it. With webgl1, the output is copied to a snapshot
+    /// texture, which is read in the next animation frame.
+    ///
+    /// Fails if the buffers or textures for the readback can not be created.
+    pub fn read_output_async(
+        &self,
+        gl: &GL,
+        output_index: usize,
+    ) -> Result<impl Future<Output = Float32Array> + use<Set>, WebglError> {
+        let gl = gl.clone();
+        let (width, height) = (self.width as i32, self.height as i32);
+        let output = Float32Array::new_with_length(self.width * self.height * 4);
+
+        let pending = if let Some(gl2) = webgl2(&gl) {
+            let buffer = gl
+                .create_buffer()
+                .ok_or(WebglError::CreationFailed("buffer"))?;
+            gl.bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, Some(&buffer));
+            gl2.buffer_data_with_i32(
+                WebGl2RenderingContext::PIXEL_PACK_BUFFER,
+                output.byte_length() as i32,
+                WebGl2RenderingContext::STREAM_READ,
+            );
+            gl.bind_framebuffer(
+                GL::FRAMEBUFFER,
+                Some(&self.outputs.textures[output_index].1),
+            );
+            gl2.read_pixels_with_i32(0, 0, width, height, GL::RGBA, GL::FLOAT, 0)
+                .unwrap();
+            gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+            gl.bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, None);
+
+            let sync = gl2
+                .fence_sync(WebGl2RenderingContext::SYNC_GPU_COMMANDS_COMPLETE, 0)
+                .ok_or(WebglError::CreationFailed("fence"))?;
+            gl.flush();
+            PendingReadback::PackBuffer(buffer, sync)
+        } else {
+            let snapshot = Self::create_texture(&gl, width, height)?;
+            self.copy_output(&gl, output_index, &snapshot);
+            let frame_buffer = RenderTargets::create_frame_buffer(&gl, &[&snapshot])?;
+            PendingReadback::Snapshot(snapshot, frame_buffer)
+        };
+
+        Ok(async move {
+            match pending {
+                PendingReadback::PackBuffer(buffer, sync) => {
+                    let gl2 = webgl2(&gl).unwrap();
+                    while gl2.client_wait_sync_with_u32(&sync, 0, 0)
+                        == WebGl2RenderingContext::TIMEOUT_EXPIRED
+                    {
+                        next_animation_frame().await;
+                    }
+                    gl2.delete_sync(Some(&sync));
+
+                    gl.bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, Some(&buffer));
+                    gl2.get_buffer_sub_data_with_i32_and_array_buffer_view(
+                        WebGl2RenderingContext::PIXEL_PACK_BUFFER,
+                        0,
+                        &output,
+                    );
+                    gl.bind_buffer(WebGl2RenderingContext::PIXEL_PACK_BUFFER, None);
+                    gl.delete_buffer(Some(&buffer));
+                }
+                PendingReadback::Snapshot(snapshot, frame_buffer) => {
+                    next_animation_frame().await;
+
+                    gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&frame_buffer));
+                    gl.read_pixels_with_opt_array_buffer_view(
+                        0,
+                        0,
+                        width,
+                        height,
+                        GL::RGBA,
+                        GL::FLOAT,
+                        Some(&output),
+                    )
+                    .unwrap();
+                    gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+                    gl.delete_framebuffer(Some(&frame_buffer));
+                    gl.delete_texture(Some(&snapshot));
+                }
+            }
+
+            output
+        })
+    }
+
     /// Return the input texture handle at the given index
     pub fn input_texture(&self, index: usize) -> &WebGlTexture {
-        &self.inputs[index].0
+        self.inputs[index].texture().unwrap()
     }
 
     /// Return an iterator of the input textures
     pub fn input_textures(&self) -> impl Iterator<Item = &WebGlTexture> {
-        self.inputs.iter().map(|(texture, _)| texture)
+        self.inputs.iter().filter_map(TextureUniform::texture)
+    }
+
+    /// Return the output texture handle at the given index
+    pub fn output_texture(&self, index: usize) -> &WebGlTexture {
+        &self.outputs.textures[index].0
     }
 
-    /// Return the output texture
-    pub fn output_texture(&self) -> &WebGlTexture {
-        &self.output_texture
+    /// Return an iterator of the output textures
+    pub fn output_textures(&self) -> impl Iterator<Item = &WebGlTexture> {
+        self.outputs.textures.iter().map(|(texture, _)| texture)
     }
 
     /// Set a given uniform
@@ -367,4 +840,14 @@ impl<Set: UniformSet> ComputeProgram<Set> {
     {
         self.uniforms.access().set_data(data);
     }
+
+    /// Set all members of a given uniform group
+    pub fn set_uniform_group<const UNIFORM_LOCATION: u32>(
+        &mut self,
+        data: <Set as UniformGroupConstAccess<UNIFORM_LOCATION>>::UniformGroupDataType,
+    ) where
+        Set: UniformGroupConstAccess<UNIFORM_LOCATION>,
+    {
+        self.uniforms.access_group().set_data(data);
+    }
 }
@@ -0,0 +1,66 @@
+//! Errors while setting up webgl resources
+
+use std::fmt::Display;
+
+/// An error while setting up webgl resources, e.g. in
+/// [`CanvasRenderer::initial_render_state`](crate::webgl::CanvasRenderer::initial_render_state)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebglError {
+    /// The browser or device does not support webgl
+    MissingContext,
+    /// A required extension is not supported by the context
+    MissingExtension(&'static str),
+    /// A webgl object (e.g. `"texture"`) could not be created, usually since the context was lost
+    CreationFailed(&'static str),
+    /// A shader failed to compile, with the info log
+    ShaderCompilation(String),
+    /// A program failed to link, with the info log
+    ProgramLinking(String),
+    /// A program has no active attribute of the given name
+    MissingAttribute(String),
+    /// A program has no active uniform of the given name
+    MissingUniform(String),
+    /// A program uses more samplers than the context has texture units
+    TextureUnitsExhausted(u32),
+    /// A texture exceeds the maximum texture size of the context
+    TextureTooLarge {
+        /// The requested width or height
+        size: u32,
+        /// The maximum width and height
+        max_size: u32,
+    },
+    /// An image could not be loaded from the given url
+    ImageLoading(String),
+}
+
+impl Display for WebglError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebglError::MissingContext => write!(f, "WebGL is not supported by this browser"),
+            WebglError::MissingExtension(name) => {
+                write!(f, "The required WebGL extension {name} is not supported")
+            }
+            WebglError::CreationFailed(object) => write!(f, "Failed to create a WebGL {object}"),
+            WebglError::ShaderCompilation(log) => write!(f, "Shader compilation failed: {log}"),
+            WebglError::ProgramLinking(log) => write!(f, "Program linking failed: {log}"),
+            WebglError::MissingAttribute(name) => {
+                write!(f, "Expected an active attribute named {name}")
+            }
+            WebglError::MissingUniform(name) => {
+                write!(f, "Expected an active uniform named {name}")
+            }
+            WebglError::TextureUnitsExhausted(max_units) => {
+                write!(f, "Only {max_units} texture units are available")
+            }
+            WebglError::TextureTooLarge { size, max_size } => {
+                write!(
+                    f,
+                    "Textures of size {size} exceed the maximum size of {max_size}"
+                )
+            }
+            WebglError::ImageLoading(url) => write!(f, "Failed to load the image {url}"),
+        }
+    }
+}
+
+impl std::error::Error for WebglError {}
@@ -0,0 +1,222 @@
+//! Vertex buffers declared by their attribute layout
+//!
+//! Instead of creating buffers and specifying attribute pointers with hand computed strides, a
+//! [`Geometry`] is created from [`VertexLayout`]s naming the attributes of each buffer. The
+//! attributes are interleaved in declaration order and captured in a [`Vao`].
+
+use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext as GL, js_sys::Float32Array};
+
+use crate::webgl::{Vao, VertexAttribute, WebglError, create_vertex_buffer, draw_arrays_instanced};
+
+/// The type of a floating point vertex attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeType {
+    /// A `float` attribute
+    Float,
+    /// A `vec2` attribute
+    Vec2,
+    /// A `vec3` attribute
+    Vec3,
+    /// A `vec4` attribute
+    Vec4,
+}
+
+impl AttributeType {
+    /// Returns the amount of floats of the attribute
+    pub fn components(self) -> usize {
+        match self {
+            AttributeType::Float => 1,
+            AttributeType::Vec2 => 2,
+            AttributeType::Vec3 => 3,
+            AttributeType::Vec4 => 4,
+        }
+    }
+}
+
+/// The named attributes of the vertices in a buffer, interleaved in declaration order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VertexLayout {
+    /// The attribute names and types
+    attributes: Vec<(&'static str, AttributeType)>,
+    /// Whether the buffer advances once per instance instead of once per vertex
+    per_instance: bool,
+}
+
+impl VertexLayout {
+    /// Create a layout without any attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an attribute of the given name in the program
+    pub fn attribute(mut self, name: &'static str, attribute_type: AttributeType) -> Self {
+        self.attributes.push((name, attribute_type));
+        self
+    }
+
+    /// Advance the attributes once per instance, see
+    /// [`vertex_attrib_divisor`](crate::webgl::vertex_attrib_divisor)
+    pub fn per_instance(mut self) -> Self {
+        self.per_instance = true;
+        self
+    }
+
+    /// Returns the amount of floats per vertex
+    pub fn floats_per_vertex(&self) -> usize {
+        self.attributes
+            .iter()
+            .map(|(_, attribute_type)| attribute_type.components())
+            .sum()
+    }
+
+    /// Returns the attributes of the layout in the given buffer
+    fn vertex_attributes(
+        &self,
+        gl: &GL,
+        program: &WebGlProgram,
+        buffer: &WebGlBuffer,
+    ) -> Result<Vec<VertexAttribute>, WebglError> {
+        let stride = (self.floats_per_vertex() * size_of::<f32>()) as i32;
+        let mut offset = 0;
+
+        self.attributes
+            .iter()
+            .map(|(name, attribute_type)| {
+                let size = attribute_type.components();
+                let mut attribute = VertexAttribute::new(gl, program, name, buffer, size as i32)?
+                    .with_divisor(self.per_instance.into());
+                attribute.stride = stride;
+                attribute.offset = (offset * size_of::<f32>()) as i32;
+                offset += size;
+                Ok(attribute)
+            })
+            .collect()
+    }
+}
+
+/// A set of vertex buffers and their attributes, bound with a single [`Vao`]
+#[derive(Debug)]
+pub struct Geometry {
+    /// The buffers with their layouts
+    buffers: Vec<(WebGlBuffer, VertexLayout)>,
+    /// The vertex array capturing the attributes of all buffers
+    vao: Vao,
+    /// The amount of vertices in the per-vertex buffers
+    vertex_count: usize,
+    /// The amount of instances in the per-instance buffers
+    instance_count: usize,
+}
+
+impl Geometry {
+    /// Create a buffer for each layout, holding the given interleaved data.
+    ///
+    /// Fails if the program has no active attribute of a declared name.
+    ///
+    /// # Panics
+    /// If the length of the data is not a multiple of the floats per vertex of its layout
+    pub fn new(
+        gl: &GL,
+        program: &WebGlProgram,
+        buffers: Vec<(VertexLayout, &[f32])>,
+    ) -> Result<Self, WebglError> {
+        let mut attributes = Vec::new();
+        let mut vertex_count = 0;
+        let mut instance_count = 0;
+        let buffers = buffers
+            .into_iter()
+            .map(|(layout, data)| {
+                let count = Self::vertex_count_of(&layout, data);
+                if layout.per_instance {
+                    instance_count = count;
+                } else {
+                    vertex_count = count;
+                }
+
+                let buffer = create_vertex_buffer(gl, data)?;
+                attributes.extend(layout.vertex_attributes(gl, program, &buffer)?);
+                Ok((buffer, layout))
+            })
+            .collect::<Result<_, WebglError>>()?;
+
+        Ok(Self {
+            buffers,
+            vao: Vao::new(gl, attributes),
+            vertex_count,
+            instance_count,
+        })
+    }
+
+    /// Returns the amount of vertices in the given data
+    fn vertex_count_of(layout: &VertexLayout, data: &[f32]) -> usize {
+        let floats_per_vertex = layout.floats_per_vertex().max(1);
+        assert_eq!(
+            data.len() % floats_per_vertex,
+            0,
+            "Vertex data does not match the layout of {floats_per_vertex} floats per vertex"
+        );
+        data.len() / floats_per_vertex
+    }
+
+    /// Replace the data of the buffer at the given index, e.g. for geometry changing every frame
+    ///
+    /// # Panics
+    /// If the length of the data is not a multiple of the floats per vertex of the layout
+    pub fn update(&mut self, gl: &GL, index: usize, data: &[f32]) {
+        let (buffer, layout) = &self.buffers[index];
+        let count = Self::vertex_count_of(layout, data);
+        if layout.per_instance {
+            self.instance_count = count;
+        } else {
+            self.vertex_count = count;
+        }
+
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(buffer));
+        gl.buffer_data_with_array_buffer_view(
+            GL::ARRAY_BUFFER,
+            &Float32Array::from(data),
+            GL::STREAM_DRAW,
+        );
+        gl.bind_buffer(GL::ARRAY_BUFFER, None);
+    }
+
+    /// Returns the amount of vertices in the per-vertex buffers
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// Returns the amount of instances in the per-instance buffers
+    pub fn instance_count(&self) -> usize {
+        self.instance_count
+    }
+
+    /// Bind the attributes for drawing
+    pub fn bind(&self, gl: &GL) {
+        self.vao.bind(gl);
+    }
+
+    /// Unbind the attributes
+    pub fn unbind(&self, gl: &GL) {
+        self.vao.unbind(gl);
+    }
+
+    /// Delete the buffers and the vertex array
+    pub fn delete(self, gl: &GL) {
+        for (buffer, _) in &self.buffers {
+            gl.delete_buffer(Some(buffer));
+        }
+        self.vao.delete(gl);
+    }
+
+    /// Draw all vertices, the attributes have to be bound
+    pub fn draw(&self, gl: &GL, mode: u32) {
+        gl.draw_arrays(mode, 0, self.vertex_count as i32);
+    }
+
+    /// Draw all vertices once per instance, the attributes have to be bound
+    ///
+    /// # Panics
+    /// If the context does not support instanced drawing
+    pub fn draw_instanced(&self, gl: &GL, mode: u32, instance_count: usize) {
+        draw_arrays_instanced(gl, mode, 0, self.vertex_count as i32, instance_count as i32);
+    }
+}
@@ -0,0 +1,87 @@
+//! Index buffers, for drawing vertices shared between primitives
+
+use std::ops::Range;
+
+use web_sys::{WebGlBuffer, WebGlRenderingContext as GL, js_sys::Uint16Array};
+
+use crate::webgl::{WebglError, draw_elements_instanced};
+
+/// A buffer of vertex indices (`ELEMENT_ARRAY_BUFFER`).
+///
+/// Indices are 16 bit, since larger ones require an extension in webgl1.
+#[derive(Debug)]
+pub struct IndexBuffer {
+    /// The buffer handle
+    buffer: WebGlBuffer,
+    /// The amount of indices in the buffer
+    count: usize,
+}
+
+impl IndexBuffer {
+    /// Create an index buffer containing the given indices
+    pub fn new(gl: &GL, indices: &[u16]) -> Result<Self, WebglError> {
+        let mut index_buffer = Self {
+            buffer: gl
+                .create_buffer()
+                .ok_or(WebglError::CreationFailed("buffer"))?,
+            count: 0,
+        };
+        index_buffer.set_indices(gl, indices);
+        Ok(index_buffer)
+    }
+
+    /// Replace the indices in the buffer
+    pub fn set_indices(&mut self, gl: &GL, indices: &[u16]) {
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.buffer));
+        gl.buffer_data_with_array_buffer_view(
+            GL::ELEMENT_ARRAY_BUFFER,
+            &Uint16Array::from(indices),
+            GL::STATIC_DRAW,
+        );
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, None);
+        self.count = indices.len();
+    }
+
+    /// Returns the amount of indices in the buffer
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Draw primitives of the given mode (e.g. `GL::TRIANGLES`) using all indices.
+    ///
+    /// The program and vertex attributes have to be set up already.
+    pub fn draw(&self, gl: &GL, mode: u32) {
+        self.draw_range(gl, mode, 0..self.count);
+    }
+
+    /// Draw primitives of the given mode using the given range of indices.
+    ///
+    /// # Panics
+    /// If the range exceeds the indices in the buffer
+    pub fn draw_range(&self, gl: &GL, mode: u32, range: Range<usize>) {
+        assert!(
+            range.end <= self.count,
+            "Index range {range:?} exceeds the {count} indices",
+            count = self.count
+        );
+
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.buffer));
+        gl.draw_elements_with_i32(
+            mode,
+            range.len() as i32,
+            GL::UNSIGNED_SHORT,
+            (range.start * size_of::<u16>()) as i32,
+        );
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, None);
+    }
+
+    /// Draw `instance_count` instances of the primitives of the given mode, using all indices.
+    ///
+    /// # Panics
+    /// If the context does not support instanced drawing
+    pub fn draw_instanced(&self, gl: &GL, mode: u32, instance_count: usize) {
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.buffer));
+        draw_elements_instanced(gl, mode, self.count as i32, 0, instance_count as i32);
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, None);
+    }
+}
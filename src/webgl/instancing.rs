@@ -0,0 +1,79 @@
+//! Instanced drawing, natively on webgl2 and using `ANGLE_instanced_arrays` on webgl1
+//!
+//! Instead of unrolling every vertex of every instance into a buffer, a base mesh is drawn once
+//! per instance, with per-instance attributes advancing only between instances (see
+//! [`vertex_attrib_divisor`]).
+
+use wasm_bindgen::JsCast;
+use web_sys::{AngleInstancedArrays, WebGlRenderingContext as GL};
+
+use crate::webgl::webgl2;
+
+/// Returns the instancing extension of a webgl1 context
+fn extension(gl: &GL) -> Option<AngleInstancedArrays> {
+    gl.get_extension("ANGLE_instanced_arrays")
+        .ok()
+        .flatten()
+        .map(JsCast::unchecked_into)
+}
+
+/// Returns whether the context supports instanced drawing
+pub fn instancing_supported(gl: &GL) -> bool {
+    webgl2(gl).is_some() || extension(gl).is_some()
+}
+
+/// Set the rate at which the attribute at the given location advances, 0 for every vertex and `n`
+/// for every `n` instances.
+///
+/// # Panics
+/// If the context does not support instanced drawing
+pub fn vertex_attrib_divisor(gl: &GL, location: u32, divisor: u32) {
+    if let Some(gl) = webgl2(gl) {
+        gl.vertex_attrib_divisor(location, divisor);
+    } else {
+        extension(gl)
+            .expect("Instanced drawing is not supported")
+            .vertex_attrib_divisor_angle(location, divisor);
+    }
+}
+
+/// Draw `instance_count` instances of the vertices `first..first + count`.
+///
+/// # Panics
+/// If the context does not support instanced drawing
+pub fn draw_arrays_instanced(gl: &GL, mode: u32, first: i32, count: i32, instance_count: i32) {
+    if let Some(gl) = webgl2(gl) {
+        gl.draw_arrays_instanced(mode, first, count, instance_count);
+    } else {
+        extension(gl)
+            .expect("Instanced drawing is not supported")
+            .draw_arrays_instanced_angle(mode, first, count, instance_count);
+    }
+}
+
+/// Draw `instance_count` instances of the `u16` indices in the bound element array buffer,
+/// starting at the given byte offset.
+///
+/// # Panics
+/// If the context does not support instanced drawing
+pub fn draw_elements_instanced(gl: &GL, mode: u32, count: i32, offset: i32, instance_count: i32) {
+    if let Some(gl) = webgl2(gl) {
+        gl.draw_elements_instanced_with_i32(
+            mode,
+            count,
+            GL::UNSIGNED_SHORT,
+            offset,
+            instance_count,
+        );
+    } else {
+        extension(gl)
+            .expect("Instanced drawing is not supported")
+            .draw_elements_instanced_angle_with_i32(
+                mode,
+                count,
+                GL::UNSIGNED_SHORT,
+                offset,
+                instance_count,
+            );
+    }
+}
@@ -1,15 +1,61 @@
 //! General webgl primitives
 
+use wasm_bindgen::JsCast;
+use web_sys::HtmlCanvasElement;
+use web_sys::WebGl2RenderingContext;
+use web_sys::WebGlContextAttributes;
 use web_sys::WebGlProgram;
 use web_sys::WebGlRenderingContext as GL;
 use web_sys::WebGlShader;
 
 mod canvas;
 mod compute;
+mod error;
+mod geometry;
+mod index_buffer;
+mod instancing;
+mod post_process;
+mod preprocess;
+mod replay;
+mod scheduler;
+mod stats;
+mod text;
+mod texture;
+mod timer;
+mod vao;
+mod xr;
 
-pub use canvas::{Canvas, CanvasProperties, CanvasRenderer, RenderData, RenderLoopState};
-pub use compute::{ComputeProgram, UniformConstAccess, UniformSet};
+pub use canvas::{
+    Canvas, CanvasMessage, CanvasProperties, CanvasRenderer, ContextOptions, MouseData,
+    RenderColors, RenderData, RenderLoopState, use_renderer,
+};
+pub use compute::{
+    ComputeProgram, UniformConstAccess, UniformGroup, UniformGroupConstAccess, UniformGroupData,
+    UniformSet,
+};
+pub use error::WebglError;
+pub use geometry::{AttributeType, Geometry, VertexLayout};
+pub use index_buffer::IndexBuffer;
+pub use instancing::{
+    draw_arrays_instanced, draw_elements_instanced, instancing_supported, vertex_attrib_divisor,
+};
+pub use post_process::{NoUniforms, PostProcessChain, PostProcessPass};
+pub use preprocess::preprocess_shader;
+pub use replay::{Replay, ReplayController, ReplayFrame, SeededRng};
+pub use scheduler::{CanvasScheduler, ChunkedTask, DEFAULT_CPU_BUDGET};
+pub use stats::FrameStats;
+pub use text::{TextAnchor, TextRenderer};
+pub use texture::{
+    LoadedTexture, TextureUniform, TextureUnits, create_ramp_texture, load_texture,
+    update_ramp_texture,
+};
+pub use timer::GpuTimer;
+pub use vao::{Vao, VertexAttribute, create_vertex_buffer};
 use web_sys::WebGlUniformLocation;
+pub use xr::{
+    Camera, Matrix4, OrbitCamera, OrbitRenderState, OrbitRenderer, SceneRenderer, XrViewer,
+    XrViewerProperties,
+};
 
 /// Wrapper around a uniform location and data
 #[derive(Debug)]
@@ -25,27 +71,29 @@ pub struct Uniform<Data> {
 impl<Data: UniformData> Uniform<Data> {
     /// Create a new uniform wrapper around a uniform in the given program.
     ///
-    /// # Panics
-    /// If the uniform name can not be resolved.
-    pub fn new(gl: &GL, program: &WebGlProgram, name: impl Into<String>, data: Data) -> Self {
-        let name: String = name.into();
+    /// Fails if the program has no active uniform of that name, e.g. since the driver removed it
+    /// for not being used by the shader.
+    pub fn new(
+        gl: &GL,
+        program: &WebGlProgram,
+        name: impl Into<String>,
+        data: Data,
+    ) -> Result<Self, WebglError> {
+        let uniform = Self::optional(gl, program, name, data);
+        if uniform.location.is_none() {
+            return Err(WebglError::MissingUniform(uniform.name));
+        }
+
+        Ok(uniform)
+    }
 
+    /// Create a new uniform wrapper around a uniform the shader may leave out.
+    ///
+    /// Used for uniforms provided to every shader of a kind, like the `u_dimensions` of
+    /// compute programs, applying them is skipped if the program has no such uniform.
+    pub fn optional(gl: &GL, program: &WebGlProgram, name: impl Into<String>, data: Data) -> Self {
+        let name: String = name.into();
         let location = gl.get_uniform_location(program, &name);
-        if location.is_none() {
-            let max_uniforms = gl
-                .get_program_parameter(program, GL::ACTIVE_UNIFORMS)
-                .as_f64()
-                .unwrap() as u32;
-            let valid_locations: String = (0..max_uniforms)
-                .map(|i| gl.get_active_uniform(program, i).unwrap().name())
-                .reduce(|mut a, b| {
-                    a.push_str(", ");
-                    a.push_str(&b);
-                    a
-                })
-                .unwrap_or("No options found".to_owned());
-            log::error!("Expected valid uniform location\nGot: {name}\nOptions: {valid_locations}");
-        };
 
         Self {
             name,
@@ -68,11 +116,6 @@ impl<Data: UniformData> Uniform<Data> {
     pub fn apply(&self, gl: &GL) {
         if let Some(location) = self.location.as_ref() {
             self.data.apply(gl, location);
-        } else {
-            log::warn!(
-                "Tried to set uniform without valid location: {name}",
-                name = self.name()
-            );
         }
     }
 
@@ -142,27 +185,101 @@ impl_uniform_data! {
     }
 }
 
+/// Implement uniform data for arrays, slices and vectors, uploaded with the `*v` variants
+macro_rules! impl_uniform_array_data {
+    ($($type:ty: $func:ident),* $(,)?) => {
+        $(
+            impl<const N: usize> UniformData for [$type; N] {
+                fn apply(&self, gl: &GL, location: &WebGlUniformLocation) {
+                    gl.$func(Some(location), self);
+                }
+            }
+
+            impl UniformData for &[$type] {
+                fn apply(&self, gl: &GL, location: &WebGlUniformLocation) {
+                    gl.$func(Some(location), self);
+                }
+            }
+
+            impl UniformData for Vec<$type> {
+                fn apply(&self, gl: &GL, location: &WebGlUniformLocation) {
+                    gl.$func(Some(location), self);
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_array_data! {
+    f32: uniform1fv_with_f32_array,
+    i32: uniform1iv_with_i32_array,
+}
+
+/// Returns the given extension of the context
+pub fn get_extension(gl: &GL, name: &'static str) -> Result<web_sys::js_sys::Object, WebglError> {
+    gl.get_extension(name)
+        .ok()
+        .flatten()
+        .ok_or(WebglError::MissingExtension(name))
+}
+
+/// Returns a webgl context of the given canvas, preferring webgl2 and falling back to webgl1.
+///
+/// Webgl2 contexts support every webgl1 method, so both are returned as [`GL`], use [`webgl2`] to
+/// access the additional webgl2 functionality. Once a context has been created, the same context
+/// is returned on every call.
+pub fn get_context(canvas: &HtmlCanvasElement, attributes: &WebGlContextAttributes) -> Option<GL> {
+    ["webgl2", "webgl"].into_iter().find_map(|context_id| {
+        canvas
+            .get_context_with_context_options(context_id, attributes)
+            .ok()
+            .flatten()
+            .map(JsCast::unchecked_into)
+    })
+}
+
+/// Returns the given context as a webgl2 context, if it was created as one
+pub fn webgl2(gl: &GL) -> Option<&WebGl2RenderingContext> {
+    gl.dyn_ref()
+}
+
 /// Compile a [`WebGlShader`] and log any errors to the console
 pub fn compile_shader(
     gl: &GL,
     shader_type: u32,
     shader_source: impl AsRef<str>,
-) -> Option<WebGlShader> {
-    let shader = gl.create_shader(shader_type).unwrap();
+) -> Result<WebGlShader, WebglError> {
+    let shader = gl
+        .create_shader(shader_type)
+        .ok_or(WebglError::CreationFailed("shader"))?;
 
     gl.shader_source(&shader, shader_source.as_ref());
     gl.compile_shader(&shader);
     let success = gl
         .get_shader_parameter(&shader, GL::COMPILE_STATUS)
         .as_bool()
-        .unwrap();
+        .unwrap_or(false);
 
     if success {
-        Some(shader)
+        Ok(shader)
     } else {
-        let log = gl.get_shader_info_log(&shader).unwrap();
+        let log = gl.get_shader_info_log(&shader).unwrap_or_default();
         log::error!("{log}");
-        None
+        #[cfg(debug_assertions)]
+        {
+            // The info log refers to lines by number, e.g. `ERROR: 0:12: ...`
+            let numbered_source: String = shader_source
+                .as_ref()
+                .lines()
+                .enumerate()
+                .map(|(index, line)| format!("{:>4} | {line}\n", index + 1))
+                .collect();
+            canvas::report_shader_error(
+                gl,
+                format!("Shader compilation failed\n\n{log}\n{numbered_source}"),
+            );
+        }
+        Err(WebglError::ShaderCompilation(log))
     }
 }
 
@@ -171,8 +288,10 @@ pub fn create_program(
     gl: &GL,
     vertex_shader: &WebGlShader,
     fragment_shader: &WebGlShader,
-) -> Option<WebGlProgram> {
-    let program = gl.create_program().unwrap();
+) -> Result<WebGlProgram, WebglError> {
+    let program = gl
+        .create_program()
+        .ok_or(WebglError::CreationFailed("program"))?;
 
     gl.attach_shader(&program, vertex_shader);
     gl.attach_shader(&program, fragment_shader);
@@ -181,13 +300,15 @@ pub fn create_program(
     let success = gl
         .get_program_parameter(&program, GL::LINK_STATUS)
         .as_bool()
-        .unwrap();
+        .unwrap_or(false);
 
     if success {
-        Some(program)
+        Ok(program)
     } else {
-        let log = gl.get_program_info_log(&program).unwrap();
+        let log = gl.get_program_info_log(&program).unwrap_or_default();
         log::error!("{log}");
-        None
+        #[cfg(debug_assertions)]
+        canvas::report_shader_error(gl, format!("Program linking failed\n\n{log}"));
+        Err(WebglError::ProgramLinking(log))
     }
 }
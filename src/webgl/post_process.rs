@@ -0,0 +1,342 @@
+//! Full-screen post processing passes over the output of a renderer
+
+use std::any::Any;
+
+use web_sys::{
+    WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlRenderbuffer, WebGlRenderingContext as GL,
+    WebGlTexture,
+};
+
+use crate::uniform_set;
+use crate::webgl::{
+    TextureUniform, TextureUnits, Uniform, UniformConstAccess, UniformData,
+    UniformGroupConstAccess, UniformSet, WebglError, compile_shader, create_program,
+    create_vertex_buffer,
+};
+
+// The uniforms of a pass without additional parameters
+uniform_set! {
+    pub NoUniforms {}
+}
+
+/// Vertex shader for drawing the space filling quad
+const VERTEX_SOURCE: &str = "
+    attribute vec2 a_position;
+
+    void main() {
+        gl_Position = vec4(a_position, 0.0, 1.0);
+    }
+";
+
+/// Vertex shader for drawing the space filling quad, for `#version 300 es` fragment shaders
+const VERTEX_SOURCE_300: &str = "#version 300 es
+    in vec2 a_position;
+
+    void main() {
+        gl_Position = vec4(a_position, 0.0, 1.0);
+    }
+";
+
+/// Fragment shader presenting the scene unchanged, if a chain has no passes
+const COPY_SOURCE: &str = "
+    precision mediump float;
+
+    uniform sampler2D u_input;
+    uniform vec2 u_dimensions;
+
+    void main() {
+        gl_FragColor = texture2D(u_input, gl_FragCoord.xy / u_dimensions);
+    }
+";
+
+/// Vertex coordinates for a space filling quad
+const VERTICES: [f32; 12] = [
+    -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
+];
+
+/// A full-screen fragment pass of a [`PostProcessChain`].
+///
+/// Like a [`ComputeProgram`](crate::webgl::ComputeProgram), the pass is defined by its fragment
+/// shader source. The shader reads the result of the previous pass from the `u_input` sampler,
+/// receives the size of the canvas in `u_dimensions` and any additional uniforms of the
+/// [`UniformSet`].
+#[derive(Debug)]
+pub struct PostProcessPass<Set: UniformSet> {
+    /// The program of the pass
+    program: WebGlProgram,
+    /// The location of the quad vertex attribute
+    position: u32,
+    /// The sampler of the previous result
+    input: TextureUniform,
+    /// The dimension uniform
+    dimensions_uniform: Uniform<(f32, f32)>,
+    /// Any additional uniforms for the fragment shader
+    uniforms: Set,
+}
+
+impl<Set: UniformSet> PostProcessPass<Set> {
+    /// Creates a pass from the given fragment shader source.
+    ///
+    /// Fragment shaders starting with `#version 300 es` require a webgl2 context.
+    pub fn new(gl: &GL, fragment_source: impl AsRef<str>) -> Result<Self, WebglError> {
+        let fragment_source = fragment_source.as_ref();
+        let vertex_source = if fragment_source.trim_start().starts_with("#version 300 es") {
+            VERTEX_SOURCE_300
+        } else {
+            VERTEX_SOURCE
+        };
+        let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, vertex_source)?;
+        let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, fragment_source)?;
+        let program = create_program(gl, &vertex_shader, &fragment_shader)?;
+
+        let position = gl
+            .get_attrib_location(&program, "a_position")
+            .try_into()
+            .map_err(|_| WebglError::MissingAttribute("a_position".to_owned()))?;
+        let input = TextureUnits::new().uniform(gl, &program, "u_input", None)?;
+        let dimensions_uniform = Uniform::optional(gl, &program, "u_dimensions", (1.0, 1.0));
+        let uniforms = Set::initialize(gl, &program)?;
+
+        Ok(Self {
+            program,
+            position,
+            input,
+            dimensions_uniform,
+            uniforms,
+        })
+    }
+
+    /// Set a given uniform
+    ///
+    /// # Panics
+    /// If the uniform was not given to the constructor
+    pub fn set_uniform<const UNIFORM_LOCATION: u32>(
+        &mut self,
+        data: <Set as UniformConstAccess<UNIFORM_LOCATION>>::UniformDataType,
+    ) where
+        Set: UniformConstAccess<UNIFORM_LOCATION>,
+        <Set as UniformConstAccess<UNIFORM_LOCATION>>::UniformDataType: UniformData,
+    {
+        self.uniforms.access().set_data(data);
+    }
+
+    /// Set all members of a given uniform group
+    pub fn set_uniform_group<const UNIFORM_LOCATION: u32>(
+        &mut self,
+        data: <Set as UniformGroupConstAccess<UNIFORM_LOCATION>>::UniformGroupDataType,
+    ) where
+        Set: UniformGroupConstAccess<UNIFORM_LOCATION>,
+    {
+        self.uniforms.access_group().set_data(data);
+    }
+}
+
+/// A pass of any uniform set, run by a [`PostProcessChain`]
+trait PostProcessEffect: Any {
+    /// Draw the space filling quad, reading from the given texture
+    fn run(&mut self, gl: &GL, input: &WebGlTexture, size: (u32, u32), vertex_buffer: &WebGlBuffer);
+}
+
+impl<Set: UniformSet + 'static> PostProcessEffect for PostProcessPass<Set> {
+    fn run(
+        &mut self,
+        gl: &GL,
+        input: &WebGlTexture,
+        size: (u32, u32),
+        vertex_buffer: &WebGlBuffer,
+    ) {
+        gl.use_program(Some(&self.program));
+
+        self.input.apply_texture(gl, Some(input.clone()));
+        self.dimensions_uniform
+            .apply_data(gl, (size.0 as f32, size.1 as f32));
+        self.uniforms.apply_all(gl);
+
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(vertex_buffer));
+        gl.vertex_attrib_pointer_with_i32(self.position, 2, GL::FLOAT, false, 0, 0);
+        gl.enable_vertex_attrib_array(self.position);
+
+        gl.draw_arrays(GL::TRIANGLES, 0, 6);
+
+        gl.disable_vertex_attrib_array(self.position);
+        gl.bind_buffer(GL::ARRAY_BUFFER, None);
+        self.input.unbind(gl);
+        gl.use_program(None);
+    }
+}
+
+/// A texture with a framebuffer rendering to it
+#[derive(Debug)]
+struct RenderTarget {
+    /// The color texture
+    texture: WebGlTexture,
+    /// The framebuffer rendering to the texture
+    frame_buffer: WebGlFramebuffer,
+}
+
+impl RenderTarget {
+    /// Create an empty target, allocated by [`RenderTarget::resize`]
+    fn new(gl: &GL) -> Result<Self, WebglError> {
+        let texture = gl
+            .create_texture()
+            .ok_or(WebglError::CreationFailed("texture"))?;
+        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        let frame_buffer = gl
+            .create_framebuffer()
+            .ok_or(WebglError::CreationFailed("framebuffer"))?;
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&frame_buffer));
+        gl.framebuffer_texture_2d(
+            GL::FRAMEBUFFER,
+            GL::COLOR_ATTACHMENT0,
+            GL::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+
+        Ok(Self {
+            texture,
+            frame_buffer,
+        })
+    }
+
+    /// Reallocate the texture with the given size, clearing its contents
+    fn resize(&self, gl: &GL, (width, height): (u32, u32)) -> Result<(), WebglError> {
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.texture));
+        gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            GL::TEXTURE_2D,
+            0,
+            GL::RGBA as i32,
+            width as i32,
+            height as i32,
+            0,
+            GL::RGBA,
+            GL::UNSIGNED_BYTE,
+            None,
+        )
+        .map_err(|_| WebglError::CreationFailed("texture"))?;
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        Ok(())
+    }
+}
+
+/// A sequence of full-screen [`PostProcessPass`]es (e.g. bloom, blur or tone-mapping) applied to
+/// the output of a renderer before presenting it.
+///
+/// The renderer draws the scene between [`PostProcessChain::begin`] and
+/// [`PostProcessChain::present`]. Each pass reads the result of the previous one, the last pass
+/// renders to the canvas.
+pub struct PostProcessChain {
+    /// The size of the intermediate textures
+    size: (u32, u32),
+    /// The target the scene is drawn to
+    scene: RenderTarget,
+    /// The depth buffer of the scene
+    scene_depth: WebGlRenderbuffer,
+    /// The targets the passes alternate between
+    targets: [RenderTarget; 2],
+    /// The vertex buffer of the space filling quad
+    vertex_buffer: WebGlBuffer,
+    /// The passes, in order
+    passes: Vec<Box<dyn PostProcessEffect>>,
+    /// Presents the scene if there are no passes
+    copy: PostProcessPass<NoUniforms>,
+}
+
+impl PostProcessChain {
+    /// Create a chain without any passes, presenting the scene unchanged
+    pub fn new(gl: &GL) -> Result<Self, WebglError> {
+        let scene = RenderTarget::new(gl)?;
+        let scene_depth = gl
+            .create_renderbuffer()
+            .ok_or(WebglError::CreationFailed("renderbuffer"))?;
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&scene.frame_buffer));
+        gl.framebuffer_renderbuffer(
+            GL::FRAMEBUFFER,
+            GL::DEPTH_ATTACHMENT,
+            GL::RENDERBUFFER,
+            Some(&scene_depth),
+        );
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+
+        Ok(Self {
+            size: (0, 0),
+            scene,
+            scene_depth,
+            targets: [RenderTarget::new(gl)?, RenderTarget::new(gl)?],
+            vertex_buffer: create_vertex_buffer(gl, &VERTICES)?,
+            passes: Vec::new(),
+            copy: PostProcessPass::new(gl, COPY_SOURCE)?,
+        })
+    }
+
+    /// Append a pass to the chain
+    pub fn with_pass<Set: UniformSet + 'static>(mut self, pass: PostProcessPass<Set>) -> Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    /// Returns the pass at the given index, e.g. for setting its uniforms.
+    ///
+    /// Returns `None` if there is no such pass, or it has a different uniform set.
+    pub fn pass_mut<Set: UniformSet + 'static>(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut PostProcessPass<Set>> {
+        let pass: &mut dyn Any = self.passes.get_mut(index)?.as_mut();
+        pass.downcast_mut()
+    }
+
+    /// Bind the scene framebuffer of the given size, which the renderer draws to afterwards
+    pub fn begin(&mut self, gl: &GL, width: u32, height: u32) -> Result<(), WebglError> {
+        if self.size != (width, height) {
+            self.size = (width, height);
+            for target in [&self.scene, &self.targets[0], &self.targets[1]] {
+                target.resize(gl, self.size)?;
+            }
+            gl.bind_renderbuffer(GL::RENDERBUFFER, Some(&self.scene_depth));
+            gl.renderbuffer_storage(
+                GL::RENDERBUFFER,
+                GL::DEPTH_COMPONENT16,
+                width as i32,
+                height as i32,
+            );
+            gl.bind_renderbuffer(GL::RENDERBUFFER, None);
+        }
+
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.scene.frame_buffer));
+        gl.viewport(0, 0, width as i32, height as i32);
+
+        Ok(())
+    }
+
+    /// Run all passes over the scene, presenting the result on the canvas
+    pub fn present(&mut self, gl: &GL) {
+        let (width, height) = self.size;
+        gl.viewport(0, 0, width as i32, height as i32);
+
+        let mut input = &self.scene.texture;
+        let last = self.passes.len().saturating_sub(1);
+        for (index, pass) in self.passes.iter_mut().enumerate() {
+            let target = (index != last).then(|| &self.targets[index % 2]);
+            gl.bind_framebuffer(GL::FRAMEBUFFER, target.map(|target| &target.frame_buffer));
+            pass.run(gl, input, self.size, &self.vertex_buffer);
+            if let Some(target) = target {
+                input = &target.texture;
+            }
+        }
+
+        if self.passes.is_empty() {
+            gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+            self.copy.run(gl, input, self.size, &self.vertex_buffer);
+        }
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+    }
+}
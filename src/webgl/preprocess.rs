@@ -0,0 +1,58 @@
+//! A small GLSL preprocessor, resolving shared snippets and injecting constants
+
+use std::fmt::{Display, Write};
+
+/// Shared GLSL snippets, available through `#include "<name>"`
+const INCLUDES: &[(&str, &str)] = &[(
+    "texture_array.glsl",
+    include_str!("shaders/texture_array.glsl"),
+)];
+
+/// How deep includes may be nested, guarding against include cycles
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Prepare a shader source for [`compile_shader`](crate::webgl::compile_shader).
+///
+/// Lines of the form `#include "<name>"` are replaced with the snippet of that name from
+/// `src/webgl/shaders`, and each of the given defines is injected as `#define <name> <value>`
+/// (after the `#version` directive, if there is one).
+pub fn preprocess_shader(source: &str, defines: &[(&str, &dyn Display)]) -> String {
+    let mut output = String::with_capacity(source.len());
+
+    let mut lines = source.lines().peekable();
+    if let Some(version) = lines.next_if(|line| line.trim_start().starts_with("#version")) {
+        output.push_str(version);
+        output.push('\n');
+    }
+    for (name, value) in defines {
+        let _ = writeln!(output, "#define {name} {value}");
+    }
+    for line in lines {
+        write_line(&mut output, line, 0);
+    }
+
+    output
+}
+
+/// Append a line to the output, resolving includes
+fn write_line(output: &mut String, line: &str, depth: usize) {
+    let Some(name) = line
+        .trim()
+        .strip_prefix("#include")
+        .map(|name| name.trim().trim_matches('"'))
+    else {
+        output.push_str(line);
+        output.push('\n');
+        return;
+    };
+
+    match INCLUDES.iter().find(|(include, _)| *include == name) {
+        Some((_, snippet)) if depth < MAX_INCLUDE_DEPTH => {
+            for line in snippet.lines() {
+                write_line(output, line, depth + 1);
+            }
+        }
+        Some(_) => log::error!("Shader includes nested too deep at \"{name}\""),
+        None => log::error!("Unknown shader include \"{name}\""),
+    }
+}
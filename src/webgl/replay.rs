@@ -0,0 +1,249 @@
+//! Recording and deterministic playback of canvas render loops
+
+use std::{cell::RefCell, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+use web_sys::js_sys::Math::random;
+
+use crate::webgl::{MouseData, RenderData};
+
+/// A small deterministic random number generator (xorshift32).
+///
+/// Renderers should draw all random values from a generator seeded with [`RenderData::seed`], so
+/// their output can be reproduced by a [`Replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng(u32);
+
+impl SeededRng {
+    /// Create a new generator from the given seed
+    pub fn new(seed: u32) -> Self {
+        // Xorshift gets stuck on zero
+        Self(seed.max(1))
+    }
+
+    /// Returns a random seed for a new generator
+    pub fn random_seed() -> u32 {
+        (random() * f64::from(u32::MAX)) as u32
+    }
+
+    /// Returns the next random value in `[0, 1)`
+    pub fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+
+        (self.0 >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+/// A single recorded frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame<I> {
+    /// The render data passed to the renderer
+    pub render_data: RenderData,
+    /// The render input, only recorded if it changed since the previous frame
+    pub input: Option<I>,
+}
+
+/// A recording of a render loop, which can be played back deterministically
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay<I> {
+    /// The seed the render state was initialized with
+    pub seed: u32,
+    /// All recorded frames
+    pub frames: Vec<ReplayFrame<I>>,
+}
+
+/// The current replay mode of a canvas
+#[derive(Debug)]
+enum ReplayState<I> {
+    /// Rendering normally
+    Live,
+    /// Recording every frame
+    Recording(Replay<I>),
+    /// Playing back a recording
+    Playing {
+        /// The recording
+        replay: Rc<Replay<I>>,
+        /// The index of the next frame
+        frame: usize,
+        /// The input to restore after playback, updated with inputs received from outside
+        live_input: Option<I>,
+    },
+}
+
+/// Shared data behind a [`ReplayController`]
+#[derive(Debug)]
+struct ReplayControllerInner<I> {
+    /// The current mode
+    state: ReplayState<I>,
+    /// A seed the render state should be reinitialized with on the next frame
+    restart: Option<u32>,
+}
+
+/// A handle for recording and playing back the render loop of a [`Canvas`](super::Canvas)
+#[derive(Debug)]
+pub struct ReplayController<I> {
+    inner: Rc<RefCell<ReplayControllerInner<I>>>,
+}
+
+impl<I> Clone for ReplayController<I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<I> PartialEq for ReplayController<I> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<I> Default for ReplayController<I> {
+    fn default() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(ReplayControllerInner {
+                state: ReplayState::Live,
+                restart: None,
+            })),
+        }
+    }
+}
+
+impl<I: Clone> ReplayController<I> {
+    /// Restart rendering with a fresh seed and record every following frame
+    pub fn start_recording(&self) {
+        let seed = SeededRng::random_seed();
+        let mut inner = self.inner.borrow_mut();
+        inner.state = ReplayState::Recording(Replay {
+            seed,
+            frames: Vec::new(),
+        });
+        inner.restart = Some(seed);
+    }
+
+    /// Stop recording, returning the recorded replay
+    pub fn stop_recording(&self) -> Option<Replay<I>> {
+        let mut inner = self.inner.borrow_mut();
+        match std::mem::replace(&mut inner.state, ReplayState::Live) {
+            ReplayState::Recording(replay) => Some(replay),
+            state => {
+                inner.state = state;
+                None
+            }
+        }
+    }
+
+    /// Restart rendering from the given replay, returning to live rendering after its last frame
+    pub fn play(&self, replay: Replay<I>) {
+        let mut inner = self.inner.borrow_mut();
+        inner.restart = Some(replay.seed);
+        inner.state = ReplayState::Playing {
+            replay: Rc::new(replay),
+            frame: 0,
+            live_input: None,
+        };
+    }
+
+    /// Returns whether frames are currently being recorded
+    pub fn is_recording(&self) -> bool {
+        matches!(self.inner.borrow().state, ReplayState::Recording(_))
+    }
+
+    /// Returns whether a replay is currently being played back
+    pub fn is_playing(&self) -> bool {
+        matches!(self.inner.borrow().state, ReplayState::Playing { .. })
+    }
+
+    /// Hand a new render input from outside to the controller.
+    ///
+    /// Returns the input if it should be applied right away, during playback it is held back until
+    /// the replay finished.
+    pub(super) fn intercept_input(&self, input: I) -> Option<I> {
+        match &mut self.inner.borrow_mut().state {
+            ReplayState::Playing { live_input, .. } => {
+                *live_input = Some(input);
+                None
+            }
+            _ => Some(input),
+        }
+    }
+
+    /// Take the seed the render state needs to be reinitialized with, if any
+    pub(super) fn take_restart(&self) -> Option<u32> {
+        self.inner.borrow_mut().restart.take()
+    }
+
+    /// Process a single frame, recording it or replacing data and input with the recorded ones
+    pub(super) fn frame(&self, render_data: RenderData, input: &mut I) -> RenderData {
+        let mut inner = self.inner.borrow_mut();
+        match &mut inner.state {
+            ReplayState::Live => render_data,
+            ReplayState::Recording(replay) => {
+                let input_changed = replay.frames.is_empty() || render_data.input_changed;
+                replay.frames.push(ReplayFrame {
+                    render_data,
+                    input: input_changed.then(|| input.clone()),
+                });
+                render_data
+            }
+            ReplayState::Playing {
+                replay,
+                frame,
+                live_input,
+            } => match replay.frames.get(*frame) {
+                Some(recorded) => {
+                    // Keep the current input around to restore it after playback
+                    if live_input.is_none() {
+                        *live_input = Some(input.clone());
+                    }
+                    if let Some(recorded_input) = &recorded.input {
+                        *input = recorded_input.clone();
+                    }
+                    *frame += 1;
+                    let recorded = recorded.render_data;
+                    // The mouse is moved to the same relative position on the canvas of the viewer
+                    let scale = |value: u32, recorded: u32, viewer: u32| {
+                        (u64::from(value) * u64::from(viewer) / u64::from(recorded.max(1))) as u32
+                    };
+                    let mouse_data = MouseData {
+                        position: recorded.mouse_data.position.map(|(x, y)| {
+                            (
+                                scale(x, recorded.logical_width, render_data.logical_width),
+                                scale(y, recorded.logical_height, render_data.logical_height),
+                            )
+                        }),
+                        ..recorded.mouse_data
+                    };
+                    RenderData {
+                        initial_render: render_data.initial_render,
+                        // The replay is drawn at the canvas size of the viewer
+                        width: render_data.width,
+                        height: render_data.height,
+                        logical_width: render_data.logical_width,
+                        logical_height: render_data.logical_height,
+                        resized: render_data.resized,
+                        mouse_data,
+                        // The replay is drawn in the theme of the viewer, not of the recording
+                        colors: render_data.colors,
+                        // The progress belongs to the render state of the viewer
+                        loading_progress: render_data.loading_progress,
+                        ..recorded
+                    }
+                }
+                None => {
+                    if let Some(live_input) = live_input.take() {
+                        *input = live_input;
+                    }
+                    inner.state = ReplayState::Live;
+                    RenderData {
+                        input_changed: true,
+                        ..render_data
+                    }
+                }
+            },
+        }
+    }
+}
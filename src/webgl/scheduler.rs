@@ -0,0 +1,100 @@
+//! Site-wide coordination of all canvas render loops
+
+use web_sys::js_sys::Date;
+
+/// The default amount of milliseconds CPU-side work may take per frame
+pub const DEFAULT_CPU_BUDGET: u32 = 4;
+
+/// Scheduling constraints shared by all [`Canvas`](super::Canvas) render loops.
+///
+/// Provide this through a [`ContextProvider`](yew::ContextProvider), every canvas below picks up
+/// changes immediately. Canvases without a provided scheduler use the [`Default`] one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanvasScheduler {
+    /// The maximum amount of frames rendered per second, `None` for no limit
+    pub max_fps: Option<u32>,
+    /// Whether all render loops are suspended
+    pub paused: bool,
+    /// The amount of milliseconds CPU-side work of a renderer may take per frame, passed on as
+    /// [`RenderData::cpu_budget`](super::RenderData::cpu_budget)
+    pub cpu_budget: u32,
+    /// Whether renderers should reduce their expensive work, passed on as
+    /// [`RenderData::low_power`](super::RenderData::low_power)
+    pub low_power: bool,
+}
+
+impl Default for CanvasScheduler {
+    fn default() -> Self {
+        Self {
+            max_fps: None,
+            paused: false,
+            cpu_budget: DEFAULT_CPU_BUDGET,
+            low_power: false,
+        }
+    }
+}
+
+impl CanvasScheduler {
+    /// Returns the minimum amount of milliseconds between two rendered frames
+    pub fn frame_interval(&self) -> u32 {
+        self.max_fps.map_or(0, |fps| 1000 / fps.max(1))
+    }
+}
+
+/// CPU-side work split into steps, executed across as many frames as needed so that no frame takes
+/// longer than its [`cpu_budget`](super::RenderData::cpu_budget).
+///
+/// Renderers keep the task in their render state, [restart](Self::restart) it whenever the work
+/// has to be redone and [run](Self::run) it each frame until it is finished.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedTask {
+    /// The index of the next step
+    next_step: usize,
+    /// The total amount of steps
+    steps: usize,
+}
+
+impl ChunkedTask {
+    /// The amount of steps run between checking the elapsed time
+    const STEPS_PER_CHECK: usize = 32;
+
+    /// Start over with the given amount of steps
+    pub fn restart(&mut self, steps: usize) {
+        self.next_step = 0;
+        self.steps = steps;
+    }
+
+    /// Returns whether all steps have been run
+    pub fn finished(&self) -> bool {
+        self.next_step >= self.steps
+    }
+
+    /// Returns the fraction of steps run so far, between 0 and 1
+    pub fn progress(&self) -> f32 {
+        if self.steps == 0 {
+            1.0
+        } else {
+            self.next_step as f32 / self.steps as f32
+        }
+    }
+
+    /// Run the next steps until the task is finished or the budget in milliseconds is used up.
+    ///
+    /// At least a few steps are run every time, so the task finishes even with a tiny budget.
+    /// Returns whether the task is finished.
+    pub fn run(&mut self, budget: u32, mut step: impl FnMut(usize)) -> bool {
+        let start = Date::now();
+        while !self.finished() {
+            let end = (self.next_step + Self::STEPS_PER_CHECK).min(self.steps);
+            for index in self.next_step..end {
+                step(index);
+            }
+            self.next_step = end;
+
+            if Date::now() - start >= f64::from(budget) {
+                break;
+            }
+        }
+        self.finished()
+    }
+}
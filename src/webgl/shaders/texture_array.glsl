@@ -0,0 +1,7 @@
+// Reads the value at the given index of a 1D array stored row by row in a 2D texture
+vec4 getValueFrom2DTextureAs1DArray(sampler2D tex, vec2 dimensions, float index) {
+    float y = floor(index / dimensions.x);
+    float x = mod(index, dimensions.x);
+    vec2 texcoord = (vec2(x, y) + 0.5) / dimensions;
+    return texture2D(tex, texcoord);
+}
@@ -0,0 +1,40 @@
+//! Frame time statistics of a render loop
+
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// A shared handle collecting the frame times of a [`Canvas`](super::Canvas).
+///
+/// Pass it to the canvas as a property, the canvas records the
+/// [`delta_time`](super::RenderData::delta_time) of every rendered frame.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    /// The most recent frame times in milliseconds, oldest first
+    frame_times: Rc<RefCell<VecDeque<f64>>>,
+}
+
+impl PartialEq for FrameStats {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.frame_times, &other.frame_times)
+    }
+}
+
+impl Eq for FrameStats {}
+
+impl FrameStats {
+    /// The amount of frames kept
+    pub const HISTORY: usize = 120;
+
+    /// Record the time a frame took in milliseconds
+    pub(super) fn record(&self, frame_time: f64) {
+        let mut frame_times = self.frame_times.borrow_mut();
+        if frame_times.len() == Self::HISTORY {
+            frame_times.pop_front();
+        }
+        frame_times.push_back(frame_time);
+    }
+
+    /// Returns the recorded frame times in milliseconds, oldest first
+    pub fn frame_times(&self) -> Vec<f64> {
+        self.frame_times.borrow().iter().copied().collect()
+    }
+}
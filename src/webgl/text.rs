@@ -0,0 +1,393 @@
+//! Rendering text labels with webgl
+//!
+//! Labels are rasterized once into a texture atlas using an offscreen 2d canvas and then drawn
+//! as textured quads, so renderers can annotate their output without any dom overlays.
+
+use std::collections::HashMap;
+
+use gloo::utils::document;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, WebGlProgram, WebGlRenderingContext as GL,
+    WebGlTexture,
+};
+
+use crate::{
+    fonts,
+    webgl::{
+        AttributeType, Geometry, Uniform, VertexLayout, WebglError, compile_shader, create_program,
+    },
+};
+
+/// The width and height of the atlas texture
+const ATLAS_SIZE: u32 = 1024;
+
+/// Empty space around each label, so neighbouring labels do not bleed into each other
+const LABEL_PADDING: u32 = 2;
+
+/// The amount of floats per vertex (position, texture coordinates, color)
+const VERTEX_FLOATS: usize = 8;
+
+/// The horizontal alignment of a label relative to its position
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextAnchor {
+    /// The label starts at the position
+    Start,
+    /// The label is centered on the position
+    #[default]
+    Center,
+    /// The label ends at the position
+    End,
+}
+
+/// The location of a rasterized label inside the atlas, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AtlasRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// Labels rasterized into an offscreen canvas, packed row by row
+#[derive(Debug)]
+struct TextAtlas {
+    /// The offscreen canvas holding the labels
+    canvas: HtmlCanvasElement,
+    /// The 2d context of the canvas
+    context: CanvasRenderingContext2d,
+    /// The font size in pixels
+    font_size: u32,
+    /// The rasterized labels
+    labels: HashMap<String, AtlasRect>,
+    /// The position of the next label
+    cursor: (u32, u32),
+    /// The height of the current row
+    row_height: u32,
+    /// Whether the website font was ready when the labels were rasterized
+    fonts_ready: bool,
+    /// Whether the canvas changed since the last texture upload
+    dirty: bool,
+}
+
+impl TextAtlas {
+    /// Create a new empty atlas using the given font size
+    fn new(font_size: u32) -> Self {
+        let canvas: HtmlCanvasElement = document()
+            .create_element("canvas")
+            .unwrap()
+            .dyn_into()
+            .unwrap();
+        canvas.set_width(ATLAS_SIZE);
+        canvas.set_height(ATLAS_SIZE);
+        let context: CanvasRenderingContext2d = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into()
+            .unwrap();
+
+        let mut atlas = Self {
+            canvas,
+            context,
+            font_size,
+            labels: HashMap::new(),
+            cursor: (0, 0),
+            row_height: 0,
+            fonts_ready: false,
+            dirty: false,
+        };
+        atlas.clear();
+        atlas
+    }
+
+    /// Remove all labels from the atlas
+    fn clear(&mut self) {
+        let size = f64::from(ATLAS_SIZE);
+        self.context.clear_rect(0.0, 0.0, size, size);
+        self.context.set_font(&format!(
+            "{size}px {family}",
+            size = self.font_size,
+            family = fonts::font_family()
+        ));
+        self.context.set_text_baseline("middle");
+        self.context.set_fill_style_str("#ffffff");
+
+        self.labels.clear();
+        self.cursor = (0, 0);
+        self.row_height = 0;
+        self.fonts_ready = fonts::fonts_ready();
+        self.dirty = true;
+    }
+
+    /// Returns the location of the given label, rasterizing it if needed.
+    ///
+    /// Returns `None` if the atlas has no space left.
+    fn label(&mut self, text: &str) -> Option<AtlasRect> {
+        if let Some(rect) = self.labels.get(text) {
+            return Some(*rect);
+        }
+
+        let text_width = self.context.measure_text(text).unwrap().width().ceil() as u32;
+        let width = text_width + 2 * LABEL_PADDING;
+        let height = self.font_size * 5 / 4 + 2 * LABEL_PADDING;
+        if width > ATLAS_SIZE {
+            log::warn!("Label too wide for the text atlas: {text}");
+            return None;
+        }
+
+        if self.cursor.0 + width > ATLAS_SIZE {
+            self.cursor = (0, self.cursor.1 + self.row_height);
+            self.row_height = 0;
+        }
+        if self.cursor.1 + height > ATLAS_SIZE {
+            return None;
+        }
+
+        let rect = AtlasRect {
+            x: self.cursor.0,
+            y: self.cursor.1,
+            width,
+            height,
+        };
+        self.context
+            .fill_text(
+                text,
+                f64::from(rect.x + LABEL_PADDING),
+                f64::from(rect.y) + f64::from(height) / 2.0,
+            )
+            .unwrap();
+
+        self.cursor.0 += width;
+        self.row_height = self.row_height.max(height);
+        self.labels.insert(text.to_owned(), rect);
+        self.dirty = true;
+
+        Some(rect)
+    }
+}
+
+/// A queued label
+#[derive(Debug, Clone, PartialEq)]
+struct QueuedLabel {
+    text: String,
+    position: (f32, f32),
+    anchor: TextAnchor,
+    color: [f32; 4],
+    size: f32,
+}
+
+/// Draws text labels on top of a webgl canvas.
+///
+/// Labels are queued during a frame using [`TextRenderer::queue`] and drawn all at once with
+/// [`TextRenderer::render`]. Positions and font sizes are given in canvas pixels with the origin
+/// at the top left corner, labels are vertically centered on their position. Labels are
+/// rasterized once at the font size given to [`TextRenderer::new`] and scaled when drawn, so that
+/// size should be about the largest one used.
+///
+/// Labels rasterized before the website font finished loading are rasterized again once it is
+/// ready.
+#[derive(Debug)]
+pub struct TextRenderer {
+    /// The rasterized labels
+    atlas: TextAtlas,
+    /// The atlas texture
+    texture: WebGlTexture,
+    /// The program drawing the label quads
+    program: WebGlProgram,
+    /// The label quads, updated every render
+    geometry: Geometry,
+    /// The canvas resolution uniform
+    resolution_uniform: Uniform<(f32, f32)>,
+    /// The atlas texture uniform
+    atlas_uniform: Uniform<(i32,)>,
+    /// The labels queued for the next render
+    queue: Vec<QueuedLabel>,
+}
+
+impl TextRenderer {
+    /// Vertex shader transforming pixel coordinates to clip space
+    const VERTEX_SOURCE: &'static str = "
+        attribute vec2 a_position;
+        attribute vec2 a_uv;
+        attribute vec4 a_color;
+
+        uniform vec2 u_resolution;
+
+        varying vec2 v_uv;
+        varying vec4 v_color;
+
+        void main() {
+            vec2 position = a_position / u_resolution * 2.0 - 1.0;
+            gl_Position = vec4(position.x, -position.y, 0.0, 1.0);
+            v_uv = a_uv;
+            v_color = a_color;
+        }
+    ";
+
+    /// Fragment shader tinting the atlas with the label color
+    const FRAGMENT_SOURCE: &'static str = "
+        precision mediump float;
+
+        uniform sampler2D u_atlas;
+
+        varying vec2 v_uv;
+        varying vec4 v_color;
+
+        void main() {
+            float coverage = texture2D(u_atlas, v_uv).a;
+            gl_FragColor = vec4(v_color.rgb, v_color.a * coverage);
+        }
+    ";
+
+    /// Create a new text renderer using the given font size in pixels
+    pub fn new(gl: &GL, font_size: u32) -> Result<Self, WebglError> {
+        let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, Self::VERTEX_SOURCE)?;
+        let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, Self::FRAGMENT_SOURCE)?;
+        let program = create_program(gl, &vertex_shader, &fragment_shader)?;
+
+        let texture = gl
+            .create_texture()
+            .ok_or(WebglError::CreationFailed("texture"))?;
+        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        Ok(Self {
+            atlas: TextAtlas::new(font_size),
+            texture,
+            geometry: Geometry::new(
+                gl,
+                &program,
+                vec![(
+                    VertexLayout::new()
+                        .attribute("a_position", AttributeType::Vec2)
+                        .attribute("a_uv", AttributeType::Vec2)
+                        .attribute("a_color", AttributeType::Vec4),
+                    &[],
+                )],
+            )?,
+            resolution_uniform: Uniform::new(gl, &program, "u_resolution", (1.0, 1.0))?,
+            atlas_uniform: Uniform::new(gl, &program, "u_atlas", (0,))?,
+            program,
+            queue: Vec::new(),
+        })
+    }
+
+    /// Queue a label to be drawn with the next [`TextRenderer::render`]
+    pub fn queue(
+        &mut self,
+        text: impl Into<String>,
+        position: (f32, f32),
+        anchor: TextAnchor,
+        color: [f32; 4],
+        size: f32,
+    ) {
+        self.queue.push(QueuedLabel {
+            text: text.into(),
+            position,
+            anchor,
+            color,
+            size,
+        });
+    }
+
+    /// Draw all queued labels onto a canvas of the given size and clear the queue
+    pub fn render(&mut self, gl: &GL, width: u32, height: u32) {
+        let queue = std::mem::take(&mut self.queue);
+        if queue.is_empty() {
+            return;
+        }
+
+        if !self.atlas.fonts_ready && fonts::fonts_ready() {
+            self.atlas.clear();
+        }
+        let mut rects: Vec<_> = queue
+            .iter()
+            .map(|label| self.atlas.label(&label.text))
+            .collect();
+        if rects.iter().any(Option::is_none) {
+            // Make room by dropping all labels not used this frame
+            self.atlas.clear();
+            rects = queue
+                .iter()
+                .map(|label| self.atlas.label(&label.text))
+                .collect();
+        }
+
+        gl.active_texture(GL::TEXTURE0);
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.texture));
+        if self.atlas.dirty {
+            gl.tex_image_2d_with_u32_and_u32_and_canvas(
+                GL::TEXTURE_2D,
+                0,
+                GL::RGBA as i32,
+                GL::RGBA,
+                GL::UNSIGNED_BYTE,
+                &self.atlas.canvas,
+            )
+            .unwrap();
+            self.atlas.dirty = false;
+        }
+
+        let mut vertices = Vec::with_capacity(queue.len() * 6 * VERTEX_FLOATS);
+        for (label, rect) in queue.iter().zip(rects) {
+            let Some(rect) = rect else {
+                continue;
+            };
+
+            let label_scale = label.size / self.atlas.font_size as f32;
+            let (label_width, label_height) = (
+                rect.width as f32 * label_scale,
+                rect.height as f32 * label_scale,
+            );
+            let left = match label.anchor {
+                TextAnchor::Start => label.position.0,
+                TextAnchor::Center => label.position.0 - label_width / 2.0,
+                TextAnchor::End => label.position.0 - label_width,
+            };
+            let top = label.position.1 - label_height / 2.0;
+
+            let size = ATLAS_SIZE as f32;
+            let (u0, v0) = (rect.x as f32 / size, rect.y as f32 / size);
+            let (u1, v1) = (
+                u0 + rect.width as f32 / size,
+                v0 + rect.height as f32 / size,
+            );
+            let (x0, y0, x1, y1) = (left, top, left + label_width, top + label_height);
+
+            for (x, y, u, v) in [
+                (x0, y0, u0, v0),
+                (x1, y0, u1, v0),
+                (x0, y1, u0, v1),
+                (x0, y1, u0, v1),
+                (x1, y0, u1, v0),
+                (x1, y1, u1, v1),
+            ] {
+                vertices.extend_from_slice(&[x, y, u, v]);
+                vertices.extend_from_slice(&label.color);
+            }
+        }
+
+        gl.use_program(Some(&self.program));
+        self.geometry.update(gl, 0, &vertices);
+        self.geometry.bind(gl);
+
+        self.resolution_uniform
+            .apply_data(gl, (width as f32, height as f32));
+        self.atlas_uniform.apply(gl);
+
+        gl.enable(GL::BLEND);
+        gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
+        gl.viewport(0, 0, width as i32, height as i32);
+        self.geometry.draw(gl, GL::TRIANGLES);
+        gl.disable(GL::BLEND);
+
+        self.geometry.unbind(gl);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+        gl.use_program(None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Sampler uniforms, binding textures to texture units, and loading textures from images or colors
+
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    HtmlImageElement, WebGlProgram, WebGlRenderingContext as GL, WebGlTexture, js_sys::Promise,
+};
+
+use crate::webgl::{Uniform, WebglError};
+
+/// Assigns texture units to the samplers of a single program
+#[derive(Debug, Default)]
+pub struct TextureUnits {
+    /// The next unassigned texture unit
+    next_unit: u32,
+}
+
+impl TextureUnits {
+    /// Create an allocator, starting at texture unit 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a sampler uniform in the given program, assigned to the next free texture unit
+    ///
+    /// Fails if the program uses more samplers than the context has texture units.
+    pub fn uniform(
+        &mut self,
+        gl: &GL,
+        program: &WebGlProgram,
+        name: impl Into<String>,
+        texture: Option<WebGlTexture>,
+    ) -> Result<TextureUniform, WebglError> {
+        let max_units = gl
+            .get_parameter(GL::MAX_COMBINED_TEXTURE_IMAGE_UNITS)
+            .ok()
+            .and_then(|max_units| max_units.as_f64())
+            .unwrap_or_default() as u32;
+        if self.next_unit >= max_units {
+            return Err(WebglError::TextureUnitsExhausted(max_units));
+        }
+
+        let unit = self.next_unit;
+        self.next_unit += 1;
+
+        Ok(TextureUniform {
+            unit,
+            sampler: Uniform::new(gl, program, name, (unit as i32,))?,
+            texture,
+        })
+    }
+}
+
+/// A sampler uniform with a fixed texture unit and the texture bound to it
+#[derive(Debug)]
+pub struct TextureUniform {
+    /// The assigned texture unit, as an offset to `TEXTURE0`
+    unit: u32,
+    /// The sampler uniform, pointing to the texture unit
+    sampler: Uniform<(i32,)>,
+    /// The texture bound when applying
+    texture: Option<WebGlTexture>,
+}
+
+impl TextureUniform {
+    /// Returns the assigned texture unit, as an offset to `TEXTURE0`
+    pub fn unit(&self) -> u32 {
+        self.unit
+    }
+
+    /// Returns the texture bound when applying
+    pub fn texture(&self) -> Option<&WebGlTexture> {
+        self.texture.as_ref()
+    }
+
+    /// Sets the texture bound when applying
+    pub fn set_texture(&mut self, texture: Option<WebGlTexture>) {
+        self.texture = texture;
+    }
+
+    /// Binds the texture to the assigned texture unit and points the sampler to it.
+    ///
+    /// The program has to be in use.
+    pub fn apply(&self, gl: &GL) {
+        gl.active_texture(GL::TEXTURE0 + self.unit);
+        gl.bind_texture(GL::TEXTURE_2D, self.texture.as_ref());
+        self.sampler.apply(gl);
+    }
+
+    /// A convenience wrapper for setting and then applying the texture
+    pub fn apply_texture(&mut self, gl: &GL, texture: Option<WebGlTexture>) {
+        self.set_texture(texture);
+        self.apply(gl);
+    }
+
+    /// Unbinds the texture from the assigned texture unit
+    pub fn unbind(&self, gl: &GL) {
+        gl.active_texture(GL::TEXTURE0 + self.unit);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+    }
+}
+
+/// A texture loaded from an image by [`load_texture`]
+#[derive(Debug, Clone)]
+pub struct LoadedTexture {
+    /// The texture holding the image
+    pub texture: WebGlTexture,
+    /// The width of the image in pixels
+    pub width: u32,
+    /// The height of the image in pixels
+    pub height: u32,
+}
+
+/// Load the image at the given url (e.g. a png or jpeg) into a texture.
+///
+/// The first row of the texture is the top row of the image. The texture is clamped to its edges
+/// and filtered linearly, so images of any size can be sampled in webgl1.
+///
+/// Fails if the image can not be loaded or exceeds the maximum texture size.
+pub async fn load_texture(gl: &GL, url: &str) -> Result<LoadedTexture, WebglError> {
+    let image = HtmlImageElement::new().map_err(|_| WebglError::CreationFailed("image"))?;
+    // Cross origin images can only be uploaded with permission of the server
+    image.set_cross_origin(Some("anonymous"));
+    let loaded = Promise::new(&mut |resolve, reject| {
+        image.set_onload(Some(&resolve));
+        image.set_onerror(Some(&reject));
+    });
+    image.set_src(url);
+    JsFuture::from(loaded)
+        .await
+        .map_err(|_| WebglError::ImageLoading(url.to_owned()))?;
+    image.set_onload(None);
+    image.set_onerror(None);
+
+    let (width, height) = (image.natural_width(), image.natural_height());
+    let max_texture_size = gl
+        .get_parameter(GL::MAX_TEXTURE_SIZE)
+        .ok()
+        .and_then(|max_texture_size| max_texture_size.as_f64())
+        .unwrap_or_default() as u32;
+    let size = width.max(height);
+    if max_texture_size < size {
+        return Err(WebglError::TextureTooLarge {
+            size,
+            max_size: max_texture_size,
+        });
+    }
+
+    let texture = gl
+        .create_texture()
+        .ok_or(WebglError::CreationFailed("texture"))?;
+    gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+    gl.tex_image_2d_with_u32_and_u32_and_image(
+        GL::TEXTURE_2D,
+        0,
+        GL::RGBA as i32,
+        GL::RGBA,
+        GL::UNSIGNED_BYTE,
+        &image,
+    )
+    .map_err(|_| WebglError::CreationFailed("texture"))?;
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+    gl.bind_texture(GL::TEXTURE_2D, None);
+
+    Ok(LoadedTexture {
+        texture,
+        width,
+        height,
+    })
+}
+
+/// Create a texture holding a single row of RGBA colors, e.g. a color ramp sampled by a value in
+/// `[0, 1]` as its horizontal coordinate.
+///
+/// The texture is clamped to its edges and filtered linearly, so it can have any width in webgl1.
+pub fn create_ramp_texture(gl: &GL, colors: &[[u8; 4]]) -> Result<WebGlTexture, WebglError> {
+    let texture = gl
+        .create_texture()
+        .ok_or(WebglError::CreationFailed("texture"))?;
+    update_ramp_texture(gl, &texture, colors);
+
+    gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+    gl.bind_texture(GL::TEXTURE_2D, None);
+
+    Ok(texture)
+}
+
+/// Replace the colors of a texture created by [`create_ramp_texture`], the amount may change
+pub fn update_ramp_texture(gl: &GL, texture: &WebGlTexture, colors: &[[u8; 4]]) {
+    gl.bind_texture(GL::TEXTURE_2D, Some(texture));
+    gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+        GL::TEXTURE_2D,
+        0,
+        GL::RGBA as i32,
+        colors.len() as i32,
+        1,
+        0,
+        GL::RGBA,
+        GL::UNSIGNED_BYTE,
+        Some(colors.as_flattened()),
+    )
+    .unwrap();
+    gl.bind_texture(GL::TEXTURE_2D, None);
+}
@@ -0,0 +1,236 @@
+//! Measuring gpu time of render passes, using `EXT_disjoint_timer_query`
+
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{ExtDisjointTimerQuery, WebGlQuery, WebGlRenderingContext as GL};
+
+use crate::webgl::webgl2;
+
+/// The timer query extension of a context
+#[derive(Debug)]
+enum TimerExtension {
+    /// Not checked yet
+    Unknown,
+    /// The context does not support timer queries
+    Unsupported,
+    /// `EXT_disjoint_timer_query`, queries are managed by the extension
+    WebGl1(ExtDisjointTimerQuery),
+    /// `EXT_disjoint_timer_query_webgl2`, queries are managed by the context
+    WebGl2,
+}
+
+/// Shared data behind a [`GpuTimer`]
+#[derive(Debug)]
+struct GpuTimerInner {
+    /// Whether passes are measured
+    enabled: bool,
+    /// The extension used for measuring
+    extension: TimerExtension,
+    /// Queries waiting for their result, oldest first
+    pending: Vec<(&'static str, WebGlQuery)>,
+    /// The last measured duration of each pass in milliseconds, in order of their first measurement
+    timings: Vec<(&'static str, f64)>,
+}
+
+/// A shared handle for measuring the gpu time of render passes.
+///
+/// Renderers wrap their passes in [`GpuTimer::measure`], which does nothing until the timer is
+/// [enabled](GpuTimer::enable) and the context supports timer queries. Results arrive a few frames
+/// later, since the queries are only read once the gpu finished them.
+#[derive(Debug, Clone)]
+pub struct GpuTimer {
+    /// The shared data
+    inner: Rc<RefCell<GpuTimerInner>>,
+}
+
+impl PartialEq for GpuTimer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for GpuTimer {}
+
+impl Default for GpuTimer {
+    fn default() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(GpuTimerInner {
+                enabled: false,
+                extension: TimerExtension::Unknown,
+                pending: Vec::new(),
+                timings: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl GpuTimer {
+    /// Start measuring passes
+    pub fn enable(&self) {
+        self.inner.borrow_mut().enabled = true;
+    }
+
+    /// Returns whether the context supports timer queries, `None` if nothing was measured yet
+    pub fn supported(&self) -> Option<bool> {
+        match self.inner.borrow().extension {
+            TimerExtension::Unknown => None,
+            TimerExtension::Unsupported => Some(false),
+            TimerExtension::WebGl1(_) | TimerExtension::WebGl2 => Some(true),
+        }
+    }
+
+    /// Forget the extension and pending queries, e.g. after the context was lost, since they
+    /// belong to the previous context
+    pub fn reset(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.extension = TimerExtension::Unknown;
+        inner.pending.clear();
+    }
+
+    /// Returns the last measured duration of each pass in milliseconds
+    pub fn timings(&self) -> Vec<(&'static str, f64)> {
+        self.inner.borrow().timings.clone()
+    }
+
+    /// Measure the gpu time of the commands issued by `pass`.
+    ///
+    /// Passes can not be nested, the inner pass is not measured.
+    pub fn measure<T>(&self, gl: &GL, name: &'static str, pass: impl FnOnce() -> T) -> T {
+        let mut inner = self.inner.borrow_mut();
+        if !inner.enabled {
+            drop(inner);
+            return pass();
+        }
+
+        if let TimerExtension::Unknown = inner.extension {
+            inner.extension = Self::load_extension(gl);
+        }
+        inner.poll(gl);
+
+        let Some(query) = inner.begin(gl) else {
+            drop(inner);
+            return pass();
+        };
+        // Keep the handle usable within the pass
+        drop(inner);
+        let result = pass();
+
+        let mut inner = self.inner.borrow_mut();
+        inner.end(gl);
+        inner.pending.push((name, query));
+
+        result
+    }
+
+    /// Returns the timer query extension of the given context
+    fn load_extension(gl: &GL) -> TimerExtension {
+        if webgl2(gl).is_some() {
+            match gl.get_extension("EXT_disjoint_timer_query_webgl2") {
+                Ok(Some(_)) => TimerExtension::WebGl2,
+                _ => TimerExtension::Unsupported,
+            }
+        } else {
+            match gl.get_extension("EXT_disjoint_timer_query") {
+                Ok(Some(extension)) => TimerExtension::WebGl1(extension.unchecked_into()),
+                _ => TimerExtension::Unsupported,
+            }
+        }
+    }
+}
+
+impl GpuTimerInner {
+    /// Begin a new time elapsed query, if supported
+    fn begin(&self, gl: &GL) -> Option<WebGlQuery> {
+        let target = ExtDisjointTimerQuery::TIME_ELAPSED_EXT;
+        match &self.extension {
+            TimerExtension::WebGl1(extension) => {
+                let query = extension.create_query_ext()?;
+                extension.begin_query_ext(target, &query);
+                Some(query)
+            }
+            TimerExtension::WebGl2 => {
+                let gl = webgl2(gl)?;
+                let query = gl.create_query()?;
+                gl.begin_query(target, &query);
+                Some(query)
+            }
+            TimerExtension::Unknown | TimerExtension::Unsupported => None,
+        }
+    }
+
+    /// End the running time elapsed query
+    fn end(&self, gl: &GL) {
+        let target = ExtDisjointTimerQuery::TIME_ELAPSED_EXT;
+        match &self.extension {
+            TimerExtension::WebGl1(extension) => extension.end_query_ext(target),
+            TimerExtension::WebGl2 => {
+                if let Some(gl) = webgl2(gl) {
+                    gl.end_query(target);
+                }
+            }
+            TimerExtension::Unknown | TimerExtension::Unsupported => {}
+        }
+    }
+
+    /// Returns the given parameter of a query
+    fn query_parameter(&self, gl: &GL, query: &WebGlQuery, parameter: u32) -> JsValue {
+        match &self.extension {
+            TimerExtension::WebGl1(extension) => extension.get_query_object_ext(query, parameter),
+            TimerExtension::WebGl2 => webgl2(gl)
+                .map(|gl| gl.get_query_parameter(query, parameter))
+                .unwrap_or(JsValue::UNDEFINED),
+            TimerExtension::Unknown | TimerExtension::Unsupported => JsValue::UNDEFINED,
+        }
+    }
+
+    /// Delete the given query
+    fn delete_query(&self, gl: &GL, query: &WebGlQuery) {
+        match &self.extension {
+            TimerExtension::WebGl1(extension) => extension.delete_query_ext(Some(query)),
+            TimerExtension::WebGl2 => {
+                if let Some(gl) = webgl2(gl) {
+                    gl.delete_query(Some(query));
+                }
+            }
+            TimerExtension::Unknown | TimerExtension::Unsupported => {}
+        }
+    }
+
+    /// Collect the results of finished queries
+    fn poll(&mut self, gl: &GL) {
+        // Results are meaningless if the gpu was interrupted, e.g. by a power state change
+        let disjoint = gl
+            .get_parameter(ExtDisjointTimerQuery::GPU_DISJOINT_EXT)
+            .ok()
+            .and_then(|disjoint| disjoint.as_bool())
+            .unwrap_or(false);
+
+        // Queries finish in order, so the first unavailable result ends the polling
+        let finished = self
+            .pending
+            .iter()
+            .take_while(|(_, query)| {
+                self.query_parameter(gl, query, ExtDisjointTimerQuery::QUERY_RESULT_AVAILABLE_EXT)
+                    .as_bool()
+                    .unwrap_or(false)
+            })
+            .count();
+
+        for (name, query) in self.pending.drain(..finished).collect::<Vec<_>>() {
+            let nanoseconds = self
+                .query_parameter(gl, &query, ExtDisjointTimerQuery::QUERY_RESULT_EXT)
+                .as_f64();
+            self.delete_query(gl, &query);
+
+            let Some(nanoseconds) = nanoseconds.filter(|_| !disjoint) else {
+                continue;
+            };
+            let milliseconds = nanoseconds / 1_000_000.0;
+            match self.timings.iter_mut().find(|(pass, _)| *pass == name) {
+                Some((_, timing)) => *timing = milliseconds,
+                None => self.timings.push((name, milliseconds)),
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Vertex array objects, capturing the vertex attribute setup of a draw call
+
+use wasm_bindgen::JsCast;
+use web_sys::{
+    OesVertexArrayObject, WebGlBuffer, WebGlProgram, WebGlRenderingContext as GL,
+    WebGlVertexArrayObject, js_sys::Float32Array,
+};
+
+use crate::webgl::{WebglError, vertex_attrib_divisor, webgl2};
+
+/// Create a static `ARRAY_BUFFER` holding the given data
+pub fn create_vertex_buffer(gl: &GL, data: &[f32]) -> Result<WebGlBuffer, WebglError> {
+    let buffer = gl
+        .create_buffer()
+        .ok_or(WebglError::CreationFailed("buffer"))?;
+    gl.bind_buffer(GL::ARRAY_BUFFER, Some(&buffer));
+    gl.buffer_data_with_array_buffer_view(
+        GL::ARRAY_BUFFER,
+        &Float32Array::from(data),
+        GL::STATIC_DRAW,
+    );
+    gl.bind_buffer(GL::ARRAY_BUFFER, None);
+    Ok(buffer)
+}
+
+/// A floating point vertex attribute, read from a buffer
+#[derive(Debug, Clone)]
+pub struct VertexAttribute {
+    /// The attribute location in the program
+    pub location: u32,
+    /// The buffer the attribute is read from
+    pub buffer: WebGlBuffer,
+    /// The amount of components per vertex
+    pub size: i32,
+    /// The byte offset between consecutive vertices, 0 for tightly packed ones
+    pub stride: i32,
+    /// The byte offset of the first component in the buffer
+    pub offset: i32,
+    /// The amount of instances sharing a value, 0 for a per-vertex attribute
+    pub divisor: u32,
+}
+
+impl VertexAttribute {
+    /// A tightly packed attribute of the given name in the program, read from the start of the
+    /// buffer
+    ///
+    /// Fails if the program has no active attribute of the given name.
+    pub fn new(
+        gl: &GL,
+        program: &WebGlProgram,
+        name: &str,
+        buffer: &WebGlBuffer,
+        size: i32,
+    ) -> Result<Self, WebglError> {
+        let location = gl
+            .get_attrib_location(program, name)
+            .try_into()
+            .map_err(|_| WebglError::MissingAttribute(name.to_owned()))?;
+
+        Ok(Self {
+            location,
+            buffer: buffer.clone(),
+            size,
+            stride: 0,
+            offset: 0,
+            divisor: 0,
+        })
+    }
+
+    /// Make this a per-instance attribute, advancing once every `divisor` instances
+    ///
+    /// # Panics
+    /// When enabled, if the context does not support instanced drawing
+    pub fn with_divisor(mut self, divisor: u32) -> Self {
+        self.divisor = divisor;
+        self
+    }
+
+    /// Bind the buffer and specify the attribute pointer
+    fn enable(&self, gl: &GL) {
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&self.buffer));
+        gl.vertex_attrib_pointer_with_i32(
+            self.location,
+            self.size,
+            GL::FLOAT,
+            false,
+            self.stride,
+            self.offset,
+        );
+        gl.enable_vertex_attrib_array(self.location);
+        if self.divisor != 0 {
+            vertex_attrib_divisor(gl, self.location, self.divisor);
+        }
+    }
+}
+
+/// How the vertex array is stored
+#[derive(Debug)]
+enum VaoHandle {
+    /// A vertex array object of the `OES_vertex_array_object` extension (webgl1)
+    Extension(OesVertexArrayObject, WebGlVertexArrayObject),
+    /// A native vertex array object (webgl2)
+    Native(WebGlVertexArrayObject),
+    /// Without support, the attributes are specified on every bind
+    Emulated,
+}
+
+/// A vertex array object, capturing the vertex attribute setup once so it can be restored with a
+/// single bind.
+///
+/// Falls back to specifying the attributes on every bind, if vertex array objects are not
+/// supported.
+#[derive(Debug)]
+pub struct Vao {
+    /// The vertex array handle
+    handle: VaoHandle,
+    /// The captured attributes
+    attributes: Vec<VertexAttribute>,
+}
+
+impl Vao {
+    /// Create a vertex array object with the given attributes
+    pub fn new(gl: &GL, attributes: Vec<VertexAttribute>) -> Self {
+        let handle = if let Some(gl) = webgl2(gl) {
+            gl.create_vertex_array().map(VaoHandle::Native)
+        } else {
+            gl.get_extension("OES_vertex_array_object")
+                .ok()
+                .flatten()
+                .map(JsCast::unchecked_into::<OesVertexArrayObject>)
+                .and_then(|extension| {
+                    let vao = extension.create_vertex_array_oes()?;
+                    Some(VaoHandle::Extension(extension, vao))
+                })
+        }
+        .unwrap_or(VaoHandle::Emulated);
+
+        let vao = Self { handle, attributes };
+        if !matches!(vao.handle, VaoHandle::Emulated) {
+            vao.bind_handle(gl, true);
+            for attribute in &vao.attributes {
+                attribute.enable(gl);
+            }
+            vao.bind_handle(gl, false);
+            gl.bind_buffer(GL::ARRAY_BUFFER, None);
+        }
+
+        vao
+    }
+
+    /// Returns whether vertex array objects are supported, or emulated otherwise
+    pub fn is_native(&self) -> bool {
+        !matches!(self.handle, VaoHandle::Emulated)
+    }
+
+    /// Bind or unbind the vertex array object
+    fn bind_handle(&self, gl: &GL, bind: bool) {
+        match &self.handle {
+            VaoHandle::Extension(extension, vao) => {
+                extension.bind_vertex_array_oes(bind.then_some(vao))
+            }
+            VaoHandle::Native(vao) => {
+                if let Some(gl) = webgl2(gl) {
+                    gl.bind_vertex_array(bind.then_some(vao));
+                }
+            }
+            VaoHandle::Emulated => {}
+        }
+    }
+
+    /// Restore the captured attributes for drawing
+    pub fn bind(&self, gl: &GL) {
+        match self.handle {
+            VaoHandle::Emulated => {
+                for attribute in &self.attributes {
+                    attribute.enable(gl);
+                }
+            }
+            _ => self.bind_handle(gl, true),
+        }
+    }
+
+    /// Delete the vertex array object, the buffers of the attributes are left untouched
+    pub fn delete(self, gl: &GL) {
+        match &self.handle {
+            VaoHandle::Extension(extension, vao) => extension.delete_vertex_array_oes(Some(vao)),
+            VaoHandle::Native(vao) => {
+                if let Some(gl) = webgl2(gl) {
+                    gl.delete_vertex_array(Some(vao));
+                }
+            }
+            VaoHandle::Emulated => {}
+        }
+    }
+
+    /// Unbind the vertex array object, so later attribute changes do not modify it
+    pub fn unbind(&self, gl: &GL) {
+        match self.handle {
+            VaoHandle::Emulated => {
+                for attribute in &self.attributes {
+                    gl.disable_vertex_attrib_array(attribute.location);
+                    if attribute.divisor != 0 {
+                        vertex_attrib_divisor(gl, attribute.location, 0);
+                    }
+                }
+                gl.bind_buffer(GL::ARRAY_BUFFER, None);
+            }
+            _ => self.bind_handle(gl, false),
+        }
+    }
+}
@@ -0,0 +1,497 @@
+//! Viewing 3D scenes in WebXR, falling back to an orbit camera on a regular [`Canvas`]
+
+use std::cell::{Cell, RefCell};
+use std::f32::consts::FRAC_PI_2;
+use std::rc::Rc;
+
+use gloo::events::EventListener;
+use gloo::utils::window;
+use serde::{Serialize, de::DeserializeOwned};
+use stylist::yew::use_style;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::WebGlRenderingContext as GL;
+use web_sys::{
+    HtmlCanvasElement, WebGlContextAttributes, XrFrame, XrReferenceSpace, XrReferenceSpaceType,
+    XrRenderStateInit, XrSession, XrSessionMode, XrView, XrWebGlLayer,
+};
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+use crate::theme::ThemeColor;
+use crate::webgl::{
+    Canvas, CanvasRenderer, DEFAULT_CPU_BUDGET, MouseData, RenderColors, RenderData,
+    RenderLoopState, SeededRng, WebglError, get_context,
+};
+
+/// A 4x4 matrix in column major order
+pub type Matrix4 = [f32; 16];
+
+/// The camera a single view of a scene is rendered from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// The projection matrix
+    pub projection: Matrix4,
+    /// The view matrix, transforming world space into view space
+    pub view: Matrix4,
+}
+
+/// A trait for rendering 3D scenes, used by the [`XrViewer`]
+pub trait SceneRenderer: Clone + PartialEq + 'static {
+    /// Internal state that can be modified each render
+    type RenderState: 'static;
+    /// External input that can not be modified from within the renderer
+    type RenderInput: Clone + PartialEq + Serialize + DeserializeOwned + 'static;
+
+    /// Called once per frame before any view is rendered, e.g. to advance a simulation
+    fn update(
+        &self,
+        _state: &mut Self::RenderState,
+        _input: &Self::RenderInput,
+        _gl: &GL,
+        _render_data: RenderData,
+    ) {
+    }
+
+    /// Called for every view of a frame, the viewport and framebuffer are already set up
+    fn render_view(
+        &self,
+        state: &mut Self::RenderState,
+        input: &Self::RenderInput,
+        gl: &GL,
+        render_data: RenderData,
+        camera: &Camera,
+    );
+
+    /// Create the initial render state
+    fn initial_render_state(
+        &self,
+        input: &Self::RenderInput,
+        gl: &GL,
+        render_data: RenderData,
+    ) -> Result<Self::RenderState, WebglError>;
+}
+
+/// A camera orbiting around the origin, rotated by dragging with the primary mouse button
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCamera {
+    /// The rotation around the y axis in radians
+    pub yaw: f32,
+    /// The rotation above the xz plane in radians
+    pub pitch: f32,
+    /// The distance to the origin
+    pub distance: f32,
+    /// The last mouse position while dragging
+    drag_position: Option<(u32, u32)>,
+}
+
+impl OrbitCamera {
+    /// The vertical field of view in radians
+    const FIELD_OF_VIEW: f32 = 0.8;
+    /// Radians rotated per dragged pixel
+    const DRAG_SPEED: f32 = 0.01;
+
+    /// Create a new camera at the given distance to the origin
+    pub fn new(distance: f32) -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            distance,
+            drag_position: None,
+        }
+    }
+
+    /// Rotate the camera according to the mouse movement since the last frame
+    fn drag(&mut self, mouse_data: MouseData) {
+        let position = mouse_data.position.filter(|_| mouse_data.primary_button);
+        if let (Some((x, y)), Some((last_x, last_y))) = (position, self.drag_position) {
+            self.yaw -= (x as f32 - last_x as f32) * Self::DRAG_SPEED;
+            self.pitch = (self.pitch + (y as f32 - last_y as f32) * Self::DRAG_SPEED)
+                .clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        }
+        self.drag_position = position;
+    }
+
+    /// Returns the camera for a viewport with the given aspect ratio
+    pub fn camera(&self, aspect: f32) -> Camera {
+        let eye = [
+            self.distance * self.pitch.cos() * self.yaw.sin(),
+            self.distance * self.pitch.sin(),
+            self.distance * self.pitch.cos() * self.yaw.cos(),
+        ];
+
+        Camera {
+            projection: perspective(Self::FIELD_OF_VIEW, aspect, 0.1, 100.0),
+            view: look_at_origin(eye),
+        }
+    }
+}
+
+/// Returns a perspective projection matrix
+fn perspective(field_of_view: f32, aspect: f32, near: f32, far: f32) -> Matrix4 {
+    let f = 1.0 / (field_of_view / 2.0).tan();
+    let range_inverse = 1.0 / (near - far);
+
+    #[rustfmt::skip]
+    let matrix = [
+        f / aspect, 0.0, 0.0, 0.0,
+        0.0, f, 0.0, 0.0,
+        0.0, 0.0, (near + far) * range_inverse, -1.0,
+        0.0, 0.0, 2.0 * near * far * range_inverse, 0.0,
+    ];
+    matrix
+}
+
+/// Returns a view matrix looking from `eye` at the origin, with y pointing up
+fn look_at_origin(eye: [f32; 3]) -> Matrix4 {
+    fn normalize([x, y, z]: [f32; 3]) -> [f32; 3] {
+        let length = (x * x + y * y + z * z).sqrt();
+        [x / length, y / length, z / length]
+    }
+    fn cross([ax, ay, az]: [f32; 3], [bx, by, bz]: [f32; 3]) -> [f32; 3] {
+        [ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx]
+    }
+    fn dot([ax, ay, az]: [f32; 3], [bx, by, bz]: [f32; 3]) -> f32 {
+        ax * bx + ay * by + az * bz
+    }
+
+    let z = normalize(eye);
+    let x = normalize(cross([0.0, 1.0, 0.0], z));
+    let y = cross(z, x);
+
+    #[rustfmt::skip]
+    let matrix = [
+        x[0], y[0], z[0], 0.0,
+        x[1], y[1], z[1], 0.0,
+        x[2], y[2], z[2], 0.0,
+        -dot(x, eye), -dot(y, eye), -dot(z, eye), 1.0,
+    ];
+    matrix
+}
+
+/// Clear the currently bound framebuffer
+fn clear(gl: &GL) {
+    gl.clear_color(0.0, 0.0, 0.0, 1.0);
+    gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+}
+
+/// Render state of the [`OrbitRenderer`]
+pub struct OrbitRenderState<S: SceneRenderer> {
+    /// The render state of the scene
+    scene: S::RenderState,
+    /// The camera
+    camera: OrbitCamera,
+}
+
+/// A [`CanvasRenderer`] rendering a [`SceneRenderer`] through an [`OrbitCamera`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitRenderer<S> {
+    /// The scene
+    pub scene: S,
+    /// The initial distance of the camera to the origin
+    pub distance: f32,
+}
+
+impl<S: SceneRenderer> CanvasRenderer for OrbitRenderer<S> {
+    type RenderState = OrbitRenderState<S>;
+    type RenderInput = S::RenderInput;
+    type RenderOutput = ();
+
+    fn render(
+        &self,
+        state: &mut Self::RenderState,
+        input: &Self::RenderInput,
+        gl: &GL,
+        render_data: RenderData,
+    ) {
+        state.camera.drag(render_data.mouse_data);
+
+        gl.viewport(0, 0, render_data.width as i32, render_data.height as i32);
+        clear(gl);
+
+        let camera = state
+            .camera
+            .camera(render_data.width as f32 / render_data.height.max(1) as f32);
+        self.scene.update(&mut state.scene, input, gl, render_data);
+        self.scene
+            .render_view(&mut state.scene, input, gl, render_data, &camera);
+    }
+
+    fn initial_render_state(
+        &self,
+        input: &Self::RenderInput,
+        gl: &GL,
+        render_data: RenderData,
+    ) -> Result<Self::RenderState, WebglError> {
+        Ok(OrbitRenderState {
+            scene: self.scene.initial_render_state(input, gl, render_data)?,
+            camera: OrbitCamera::new(self.distance),
+        })
+    }
+}
+
+/// Returns the immersive session mode supported by this device, preferring VR over AR
+async fn supported_session_mode() -> Option<XrSessionMode> {
+    let xr = window().navigator().xr();
+    if xr.is_undefined() {
+        return None;
+    }
+
+    for mode in [XrSessionMode::ImmersiveVr, XrSessionMode::ImmersiveAr] {
+        let supported = JsFuture::from(xr.is_session_supported(mode))
+            .await
+            .ok()
+            .and_then(|supported| supported.as_bool())
+            .unwrap_or(false);
+        if supported {
+            return Some(mode);
+        }
+    }
+
+    None
+}
+
+/// Start an immersive session rendering the scene on the given canvas.
+///
+/// The session drives its own render loop through `XRSession.requestAnimationFrame`, rendering
+/// every view of the viewer pose. `on_end` is emitted once the session ended.
+async fn run_session<S: SceneRenderer>(
+    canvas: HtmlCanvasElement,
+    mode: XrSessionMode,
+    renderer: S,
+    render_input: Rc<RefCell<S::RenderInput>>,
+    on_end: Callback<()>,
+) -> Result<(), JsValue> {
+    // Returns the context the canvas was already created with
+    let gl = get_context(&canvas, &WebGlContextAttributes::new()).ok_or("Missing webgl context")?;
+    JsFuture::from(gl.make_xr_compatible()).await?;
+
+    let session: XrSession = JsFuture::from(window().navigator().xr().request_session(mode))
+        .await?
+        .dyn_into()?;
+    let layer = XrWebGlLayer::new_with_web_gl_rendering_context(&session, &gl)?;
+    let render_state_init = XrRenderStateInit::new();
+    render_state_init.set_base_layer(Some(&layer));
+    session.update_render_state_with_state(&render_state_init);
+    let reference_space: XrReferenceSpace =
+        JsFuture::from(session.request_reference_space(XrReferenceSpaceType::Local))
+            .await?
+            .dyn_into()?;
+
+    type SelfOwnedSharedFunction = Rc<RefCell<Option<Closure<dyn FnMut(f64, XrFrame)>>>>;
+    let cb: SelfOwnedSharedFunction = Rc::new(RefCell::new(None));
+    let ended = Rc::new(Cell::new(false));
+
+    EventListener::once(&session, "end", {
+        let cb = cb.clone();
+        let gl = gl.clone();
+        let ended = ended.clone();
+        move |_| {
+            ended.set(true);
+            *cb.borrow_mut() = None;
+            gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+            on_end.emit(());
+        }
+    })
+    .forget();
+
+    *cb.borrow_mut() = Some(Closure::wrap(Box::new({
+        let cb = cb.clone();
+        let seed = SeededRng::random_seed();
+        let mut render_state: Option<S::RenderState> = None;
+        let mut last_input: Option<S::RenderInput> = None;
+        let mut last_time = 0.0;
+        move |time: f64, frame: XrFrame| {
+            if ended.get() {
+                return;
+            }
+            let session = frame.session();
+            session.request_animation_frame(cb.borrow().as_ref().unwrap().as_ref().unchecked_ref());
+
+            let Some(pose) = frame.get_viewer_pose(&reference_space) else {
+                return;
+            };
+
+            let input = render_input.borrow();
+            let render_data = RenderData {
+                initial_render: render_state.is_none(),
+                width: layer.framebuffer_width(),
+                height: layer.framebuffer_height(),
+                logical_width: layer.framebuffer_width(),
+                logical_height: layer.framebuffer_height(),
+                resized: false,
+                input_changed: last_input.as_ref() != Some(&*input),
+                time,
+                delta_time: time - last_time,
+                mouse_data: MouseData::default(),
+                seed,
+                cpu_budget: DEFAULT_CPU_BUDGET,
+                // Immersive sessions have no page around them to match
+                colors: RenderColors::default(),
+                low_power: false,
+                loading_progress: None,
+            };
+            last_input = Some(input.clone());
+            last_time = time;
+
+            if render_state.is_none() {
+                match renderer.initial_render_state(&input, &gl, render_data) {
+                    Ok(initial_state) => render_state = Some(initial_state),
+                    Err(error) => {
+                        log::error!("Ending immersive session: {error}");
+                        let _ = session.end();
+                        return;
+                    }
+                }
+            }
+            let render_state = render_state.as_mut().unwrap();
+
+            gl.bind_framebuffer(GL::FRAMEBUFFER, layer.framebuffer().as_ref());
+            clear(&gl);
+            renderer.update(render_state, &input, &gl, render_data);
+
+            for view in pose.views() {
+                let view: XrView = view.unchecked_into();
+                let Some(viewport) = layer.get_viewport(&view) else {
+                    continue;
+                };
+                gl.viewport(
+                    viewport.x(),
+                    viewport.y(),
+                    viewport.width(),
+                    viewport.height(),
+                );
+
+                let camera = Camera {
+                    projection: view.projection_matrix().try_into().unwrap(),
+                    view: view.transform().inverse().matrix().try_into().unwrap(),
+                };
+                renderer.render_view(render_state, &input, &gl, render_data, &camera);
+            }
+        }
+    }) as Box<dyn FnMut(f64, XrFrame)>));
+
+    session.request_animation_frame(cb.borrow().as_ref().unwrap().as_ref().unchecked_ref());
+
+    Ok(())
+}
+
+/// Properties for the [`XrViewer`] component
+#[derive(Debug, Clone, Properties, PartialEq)]
+pub struct XrViewerProperties<S: SceneRenderer> {
+    /// The scene to view
+    pub renderer: S,
+    /// Input to the scene
+    pub render_input: S::RenderInput,
+    /// The initial distance of the orbit camera to the origin
+    #[prop_or(3.0)]
+    pub distance: f32,
+    /// The width of the viewer, valid css
+    #[prop_or(AttrValue::from("100%"))]
+    pub width: AttrValue,
+    /// The height of the viewer, valid css
+    #[prop_or(AttrValue::from("500px"))]
+    pub height: AttrValue,
+}
+
+/// A viewer for 3D scenes.
+///
+/// Renders the scene on a [`Canvas`] through an [`OrbitCamera`], and offers to enter an immersive
+/// VR or AR session if `navigator.xr` supports one. The canvas is paused while the session runs.
+#[function_component(XrViewer)]
+pub fn xr_viewer<S: SceneRenderer>(props: &XrViewerProperties<S>) -> Html {
+    let canvas_node_ref = use_node_ref();
+    let session_mode = use_state(|| None::<XrSessionMode>);
+    let in_session = use_state(|| false);
+    let render_input = use_mut_ref(|| props.render_input.clone());
+
+    use_effect_with((), {
+        let session_mode = session_mode.setter();
+        move |_| spawn_local(async move { session_mode.set(supported_session_mode().await) })
+    });
+    use_effect_with(props.render_input.clone(), {
+        let render_input = render_input.clone();
+        move |input| *render_input.borrow_mut() = input.clone()
+    });
+
+    let enter_session = session_mode.map(|mode| {
+        let canvas_node_ref = canvas_node_ref.clone();
+        let in_session = in_session.clone();
+        let renderer = props.renderer.clone();
+        Callback::from(move |_| {
+            let Some(canvas) = canvas_node_ref.cast::<HtmlCanvasElement>() else {
+                return;
+            };
+            let on_end = Callback::from({
+                let in_session = in_session.clone();
+                move |_| in_session.set(false)
+            });
+            in_session.set(true);
+
+            let in_session = in_session.clone();
+            let renderer = renderer.clone();
+            let render_input = render_input.clone();
+            spawn_local(async move {
+                if let Err(error) = run_session(canvas, mode, renderer, render_input, on_end).await
+                {
+                    log::error!("Failed to start xr session: {error:?}");
+                    in_session.set(false);
+                }
+            });
+        })
+    });
+
+    let style = use_style!(
+        r#"
+            position: relative;
+
+            button {
+                position: absolute;
+                bottom: 10px;
+                right: 10px;
+                padding: 5px 15px;
+                color: ${fg};
+                background-color: ${bg};
+                border: none;
+                cursor: pointer;
+            }
+
+            button:hover {
+                background-color: ${bg_hover};
+            }
+        "#,
+        fg = ThemeColor::Base00.var(),
+        bg = ThemeColor::Base0D.var(),
+        bg_hover = ThemeColor::Base0C.var(),
+    );
+    let render_loop_state = if *in_session {
+        RenderLoopState::Paused
+    } else {
+        RenderLoopState::Rendering
+    };
+    html! {
+        <div class={style}>
+            <Canvas<OrbitRenderer<S>>
+                canvas_node_ref={canvas_node_ref}
+                renderer={OrbitRenderer {
+                    scene: props.renderer.clone(),
+                    distance: props.distance,
+                }}
+                render_input={props.render_input.clone()}
+                width={props.width.clone()}
+                height={props.height.clone()}
+                {render_loop_state}
+            />
+            if let (Some(enter_session), false) = (enter_session, *in_session) {
+                <button onclick={enter_session}>
+                    if *session_mode == Some(XrSessionMode::ImmersiveAr) {
+                        {"Enter AR"}
+                    } else {
+                        {"Enter VR"}
+                    }
+                </button>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,5 @@
+//! Runs the tests of the base16 scheme parsing of the build script, which cargo does not test
+//! itself
+
+#[path = "../build/scheme.rs"]
+mod scheme;
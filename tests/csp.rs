@@ -0,0 +1,69 @@
+//! Checks the website against a strict Content-Security-Policy, run with
+//! `wasm-pack test --headless --firefox`
+//!
+//! The static file generator also writes `/csp-check.html` for checking pages by hand.
+
+#![cfg(target_arch = "wasm32")]
+
+use std::{cell::Cell, rc::Rc};
+
+use gloo::{events::EventListener, utils::document};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{js_sys::Promise, window};
+use website::App;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// The nonce passed to the website
+const NONCE: &str = "wasm-bindgen-test";
+
+/// How long the website gets to mount its styles, in milliseconds
+const SETTLE_TIME: i32 = 1000;
+
+/// Wait for the given number of milliseconds
+async fn sleep(milliseconds: i32) {
+    let promise = Promise::new(&mut |resolve, _| {
+        window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, milliseconds)
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn app_mounts_under_strict_policy() {
+    let document = document();
+    let head = document.head().unwrap();
+
+    // The same policy and nonce element as written into `/csp-check.html`
+    let policy = document.create_element("meta").unwrap();
+    policy
+        .set_attribute("http-equiv", "Content-Security-Policy")
+        .unwrap();
+    policy
+        .set_attribute("content", &format!("style-src 'self' 'nonce-{NONCE}'"))
+        .unwrap();
+    head.append_child(&policy).unwrap();
+    let nonce = document.create_element("meta").unwrap();
+    nonce.set_attribute("property", "csp-nonce").unwrap();
+    nonce.set_attribute("nonce", NONCE).unwrap();
+    head.append_child(&nonce).unwrap();
+
+    let violations = Rc::new(Cell::new(0));
+    let _listener = EventListener::new(&document, "securitypolicyviolation", {
+        let violations = violations.clone();
+        move |_| violations.set(violations.get() + 1)
+    });
+
+    let root = document.create_element("div").unwrap();
+    document.body().unwrap().append_child(&root).unwrap();
+    let app = yew::Renderer::<App>::with_root(root).render();
+    sleep(SETTLE_TIME).await;
+
+    let style = head.query_selector("style").unwrap();
+    assert!(style.is_some(), "the website mounted no styles");
+    assert_eq!(violations.get(), 0, "styles were blocked by the policy");
+    app.destroy();
+}